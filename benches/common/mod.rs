@@ -0,0 +1,24 @@
+use bio_utils_rs::test_utils::{LengthDistribution, SimConfig, generate_fasta};
+
+/// Sequence lengths benchmarked by the sequence-level groups.
+#[allow(dead_code)] // Not used by every bench.
+pub const SEQ_LENS: [usize; 3] = [1_000, 100_000, 1_000_000];
+
+/// Seeded random DNA sequence of `len` bases with 50% GC.
+pub fn random_seq(len: usize, seed: u64) -> Vec<u8> {
+    let config = SimConfig {
+        num_records: 1,
+        length: LengthDistribution::Fixed(len),
+        seed,
+        ..Default::default()
+    };
+
+    let mut fasta = Vec::new();
+    generate_fasta(&mut fasta, &config).expect("valid config");
+
+    fasta
+        .split(|&b| b == b'\n')
+        .nth(1)
+        .expect("one record")
+        .to_vec()
+}
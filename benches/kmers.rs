@@ -0,0 +1,38 @@
+mod common;
+
+use bio_utils_rs::kmers::{CanonicalKmerIter, NtHashIter, frac_min_hash};
+use common::{SEQ_LENS, random_seq};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+fn bench_kmer_iterators(c: &mut Criterion) {
+    let seq = random_seq(1_000_000, 1);
+
+    let mut group = c.benchmark_group("kmer_iter");
+    group.throughput(Throughput::Bytes(seq.len() as u64));
+
+    group.bench_function("canonical", |b| {
+        b.iter(|| CanonicalKmerIter::new(black_box(&seq), 21).unwrap().count())
+    });
+    group.bench_function("nthash", |b| {
+        b.iter(|| NtHashIter::new(black_box(&seq), 21).unwrap().count())
+    });
+
+    group.finish();
+}
+
+fn bench_frac_min_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frac_min_hash");
+
+    for len in SEQ_LENS {
+        let seq = random_seq(len, 2);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &seq, |b, seq| {
+            b.iter(|| frac_min_hash(21, 100, black_box(seq)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_kmer_iterators, bench_frac_min_hash);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+mod common;
+
+use bio_utils_rs::nucleotide::{
+    gc_content, reverse_complement, search_exact, search_exact_both_strands, search_fuzzy,
+};
+use common::{SEQ_LENS, random_seq};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+fn bench_reverse_complement(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reverse_complement");
+
+    for len in SEQ_LENS {
+        let seq = random_seq(len, 1);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &seq, |b, seq| {
+            b.iter(|| reverse_complement(black_box(seq)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_gc_content(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gc_content");
+
+    for len in SEQ_LENS {
+        let seq = random_seq(len, 2);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &seq, |b, seq| {
+            b.iter(|| gc_content(black_box(seq)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_search(c: &mut Criterion) {
+    let seq = random_seq(1_000_000, 3);
+    let pattern = seq[500_000..500_024].to_vec();
+
+    let mut group = c.benchmark_group("search");
+    group.throughput(Throughput::Bytes(seq.len() as u64));
+
+    group.bench_function("exact", |b| {
+        b.iter(|| search_exact(black_box(&seq), black_box(&pattern)))
+    });
+    group.bench_function("exact_both_strands", |b| {
+        b.iter(|| search_exact_both_strands(black_box(&seq), black_box(&pattern)))
+    });
+    for max_mismatches in [0, 2, 4] {
+        group.bench_with_input(
+            BenchmarkId::new("fuzzy", max_mismatches),
+            &max_mismatches,
+            |b, &max_mismatches| {
+                b.iter(|| search_fuzzy(black_box(&seq), black_box(&pattern), max_mismatches))
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_reverse_complement,
+    bench_gc_content,
+    bench_search
+);
+criterion_main!(benches);
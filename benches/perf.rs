@@ -0,0 +1,61 @@
+//! Fast paths exposed by the `perf` feature, compared with the public API
+//! built on top of them.
+
+mod common;
+
+use bio_utils_rs::kmers::{CanonicalKmerIter, mm_hash64};
+use bio_utils_rs::nucleotide::{iupac_myers_builder, search_fuzzy};
+use common::random_seq;
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+fn bench_mm_hash64(c: &mut Criterion) {
+    let seq = random_seq(1_000_000, 1);
+    let kmers: Vec<u64> = CanonicalKmerIter::new(&seq, 21)
+        .unwrap()
+        .map(|(_, _, canonical)| canonical)
+        .collect();
+
+    let mut group = c.benchmark_group("mm_hash64");
+    group.throughput(Throughput::Elements(kmers.len() as u64));
+    group.bench_function("hash", |b| {
+        b.iter(|| {
+            black_box(&kmers)
+                .iter()
+                .fold(0u64, |acc, &kmer| acc ^ mm_hash64(kmer))
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_myers_reuse(c: &mut Criterion) {
+    // Many short reads searched for one primer.
+    let reads: Vec<Vec<u8>> = (0..1_000).map(|seed| random_seq(150, seed)).collect();
+    let pattern = b"GTGCCAGCMGCCGCGGTAA";
+
+    let mut group = c.benchmark_group("myers_reuse");
+    group.throughput(Throughput::Elements(reads.len() as u64));
+
+    group.bench_function("search_fuzzy", |b| {
+        b.iter(|| {
+            reads
+                .iter()
+                .map(|read| search_fuzzy(black_box(read), pattern, 2).unwrap().len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("prebuilt", |b| {
+        let myers = iupac_myers_builder().build_64(pattern);
+        b.iter(|| {
+            reads
+                .iter()
+                .map(|read| myers.find_all_end(black_box(read), 2).count())
+                .sum::<usize>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_mm_hash64, bench_myers_reuse);
+criterion_main!(benches);
@@ -0,0 +1,52 @@
+//! Throughput of every [`Sketcher`] on the same sequence, for choosing a
+//! sketching scheme.
+
+mod common;
+
+use bio_utils_rs::simd_sketch::{
+    ClosedSyncmerSketch, FracMinHashSketch, MinimizerSketch, OpenSyncmerSketch, Sketcher,
+};
+use common::random_seq;
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+fn bench_sketchers(c: &mut Criterion) {
+    let seq = random_seq(1_000_000, 1);
+
+    let sketchers: Vec<(&str, Box<dyn Sketcher>)> = vec![
+        (
+            "frac_min_hash",
+            Box::new(FracMinHashSketch::new(21, 10).unwrap()),
+        ),
+        (
+            "minimizer",
+            Box::new(MinimizerSketch {
+                kmer_size: 21,
+                window_size: 11,
+            }),
+        ),
+        (
+            "open_syncmer",
+            Box::new(OpenSyncmerSketch::new(21, 11, 5).unwrap()),
+        ),
+        (
+            "open_syncmer_scalar",
+            Box::new(OpenSyncmerSketch::new(21, 11, 0).unwrap()),
+        ),
+        (
+            "closed_syncmer",
+            Box::new(ClosedSyncmerSketch::new(21, 11).unwrap()),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("sketch");
+    group.throughput(Throughput::Bytes(seq.len() as u64));
+
+    for (name, sketcher) in &sketchers {
+        group.bench_function(*name, |b| b.iter(|| sketcher.sketch(black_box(&seq))));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sketchers);
+criterion_main!(benches);
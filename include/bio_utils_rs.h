@@ -0,0 +1,47 @@
+/* C declarations of the `ffi` feature of bio_utils_rs. */
+#ifndef BIO_UTILS_RS_H
+#define BIO_UTILS_RS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define BU_OK 0
+#define BU_ERR_NULL 1
+#define BU_ERR_INVALID 2
+
+typedef struct BuSketch BuSketch;
+
+/* Writes the reverse complement of seq to out, both len bytes. */
+int32_t bu_reverse_complement(const uint8_t *seq, size_t len, uint8_t *out);
+
+double bu_gc_content(const uint8_t *seq, size_t len);
+
+/* Shannon entropy in bits of the A/C/G/T frequencies. */
+float bu_entropy(const uint8_t *seq, size_t len);
+
+uint8_t bu_error_to_phred(double error);
+
+/* Error probability of a raw Phred+33 quality byte. */
+double bu_qual_to_error(uint8_t qual);
+
+/* On BU_OK, *sketch owns a handle to release with bu_sketch_free. */
+int32_t bu_frac_min_hash(const uint8_t *seq, size_t len, size_t kmer_size,
+                         uint64_t scaled, BuSketch **sketch);
+
+size_t bu_sketch_len(const BuSketch *sketch);
+
+double bu_sketch_jaccard(const BuSketch *a, const BuSketch *b);
+
+double bu_sketch_containment(const BuSketch *query, const BuSketch *reference);
+
+void bu_sketch_free(BuSketch *sketch);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BIO_UTILS_RS_H */
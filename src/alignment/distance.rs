@@ -0,0 +1,108 @@
+use bio::alignment::distance::simd::{bounded_levenshtein, levenshtein};
+
+/// Levenshtein (edit) distance between `a` and `b`.
+///
+/// Counts substitutions, insertions and deletions with unit cost. Uses a
+/// SIMD-accelerated banded algorithm with exponential search, which is fast
+/// when the sequences are similar. Comparison is case sensitive and does not
+/// expand IUPAC codes.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::alignment::edit_distance;
+///
+/// assert_eq!(edit_distance(b"ACGTACGT", b"ACGAACGTT"), 2);
+/// ```
+pub fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    levenshtein(a, b) as usize
+}
+
+/// Levenshtein distance between `a` and `b`, or `None` if it exceeds `max_dist`.
+///
+/// Only a band of `max_dist` diagonals is computed, so this is much faster
+/// than [`edit_distance`] for filtering dissimilar pairs.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::alignment::edit_distance_bounded;
+///
+/// assert_eq!(edit_distance_bounded(b"ACGTACGT", b"ACGAACGTT", 2), Some(2));
+/// assert_eq!(edit_distance_bounded(b"ACGTACGT", b"ACGAACGTT", 1), None);
+/// ```
+pub fn edit_distance_bounded(a: &[u8], b: &[u8], max_dist: usize) -> Option<usize> {
+    let max_dist = u32::try_from(max_dist).unwrap_or(u32::MAX);
+    bounded_levenshtein(a, b, max_dist).map(|dist| dist as usize)
+}
+
+/// Identity between `a` and `b` as `1 - edit_distance / max(len(a), len(b))`.
+///
+/// Returns a value in `[0.0, 1.0]`, with `1.0` for two empty sequences.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::alignment::identity;
+///
+/// assert_eq!(identity(b"ACGTACGTAC", b"ACGTTCGTAC"), 0.9);
+/// ```
+pub fn identity(a: &[u8], b: &[u8]) -> f64 {
+    match a.len().max(b.len()) {
+        0 => 1.0,
+        len => 1.0 - edit_distance(a, b) as f64 / len as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"", b"", 0)]
+    #[case(b"ACGT", b"", 4)]
+    #[case(b"ACGT", b"ACGT", 0)]
+    #[case(b"ACGT", b"AGGT", 1)]
+    #[case(b"ACGT", b"ACT", 1)]
+    #[case(b"ACGT", b"acgt", 4)]
+    #[case(b"ACCGTGGAT", b"AAAAACCGTTGAT", 5)]
+    fn test_edit_distance(#[case] a: &[u8], #[case] b: &[u8], #[case] expected: usize) {
+        assert_eq!(edit_distance(a, b), expected);
+        assert_eq!(edit_distance(b, a), expected);
+        assert_eq!(edit_distance_bounded(a, b, expected), Some(expected));
+        if expected > 0 {
+            assert_eq!(edit_distance_bounded(a, b, expected - 1), None);
+        }
+    }
+
+    #[test]
+    fn test_edit_distance_matches_dp() {
+        let mut rng = SplitMix64::new(11);
+        let a: Vec<u8> = (0..500).map(|_| b"ACGT"[rng.next_below(4)]).collect();
+        let mut b = a.clone();
+        for _ in 0..40 {
+            let pos = rng.next_below(b.len());
+            match rng.next_below(3) {
+                0 => b[pos] = b"ACGT"[rng.next_below(4)],
+                1 => {
+                    b.remove(pos);
+                }
+                _ => b.insert(pos, b"ACGT"[rng.next_below(4)]),
+            }
+        }
+
+        let expected = bio::alignment::distance::levenshtein(&a, &b) as usize;
+        assert_eq!(edit_distance(&a, &b), expected);
+        assert_eq!(edit_distance_bounded(&a, &b, usize::MAX), Some(expected));
+    }
+
+    #[rstest]
+    #[case(b"", b"", 1.0)]
+    #[case(b"ACGT", b"", 0.0)]
+    #[case(b"ACGT", b"ACGA", 0.75)]
+    fn test_identity(#[case] a: &[u8], #[case] b: &[u8], #[case] expected: f64) {
+        assert_eq!(identity(a, b), expected);
+    }
+}
@@ -0,0 +1,14 @@
+//! Pairwise sequence comparison.
+//!
+//! [`edit_distance`] and [`identity`] give quick global comparisons of two
+//! sequences without computing an alignment, and [`edit_distance_bounded`]
+//! exits early once a maximum distance is exceeded, e.g. when clustering
+//! barcodes or UMIs. [`align_wfa`] computes optimal gap-affine global
+//! alignments of long, similar sequences with the wavefront algorithm,
+//! returned as a [`Cigar`](crate::cigar::Cigar).
+
+mod distance;
+pub use distance::*;
+
+mod wfa;
+pub use wfa::*;
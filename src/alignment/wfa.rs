@@ -0,0 +1,399 @@
+use crate::cigar::{Cigar, CigarOp};
+use crate::errors::BioError;
+
+/// Offset of an unreachable wavefront cell.
+const NULL: i32 = i32::MIN / 2;
+
+/// Gap-affine penalties for [`align_wfa`], with matches scoring `0`.
+///
+/// A gap of length `l` costs `gap_open + l * gap_extend`. The default of
+/// `4/6/2` matches WFA2-lib.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WfaPenalties {
+    pub mismatch: u32,
+    pub gap_open: u32,
+    pub gap_extend: u32,
+}
+
+impl Default for WfaPenalties {
+    fn default() -> Self {
+        Self {
+            mismatch: 4,
+            gap_open: 6,
+            gap_extend: 2,
+        }
+    }
+}
+
+impl WfaPenalties {
+    fn validate(&self) -> Result<(), BioError> {
+        if self.mismatch == 0 || self.gap_extend == 0 {
+            return Err(BioError::InvalidParameterError(format!(
+                "mismatch and gap extend penalties must be greater than 0, got {} and {}.",
+                self.mismatch, self.gap_extend
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Furthest reaching offsets on diagonals `lo..=hi` for a single score.
+///
+/// Diagonal `k` is `h - v` for target offset `h` and query offset `v`, and
+/// offsets are stored as `h`. `ins` consumes query only, `del` target only.
+struct Wavefront {
+    lo: i32,
+    hi: i32,
+    m: Vec<i32>,
+    ins: Vec<i32>,
+    del: Vec<i32>,
+}
+
+#[derive(Clone, Copy)]
+enum Component {
+    M,
+    Ins,
+    Del,
+}
+
+impl Wavefront {
+    fn get(&self, component: Component, k: i32) -> i32 {
+        if k < self.lo || k > self.hi {
+            return NULL;
+        }
+
+        let i = (k - self.lo) as usize;
+        match component {
+            Component::M => self.m[i],
+            Component::Ins => self.ins[i],
+            Component::Del => self.del[i],
+        }
+    }
+}
+
+struct Wavefronts {
+    fronts: Vec<Option<Wavefront>>,
+}
+
+impl Wavefronts {
+    fn get(&self, s: i64, component: Component, k: i32) -> i32 {
+        match s >= 0 {
+            true => match &self.fronts[s as usize] {
+                Some(front) => front.get(component, k),
+                None => NULL,
+            },
+            false => NULL,
+        }
+    }
+
+    fn range(&self, s: i64) -> Option<(i32, i32)> {
+        match s >= 0 {
+            true => self.fronts[s as usize].as_ref().map(|f| (f.lo, f.hi)),
+            false => None,
+        }
+    }
+}
+
+/// Globally aligns `query` to `target` with the gap-affine wavefront
+/// algorithm (WFA).
+///
+/// Returns the optimal alignment score (the total penalty, `0` for identical
+/// sequences) and a [`Cigar`] with `=` (match), `X` (mismatch), `I`
+/// (query-only) and `D` (target-only) operations. Runs in `O(n * s)` time
+/// for sequence length `n` and score `s`, so similar sequences such as ONT
+/// reads against their reference align far faster than with classic dynamic
+/// programming. All wavefronts are kept for the traceback, using `O(s^2)`
+/// memory. Comparison is case sensitive.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if the mismatch or gap extend
+/// penalty is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::alignment::{WfaPenalties, align_wfa};
+///
+/// let (score, cigar) =
+///     align_wfa(b"GATTACAGATTACA", b"GATTACACCCCGATTACA", WfaPenalties::default()).unwrap();
+///
+/// // A single gap of four target bases: 6 + 4 * 2.
+/// assert_eq!(score, 14);
+/// assert_eq!(cigar.to_string(), "7=4D7=");
+/// ```
+pub fn align_wfa(
+    query: &[u8],
+    target: &[u8],
+    penalties: WfaPenalties,
+) -> Result<(u32, Cigar), BioError> {
+    penalties.validate()?;
+
+    let n = query.len() as i32;
+    let m = target.len() as i32;
+    let x = penalties.mismatch as i64;
+    let oe = (penalties.gap_open + penalties.gap_extend) as i64;
+    let e = penalties.gap_extend as i64;
+
+    let extend = |k: i32, mut h: i32| -> i32 {
+        while h - k < n && h < m && query[(h - k) as usize] == target[h as usize] {
+            h += 1;
+        }
+        h
+    };
+    let valid_or_null = |k: i32, h: i32| match h >= 0 && h <= m && h - k >= 0 && h - k <= n {
+        true => h,
+        false => NULL,
+    };
+
+    let k_end = m - n;
+    let mut wf = Wavefronts {
+        fronts: vec![Some(Wavefront {
+            lo: 0,
+            hi: 0,
+            m: vec![extend(0, 0)],
+            ins: vec![NULL],
+            del: vec![NULL],
+        })],
+    };
+
+    let mut s: i64 = 0;
+    while wf.get(s, Component::M, k_end) < m {
+        s += 1;
+
+        let ranges = [
+            wf.range(s - x),
+            wf.range(s - oe).map(|(lo, hi)| (lo - 1, hi + 1)),
+            wf.range(s - e).map(|(lo, hi)| (lo - 1, hi + 1)),
+        ];
+        let Some((lo, hi)) = ranges
+            .into_iter()
+            .flatten()
+            .reduce(|(lo, hi), (l, h)| (lo.min(l), hi.max(h)))
+        else {
+            wf.fronts.push(None);
+            continue;
+        };
+        let (lo, hi) = (lo.max(-n), hi.min(m));
+
+        let len = (hi - lo + 1) as usize;
+        let mut front = Wavefront {
+            lo,
+            hi,
+            m: vec![NULL; len],
+            ins: vec![NULL; len],
+            del: vec![NULL; len],
+        };
+
+        for k in lo..=hi {
+            let i = (k - lo) as usize;
+
+            let ins = wf
+                .get(s - oe, Component::M, k + 1)
+                .max(wf.get(s - e, Component::Ins, k + 1));
+            let del = wf
+                .get(s - oe, Component::M, k - 1)
+                .max(wf.get(s - e, Component::Del, k - 1))
+                + 1;
+            let sub = valid_or_null(k, wf.get(s - x, Component::M, k) + 1);
+
+            front.ins[i] = valid_or_null(k, ins);
+            front.del[i] = valid_or_null(k, del);
+
+            front.m[i] = match sub.max(front.ins[i]).max(front.del[i]) {
+                NULL => NULL,
+                best => extend(k, best),
+            };
+        }
+
+        wf.fronts.push(Some(front));
+    }
+
+    // Trace back from the end of the alignment to the origin, collecting ops in reverse.
+    let score = s as u32;
+    let (mut k, mut h) = (k_end, m);
+    let mut ops: Vec<CigarOp> = Vec::new();
+    let mut component = Component::M;
+
+    loop {
+        match component {
+            Component::M => {
+                if s == 0 {
+                    ops.extend(std::iter::repeat_n(CigarOp::Equal, h as usize));
+                    break;
+                }
+
+                let sub = valid_or_null(k, wf.get(s - x, Component::M, k) + 1);
+                let ins = wf.get(s, Component::Ins, k);
+                let del = wf.get(s, Component::Del, k);
+                let start = sub.max(ins).max(del);
+
+                ops.extend(std::iter::repeat_n(CigarOp::Equal, (h - start) as usize));
+                h = start;
+
+                if sub == start {
+                    ops.push(CigarOp::Diff);
+                    h -= 1;
+                    s -= x;
+                } else if ins == start {
+                    component = Component::Ins;
+                } else {
+                    component = Component::Del;
+                }
+            }
+            Component::Ins => {
+                ops.push(CigarOp::Ins);
+                if wf.get(s - oe, Component::M, k + 1) == h {
+                    component = Component::M;
+                    s -= oe;
+                } else {
+                    s -= e;
+                }
+                k += 1;
+            }
+            Component::Del => {
+                ops.push(CigarOp::Del);
+                if wf.get(s - oe, Component::M, k - 1) == h - 1 {
+                    component = Component::M;
+                    s -= oe;
+                } else {
+                    s -= e;
+                }
+                k -= 1;
+                h -= 1;
+            }
+        }
+    }
+
+    Ok((score, ops.into_iter().rev().map(|op| (1, op)).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use rstest::*;
+
+    /// Gotoh's O(nm) gap-affine global alignment score.
+    fn gotoh(query: &[u8], target: &[u8], p: WfaPenalties) -> i64 {
+        let (x, o, e) = (p.mismatch as i64, p.gap_open as i64, p.gap_extend as i64);
+        let inf = i64::MAX / 4;
+        let (n, m) = (query.len(), target.len());
+
+        let mut mm = vec![vec![inf; m + 1]; n + 1];
+        let mut ins = vec![vec![inf; m + 1]; n + 1];
+        let mut del = vec![vec![inf; m + 1]; n + 1];
+        mm[0][0] = 0;
+
+        for i in 0..=n {
+            for j in 0..=m {
+                if i > 0 {
+                    ins[i][j] = (mm[i - 1][j] + o + e).min(ins[i - 1][j] + e);
+                }
+                if j > 0 {
+                    del[i][j] = (mm[i][j - 1] + o + e).min(del[i][j - 1] + e);
+                }
+                if i > 0 && j > 0 {
+                    let cost = if query[i - 1] == target[j - 1] { 0 } else { x };
+                    mm[i][j] = mm[i - 1][j - 1] + cost;
+                }
+                if i > 0 || j > 0 {
+                    mm[i][j] = mm[i][j].min(ins[i][j]).min(del[i][j]);
+                }
+            }
+        }
+
+        mm[n][m]
+    }
+
+    /// Checks that `cigar` spells out `query` against `target` and returns its cost.
+    fn cigar_cost(query: &[u8], target: &[u8], cigar: &Cigar, p: WfaPenalties) -> i64 {
+        let (mut v, mut h, mut cost) = (0, 0, 0i64);
+        let gap_cost = |len: u32| (p.gap_open + p.gap_extend * len) as i64;
+
+        for (len, op) in cigar.ops() {
+            match op {
+                CigarOp::Equal | CigarOp::Diff => {
+                    for _ in 0..*len {
+                        assert_eq!(query[v] == target[h], *op == CigarOp::Equal);
+                        v += 1;
+                        h += 1;
+                    }
+                    if *op == CigarOp::Diff {
+                        cost += (p.mismatch * len) as i64;
+                    }
+                }
+                CigarOp::Ins => {
+                    cost += gap_cost(*len);
+                    v += *len as usize;
+                }
+                CigarOp::Del => {
+                    cost += gap_cost(*len);
+                    h += *len as usize;
+                }
+                op => panic!("unexpected op {:?}", op),
+            }
+        }
+
+        assert_eq!((v, h), (query.len(), target.len()));
+        assert_eq!((cigar.query_len(), cigar.target_len()), (v, h));
+        cost
+    }
+
+    #[rstest]
+    #[case(b"", b"", 0, "*")]
+    #[case(b"ACGT", b"ACGT", 0, "4=")]
+    #[case(b"ACGT", b"AGGT", 4, "1=1X2=")]
+    #[case(b"ACGT", b"", 14, "4I")]
+    #[case(b"", b"ACG", 12, "3D")]
+    #[case(b"AAACCC", b"AAAGGGCCC", 12, "3=3D3=")]
+    fn test_align_wfa(
+        #[case] query: &[u8],
+        #[case] target: &[u8],
+        #[case] score: u32,
+        #[case] cigar: &str,
+    ) {
+        let aligned = align_wfa(query, target, WfaPenalties::default()).unwrap();
+        assert_eq!(
+            (aligned.0, aligned.1.to_string()),
+            (score, cigar.to_string())
+        );
+    }
+
+    #[rstest]
+    #[case(WfaPenalties::default())]
+    #[case(WfaPenalties { mismatch: 1, gap_open: 0, gap_extend: 1 })]
+    #[case(WfaPenalties { mismatch: 3, gap_open: 10, gap_extend: 1 })]
+    fn test_align_wfa_matches_gotoh(#[case] penalties: WfaPenalties) {
+        let mut rng = SplitMix64::new(5);
+
+        for _ in 0..20 {
+            let query: Vec<u8> = (0..rng.next_below(300))
+                .map(|_| b"ACGT"[rng.next_below(4)])
+                .collect();
+            let mut target = query.clone();
+            for _ in 0..rng.next_below(30) {
+                let pos = rng.next_below(target.len() + 1);
+                match rng.next_below(3) {
+                    0 if pos < target.len() => target[pos] = b"ACGT"[rng.next_below(4)],
+                    1 if pos < target.len() => {
+                        target.remove(pos);
+                    }
+                    _ => target.insert(pos, b"ACGT"[rng.next_below(4)]),
+                }
+            }
+
+            let (score, cigar) = align_wfa(&query, &target, penalties).unwrap();
+            assert_eq!(score as i64, gotoh(&query, &target, penalties));
+            assert_eq!(cigar_cost(&query, &target, &cigar, penalties), score as i64);
+        }
+    }
+
+    #[rstest]
+    #[case(WfaPenalties { mismatch: 0, gap_open: 6, gap_extend: 2 })]
+    #[case(WfaPenalties { mismatch: 4, gap_open: 6, gap_extend: 0 })]
+    fn test_align_wfa_invalid(#[case] penalties: WfaPenalties) {
+        assert!(align_wfa(b"ACGT", b"ACGT", penalties).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! Sequence alphabet validation.
+//!
+//! [`validate_alphabet`] checks a sequence against an [`Alphabet`] and reports
+//! the first offending byte and its position, so parsers and writers can
+//! reject malformed data early. All alphabets are case insensitive.
+
+use crate::errors::BioError;
+
+const fn lookup(symbols: &[u8]) -> [bool; 256] {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < symbols.len() {
+        table[symbols[i].to_ascii_uppercase() as usize] = true;
+        table[symbols[i].to_ascii_lowercase() as usize] = true;
+        i += 1;
+    }
+    table
+}
+
+const DNA: [bool; 256] = lookup(b"ACGT");
+const DNA_IUPAC: [bool; 256] = lookup(b"ACGTRYSWKMBDHVN");
+const RNA: [bool; 256] = lookup(b"ACGU");
+const PROTEIN: [bool; 256] = lookup(crate::aminoacid::AA_ALPHABET);
+
+/// Sequence alphabets accepted by [`validate_alphabet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    /// `ACGT`.
+    Dna,
+    /// `ACGT` plus the IUPAC ambiguity codes `RYSWKMBDHVN`.
+    DnaIupac,
+    /// `ACGU`.
+    Rna,
+    /// Amino acids as in [`AA_ALPHABET`](crate::aminoacid::AA_ALPHABET), including `*`, `X`, `U` and `O`.
+    Protein,
+}
+
+impl Alphabet {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Alphabet::Dna => "DNA",
+            Alphabet::DnaIupac => "IUPAC DNA",
+            Alphabet::Rna => "RNA",
+            Alphabet::Protein => "protein",
+        }
+    }
+
+    fn table(&self) -> &'static [bool; 256] {
+        match self {
+            Alphabet::Dna => &DNA,
+            Alphabet::DnaIupac => &DNA_IUPAC,
+            Alphabet::Rna => &RNA,
+            Alphabet::Protein => &PROTEIN,
+        }
+    }
+
+    /// Whether `symbol` belongs to the alphabet.
+    #[inline]
+    pub fn contains(&self, symbol: u8) -> bool {
+        self.table()[symbol as usize]
+    }
+}
+
+/// Position and value of the first byte of `seq` that is not in `alphabet`.
+#[inline]
+pub fn first_invalid(seq: &[u8], alphabet: Alphabet) -> Option<(usize, u8)> {
+    let table = alphabet.table();
+    seq.iter()
+        .position(|b| !table[*b as usize])
+        .map(|pos| (pos, seq[pos]))
+}
+
+/// Checks that every byte of `seq` is in `alphabet`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidSymbolError`] with the position and value of
+/// the first offending byte.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::alphabet::{Alphabet, validate_alphabet};
+/// use bio_utils_rs::errors::BioError;
+///
+/// assert!(validate_alphabet(b"ACGTN", Alphabet::DnaIupac).is_ok());
+///
+/// match validate_alphabet(b"ACGTN", Alphabet::Dna) {
+///     Err(BioError::InvalidSymbolError { pos, symbol, .. }) => assert_eq!((pos, symbol), (4, 'N')),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub fn validate_alphabet(seq: &[u8], alphabet: Alphabet) -> Result<(), BioError> {
+    match first_invalid(seq, alphabet) {
+        Some((pos, byte)) => Err(BioError::InvalidSymbolError {
+            pos,
+            symbol: byte as char,
+            alphabet: alphabet.as_str(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Whether `seq` only contains `ACGT` (case insensitive).
+#[inline]
+pub fn is_valid_dna(seq: &[u8]) -> bool {
+    first_invalid(seq, Alphabet::Dna).is_none()
+}
+
+/// Whether `seq` only contains `ACGT` and IUPAC ambiguity codes (case insensitive).
+#[inline]
+pub fn is_valid_dna_iupac(seq: &[u8]) -> bool {
+    first_invalid(seq, Alphabet::DnaIupac).is_none()
+}
+
+/// Whether `seq` only contains `ACGU` (case insensitive).
+#[inline]
+pub fn is_valid_rna(seq: &[u8]) -> bool {
+    first_invalid(seq, Alphabet::Rna).is_none()
+}
+
+/// Whether `seq` only contains amino acid symbols (case insensitive).
+#[inline]
+pub fn is_valid_protein(seq: &[u8]) -> bool {
+    first_invalid(seq, Alphabet::Protein).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"", Alphabet::Dna, None)]
+    #[case(b"ACGTacgt", Alphabet::Dna, None)]
+    #[case(b"ACGTN", Alphabet::Dna, Some((4, b'N')))]
+    #[case(b"ACGTNryk", Alphabet::DnaIupac, None)]
+    #[case(b"ACG-T", Alphabet::DnaIupac, Some((3, b'-')))]
+    #[case(b"ACGU", Alphabet::Rna, None)]
+    #[case(b"ACGT", Alphabet::Rna, Some((3, b'T')))]
+    #[case(b"MKVUOX*", Alphabet::Protein, None)]
+    #[case(b"MK V", Alphabet::Protein, Some((2, b' ')))]
+    fn test_first_invalid(
+        #[case] seq: &[u8],
+        #[case] alphabet: Alphabet,
+        #[case] expected: Option<(usize, u8)>,
+    ) {
+        assert_eq!(first_invalid(seq, alphabet), expected);
+        assert_eq!(validate_alphabet(seq, alphabet).is_ok(), expected.is_none());
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert!(is_valid_dna(b"acgt"));
+        assert!(!is_valid_dna(b"acgu"));
+        assert!(is_valid_dna_iupac(b"ACGTN"));
+        assert!(is_valid_rna(b"acgu"));
+        assert!(is_valid_protein(b"MKV*"));
+        assert!(!is_valid_protein(b"MK1"));
+    }
+
+    #[test]
+    fn test_error_message() {
+        let err = validate_alphabet(b"ACGU", Alphabet::Dna).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid symbol 'U' at position 3 for DNA alphabet"
+        );
+    }
+}
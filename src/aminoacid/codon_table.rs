@@ -2,11 +2,24 @@
 pub const CODON_STANDARD: &[u8; 64] =
     b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG";
 
+/// Start codons of the standard code (`TTG`, `CTG`, `ATG`), marked with `M`.
+pub const STARTS_STANDARD: &[u8; 64] =
+    b"---M---------------M---------------M----------------------------";
+
+/// https://www.ncbi.nlm.nih.gov/Taxonomy/Utils/wprintgc.cgi?chapter=cgencodes#SG11
+pub const CODON_BACTERIAL: &[u8; 64] =
+    b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG";
+
+/// Start codons of the bacterial, archaeal and plant plastid code
+/// (`TTG`, `CTG`, `ATT`, `ATC`, `ATA`, `ATG`, `GTG`), marked with `M`.
+pub const STARTS_BACTERIAL: &[u8; 64] =
+    b"---M---------------M------------MMMM---------------M------------";
+
 /// The NCBI codon tables are designed so that the mapping
-/// t/T/U	->	0
-/// c/C		->	1
-/// a/A		->	2
-/// g/G		->	3
+/// t/T/U  ->  0
+/// c/C    ->  1
+/// a/A    ->  2
+/// g/G    ->  3
 /// enables extremely efficient codon lookups by converting the codon to a 6-bit representation. Essentially, it is a kmer of length 3.
 ///
 /// With this 2-bit encoding (00, 01, 10, 11) we can create the index for our codon table array as index = (base_1 << 4) | (base_2 << 2) | base_3.
@@ -44,8 +57,12 @@ pub const NT_CODON_MAP: [u8; 256] = {
 };
 
 /// Types of codon tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CodonTable {
+    /// NCBI table 1.
     Standard,
+    /// NCBI table 11.
+    Bacterial,
 }
 
 impl CodonTable {
@@ -53,6 +70,30 @@ impl CodonTable {
     pub fn table(&self) -> &[u8; 64] {
         match self {
             CodonTable::Standard => CODON_STANDARD,
+            CodonTable::Bacterial => CODON_BACTERIAL,
+        }
+    }
+
+    /// Map codon table type to its start codon array, where `M` marks an alternative start.
+    pub fn starts(&self) -> &[u8; 64] {
+        match self {
+            CodonTable::Standard => STARTS_STANDARD,
+            CodonTable::Bacterial => STARTS_BACTERIAL,
         }
     }
+
+    /// NCBI translation table ID.
+    pub fn ncbi_id(&self) -> u8 {
+        match self {
+            CodonTable::Standard => 1,
+            CodonTable::Bacterial => 11,
+        }
+    }
+
+    /// Parses an NCBI translation table ID, `None` for unsupported tables.
+    pub fn from_ncbi_id(id: u8) -> Option<CodonTable> {
+        [CodonTable::Standard, CodonTable::Bacterial]
+            .into_iter()
+            .find(|table| table.ncbi_id() == id)
+    }
 }
@@ -1,5 +1,16 @@
 pub mod codon_table;
+
 mod utils;
+pub use utils::*;
 
 mod translation;
 pub use translation::*;
+
+mod peptide;
+pub use peptide::*;
+
+mod properties;
+pub use properties::*;
+
+mod search;
+pub use search::*;
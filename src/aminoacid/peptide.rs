@@ -0,0 +1,208 @@
+use crate::alphabet::{Alphabet, validate_alphabet};
+use crate::errors::BioError;
+use std::io::Write;
+
+#[cfg(feature = "io")]
+use crate::io::get_bufwriter;
+#[cfg(feature = "io")]
+use std::path::PathBuf;
+
+/// Amino acid symbols accepted by [`validate_peptide`].
+///
+/// The 20 standard residues, selenocysteine (`U`), pyrrolysine (`O`), the
+/// IUPAC ambiguity codes `B`, `Z`, `J` and `X`, and the stop symbol `*`.
+/// Lowercase letters are accepted as well.
+pub const AA_ALPHABET: &[u8] = b"ACDEFGHIKLMNPQRSTVWYUOBZJX*";
+
+/// Checks that every symbol in `seq` is in [`AA_ALPHABET`].
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidSymbolError`] with the position of the first invalid symbol.
+pub fn validate_peptide(seq: &[u8]) -> Result<(), BioError> {
+    validate_alphabet(seq, Alphabet::Protein)
+}
+
+/// A protein sequence with an ID and optional description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeptideRecord {
+    pub id: String,
+    pub desc: Option<String>,
+    pub seq: Vec<u8>,
+}
+
+impl PeptideRecord {
+    /// Creates a record, validating the sequence against [`AA_ALPHABET`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `id` is empty, or
+    /// [`BioError::InvalidSymbolError`] if `seq` contains a symbol that is
+    /// not an amino acid.
+    pub fn new(id: &str, desc: Option<&str>, seq: Vec<u8>) -> Result<Self, BioError> {
+        if id.is_empty() {
+            return Err(BioError::InvalidParameterError(
+                "peptide record ID cannot be empty.".to_string(),
+            ));
+        }
+
+        validate_peptide(&seq)?;
+
+        Ok(Self {
+            id: id.to_string(),
+            desc: desc.map(str::to_string),
+            seq,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.seq.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seq.is_empty()
+    }
+}
+
+/// Writes protein FASTA, validating each sequence before it is written.
+///
+/// Sequences are written on a single line unless a line width is set with
+/// [`PeptideFastaWriter::with_line_width`].
+pub struct PeptideFastaWriter<W: Write> {
+    inner: W,
+    line_width: Option<usize>,
+}
+
+impl<W: Write> PeptideFastaWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            line_width: None,
+        }
+    }
+
+    /// Wraps sequence lines at `line_width` residues.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `line_width` is `0`.
+    pub fn with_line_width(mut self, line_width: usize) -> Result<Self, BioError> {
+        if line_width == 0 {
+            return Err(BioError::InvalidParameterError(
+                "line width must be greater than 0.".to_string(),
+            ));
+        }
+
+        self.line_width = Some(line_width);
+        Ok(self)
+    }
+
+    /// Writes a single record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidSymbolError`] if `seq` is not a valid
+    /// peptide, or [`BioError::IoError`] if writing fails.
+    pub fn write(&mut self, id: &str, desc: Option<&str>, seq: &[u8]) -> Result<(), BioError> {
+        validate_peptide(seq)?;
+
+        match desc {
+            Some(desc) => writeln!(self.inner, ">{} {}", id, desc)?,
+            None => writeln!(self.inner, ">{}", id)?,
+        }
+
+        match self.line_width {
+            Some(width) => {
+                for line in seq.chunks(width) {
+                    self.inner.write_all(line)?;
+                    self.inner.write_all(b"\n")?;
+                }
+            }
+            None => {
+                self.inner.write_all(seq)?;
+                self.inner.write_all(b"\n")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a [`PeptideRecord`].
+    ///
+    /// # Errors
+    ///
+    /// See [`PeptideFastaWriter::write`].
+    pub fn write_record(&mut self, record: &PeptideRecord) -> Result<(), BioError> {
+        self.write(&record.id, record.desc.as_deref(), &record.seq)
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if flushing fails.
+    pub fn flush(&mut self) -> Result<(), BioError> {
+        self.inner.flush()?;
+        Ok(())
+    }
+}
+
+/// Creates a [`PeptideFastaWriter`] for a file or stdout.
+///
+/// Files ending in `.gz` are gzip-compressed automatically.
+/// Pass `None` to write to stdout.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the output file cannot be created.
+#[cfg(feature = "io")]
+pub fn peptide_fasta_writer(
+    outfile: Option<PathBuf>,
+) -> Result<PeptideFastaWriter<Box<dyn Write + Send>>, BioError> {
+    Ok(PeptideFastaWriter::new(get_bufwriter(outfile)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"", true)]
+    #[case(b"MKV*", true)]
+    #[case(b"mkvUOX", true)]
+    #[case(b"BZJ", true)]
+    #[case(b"MK-V", false)]
+    #[case(b"MK V", false)]
+    #[case(b"MK1", false)]
+    fn test_validate_peptide(#[case] seq: &[u8], #[case] valid: bool) {
+        assert_eq!(validate_peptide(seq).is_ok(), valid);
+    }
+
+    #[test]
+    fn test_record() {
+        assert!(PeptideRecord::new("p1", None, b"MKV*".to_vec()).is_ok());
+        assert!(PeptideRecord::new("", None, b"MKV*".to_vec()).is_err());
+        assert!(PeptideRecord::new("p1", None, b"MK#".to_vec()).is_err());
+    }
+
+    #[rstest]
+    #[case(None, ">p1 kinase\nMKVLAAGW*\n>p2\nMU\n")]
+    #[case(Some(3), ">p1 kinase\nMKV\nLAA\nGW*\n>p2\nMU\n")]
+    fn test_writer(#[case] line_width: Option<usize>, #[case] expected: &str) {
+        let mut buf = Vec::new();
+        {
+            let mut writer = PeptideFastaWriter::new(&mut buf);
+            if let Some(width) = line_width {
+                writer = writer.with_line_width(width).unwrap();
+            }
+
+            let record = PeptideRecord::new("p1", Some("kinase"), b"MKVLAAGW*".to_vec()).unwrap();
+            writer.write_record(&record).unwrap();
+            writer.write("p2", None, b"MU").unwrap();
+            assert!(writer.write("p3", None, b"ACGN!").is_err());
+        }
+
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+}
@@ -0,0 +1,154 @@
+/// Physicochemical class of an amino acid side chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AaClass {
+    /// `A`, `V`, `I`, `L`, `M`, `F`, `W`, `P`.
+    Hydrophobic,
+    /// `G`, `S`, `T`, `C`, `Y`, `N`, `Q`, `U`.
+    Polar,
+    /// `D`, `E`, `K`, `R`, `H`, `O`.
+    Charged,
+}
+
+impl AaClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AaClass::Hydrophobic => "hydrophobic",
+            AaClass::Polar => "polar",
+            AaClass::Charged => "charged",
+        }
+    }
+}
+
+/// One-letter code, three-letter code, full name and class for each known symbol.
+const AA_TABLE: [(u8, &str, &str, Option<AaClass>); 27] = [
+    (b'A', "Ala", "Alanine", Some(AaClass::Hydrophobic)),
+    (b'R', "Arg", "Arginine", Some(AaClass::Charged)),
+    (b'N', "Asn", "Asparagine", Some(AaClass::Polar)),
+    (b'D', "Asp", "Aspartic acid", Some(AaClass::Charged)),
+    (b'C', "Cys", "Cysteine", Some(AaClass::Polar)),
+    (b'E', "Glu", "Glutamic acid", Some(AaClass::Charged)),
+    (b'Q', "Gln", "Glutamine", Some(AaClass::Polar)),
+    (b'G', "Gly", "Glycine", Some(AaClass::Polar)),
+    (b'H', "His", "Histidine", Some(AaClass::Charged)),
+    (b'I', "Ile", "Isoleucine", Some(AaClass::Hydrophobic)),
+    (b'L', "Leu", "Leucine", Some(AaClass::Hydrophobic)),
+    (b'K', "Lys", "Lysine", Some(AaClass::Charged)),
+    (b'M', "Met", "Methionine", Some(AaClass::Hydrophobic)),
+    (b'F', "Phe", "Phenylalanine", Some(AaClass::Hydrophobic)),
+    (b'P', "Pro", "Proline", Some(AaClass::Hydrophobic)),
+    (b'S', "Ser", "Serine", Some(AaClass::Polar)),
+    (b'T', "Thr", "Threonine", Some(AaClass::Polar)),
+    (b'W', "Trp", "Tryptophan", Some(AaClass::Hydrophobic)),
+    (b'Y', "Tyr", "Tyrosine", Some(AaClass::Polar)),
+    (b'V', "Val", "Valine", Some(AaClass::Hydrophobic)),
+    (b'U', "Sec", "Selenocysteine", Some(AaClass::Polar)),
+    (b'O', "Pyl", "Pyrrolysine", Some(AaClass::Charged)),
+    (b'B', "Asx", "Asparagine or aspartic acid", None),
+    (b'Z', "Glx", "Glutamine or glutamic acid", None),
+    (b'J', "Xle", "Leucine or isoleucine", None),
+    (b'X', "Xaa", "Unknown", None),
+    (b'*', "Ter", "Stop", None),
+];
+
+const fn build_index() -> [u8; 256] {
+    let mut index = [u8::MAX; 256];
+    let mut i = 0;
+    while i < AA_TABLE.len() {
+        let aa = AA_TABLE[i].0;
+        index[aa as usize] = i as u8;
+        index[aa.to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    index
+}
+
+/// Maps a one-letter code (either case) to its row in the amino acid table, or `u8::MAX`.
+const AA_INDEX: [u8; 256] = build_index();
+
+fn lookup(aa: u8) -> Option<&'static (u8, &'static str, &'static str, Option<AaClass>)> {
+    AA_TABLE.get(AA_INDEX[aa as usize] as usize)
+}
+
+/// Three-letter code of a one-letter amino acid code, e.g. `M` -> `Met`.
+///
+/// Stop (`*`) maps to `Ter` and unknown (`X`) to `Xaa`. Returns `None` for
+/// symbols that are not amino acids.
+pub fn three_letter(aa: u8) -> Option<&'static str> {
+    lookup(aa).map(|row| row.1)
+}
+
+/// Full name of a one-letter amino acid code, e.g. `W` -> `Tryptophan`.
+pub fn aa_name(aa: u8) -> Option<&'static str> {
+    lookup(aa).map(|row| row.2)
+}
+
+/// Side chain class of a one-letter amino acid code.
+///
+/// Returns `None` for ambiguity codes, stop and non-amino acid symbols.
+pub fn aa_class(aa: u8) -> Option<AaClass> {
+    lookup(aa).and_then(|row| row.3)
+}
+
+/// Converts a one-letter sequence to concatenated three-letter codes, e.g. `MK*` -> `MetLysTer`.
+///
+/// Returns `None` if any symbol is not an amino acid.
+pub fn to_three_letter(seq: &[u8]) -> Option<String> {
+    let mut out = String::with_capacity(seq.len() * 3);
+    for aa in seq {
+        out.push_str(three_letter(*aa)?);
+    }
+    Some(out)
+}
+
+/// Counts residues per [`AaClass`] as `[hydrophobic, polar, charged]`.
+///
+/// Symbols without a class are not counted.
+pub fn aa_class_counts(seq: &[u8]) -> [usize; 3] {
+    let mut counts = [0usize; 3];
+    for aa in seq {
+        match aa_class(*aa) {
+            Some(AaClass::Hydrophobic) => counts[0] += 1,
+            Some(AaClass::Polar) => counts[1] += 1,
+            Some(AaClass::Charged) => counts[2] += 1,
+            None => {}
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b'M', Some("Met"), Some("Methionine"), Some(AaClass::Hydrophobic))]
+    #[case(b'k', Some("Lys"), Some("Lysine"), Some(AaClass::Charged))]
+    #[case(b'U', Some("Sec"), Some("Selenocysteine"), Some(AaClass::Polar))]
+    #[case(b'*', Some("Ter"), Some("Stop"), None)]
+    #[case(b'X', Some("Xaa"), Some("Unknown"), None)]
+    #[case(b'1', None, None, None)]
+    fn test_lookup(
+        #[case] aa: u8,
+        #[case] three: Option<&str>,
+        #[case] name: Option<&str>,
+        #[case] class: Option<AaClass>,
+    ) {
+        assert_eq!(three_letter(aa), three);
+        assert_eq!(aa_name(aa), name);
+        assert_eq!(aa_class(aa), class);
+    }
+
+    #[rstest]
+    #[case(b"MK*", Some("MetLysTer"))]
+    #[case(b"", Some(""))]
+    #[case(b"M-K", None)]
+    fn test_to_three_letter(#[case] seq: &[u8], #[case] expected: Option<&str>) {
+        assert_eq!(to_three_letter(seq).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_class_counts() {
+        assert_eq!(aa_class_counts(b"MAGSDEX*"), [2, 2, 2]);
+    }
+}
@@ -0,0 +1,220 @@
+use super::peptide::validate_peptide;
+use crate::errors::BioError;
+use bio::scores::blosum62;
+use memchr::memmem;
+
+/// Cost of opening a gap, charged in addition to [`GAP_EXTEND`] for its first residue.
+const GAP_OPEN: i32 = 11;
+
+/// Cost of each gapped residue, matching the BLASTP default of 11/1.
+const GAP_EXTEND: i32 = 1;
+
+const NEG_INF: i32 = i32::MIN / 2;
+
+/// A local alignment of a query against a protein sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProteinHit {
+    /// Start of the aligned region in the searched sequence (inclusive).
+    pub start: usize,
+    /// End of the aligned region in the searched sequence (exclusive).
+    pub end: usize,
+    /// BLOSUM62 alignment score.
+    pub score: i32,
+}
+
+/// Searches `seq` for all exact occurrences of the peptide `pattern`.
+///
+/// Uses SIMD-accelerated substring search via [`memchr::memmem`].
+/// Returns the starting offset of each non-overlapping match.
+pub fn search_protein_exact(seq: &[u8], pattern: &[u8]) -> Vec<usize> {
+    memmem::find_iter(seq, pattern).collect()
+}
+
+#[inline]
+fn score(a: u8, b: u8) -> i32 {
+    blosum62(a.to_ascii_uppercase(), b.to_ascii_uppercase())
+}
+
+/// Searches `seq` for regions similar to `pattern` using Smith-Waterman local
+/// alignment with BLOSUM62 scores and BLASTP default affine gaps (open 11, extend 1).
+///
+/// For every position in `seq` the best local alignment ending there is a
+/// candidate; candidates scoring at least `min_score` are then selected
+/// greedily by score so that reported hits do not overlap. Hits are returned
+/// sorted by start. Runs in `O(seq.len() * pattern.len())` time and
+/// `O(pattern.len())` memory.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `pattern` is empty or
+/// `min_score` is not positive, or [`BioError::InvalidSymbolError`] if either
+/// sequence contains a symbol that is not an amino acid.
+pub fn search_protein_fuzzy(
+    seq: &[u8],
+    pattern: &[u8],
+    min_score: i32,
+) -> Result<Vec<ProteinHit>, BioError> {
+    if pattern.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "protein search pattern cannot be empty.".to_string(),
+        ));
+    }
+
+    if min_score <= 0 {
+        return Err(BioError::InvalidParameterError(format!(
+            "min score must be greater than 0, got {}.",
+            min_score
+        )));
+    }
+
+    validate_peptide(seq)?;
+    validate_peptide(pattern)?;
+
+    let m = pattern.len();
+
+    // Column of the DP matrix for the previous sequence position, with the
+    // sequence start of the alignment ending in each cell.
+    let mut h = vec![0i32; m + 1];
+    let mut h_start = vec![0usize; m + 1];
+    let mut e = vec![NEG_INF; m + 1];
+    let mut e_start = vec![0usize; m + 1];
+
+    let mut candidates: Vec<ProteinHit> = Vec::new();
+
+    for (j, &aa) in seq.iter().enumerate() {
+        let mut diag = 0;
+        let mut diag_start = j;
+        let mut f = NEG_INF;
+        let mut f_start = 0;
+
+        let mut best = ProteinHit {
+            start: 0,
+            end: j + 1,
+            score: 0,
+        };
+
+        for i in 1..=m {
+            // Gap in the pattern: consumes `aa`.
+            let open = h[i] - GAP_OPEN - GAP_EXTEND;
+            let extend = e[i] - GAP_EXTEND;
+            (e[i], e_start[i]) = match open >= extend {
+                true => (open, h_start[i]),
+                false => (extend, e_start[i]),
+            };
+
+            // Gap in the sequence: consumes `pattern[i - 1]`.
+            let open = h[i - 1] - GAP_OPEN - GAP_EXTEND;
+            let extend = f - GAP_EXTEND;
+            (f, f_start) = match open >= extend {
+                true => (open, h_start[i - 1]),
+                false => (extend, f_start),
+            };
+
+            let (diag_score, diag_from) = match diag > 0 {
+                true => (diag + score(pattern[i - 1], aa), diag_start),
+                false => (score(pattern[i - 1], aa), j),
+            };
+
+            diag = h[i];
+            diag_start = h_start[i];
+
+            (h[i], h_start[i]) = if diag_score >= e[i] && diag_score >= f {
+                (diag_score, diag_from)
+            } else if e[i] >= f {
+                (e[i], e_start[i])
+            } else {
+                (f, f_start)
+            };
+
+            if h[i] <= 0 {
+                h[i] = 0;
+                h_start[i] = j + 1;
+            }
+
+            if h[i] > best.score {
+                best.score = h[i];
+                best.start = h_start[i];
+            }
+        }
+
+        if best.score >= min_score {
+            candidates.push(best);
+        }
+    }
+
+    candidates.sort_by(|a, b| b.score.cmp(&a.score).then(a.start.cmp(&b.start)));
+
+    let mut hits: Vec<ProteinHit> = Vec::new();
+    for candidate in candidates {
+        if hits
+            .iter()
+            .all(|hit| candidate.end <= hit.start || candidate.start >= hit.end)
+        {
+            hits.push(candidate);
+        }
+    }
+
+    hits.sort_by_key(|hit| hit.start);
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"MKVLAAGWKVL", b"KVL", vec![1, 8])]
+    #[case(b"MKVLAAGW", b"WW", vec![])]
+    fn test_search_protein_exact(
+        #[case] seq: &[u8],
+        #[case] pattern: &[u8],
+        #[case] expected: Vec<usize>,
+    ) {
+        assert_eq!(search_protein_exact(seq, pattern), expected);
+    }
+
+    #[test]
+    fn test_fuzzy_exact_match() {
+        // W=11, C=9, H=8, M=5 on the BLOSUM62 diagonal.
+        let hits = search_protein_fuzzy(b"AAAAWCHMAAAA", b"WCHM", 20).unwrap();
+        assert_eq!(
+            hits,
+            vec![ProteinHit {
+                start: 4,
+                end: 8,
+                score: 33
+            }]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_mismatch_and_multiple_hits() {
+        // `WCYM` vs `WCHM`: H/Y scores 2 instead of 8.
+        let seq = b"WCHMGGGGGGGGWCYM";
+        let hits = search_protein_fuzzy(seq, b"WCHM", 20).unwrap();
+
+        assert_eq!(hits.len(), 2);
+        assert_eq!((hits[0].start, hits[0].end, hits[0].score), (0, 4, 33));
+        assert_eq!((hits[1].start, hits[1].end, hits[1].score), (12, 16, 27));
+
+        let hits = search_protein_fuzzy(seq, b"WCHM", 30).unwrap();
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn test_fuzzy_gapped() {
+        // One-residue insertion costs 12, so `WCHWMCW` vs `WCHMCW` still scores above 30.
+        let hits = search_protein_fuzzy(b"GGWCHWMCWGG", b"WCHMCW", 30).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].start, hits[0].end), (2, 9));
+    }
+
+    #[rstest]
+    #[case(b"MKV", b"", 10)]
+    #[case(b"MKV", b"MK", 0)]
+    #[case(b"MK1", b"MK", 5)]
+    fn test_fuzzy_invalid(#[case] seq: &[u8], #[case] pattern: &[u8], #[case] min_score: i32) {
+        assert!(search_protein_fuzzy(seq, pattern, min_score).is_err());
+    }
+}
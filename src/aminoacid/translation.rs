@@ -1,7 +1,82 @@
 use super::codon_table::{CodonTable, NT_CODON_MAP};
 use super::utils::Frame;
+use crate::errors::BioError;
+use crate::nucleotide::{AmbigPolicy, NT_LOOKUP, Strand, apply_ambig_policy};
 
+/// Returns the codon table index of three bases, or `None` if any base is not `ACGTU`.
+#[inline]
+fn codon_index(b1: u8, b2: u8, b3: u8) -> Option<usize> {
+    if NT_LOOKUP[b1 as usize] > 3 || NT_LOOKUP[b2 as usize] > 3 || NT_LOOKUP[b3 as usize] > 3 {
+        return None;
+    }
+
+    let b1 = NT_CODON_MAP[b1 as usize] as usize;
+    let b2 = NT_CODON_MAP[b2 as usize] as usize;
+    let b3 = NT_CODON_MAP[b3 as usize] as usize;
+
+    Some((b1 << 4) | (b2 << 2) | b3)
+}
+
+/// Returns the codon table index of the reverse complement of three bases.
+///
+/// Codon table indices encode `T=0, C=1, A=2, G=3`, so complementing is `^ 2`.
+#[inline]
+fn revcomp_codon_index(b1: u8, b2: u8, b3: u8) -> Option<usize> {
+    codon_index(b3, b2, b1).map(|index| index ^ 0b10_1010)
+}
+
+/// Translates `seq` in `frame` until the first stop codon (inclusive).
+///
+/// Reverse frames are translated from the reverse complement of `seq`
+/// without allocating it. Codons containing a base other than `ACGTU`
+/// translate to `X`.
 pub fn translate(codon_table_type: CodonTable, frame: &Frame, seq: &[u8]) -> Vec<u8> {
+    translate_impl(codon_table_type, frame, seq, false, false)
+}
+
+/// Like [`translate`], but handles ambiguous bases according to `policy`.
+///
+/// [`AmbigPolicy::Skip`] omits codons containing an ambiguous base from the
+/// protein, [`AmbigPolicy::MapToN`] translates them to `X` (as [`translate`]
+/// does) and [`AmbigPolicy::Random`] resolves them to compatible bases first.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidSymbolError`] for the first ambiguous base
+/// under [`AmbigPolicy::Error`].
+pub fn translate_with_policy(
+    codon_table_type: CodonTable,
+    frame: &Frame,
+    seq: &[u8],
+    policy: AmbigPolicy,
+) -> Result<Vec<u8>, BioError> {
+    let seq = apply_ambig_policy(seq, policy)?;
+    Ok(translate_impl(
+        codon_table_type,
+        frame,
+        &seq,
+        false,
+        policy == AmbigPolicy::Skip,
+    ))
+}
+
+/// Like [`translate`], but treats the first codon as an initiator.
+///
+/// If the first codon is in the table's start codon set (e.g. `GTG` or `TTG`
+/// in [`CodonTable::Bacterial`]) it is translated as `M`, as it would be when
+/// translating a CDS. Other codons, including later occurrences of the same
+/// start codons, are translated normally.
+pub fn translate_with_start(codon_table_type: CodonTable, frame: &Frame, seq: &[u8]) -> Vec<u8> {
+    translate_impl(codon_table_type, frame, seq, true, false)
+}
+
+fn translate_impl(
+    codon_table_type: CodonTable,
+    frame: &Frame,
+    seq: &[u8],
+    initiator: bool,
+    skip_ambiguous: bool,
+) -> Vec<u8> {
     let start_pos = frame.start_pos();
 
     if seq.len() < 3 {
@@ -10,16 +85,28 @@ pub fn translate(codon_table_type: CodonTable, frame: &Frame, seq: &[u8]) -> Vec
 
     let codon_table = codon_table_type.table();
 
-    let mut translated: Vec<u8> = Vec::with_capacity(seq.len() / 3);
+    let codons: Box<dyn Iterator<Item = Option<usize>>> = match frame.strand() {
+        Strand::Forward => Box::new(
+            seq[start_pos..]
+                .chunks_exact(3)
+                .map(|c| codon_index(c[0], c[1], c[2])),
+        ),
+        Strand::Reverse => Box::new(
+            seq[..seq.len() - start_pos]
+                .rchunks_exact(3)
+                .map(|c| revcomp_codon_index(c[0], c[1], c[2])),
+        ),
+    };
 
-    for codon in seq[start_pos..].chunks_exact(3) {
-        let b1 = NT_CODON_MAP[codon[0] as usize] as usize;
-        let b2 = NT_CODON_MAP[codon[1] as usize] as usize;
-        let b3 = NT_CODON_MAP[codon[2] as usize] as usize;
-
-        let index = (b1 << 4) | (b2 << 2) | b3;
+    let mut translated: Vec<u8> = Vec::with_capacity(seq.len() / 3);
 
-        let aa = codon_table[index];
+    for (i, index) in codons.enumerate() {
+        let aa = match index {
+            None if skip_ambiguous => continue,
+            None => b'X',
+            Some(index) if initiator && i == 0 && codon_table_type.starts()[index] == b'M' => b'M',
+            Some(index) => codon_table[index],
+        };
 
         translated.push(aa);
 
@@ -35,23 +122,106 @@ pub fn translate(codon_table_type: CodonTable, frame: &Frame, seq: &[u8]) -> Vec
 pub mod tests {
     use super::*;
     use crate::aminoacid::utils::Frame;
+    use crate::nucleotide::reverse_complement;
     use rstest::*;
 
     #[rstest]
-    // frame 1
     #[case(b"", b"", Frame::First)]
     #[case(b"A", b"", Frame::First)]
     #[case(b"GT", b"", Frame::First)]
     #[case(b"ATG", b"M", Frame::First)]
     #[case(b"ATGTGAAAA", b"M*", Frame::First)] // terminates after `GTG` due to stop codon.
-    // frame 2
     #[case(b"A", b"", Frame::Second)]
     #[case(b"ATG", b"", Frame::Second)]
     #[case(b"AATG", b"M", Frame::Second)] // first codon is `ATG`.
     #[case(b"TTGA", b"*", Frame::Second)] // first codon is `TGA`.
+    #[case(b"CAT", b"M", Frame::ReverseFirst)] // reverse complement is `ATG`.
+    #[case(b"TTTCATG", b"MK", Frame::ReverseSecond)] // reverse complement is `CATGAAA`.
+    #[case(b"TCAAAACATAA", b"MF*", Frame::ReverseThird)]
 
     fn test_translate(#[case] seq: &[u8], #[case] expected: &[u8], #[case] frame: Frame) {
         let translated = translate(CodonTable::Standard, &frame, seq);
         assert_eq!(&translated[..], expected);
     }
+
+    #[rstest]
+    #[case(Frame::ReverseFirst, Frame::First)]
+    #[case(Frame::ReverseSecond, Frame::Second)]
+    #[case(Frame::ReverseThird, Frame::Third)]
+    fn test_reverse_frame_matches_revcomp(#[case] reverse: Frame, #[case] forward: Frame) {
+        let seq = b"ATGCGTACGTTAGCCATGGCAACGTTTGACCA";
+
+        assert_eq!(
+            translate(CodonTable::Standard, &reverse, seq),
+            translate(CodonTable::Standard, &forward, &reverse_complement(seq))
+        );
+    }
+
+    #[rstest]
+    #[case(CodonTable::Bacterial, b"GTGGTGTAA", b"MV*")]
+    #[case(CodonTable::Bacterial, b"TTGATTTAA", b"MI*")]
+    #[case(CodonTable::Bacterial, b"ATTCCC", b"MP")]
+    #[case(CodonTable::Standard, b"ATTCCC", b"IP")] // `ATT` is not a start in table 1.
+    #[case(CodonTable::Standard, b"CTGTGA", b"M*")]
+    #[case(CodonTable::Bacterial, b"TAA", b"*")]
+    fn test_translate_with_start(
+        #[case] table: CodonTable,
+        #[case] seq: &[u8],
+        #[case] expected: &[u8],
+    ) {
+        assert_eq!(translate_with_start(table, &Frame::First, seq), expected);
+    }
+
+    #[test]
+    fn test_translate_with_start_reverse() {
+        // reverse complement is `GTGAAATAG`.
+        let seq = b"CTATTTCAC";
+        assert_eq!(
+            translate_with_start(CodonTable::Bacterial, &Frame::ReverseFirst, seq),
+            b"MK*"
+        );
+        assert_eq!(
+            translate(CodonTable::Bacterial, &Frame::ReverseFirst, seq),
+            b"VK*"
+        );
+    }
+
+    #[rstest]
+    #[case(b"ATGNNNTGA", Frame::First, b"MX*")]
+    #[case(b"ATGRCCTGA", Frame::First, b"MX*")]
+    #[case(b"TCANNNCAT", Frame::ReverseFirst, b"MX*")]
+    fn test_translate_ambiguous(#[case] seq: &[u8], #[case] frame: Frame, #[case] expected: &[u8]) {
+        assert_eq!(translate(CodonTable::Standard, &frame, seq), expected);
+    }
+
+    #[rstest]
+    #[case(AmbigPolicy::Skip, Some(b"MK*".as_slice()))]
+    #[case(AmbigPolicy::MapToN, Some(b"MXK*".as_slice()))]
+    #[case(AmbigPolicy::Error, None)]
+    fn test_translate_with_policy(#[case] policy: AmbigPolicy, #[case] expected: Option<&[u8]>) {
+        let translated =
+            translate_with_policy(CodonTable::Standard, &Frame::First, b"ATGGCYAAATAA", policy);
+        assert_eq!(translated.ok().as_deref(), expected);
+    }
+
+    #[test]
+    fn test_translate_with_random_policy() {
+        // `GCY` is `GCC` or `GCT`, both alanine.
+        let translated = translate_with_policy(
+            CodonTable::Standard,
+            &Frame::First,
+            b"ATGGCYAAATAA",
+            AmbigPolicy::Random { seed: 3 },
+        );
+        assert_eq!(translated.unwrap(), b"MAK*");
+    }
+
+    #[test]
+    fn test_frame_numbers() {
+        for frame in Frame::ALL {
+            assert_eq!(Frame::from_number(frame.number()), Some(frame));
+        }
+        assert_eq!(Frame::ReverseSecond.number(), -2);
+        assert_eq!(Frame::from_number(0), None);
+    }
 }
@@ -1,15 +1,299 @@
+use crate::errors::BioError;
+use crate::nucleotide::Strand;
+
+/// Reading frame, `+1`/`+2`/`+3` on the forward strand and `-1`/`-2`/`-3` on
+/// the reverse complement.
+///
+/// Reverse frames are offset from the 3' end of the forward sequence, so
+/// `ReverseFirst` starts at the last base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Frame {
     First,
     Second,
     Third,
+    ReverseFirst,
+    ReverseSecond,
+    ReverseThird,
 }
 
 impl Frame {
+    /// All six frames, in the order `+1, +2, +3, -1, -2, -3`.
+    pub const ALL: [Frame; 6] = [
+        Frame::First,
+        Frame::Second,
+        Frame::Third,
+        Frame::ReverseFirst,
+        Frame::ReverseSecond,
+        Frame::ReverseThird,
+    ];
+
+    /// Offset of the first codon from the start of the strand being translated.
     pub fn start_pos(&self) -> usize {
         match self {
-            Frame::First => 0,
-            Frame::Second => 1,
-            Frame::Third => 2,
+            Frame::First | Frame::ReverseFirst => 0,
+            Frame::Second | Frame::ReverseSecond => 1,
+            Frame::Third | Frame::ReverseThird => 2,
+        }
+    }
+
+    pub fn strand(&self) -> Strand {
+        match self {
+            Frame::First | Frame::Second | Frame::Third => Strand::Forward,
+            _ => Strand::Reverse,
+        }
+    }
+
+    /// Frame number as `1`, `2`, `3`, `-1`, `-2` or `-3`.
+    pub fn number(&self) -> i8 {
+        let n = self.start_pos() as i8 + 1;
+        match self.strand() {
+            Strand::Forward => n,
+            Strand::Reverse => -n,
+        }
+    }
+
+    /// Parses a frame number (`1`, `2`, `3`, `-1`, `-2` or `-3`).
+    pub fn from_number(n: i8) -> Option<Frame> {
+        Frame::ALL.into_iter().find(|f| f.number() == n)
+    }
+}
+
+/// Maps between genomic coordinates and CDS/codon coordinates of a coding sequence.
+///
+/// The CDS is given as zero-based half-open `[start, end)` genomic intervals
+/// (e.g. the CDS features of one transcript) and a strand. CDS coordinates
+/// run 5' to 3' along the coding strand, so on [`Strand::Reverse`] CDS
+/// position `0` is the last base of the last interval.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::aminoacid::CdsMap;
+/// use bio_utils_rs::nucleotide::Strand;
+///
+/// // Two exons, 5 and 4 bases, on the reverse strand.
+/// let cds = CdsMap::new(&[(10, 15), (20, 24)], Strand::Reverse).unwrap();
+///
+/// assert_eq!(cds.genomic_to_cds(23), Some(0));
+/// assert_eq!(cds.genomic_to_cds(14), Some(4));
+/// assert_eq!(cds.codon_to_genomic(1), Some([20, 14, 13]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CdsMap {
+    /// Intervals in coding order, i.e. descending on the reverse strand.
+    intervals: Vec<(usize, usize)>,
+    strand: Strand,
+    len: usize,
+}
+
+impl CdsMap {
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `intervals` is empty,
+    /// contains an empty interval, or is not sorted and non-overlapping.
+    pub fn new(intervals: &[(usize, usize)], strand: Strand) -> Result<Self, BioError> {
+        if intervals.is_empty() {
+            return Err(BioError::InvalidParameterError(
+                "CDS must have at least one interval.".to_string(),
+            ));
         }
+
+        for (i, &(start, end)) in intervals.iter().enumerate() {
+            if start >= end {
+                return Err(BioError::InvalidParameterError(format!(
+                    "CDS interval [{}, {}) is empty or reversed.",
+                    start, end
+                )));
+            }
+
+            if i > 0 && start < intervals[i - 1].1 {
+                return Err(BioError::InvalidParameterError(format!(
+                    "CDS interval [{}, {}) overlaps or precedes the previous interval.",
+                    start, end
+                )));
+            }
+        }
+
+        let mut intervals = intervals.to_vec();
+        if strand == Strand::Reverse {
+            intervals.reverse();
+        }
+
+        Ok(Self {
+            len: intervals.iter().map(|(start, end)| end - start).sum(),
+            intervals,
+            strand,
+        })
+    }
+
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Total length of the CDS in bases.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of complete codons.
+    pub fn num_codons(&self) -> usize {
+        self.len / 3
+    }
+
+    /// CDS position of genomic position `pos`, or `None` if `pos` is outside the CDS.
+    pub fn genomic_to_cds(&self, pos: usize) -> Option<usize> {
+        let mut offset = 0;
+
+        for &(start, end) in &self.intervals {
+            if (start..end).contains(&pos) {
+                return Some(match self.strand {
+                    Strand::Forward => offset + pos - start,
+                    Strand::Reverse => offset + end - 1 - pos,
+                });
+            }
+            offset += end - start;
+        }
+
+        None
+    }
+
+    /// Genomic position of CDS position `cds_pos`, or `None` if it is past the end of the CDS.
+    pub fn cds_to_genomic(&self, cds_pos: usize) -> Option<usize> {
+        let mut offset = 0;
+
+        for &(start, end) in &self.intervals {
+            let len = end - start;
+            if cds_pos < offset + len {
+                return Some(match self.strand {
+                    Strand::Forward => start + cds_pos - offset,
+                    Strand::Reverse => end - 1 - (cds_pos - offset),
+                });
+            }
+            offset += len;
+        }
+
+        None
+    }
+
+    /// Codon index and position within the codon (`0..3`) of genomic position `pos`.
+    pub fn genomic_to_codon(&self, pos: usize) -> Option<(usize, usize)> {
+        self.genomic_to_cds(pos)
+            .map(|cds_pos| (cds_pos / 3, cds_pos % 3))
+    }
+
+    /// Genomic positions of the three bases of codon `codon`, in coding order.
+    ///
+    /// Returns `None` if the codon is not complete within the CDS. Codons
+    /// spanning an intron map to positions in both intervals.
+    pub fn codon_to_genomic(&self, codon: usize) -> Option<[usize; 3]> {
+        let first = codon.checked_mul(3)?;
+        Some([
+            self.cds_to_genomic(first)?,
+            self.cds_to_genomic(first + 1)?,
+            self.cds_to_genomic(first + 2)?,
+        ])
+    }
+
+    /// Reading frame of genomic position `pos` relative to the CDS.
+    ///
+    /// Returns the frame a translation of the genomic forward strand must use
+    /// for `pos` to be the first base of a codon, e.g. [`Frame::ReverseSecond`]
+    /// for a reverse strand CDS whose codon starts one base before the 3' end.
+    pub fn frame_at(&self, pos: usize, genome_len: usize) -> Option<Frame> {
+        let (_, codon_pos) = self.genomic_to_codon(pos)?;
+        let codon_start = match self.strand {
+            Strand::Forward => pos.checked_sub(codon_pos)?,
+            Strand::Reverse => pos + codon_pos,
+        };
+
+        let offset = match self.strand {
+            Strand::Forward => codon_start % 3,
+            Strand::Reverse => (genome_len.checked_sub(codon_start + 1)?) % 3,
+        };
+
+        let frame = match (self.strand, offset) {
+            (Strand::Forward, 0) => Frame::First,
+            (Strand::Forward, 1) => Frame::Second,
+            (Strand::Forward, _) => Frame::Third,
+            (Strand::Reverse, 0) => Frame::ReverseFirst,
+            (Strand::Reverse, 1) => Frame::ReverseSecond,
+            (Strand::Reverse, _) => Frame::ReverseThird,
+        };
+
+        Some(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn forward() -> CdsMap {
+        CdsMap::new(&[(2, 6), (10, 15)], Strand::Forward).unwrap()
+    }
+
+    fn reverse() -> CdsMap {
+        CdsMap::new(&[(2, 6), (10, 15)], Strand::Reverse).unwrap()
+    }
+
+    #[rstest]
+    #[case(forward(), 2, Some(0))]
+    #[case(forward(), 5, Some(3))]
+    #[case(forward(), 10, Some(4))]
+    #[case(forward(), 14, Some(8))]
+    #[case(forward(), 7, None)]
+    #[case(reverse(), 14, Some(0))]
+    #[case(reverse(), 10, Some(4))]
+    #[case(reverse(), 5, Some(5))]
+    #[case(reverse(), 2, Some(8))]
+    #[case(reverse(), 15, None)]
+    fn test_genomic_to_cds(
+        #[case] cds: CdsMap,
+        #[case] pos: usize,
+        #[case] expected: Option<usize>,
+    ) {
+        assert_eq!(cds.genomic_to_cds(pos), expected);
+        if let Some(cds_pos) = expected {
+            assert_eq!(cds.cds_to_genomic(cds_pos), Some(pos));
+        }
+    }
+
+    #[test]
+    fn test_codons() {
+        let cds = forward();
+        assert_eq!(cds.len(), 9);
+        assert_eq!(cds.num_codons(), 3);
+        assert_eq!(cds.codon_to_genomic(1), Some([5, 10, 11]));
+        assert_eq!(cds.codon_to_genomic(3), None);
+        assert_eq!(cds.genomic_to_codon(11), Some((1, 2)));
+
+        assert_eq!(reverse().codon_to_genomic(1), Some([11, 10, 5]));
+    }
+
+    #[test]
+    fn test_frame_at() {
+        let genome = b"AAATGAAAAAAAAATAGAA";
+        let cds = CdsMap::new(&[(2, 8)], Strand::Forward).unwrap();
+        assert_eq!(cds.frame_at(3, genome.len()), Some(Frame::Third));
+
+        // Reverse strand codon starting at genomic position 16, two bases from the end.
+        let cds = CdsMap::new(&[(10, 17)], Strand::Reverse).unwrap();
+        assert_eq!(cds.frame_at(16, genome.len()), Some(Frame::ReverseThird));
+        assert_eq!(cds.frame_at(15, genome.len()), Some(Frame::ReverseThird));
+        assert_eq!(cds.frame_at(9, genome.len()), None);
+    }
+
+    #[rstest]
+    #[case(vec![])]
+    #[case(vec![(5, 5)])]
+    #[case(vec![(0, 5), (4, 8)])]
+    #[case(vec![(10, 15), (0, 5)])]
+    fn test_invalid(#[case] intervals: Vec<(usize, usize)>) {
+        assert!(CdsMap::new(&intervals, Strand::Forward).is_err());
     }
 }
@@ -0,0 +1,217 @@
+use crate::errors::BioError;
+use crate::nucleotide::{find_n_runs, split_on_n_runs};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "io")]
+use crate::io::bio_fasta_reader;
+#[cfg(feature = "io")]
+use serde::Serialize;
+#[cfg(feature = "io")]
+use std::io::Write;
+#[cfg(feature = "io")]
+use std::path::Path;
+
+/// Gap (N run) statistics over a set of scaffolds.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize))]
+pub struct GapStats {
+    /// Minimum N run length counted as a gap.
+    pub min_gap: usize,
+    pub num_scaffolds: usize,
+    /// Number of contigs left after splitting every scaffold at its gaps.
+    pub num_contigs: usize,
+    /// Total scaffold length, including gaps.
+    pub total_len: usize,
+    /// Length of every gap, in the order they were found.
+    pub gap_lengths: Vec<usize>,
+}
+
+impl GapStats {
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `min_gap` is `0`.
+    pub fn new(min_gap: usize) -> Result<Self, BioError> {
+        if min_gap == 0 {
+            return Err(BioError::InvalidParameterError(
+                "min gap length must be greater than 0.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            min_gap,
+            num_scaffolds: 0,
+            num_contigs: 0,
+            total_len: 0,
+            gap_lengths: Vec::new(),
+        })
+    }
+
+    /// Adds the gaps of a single scaffold.
+    pub fn add_scaffold(&mut self, seq: &[u8]) {
+        self.num_scaffolds += 1;
+        self.total_len += seq.len();
+
+        // `min_gap` is validated on construction.
+        if let Ok(contigs) = split_on_n_runs(seq, self.min_gap) {
+            self.num_contigs += contigs.len();
+        }
+        if let Ok(gaps) = find_n_runs(seq, self.min_gap) {
+            self.gap_lengths
+                .extend(gaps.into_iter().map(|(start, end)| end - start));
+        }
+    }
+
+    pub fn num_gaps(&self) -> usize {
+        self.gap_lengths.len()
+    }
+
+    /// Total number of bases in gaps.
+    pub fn gap_bases(&self) -> usize {
+        self.gap_lengths.iter().sum()
+    }
+
+    /// Mean gap length, `0.0` if there are no gaps.
+    pub fn mean_gap_len(&self) -> f64 {
+        match self.num_gaps() {
+            0 => 0.0,
+            n => self.gap_bases() as f64 / n as f64,
+        }
+    }
+
+    pub fn max_gap_len(&self) -> Option<usize> {
+        self.gap_lengths.iter().max().copied()
+    }
+
+    /// Number of gaps of each length, ordered by length.
+    pub fn length_distribution(&self) -> BTreeMap<usize, usize> {
+        let mut distribution = BTreeMap::new();
+        for len in &self.gap_lengths {
+            *distribution.entry(*len).or_insert(0) += 1;
+        }
+        distribution
+    }
+}
+
+/// Splits a scaffold into contigs at N runs at least `min_gap` long.
+///
+/// Contigs are named `{id}:{start}-{end}` with one-based inclusive scaffold
+/// coordinates, e.g. `scaffold_1:1-5000`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_gap` is `0`.
+pub fn split_scaffold<'a>(
+    id: &str,
+    seq: &'a [u8],
+    min_gap: usize,
+) -> Result<Vec<(String, &'a [u8])>, BioError> {
+    Ok(split_on_n_runs(seq, min_gap)?
+        .into_iter()
+        .map(|(start, end)| (format!("{}:{}-{}", id, start + 1, end), &seq[start..end]))
+        .collect())
+}
+
+/// Computes [`GapStats`] over the scaffolds of a FASTA file (plain or gzip).
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_gap` is `0`, or
+/// [`BioError`] if the file cannot be read or parsed.
+#[cfg(feature = "io")]
+pub fn scaffold_gap_stats(path: &Path, min_gap: usize) -> Result<GapStats, BioError> {
+    let mut stats = GapStats::new(min_gap)?;
+
+    for record in bio_fasta_reader(Some(path.to_path_buf()))?.records() {
+        stats.add_scaffold(record?.seq());
+    }
+
+    Ok(stats)
+}
+
+/// Splits the scaffolds of a FASTA file into contigs at gaps, writing them to
+/// `writer` as described in [`split_scaffold`], and returns the [`GapStats`].
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_gap` is `0`, or
+/// [`BioError`] if the file cannot be read or parsed, or writing fails.
+#[cfg(feature = "io")]
+pub fn split_scaffolds<W: Write>(
+    path: &Path,
+    min_gap: usize,
+    writer: &mut bio::io::fasta::Writer<W>,
+) -> Result<GapStats, BioError> {
+    let mut stats = GapStats::new(min_gap)?;
+
+    for record in bio_fasta_reader(Some(path.to_path_buf()))?.records() {
+        let record = record?;
+        stats.add_scaffold(record.seq());
+
+        for (id, contig) in split_scaffold(record.id(), record.seq(), min_gap)? {
+            writer.write(&id, None, contig)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_stats() {
+        let mut stats = GapStats::new(3).unwrap();
+        stats.add_scaffold(b"ACGTNNNACGTNNACGTNNNNNNA");
+        stats.add_scaffold(b"NNNNACGT");
+        stats.add_scaffold(b"ACGT");
+
+        assert_eq!(stats.num_scaffolds, 3);
+        assert_eq!(stats.num_contigs, 5);
+        assert_eq!(stats.total_len, 36);
+        assert_eq!(stats.gap_lengths, vec![3, 6, 4]);
+        assert_eq!(stats.gap_bases(), 13);
+        assert_eq!(stats.max_gap_len(), Some(6));
+        assert!((stats.mean_gap_len() - 13.0 / 3.0).abs() < 1e-12);
+        assert_eq!(
+            stats.length_distribution().into_iter().collect::<Vec<_>>(),
+            vec![(3, 1), (4, 1), (6, 1)]
+        );
+        assert!(GapStats::new(0).is_err());
+    }
+
+    #[test]
+    fn test_split_scaffold() {
+        let contigs = split_scaffold("scf", b"ACGTNNNNNGGNCC", 2).unwrap();
+        assert_eq!(
+            contigs,
+            vec![
+                ("scf:1-4".to_string(), b"ACGT".as_slice()),
+                ("scf:10-14".to_string(), b"GGNCC".as_slice()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn test_split_scaffolds_file() {
+        use crate::test_utils::temp_seq_file;
+
+        let (_dir, path) = temp_seq_file("scaffolds.fasta");
+        std::fs::write(&path, ">s1\nACGTNNNNNGGCC\n>s2\nTTTT\n").unwrap();
+
+        let mut buf = Vec::new();
+        let stats = {
+            let mut writer = bio::io::fasta::Writer::new(&mut buf);
+            split_scaffolds(&path, 5, &mut writer).unwrap()
+        };
+
+        assert_eq!(stats.num_gaps(), 1);
+        assert_eq!(stats, scaffold_gap_stats(&path, 5).unwrap());
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            ">s1:1-4\nACGT\n>s1:10-13\nGGCC\n>s2:1-4\nTTTT\n"
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Scaffold assembly helpers.
+//!
+//! [`GapStats`] summarizes the gaps (N runs) of a set of scaffolds and
+//! [`split_scaffold`] splits a scaffold into contigs at those gaps, naming
+//! each contig by its scaffold coordinates. With the `io` feature,
+//! [`scaffold_gap_stats`] and [`split_scaffolds`] run the same over a FASTA
+//! file, optionally writing the contigs with the crate's FASTA writers.
+
+mod gaps;
+pub use gaps::*;
@@ -0,0 +1,389 @@
+//! CIGAR strings.
+//!
+//! [`Cigar`] parses and formats SAM CIGAR strings, including the extended
+//! `=`/`X` operations produced by [`align_wfa`](crate::alignment::align_wfa),
+//! and answers common questions about an alignment: how many query and
+//! target bases it consumes, its length and identity, and where a position
+//! on one sequence lands on the other.
+
+use crate::errors::BioError;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single CIGAR operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CigarOp {
+    /// `M`, alignment match (sequence match or mismatch).
+    Match,
+    /// `I`, insertion to the target (query only).
+    Ins,
+    /// `D`, deletion from the target (target only).
+    Del,
+    /// `N`, skipped target region, e.g. an intron.
+    RefSkip,
+    /// `S`, soft clip (query bases present in the record but not aligned).
+    SoftClip,
+    /// `H`, hard clip (query bases absent from the record).
+    HardClip,
+    /// `P`, padding.
+    Pad,
+    /// `=`, sequence match.
+    Equal,
+    /// `X`, sequence mismatch.
+    Diff,
+}
+
+impl CigarOp {
+    pub fn as_char(&self) -> char {
+        match self {
+            CigarOp::Match => 'M',
+            CigarOp::Ins => 'I',
+            CigarOp::Del => 'D',
+            CigarOp::RefSkip => 'N',
+            CigarOp::SoftClip => 'S',
+            CigarOp::HardClip => 'H',
+            CigarOp::Pad => 'P',
+            CigarOp::Equal => '=',
+            CigarOp::Diff => 'X',
+        }
+    }
+
+    /// Parses a CIGAR operation character, or returns `None` if unknown.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'M' => Some(CigarOp::Match),
+            'I' => Some(CigarOp::Ins),
+            'D' => Some(CigarOp::Del),
+            'N' => Some(CigarOp::RefSkip),
+            'S' => Some(CigarOp::SoftClip),
+            'H' => Some(CigarOp::HardClip),
+            'P' => Some(CigarOp::Pad),
+            '=' => Some(CigarOp::Equal),
+            'X' => Some(CigarOp::Diff),
+            _ => None,
+        }
+    }
+
+    /// Whether the operation consumes query bases, as defined by the SAM specification.
+    pub fn consumes_query(&self) -> bool {
+        matches!(
+            self,
+            CigarOp::Match | CigarOp::Ins | CigarOp::SoftClip | CigarOp::Equal | CigarOp::Diff
+        )
+    }
+
+    /// Whether the operation consumes target (reference) bases, as defined by the SAM specification.
+    pub fn consumes_target(&self) -> bool {
+        matches!(
+            self,
+            CigarOp::Match | CigarOp::Del | CigarOp::RefSkip | CigarOp::Equal | CigarOp::Diff
+        )
+    }
+}
+
+/// A run-length encoded CIGAR as `(length, operation)` pairs.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::cigar::Cigar;
+///
+/// let cigar: Cigar = "2S5=1X2I4=3D2=".parse().unwrap();
+/// assert_eq!(cigar.query_len(), 16);
+/// assert_eq!(cigar.target_len(), 15);
+/// assert_eq!(cigar.to_string(), "2S5=1X2I4=3D2=");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Cigar {
+    ops: Vec<(u32, CigarOp)>,
+}
+
+impl Cigar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a CIGAR string. `*` and the empty string give an empty CIGAR.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidFormatError`] if the string contains an
+    /// unknown operation, an operation without or with a zero length, or
+    /// trailing digits.
+    pub fn parse(cigar: &str) -> Result<Self, BioError> {
+        let mut parsed = Self::new();
+        if cigar == "*" {
+            return Ok(parsed);
+        }
+
+        let invalid = || BioError::InvalidFormatError(format!("invalid CIGAR string {}.", cigar));
+
+        let mut len: Option<u32> = None;
+        for c in cigar.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                len = Some(
+                    len.unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|len| len.checked_add(digit))
+                        .ok_or_else(invalid)?,
+                );
+                continue;
+            }
+
+            let op = CigarOp::from_char(c).ok_or_else(invalid)?;
+            match len.take() {
+                Some(len) if len > 0 => parsed.ops.push((len, op)),
+                _ => return Err(invalid()),
+            }
+        }
+
+        match len {
+            Some(_) => Err(invalid()),
+            None => Ok(parsed),
+        }
+    }
+
+    /// The `(length, operation)` runs.
+    pub fn ops(&self) -> &[(u32, CigarOp)] {
+        &self.ops
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Appends `len` operations, extending the last run if it has the same operation.
+    pub fn push(&mut self, len: u32, op: CigarOp) {
+        if len == 0 {
+            return;
+        }
+
+        match self.ops.last_mut() {
+            Some((last_len, last_op)) if *last_op == op => *last_len += len,
+            _ => self.ops.push((len, op)),
+        }
+    }
+
+    fn sum_where<F: Fn(&CigarOp) -> bool>(&self, f: F) -> usize {
+        self.ops
+            .iter()
+            .filter(|(_, op)| f(op))
+            .map(|(len, _)| *len as usize)
+            .sum()
+    }
+
+    /// Number of query bases consumed, including soft clips.
+    pub fn query_len(&self) -> usize {
+        self.sum_where(CigarOp::consumes_query)
+    }
+
+    /// Number of target bases spanned by the alignment, including skipped regions.
+    pub fn target_len(&self) -> usize {
+        self.sum_where(CigarOp::consumes_target)
+    }
+
+    /// Number of alignment columns: matches, mismatches, insertions and deletions.
+    pub fn alignment_len(&self) -> usize {
+        self.sum_where(|op| {
+            matches!(
+                op,
+                CigarOp::Match | CigarOp::Ins | CigarOp::Del | CigarOp::Equal | CigarOp::Diff
+            )
+        })
+    }
+
+    /// BLAST-like identity, the fraction of alignment columns that are `=`.
+    ///
+    /// Returns `None` if the CIGAR contains `M` (match or mismatch is unknown)
+    /// or has no alignment columns.
+    pub fn identity(&self) -> Option<f64> {
+        if self.ops.iter().any(|(_, op)| *op == CigarOp::Match) {
+            return None;
+        }
+
+        match self.alignment_len() {
+            0 => None,
+            len => Some(self.sum_where(|op| *op == CigarOp::Equal) as f64 / len as f64),
+        }
+    }
+
+    /// Maps a query position to the aligned target position.
+    ///
+    /// Query positions count from the first query base in the CIGAR (soft
+    /// clips included), target positions from the alignment start. Returns
+    /// `None` for query bases that are inserted, soft clipped or past the end.
+    pub fn query_to_target(&self, pos: usize) -> Option<usize> {
+        self.liftover(pos, CigarOp::consumes_query, CigarOp::consumes_target)
+    }
+
+    /// Maps a target position to the aligned query position, see [`Cigar::query_to_target`].
+    ///
+    /// Returns `None` for target bases that are deleted, skipped or past the end.
+    pub fn target_to_query(&self, pos: usize) -> Option<usize> {
+        self.liftover(pos, CigarOp::consumes_target, CigarOp::consumes_query)
+    }
+
+    fn liftover<F, G>(&self, pos: usize, consumes_from: F, consumes_to: G) -> Option<usize>
+    where
+        F: Fn(&CigarOp) -> bool,
+        G: Fn(&CigarOp) -> bool,
+    {
+        let (mut from, mut to) = (0, 0);
+
+        for (len, op) in &self.ops {
+            let len = *len as usize;
+            let (from_step, to_step) = (consumes_from(op), consumes_to(op));
+
+            if from_step && pos < from + len {
+                return match to_step {
+                    true => Some(to + pos - from),
+                    false => None,
+                };
+            }
+
+            if from_step {
+                from += len;
+            }
+            if to_step {
+                to += len;
+            }
+        }
+
+        None
+    }
+}
+
+impl FromStr for Cigar {
+    type Err = BioError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Cigar {
+    /// Formats the CIGAR as in SAM, with `*` for an empty CIGAR.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ops.is_empty() {
+            return write!(f, "*");
+        }
+
+        for (len, op) in &self.ops {
+            write!(f, "{}{}", len, op.as_char())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromIterator<(u32, CigarOp)> for Cigar {
+    fn from_iter<I: IntoIterator<Item = (u32, CigarOp)>>(iter: I) -> Self {
+        let mut cigar = Cigar::new();
+        for (len, op) in iter {
+            cigar.push(len, op);
+        }
+        cigar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("10M", 10, 10, 10)]
+    #[case("3S5M2I4M1D3M2H", 17, 13, 15)]
+    #[case("5M100N5M", 10, 110, 10)]
+    #[case("*", 0, 0, 0)]
+    fn test_lengths(
+        #[case] cigar: &str,
+        #[case] query_len: usize,
+        #[case] target_len: usize,
+        #[case] alignment_len: usize,
+    ) {
+        let parsed = Cigar::parse(cigar).unwrap();
+        assert_eq!(parsed.query_len(), query_len);
+        assert_eq!(parsed.target_len(), target_len);
+        assert_eq!(parsed.alignment_len(), alignment_len);
+        assert_eq!(parsed.to_string(), cigar);
+    }
+
+    #[rstest]
+    #[case("M")]
+    #[case("10")]
+    #[case("0M")]
+    #[case("5M3Q")]
+    #[case("99999999999M")]
+    fn test_parse_invalid(#[case] cigar: &str) {
+        assert!(matches!(
+            Cigar::parse(cigar),
+            Err(BioError::InvalidFormatError(_))
+        ));
+    }
+
+    #[rstest]
+    #[case("8=2X", Some(0.8))]
+    #[case("4=2I4=", Some(0.8))]
+    #[case("10M", None)]
+    #[case("5S", None)]
+    fn test_identity(#[case] cigar: &str, #[case] expected: Option<f64>) {
+        assert_eq!(Cigar::parse(cigar).unwrap().identity(), expected);
+    }
+
+    #[test]
+    fn test_liftover() {
+        // Query:  SS AAA II CC -- G
+        // Target:    AAA    CC DD G
+        let cigar = Cigar::parse("2S3=2I2=2D1=").unwrap();
+
+        let query_to_target: Vec<Option<usize>> =
+            (0..11).map(|pos| cigar.query_to_target(pos)).collect();
+        assert_eq!(
+            query_to_target,
+            vec![
+                None,
+                None,
+                Some(0),
+                Some(1),
+                Some(2),
+                None,
+                None,
+                Some(3),
+                Some(4),
+                Some(7),
+                None
+            ]
+        );
+
+        let target_to_query: Vec<Option<usize>> =
+            (0..9).map(|pos| cigar.target_to_query(pos)).collect();
+        assert_eq!(
+            target_to_query,
+            vec![
+                Some(2),
+                Some(3),
+                Some(4),
+                Some(7),
+                Some(8),
+                None,
+                None,
+                Some(9),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_push_merges_runs() {
+        let cigar: Cigar = [
+            (2, CigarOp::Equal),
+            (3, CigarOp::Equal),
+            (0, CigarOp::Diff),
+            (1, CigarOp::Ins),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(cigar.ops(), &[(5, CigarOp::Equal), (1, CigarOp::Ins)]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::errors::BioError;
+use crate::rng::SplitMix64;
+
+/// Downsamples mapped intervals towards `target_depth`, e.g. to normalize
+/// amplicon coverage before variant calling or assembly.
+///
+/// Intervals are visited in a random order determined by `seed`, and an
+/// interval is kept if it covers a position still below `target_depth`. So
+/// every position keeps a depth of at least `min(target_depth, full depth)`,
+/// and low coverage regions are not starved by overlapping high coverage
+/// ones, while depth may exceed the target where kept intervals overlap.
+/// Returns the indices of the kept intervals in input order.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `target_depth` is `0`, or
+/// an interval is empty or exceeds `ref_len`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::coverage::downsample_to_depth;
+///
+/// let intervals = vec![(0, 10); 100];
+/// let kept = downsample_to_depth(10, &intervals, 5, 42).unwrap();
+///
+/// assert_eq!(kept.len(), 5);
+/// assert_eq!(kept, downsample_to_depth(10, &intervals, 5, 42).unwrap());
+/// ```
+pub fn downsample_to_depth(
+    ref_len: usize,
+    intervals: &[(usize, usize)],
+    target_depth: u32,
+    seed: u64,
+) -> Result<Vec<usize>, BioError> {
+    if target_depth == 0 {
+        return Err(BioError::InvalidParameterError(
+            "target depth must be greater than 0.".to_string(),
+        ));
+    }
+
+    if let Some((start, end)) = intervals
+        .iter()
+        .find(|(start, end)| start >= end || *end > ref_len)
+    {
+        return Err(BioError::InvalidParameterError(format!(
+            "interval [{}, {}) is empty or exceeds reference length {}.",
+            start, end, ref_len
+        )));
+    }
+
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    SplitMix64::new(seed).shuffle(&mut order);
+
+    let mut depth = vec![0u32; ref_len];
+    let mut kept = Vec::new();
+
+    for i in order {
+        let span = &mut depth[intervals[i].0..intervals[i].1];
+        if span.iter().any(|&d| d < target_depth) {
+            span.iter_mut().for_each(|d| *d += 1);
+            kept.push(i);
+        }
+    }
+
+    kept.sort_unstable();
+    Ok(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coverage::CoverageTrack;
+    use rstest::*;
+
+    #[test]
+    fn test_downsample_to_depth_normalizes() {
+        // A deep amplicon overlapping a shallow one.
+        let intervals: Vec<(usize, usize)> = std::iter::repeat_n((0, 300), 500)
+            .chain(std::iter::repeat_n((250, 600), 40))
+            .collect();
+
+        let kept = downsample_to_depth(600, &intervals, 50, 1).unwrap();
+        let mut track = CoverageTrack::new(600);
+        track.extend(kept.iter().map(|&i| intervals[i])).unwrap();
+        let depth = track.depth();
+
+        assert_eq!((depth[100], depth[275], depth[400]), (50, 90, 40));
+    }
+
+    #[test]
+    fn test_downsample_to_depth_keeps_low_coverage() {
+        let intervals = [(0, 10), (5, 15), (20, 30)];
+        assert_eq!(
+            downsample_to_depth(30, &intervals, 2, 9).unwrap(),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[rstest]
+    #[case(0, (0, 10))]
+    #[case(5, (5, 5))]
+    #[case(5, (5, 11))]
+    fn test_downsample_to_depth_invalid(
+        #[case] target_depth: u32,
+        #[case] interval: (usize, usize),
+    ) {
+        assert!(downsample_to_depth(10, &[interval], target_depth, 1).is_err());
+    }
+}
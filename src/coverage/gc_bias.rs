@@ -0,0 +1,334 @@
+use crate::errors::BioError;
+use crate::nucleotide::{composition, windows};
+use std::io::Write;
+
+/// Maximum fraction of ambiguous bases in a window used by the GC-bias report.
+const MAX_WINDOW_AMBIGUOUS: f64 = 0.1;
+
+/// A GC content bin of a [`GcBiasReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcBiasBin {
+    /// Lower GC fraction bound (inclusive).
+    pub gc_start: f64,
+    /// Upper GC fraction bound (exclusive, except for the last bin).
+    pub gc_end: f64,
+    /// Number of reference windows in the bin.
+    pub windows: usize,
+    /// Total read count of those windows.
+    pub reads: f64,
+    /// Mean reads per window in the bin relative to the mean over all
+    /// windows, `NaN` for an empty bin. `1.0` means no bias.
+    pub normalized_coverage: f64,
+}
+
+/// Read coverage by reference GC content, computed by [`gc_bias_from_counts`]
+/// or [`gc_bias_from_intervals`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcBiasReport {
+    pub window_size: usize,
+    /// Equal width GC bins covering `[0.0, 1.0]`.
+    pub bins: Vec<GcBiasBin>,
+    /// Windows left out because they are truncated or more than 10% ambiguous.
+    pub skipped_windows: usize,
+}
+
+impl GcBiasReport {
+    /// Sum of `(window fraction - read fraction)` over the bins where windows
+    /// are underrepresented by reads, restricted to bins selected by `keep`.
+    fn dropout(&self, keep: impl Fn(&GcBiasBin) -> bool) -> f64 {
+        let total_windows: usize = self.bins.iter().map(|bin| bin.windows).sum();
+        let total_reads: f64 = self.bins.iter().map(|bin| bin.reads).sum();
+
+        if total_windows == 0 || total_reads <= 0.0 {
+            return 0.0;
+        }
+
+        self.bins
+            .iter()
+            .filter(|bin| keep(bin))
+            .map(|bin| {
+                let window_fraction = bin.windows as f64 / total_windows as f64;
+                let read_fraction = bin.reads / total_reads;
+                (window_fraction - read_fraction).max(0.0)
+            })
+            .sum::<f64>()
+            * 100.0
+    }
+
+    /// Picard's `AT_DROPOUT`: the percentage of reads missing from AT-rich
+    /// windows (bins starting at or below 50% GC) compared to the reference.
+    pub fn at_dropout(&self) -> f64 {
+        self.dropout(|bin| bin.gc_start <= 0.5)
+    }
+
+    /// Picard's `GC_DROPOUT`: as [`GcBiasReport::at_dropout`], for GC-rich
+    /// windows (bins starting at or above 50% GC).
+    pub fn gc_dropout(&self) -> f64 {
+        self.dropout(|bin| bin.gc_start >= 0.5)
+    }
+
+    /// Writes the bins as TSV with a header line, ready for plotting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if writing fails.
+    pub fn write_tsv<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), BioError> {
+        writeln!(
+            writer,
+            "gc_start\tgc_end\twindows\treads\tnormalized_coverage"
+        )?;
+
+        for bin in &self.bins {
+            writeln!(
+                writer,
+                "{:.4}\t{:.4}\t{}\t{}\t{:.4}",
+                bin.gc_start, bin.gc_end, bin.windows, bin.reads, bin.normalized_coverage
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Bins the tiled windows of `reference` by GC content and sums the read
+/// count of every window per bin.
+///
+/// `counts` holds one value per window of
+/// [`windows(reference, window_size, window_size)`](crate::nucleotide::windows),
+/// e.g. the number of reads mapped to it. GC content is computed over `ACGT`
+/// bases (case insensitive); the truncated last window and windows with more
+/// than 10% ambiguous bases are skipped. Each of the `num_bins` bins spans an
+/// equal GC range.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `window_size` or
+/// `num_bins` is `0`, or `counts` does not have one value per window.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::coverage::gc_bias_from_counts;
+///
+/// // Alternating AT- and GC-rich windows, with GC-rich windows covered half as well.
+/// let reference = b"ATATATATGCGCGCGCATTAATTAGGCCGGCC";
+/// let report = gc_bias_from_counts(reference, 8, &[10.0, 5.0, 10.0, 5.0], 2).unwrap();
+///
+/// assert_eq!(report.bins[0].windows, 2);
+/// assert!((report.bins[0].normalized_coverage - 4.0 / 3.0).abs() < 1e-9);
+/// assert!((report.bins[1].normalized_coverage - 2.0 / 3.0).abs() < 1e-9);
+/// ```
+pub fn gc_bias_from_counts(
+    reference: &[u8],
+    window_size: usize,
+    counts: &[f64],
+    num_bins: usize,
+) -> Result<GcBiasReport, BioError> {
+    if num_bins == 0 {
+        return Err(BioError::InvalidParameterError(
+            "number of GC bins must be greater than 0.".to_string(),
+        ));
+    }
+
+    let num_windows = reference.len().div_ceil(window_size.max(1));
+    let reference_windows = windows(reference, window_size, window_size)?;
+    if counts.len() != num_windows {
+        return Err(BioError::InvalidParameterError(format!(
+            "expected {} window counts, got {}.",
+            num_windows,
+            counts.len()
+        )));
+    }
+
+    let mut bins: Vec<GcBiasBin> = (0..num_bins)
+        .map(|i| GcBiasBin {
+            gc_start: i as f64 / num_bins as f64,
+            gc_end: (i + 1) as f64 / num_bins as f64,
+            windows: 0,
+            reads: 0.0,
+            normalized_coverage: f64::NAN,
+        })
+        .collect();
+    let mut skipped_windows = 0;
+
+    for ((_, window), &count) in reference_windows.zip(counts) {
+        let comp = composition(window);
+        let acgt = window.len() - comp.ambiguous;
+
+        if window.len() < window_size
+            || acgt == 0
+            || comp.ambiguous as f64 > MAX_WINDOW_AMBIGUOUS * window.len() as f64
+        {
+            skipped_windows += 1;
+            continue;
+        }
+
+        let gc = comp.gc_count() as f64 / acgt as f64;
+        let bin = &mut bins[((gc * num_bins as f64) as usize).min(num_bins - 1)];
+        bin.windows += 1;
+        bin.reads += count;
+    }
+
+    let total_windows: usize = bins.iter().map(|bin| bin.windows).sum();
+    let total_reads: f64 = bins.iter().map(|bin| bin.reads).sum();
+    let mean_reads = total_reads / total_windows as f64;
+
+    for bin in bins.iter_mut().filter(|bin| bin.windows > 0) {
+        bin.normalized_coverage = bin.reads / bin.windows as f64 / mean_reads;
+    }
+
+    Ok(GcBiasReport {
+        window_size,
+        bins,
+        skipped_windows,
+    })
+}
+
+/// Builds a [`GcBiasReport`] from mapped read intervals.
+///
+/// Each zero-based half-open `(start, end)` interval is counted once, in the
+/// window containing its midpoint. See [`gc_bias_from_counts`].
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if an interval is empty or
+/// exceeds the reference, or for the reasons of [`gc_bias_from_counts`].
+pub fn gc_bias_from_intervals<I>(
+    reference: &[u8],
+    window_size: usize,
+    intervals: I,
+    num_bins: usize,
+) -> Result<GcBiasReport, BioError>
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    if window_size == 0 {
+        return Err(BioError::InvalidParameterError(
+            "window size must be greater than 0.".to_string(),
+        ));
+    }
+
+    let mut counts = vec![0.0; reference.len().div_ceil(window_size)];
+
+    for (start, end) in intervals {
+        if start >= end || end > reference.len() {
+            return Err(BioError::InvalidParameterError(format!(
+                "interval [{}, {}) is empty or exceeds reference length {}.",
+                start,
+                end,
+                reference.len()
+            )));
+        }
+
+        counts[(start + (end - start) / 2) / window_size] += 1.0;
+    }
+
+    gc_bias_from_counts(reference, window_size, &counts, num_bins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use rstest::*;
+
+    /// 100 windows of 100 bases with GC content increasing from 0% to 99%.
+    fn gradient_reference() -> Vec<u8> {
+        let mut rng = SplitMix64::new(1);
+        (0..100)
+            .flat_map(|gc| {
+                let mut window: Vec<u8> = (0..100)
+                    .map(|i| match i < gc {
+                        true => b"GC"[i % 2],
+                        false => b"AT"[i % 2],
+                    })
+                    .collect();
+                rng.shuffle(&mut window);
+                window
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gc_bias_unbiased() {
+        let reference = gradient_reference();
+        let report = gc_bias_from_counts(&reference, 100, &[20.0; 100], 10).unwrap();
+
+        assert!(report.bins.iter().all(|bin| bin.windows == 10));
+        assert!(
+            report
+                .bins
+                .iter()
+                .all(|bin| (bin.normalized_coverage - 1.0).abs() < 1e-9)
+        );
+        assert!(report.at_dropout().abs() < 1e-9);
+        assert!(report.gc_dropout().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_gc_bias_from_intervals_gc_dropout() {
+        let reference = gradient_reference();
+        // Reads fall off linearly with GC content: 100 reads at 0% GC, 1 at 99%.
+        let intervals: Vec<(usize, usize)> = (0..100)
+            .flat_map(|window| {
+                (0..100 - window).map(move |read| (window * 100 + read % 50, window * 100 + 50))
+            })
+            .collect();
+
+        let report = gc_bias_from_intervals(&reference, 100, intervals, 10).unwrap();
+        let coverage: Vec<f64> = report
+            .bins
+            .iter()
+            .map(|bin| bin.normalized_coverage)
+            .collect();
+
+        assert!(coverage.windows(2).all(|pair| pair[0] > pair[1]));
+        // Only the 50-60% bin, shared by both metrics, counts towards AT dropout.
+        assert!(report.gc_dropout() > 20.0);
+        assert!(report.at_dropout() < 1.0);
+    }
+
+    #[test]
+    fn test_gc_bias_skipped_windows() {
+        let reference = b"ACGTACGTNNNNNNNNACGTAC";
+        let report = gc_bias_from_counts(reference, 8, &[1.0, 1.0, 1.0], 4).unwrap();
+
+        assert_eq!(report.skipped_windows, 2);
+        assert_eq!(report.bins[2].windows, 1);
+        assert!(report.bins[0].normalized_coverage.is_nan());
+    }
+
+    #[test]
+    fn test_write_tsv() {
+        let report = gc_bias_from_counts(b"AAAAGGGG", 4, &[2.0, 6.0], 2).unwrap();
+
+        let mut buf = Vec::new();
+        report.write_tsv(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "gc_start\tgc_end\twindows\treads\tnormalized_coverage\n\
+             0.0000\t0.5000\t1\t2\t0.5000\n\
+             0.5000\t1.0000\t1\t6\t1.5000\n"
+        );
+    }
+
+    #[rstest]
+    #[case(0, 4, 1)]
+    #[case(4, 0, 1)]
+    #[case(4, 4, 3)]
+    fn test_gc_bias_invalid(
+        #[case] window_size: usize,
+        #[case] num_bins: usize,
+        #[case] counts: usize,
+    ) {
+        assert!(
+            gc_bias_from_counts(b"ACGTACGT", window_size, &vec![0.0; counts], num_bins).is_err()
+        );
+    }
+
+    #[test]
+    fn test_gc_bias_from_intervals_invalid() {
+        assert!(gc_bias_from_intervals(b"ACGTACGT", 4, [(2, 9)], 2).is_err());
+        assert!(gc_bias_from_intervals(b"ACGTACGT", 4, [(2, 2)], 2).is_err());
+    }
+}
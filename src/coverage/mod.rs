@@ -0,0 +1,22 @@
+//! Per-base coverage from intervals.
+//!
+//! [`CoverageTrack`] accumulates `[start, end)` intervals, e.g. from mapping
+//! hits, over a single reference and reports depth, mean depth, breadth and a
+//! depth histogram, with bedGraph and WIG export. [`write_bedgraph_track`] and
+//! [`write_wig_track`] write any windowed metric, such as GC content or
+//! entropy from [`window_values`](crate::nucleotide::window_values), as a
+//! genome browser track. [`gc_bias_from_counts`] and
+//! [`gc_bias_from_intervals`] report read coverage by reference GC content
+//! for library QC, and [`downsample_to_depth`] normalizes deep coverage.
+
+mod downsample;
+pub use downsample::*;
+
+mod gc_bias;
+pub use gc_bias::*;
+
+mod track;
+pub use track::*;
+
+mod tracks;
+pub use tracks::*;
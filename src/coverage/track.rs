@@ -0,0 +1,242 @@
+use super::{write_bedgraph_track, write_wig_track};
+use crate::errors::BioError;
+use crate::stats::Histogram;
+use std::io::Write;
+
+/// Coverage over a single reference sequence of length `ref_len`.
+///
+/// Intervals are stored as a difference array, so adding an interval is `O(1)`
+/// and per-base depth is computed in a single `O(ref_len)` pass.
+#[derive(Debug, Clone)]
+pub struct CoverageTrack {
+    ref_len: usize,
+    diff: Vec<i64>,
+    num_intervals: usize,
+}
+
+impl CoverageTrack {
+    pub fn new(ref_len: usize) -> Self {
+        Self {
+            ref_len,
+            diff: vec![0; ref_len + 1],
+            num_intervals: 0,
+        }
+    }
+
+    pub fn ref_len(&self) -> usize {
+        self.ref_len
+    }
+
+    pub fn num_intervals(&self) -> usize {
+        self.num_intervals
+    }
+
+    /// Adds a zero-based half-open interval `[start, end)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `start > end` or `end`
+    /// exceeds the reference length.
+    pub fn add(&mut self, start: usize, end: usize) -> Result<(), BioError> {
+        if start > end {
+            return Err(BioError::InvalidParameterError(format!(
+                "interval start {} cannot be larger than end {}.",
+                start, end
+            )));
+        }
+
+        if end > self.ref_len {
+            return Err(BioError::InvalidParameterError(format!(
+                "interval end {} exceeds reference length {}.",
+                end, self.ref_len
+            )));
+        }
+
+        self.diff[start] += 1;
+        self.diff[end] -= 1;
+        self.num_intervals += 1;
+
+        Ok(())
+    }
+
+    /// Adds every interval in `intervals`, stopping at the first invalid one.
+    ///
+    /// # Errors
+    ///
+    /// See [`CoverageTrack::add`].
+    pub fn extend<I: IntoIterator<Item = (usize, usize)>>(
+        &mut self,
+        intervals: I,
+    ) -> Result<(), BioError> {
+        for (start, end) in intervals {
+            self.add(start, end)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the depth at every reference position.
+    pub fn depth(&self) -> Vec<u32> {
+        let mut running = 0i64;
+
+        self.diff[..self.ref_len]
+            .iter()
+            .map(|d| {
+                running += d;
+                running as u32
+            })
+            .collect()
+    }
+
+    /// Mean depth over the full reference length. Returns `0.0` for an empty reference.
+    pub fn mean_depth(&self) -> f64 {
+        if self.ref_len == 0 {
+            return 0.0;
+        }
+
+        let total: u64 = self.depth().iter().map(|d| *d as u64).sum();
+        total as f64 / self.ref_len as f64
+    }
+
+    /// Number of reference positions per depth.
+    pub fn depth_histogram(&self) -> Histogram {
+        let mut histogram = Histogram::default();
+        histogram.extend(self.depth().into_iter().map(u64::from));
+
+        histogram
+    }
+
+    /// Fraction of reference positions with depth of at least `min_depth`.
+    ///
+    /// Returns `0.0` for an empty reference.
+    pub fn breadth(&self, min_depth: u32) -> f64 {
+        if self.ref_len == 0 {
+            return 0.0;
+        }
+
+        let covered = self.depth().iter().filter(|d| **d >= min_depth).count();
+        covered as f64 / self.ref_len as f64
+    }
+
+    /// Returns runs of constant depth as `(start, end, depth)`, zero-based half-open.
+    ///
+    /// Zero-depth runs are included so the runs tile the whole reference.
+    pub fn runs(&self) -> Vec<(usize, usize, u32)> {
+        let depth = self.depth();
+        let mut runs = Vec::new();
+        let mut start = 0;
+
+        for i in 1..=depth.len() {
+            if i == depth.len() || depth[i] != depth[start] {
+                runs.push((start, i, depth[start]));
+                start = i;
+            }
+        }
+
+        runs
+    }
+
+    /// Writes coverage as bedGraph, omitting zero-depth runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if writing fails.
+    pub fn write_bedgraph<W: Write>(&self, writer: &mut W, chrom: &str) -> Result<(), BioError> {
+        let runs = self.runs().into_iter().filter(|(_, _, depth)| *depth > 0);
+        write_bedgraph_track(
+            writer,
+            chrom,
+            runs.map(|(start, end, depth)| (start, end, depth as f64)),
+        )?;
+
+        Ok(())
+    }
+
+    /// Writes per-base coverage as a `fixedStep` WIG track with span 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if writing fails.
+    pub fn write_wig<W: Write>(&self, writer: &mut W, chrom: &str) -> Result<(), BioError> {
+        let depth = self.depth().into_iter().map(|depth| depth as f64);
+        write_wig_track(writer, chrom, depth, 1, 1)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn track() -> CoverageTrack {
+        let mut track = CoverageTrack::new(10);
+        track.extend([(0, 4), (2, 6), (2, 4)]).unwrap();
+        track
+    }
+
+    #[test]
+    fn test_depth() {
+        assert_eq!(track().depth(), vec![1, 1, 3, 3, 1, 1, 0, 0, 0, 0]);
+        assert_eq!(track().num_intervals(), 3);
+    }
+
+    #[test]
+    fn test_mean_depth() {
+        assert_eq!(track().mean_depth(), 1.0);
+        assert_eq!(CoverageTrack::new(0).mean_depth(), 0.0);
+    }
+
+    #[test]
+    fn test_depth_histogram() {
+        let histogram = track().depth_histogram();
+
+        assert_eq!(
+            histogram.bins().collect::<Vec<_>>(),
+            vec![(0, 4), (1, 4), (3, 2)]
+        );
+        assert_eq!(histogram.median(), Some(1));
+    }
+
+    #[rstest]
+    #[case(0, 1.0)]
+    #[case(1, 0.6)]
+    #[case(2, 0.2)]
+    #[case(4, 0.0)]
+    fn test_breadth(#[case] min_depth: u32, #[case] expected: f64) {
+        assert!((track().breadth(min_depth) - expected).abs() < 1e-9);
+    }
+
+    #[rstest]
+    #[case(3, 2)]
+    #[case(0, 11)]
+    fn test_add_invalid(#[case] start: usize, #[case] end: usize) {
+        assert!(CoverageTrack::new(10).add(start, end).is_err());
+    }
+
+    #[test]
+    fn test_write_bedgraph() {
+        let mut buf = Vec::new();
+        track().write_bedgraph(&mut buf, "chr1").unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "chr1\t0\t2\t1\nchr1\t2\t4\t3\nchr1\t4\t6\t1\n"
+        );
+    }
+
+    #[test]
+    fn test_write_wig() {
+        let mut track = CoverageTrack::new(3);
+        track.add(1, 3).unwrap();
+
+        let mut buf = Vec::new();
+        track.write_wig(&mut buf, "chr1").unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "fixedStep chrom=chr1 start=1 step=1 span=1\n0\n1\n1\n"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::errors::BioError;
+use std::io::Write;
+
+/// Writes `(start, end, value)` intervals, e.g. from
+/// [`window_values`](crate::nucleotide::window_values), as bedGraph lines.
+///
+/// Intervals are zero-based half-open and must be sorted and non-overlapping,
+/// so use tiling windows (`step >= size`). `NaN` values, e.g. a metric that is
+/// undefined for a window, are skipped. Returns the number of lines written.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if an interval is empty,
+/// unsorted or overlaps the previous one, or [`BioError::IoError`] if writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::coverage::write_bedgraph_track;
+/// use bio_utils_rs::nucleotide::{gc_content, window_values};
+///
+/// let gc = window_values(b"GGGGATATGCAT", 4, 4, gc_content).unwrap();
+///
+/// let mut buf = Vec::new();
+/// write_bedgraph_track(&mut buf, "chr1", gc).unwrap();
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "chr1\t0\t4\t1\nchr1\t4\t8\t0\nchr1\t8\t12\t0.5\n"
+/// );
+/// ```
+pub fn write_bedgraph_track<W, I>(writer: &mut W, chrom: &str, values: I) -> Result<usize, BioError>
+where
+    W: Write + ?Sized,
+    I: IntoIterator<Item = (usize, usize, f64)>,
+{
+    let mut prev_end = 0;
+    let mut written = 0;
+
+    for (start, end, value) in values {
+        if start >= end || start < prev_end {
+            return Err(BioError::InvalidParameterError(format!(
+                "bedGraph interval [{}, {}) is empty, unsorted or overlaps the previous interval.",
+                start, end
+            )));
+        }
+        prev_end = end;
+
+        if value.is_nan() {
+            continue;
+        }
+
+        writeln!(writer, "{}\t{}\t{}\t{}", chrom, start, end, value)?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+/// Writes values of consecutive windows as a `fixedStep` WIG track.
+///
+/// The `i`-th value belongs to the window starting at zero-based position
+/// `i * step` and spanning `span` bases. A `NaN` value cannot be represented
+/// in `fixedStep` data, so it ends the current block and the next value starts
+/// a new declaration line. Returns the number of data lines written.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `step` or `span` is `0`, or
+/// [`BioError::IoError`] if writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::coverage::write_wig_track;
+///
+/// let mut buf = Vec::new();
+/// write_wig_track(&mut buf, "chr1", [0.5, f64::NAN, 0.25], 10, 10).unwrap();
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "fixedStep chrom=chr1 start=1 step=10 span=10\n0.5\n\
+///      fixedStep chrom=chr1 start=21 step=10 span=10\n0.25\n"
+/// );
+/// ```
+pub fn write_wig_track<W, I>(
+    writer: &mut W,
+    chrom: &str,
+    values: I,
+    step: usize,
+    span: usize,
+) -> Result<usize, BioError>
+where
+    W: Write + ?Sized,
+    I: IntoIterator<Item = f64>,
+{
+    if step == 0 || span == 0 {
+        return Err(BioError::InvalidParameterError(format!(
+            "WIG step {} and span {} must be larger than 0.",
+            step, span
+        )));
+    }
+
+    let mut in_block = false;
+    let mut written = 0;
+
+    for (i, value) in values.into_iter().enumerate() {
+        if value.is_nan() {
+            in_block = false;
+            continue;
+        }
+
+        if !in_block {
+            writeln!(
+                writer,
+                "fixedStep chrom={} start={} step={} span={}",
+                chrom,
+                i * step + 1,
+                step,
+                span
+            )?;
+            in_block = true;
+        }
+
+        writeln!(writer, "{}", value)?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_write_bedgraph_track_skips_nan() {
+        let mut buf = Vec::new();
+        let written = write_bedgraph_track(
+            &mut buf,
+            "chr1",
+            [(0, 5, 0.2), (5, 10, f64::NAN), (12, 14, 1.5)],
+        )
+        .unwrap();
+
+        assert_eq!(written, 2);
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "chr1\t0\t5\t0.2\nchr1\t12\t14\t1.5\n"
+        );
+    }
+
+    #[rstest]
+    #[case(vec![(0, 0, 1.0)])]
+    #[case(vec![(5, 10, 1.0), (0, 5, 1.0)])]
+    #[case(vec![(0, 10, 1.0), (5, 15, 1.0)])]
+    fn test_write_bedgraph_track_invalid(#[case] values: Vec<(usize, usize, f64)>) {
+        assert!(write_bedgraph_track(&mut Vec::new(), "chr1", values).is_err());
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 0)]
+    fn test_write_wig_track_invalid(#[case] step: usize, #[case] span: usize) {
+        assert!(write_wig_track(&mut Vec::new(), "chr1", [1.0], step, span).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Crate-wide error types.
 
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Unified error type for all `bio_utils_rs` operations.
@@ -18,6 +19,31 @@ pub enum BioError {
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
+    /// CSV/TSV serialization failed.
+    #[cfg(feature = "io")]
+    #[error("CSV error: {0}")]
+    CsvError(#[from] csv::Error),
+
+    /// TOML deserialization failed.
+    #[cfg(feature = "io")]
+    #[error("TOML parse error: {0}")]
+    TomlParseError(#[from] toml::de::Error),
+
+    /// An HTTP request for a remote file failed.
+    #[cfg(feature = "http")]
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] ureq::Error),
+
+    /// Building or writing an Arrow record batch failed.
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    ArrowError(#[from] arrow_schema::ArrowError),
+
+    /// Writing a Parquet file failed.
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    ParquetError(#[from] parquet::errors::ParquetError),
+
     /// File path does not end with a recognized sequence file extension.
     #[error("File has invalid extension: {0}")]
     InvalidFileExtensionError(String),
@@ -26,8 +52,94 @@ pub enum BioError {
     #[error("File does not exist: {0}")]
     FileDoesNotExistError(String),
 
+    /// A binary sequence file (e.g. `.2bit`, `.nib`) has an invalid signature or layout.
+    #[error("Invalid file format: {0}")]
+    InvalidFormatError(String),
+
+    /// A sequence contains a byte outside of the expected alphabet.
+    #[error("Invalid symbol {symbol:?} at position {pos} for {alphabet} alphabet")]
+    InvalidSymbolError {
+        pos: usize,
+        symbol: char,
+        alphabet: &'static str,
+    },
+
+    /// The same record ID was written more than once.
+    #[error("Duplicate record ID: {0}")]
+    DuplicateIdError(String),
+
+    /// A sequence file ended mid-record or is corrupt past a certain point.
+    ///
+    /// `records` complete records (`bytes` uncompressed bytes) were read before the failure.
+    #[error("File truncated after {records} complete records ({bytes} bytes): {reason}")]
+    TruncatedFileError {
+        records: usize,
+        bytes: u64,
+        reason: String,
+    },
+
+    /// An error while reading a record, with the file and record it occurred
+    /// at. Created by [`BioError::at_record`].
+    #[error(
+        "Parse error in {} at record {record}{}: {source}",
+        location_path(path),
+        location_id(id)
+    )]
+    ParseErrorAt {
+        /// Input file, `None` for stdin or in-memory input.
+        path: Option<PathBuf>,
+        /// 1-based number of the failing record.
+        record: usize,
+        /// ID of the failing record, if its header could be parsed.
+        id: Option<String>,
+        source: Box<BioError>,
+    },
+
+    /// rust-bio failed to parse a FASTQ record.
+    #[error("FASTQ parse error: {0}")]
+    FastqParseError(#[from] bio::io::fastq::Error),
+
     /// Needletail failed to open or parse a sequence file.
     #[cfg(feature = "io")]
     #[error("Needletail failed to parse file: {0}")]
     NeedletailParseError(#[from] needletail::errors::ParseError),
 }
+
+fn location_path(path: &Option<PathBuf>) -> String {
+    path.as_ref()
+        .map_or_else(|| "<stream>".to_string(), |path| path.display().to_string())
+}
+
+fn location_id(id: &Option<String>) -> String {
+    id.as_ref()
+        .map_or_else(String::new, |id| format!(" ({})", id))
+}
+
+impl BioError {
+    /// Wraps the error with the input file, 1-based record number and record
+    /// ID it occurred at, so failures deep into large files can be located.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio_utils_rs::errors::BioError;
+    /// use std::path::Path;
+    ///
+    /// let error = BioError::InvalidFormatError("sequence and quality differ in length.".into())
+    ///     .at_record(Some(Path::new("reads.fastq")), 1_204_332, Some("read_7"));
+    ///
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Parse error in reads.fastq at record 1204332 (read_7): \
+    ///      Invalid file format: sequence and quality differ in length."
+    /// );
+    /// ```
+    pub fn at_record(self, path: Option<&Path>, record: usize, id: Option<&str>) -> Self {
+        BioError::ParseErrorAt {
+            path: path.map(Path::to_path_buf),
+            record,
+            id: id.map(str::to_string),
+            source: Box::new(self),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! C ABI of the core sequence functions (`ffi` feature).
+//!
+//! Built into the `cdylib` of the crate, with declarations in
+//! `include/bio_utils_rs.h`. Sequences are passed as a byte pointer and a
+//! length and are never retained. Functions that can fail return a
+//! [`BU_OK`]-style status code, and sketches are opaque handles that must be
+//! released with [`bu_sketch_free`].
+
+use crate::kmers::{self, Sketch};
+use crate::nucleotide;
+use std::slice;
+
+/// Success.
+pub const BU_OK: i32 = 0;
+/// A required pointer was null.
+pub const BU_ERR_NULL: i32 = 1;
+/// A parameter was out of range, e.g. an invalid k-mer size.
+pub const BU_ERR_INVALID: i32 = 2;
+
+/// Opaque FracMinHash sketch handle.
+pub struct BuSketch(Sketch);
+
+/// Borrows `len` bytes at `seq`, with a null pointer allowed for `len == 0`.
+///
+/// # Safety
+///
+/// `seq` must be valid for reads of `len` bytes if `len > 0`.
+unsafe fn bytes<'a>(seq: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (seq.is_null(), len) {
+        (_, 0) => Some(&[]),
+        (true, _) => None,
+        // SAFETY: guaranteed by the caller.
+        (false, _) => Some(unsafe { slice::from_raw_parts(seq, len) }),
+    }
+}
+
+/// Writes the reverse complement of `seq` to `out`, see
+/// [`reverse_complement`](nucleotide::reverse_complement).
+///
+/// Returns [`BU_OK`], or [`BU_ERR_NULL`] if `seq` or `out` is null and
+/// `len > 0`.
+///
+/// # Safety
+///
+/// `seq` must be valid for reads and `out` for writes of `len` bytes, and
+/// they must not overlap.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_reverse_complement(seq: *const u8, len: usize, out: *mut u8) -> i32 {
+    // SAFETY: guaranteed by the caller.
+    let Some(seq) = (unsafe { bytes(seq, len) }) else {
+        return BU_ERR_NULL;
+    };
+    if len > 0 && out.is_null() {
+        return BU_ERR_NULL;
+    }
+
+    for (i, nt) in nucleotide::reverse_complement(seq).into_iter().enumerate() {
+        // SAFETY: `i < len` and `out` is valid for `len` bytes.
+        unsafe { out.add(i).write(nt) };
+    }
+
+    BU_OK
+}
+
+/// GC content of `seq`, see [`gc_content`](nucleotide::gc_content). `NaN` if
+/// `seq` is null and `len > 0`.
+///
+/// # Safety
+///
+/// `seq` must be valid for reads of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_gc_content(seq: *const u8, len: usize) -> f64 {
+    // SAFETY: guaranteed by the caller.
+    match unsafe { bytes(seq, len) } {
+        Some(seq) => nucleotide::gc_content(seq),
+        None => f64::NAN,
+    }
+}
+
+/// Shannon entropy in bits of the `A`, `C`, `G` and `T` frequencies of `seq`,
+/// `0.0` if it has none. `NaN` if `seq` is null and `len > 0`.
+///
+/// # Safety
+///
+/// `seq` must be valid for reads of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_entropy(seq: *const u8, len: usize) -> f32 {
+    // SAFETY: guaranteed by the caller.
+    match unsafe { bytes(seq, len) } {
+        Some(seq) => {
+            let (canonical, _, _) = nucleotide::nucleotide_counts(seq);
+            nucleotide::shannon_entropy(&nucleotide::nucleotide_probabilities(&canonical))
+        }
+        None => f32::NAN,
+    }
+}
+
+/// Phred score of an error probability, see
+/// [`error_to_phred`](nucleotide::error_to_phred).
+#[unsafe(no_mangle)]
+pub extern "C" fn bu_error_to_phred(error: f64) -> u8 {
+    nucleotide::error_to_phred(error)
+}
+
+/// Error probability of a raw Phred+33 quality byte, see
+/// [`qual_to_error`](nucleotide::qual_to_error).
+#[unsafe(no_mangle)]
+pub extern "C" fn bu_qual_to_error(qual: u8) -> f64 {
+    nucleotide::qual_to_error(qual)
+}
+
+/// Computes the FracMinHash sketch of `seq` into `*sketch`, see
+/// [`frac_min_hash`](kmers::frac_min_hash).
+///
+/// Returns [`BU_OK`], [`BU_ERR_NULL`] or [`BU_ERR_INVALID`] for an invalid
+/// `kmer_size` or `scaled`, leaving `*sketch` untouched on error.
+///
+/// # Safety
+///
+/// `seq` must be valid for reads of `len` bytes and `sketch` for a pointer
+/// write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_frac_min_hash(
+    seq: *const u8,
+    len: usize,
+    kmer_size: usize,
+    scaled: u64,
+    sketch: *mut *mut BuSketch,
+) -> i32 {
+    // SAFETY: guaranteed by the caller.
+    let Some(seq) = (unsafe { bytes(seq, len) }) else {
+        return BU_ERR_NULL;
+    };
+    if sketch.is_null() {
+        return BU_ERR_NULL;
+    }
+
+    match kmers::frac_min_hash(kmer_size, scaled, seq) {
+        Ok(hashes) => {
+            // SAFETY: `sketch` is non-null and valid for writes.
+            unsafe { sketch.write(Box::into_raw(Box::new(BuSketch(hashes)))) };
+            BU_OK
+        }
+        Err(_) => BU_ERR_INVALID,
+    }
+}
+
+/// Number of hashes in `sketch`, `0` if it is null.
+///
+/// # Safety
+///
+/// `sketch` must be null or a live handle from [`bu_frac_min_hash`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_sketch_len(sketch: *const BuSketch) -> usize {
+    // SAFETY: guaranteed by the caller.
+    unsafe { sketch.as_ref() }.map_or(0, |sketch| sketch.0.len())
+}
+
+/// Jaccard index of two sketches, `NaN` if either is null.
+///
+/// # Safety
+///
+/// Both sketches must be null or live handles from [`bu_frac_min_hash`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_sketch_jaccard(a: *const BuSketch, b: *const BuSketch) -> f64 {
+    // SAFETY: guaranteed by the caller.
+    match unsafe { (a.as_ref(), b.as_ref()) } {
+        (Some(a), Some(b)) => kmers::jaccard(&a.0, &b.0),
+        _ => f64::NAN,
+    }
+}
+
+/// Fraction of `query` contained in `reference`, `NaN` if either is null.
+///
+/// # Safety
+///
+/// Both sketches must be null or live handles from [`bu_frac_min_hash`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_sketch_containment(
+    query: *const BuSketch,
+    reference: *const BuSketch,
+) -> f64 {
+    // SAFETY: guaranteed by the caller.
+    match unsafe { (query.as_ref(), reference.as_ref()) } {
+        (Some(query), Some(reference)) => kmers::containment(&query.0, &reference.0),
+        _ => f64::NAN,
+    }
+}
+
+/// Releases a sketch. Does nothing for null.
+///
+/// # Safety
+///
+/// `sketch` must be null or a live handle from [`bu_frac_min_hash`], and is
+/// dangling afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_sketch_free(sketch: *mut BuSketch) {
+    if !sketch.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` and is live.
+        drop(unsafe { Box::from_raw(sketch) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    #[test]
+    fn test_bu_reverse_complement() {
+        let seq = b"AACGT";
+        let mut out = [0u8; 5];
+
+        let status = unsafe { bu_reverse_complement(seq.as_ptr(), seq.len(), out.as_mut_ptr()) };
+        assert_eq!((status, &out), (BU_OK, b"ACGTT"));
+
+        let status = unsafe { bu_reverse_complement(ptr::null(), 5, out.as_mut_ptr()) };
+        assert_eq!(status, BU_ERR_NULL);
+        let status = unsafe { bu_reverse_complement(ptr::null(), 0, ptr::null_mut()) };
+        assert_eq!(status, BU_OK);
+    }
+
+    #[test]
+    fn test_bu_metrics() {
+        let seq = b"ACGTGG";
+
+        assert!((unsafe { bu_gc_content(seq.as_ptr(), seq.len()) } - 4.0 / 6.0).abs() < 1e-9);
+        assert_eq!(unsafe { bu_entropy(b"ACGT".as_ptr(), 4) }, 2.0);
+        assert!(unsafe { bu_gc_content(ptr::null(), 1) }.is_nan());
+        assert_eq!(bu_error_to_phred(0.001), 30);
+        assert_eq!(bu_qual_to_error(b'+'), 0.1);
+    }
+
+    #[test]
+    fn test_bu_sketch() {
+        let seq = b"ACGTTGCAAGTC";
+        let mut a = ptr::null_mut();
+        let mut b = ptr::null_mut();
+
+        unsafe {
+            assert_eq!(bu_frac_min_hash(seq.as_ptr(), 12, 5, 1, &mut a), BU_OK);
+            assert_eq!(bu_frac_min_hash(seq.as_ptr(), 8, 5, 1, &mut b), BU_OK);
+            assert_eq!(bu_sketch_len(b), 4);
+            assert_eq!(bu_sketch_jaccard(a, a), 1.0);
+            assert_eq!(bu_sketch_containment(b, a), 1.0);
+            assert!(bu_sketch_jaccard(a, ptr::null()).is_nan());
+
+            let mut invalid = ptr::null_mut();
+            assert_eq!(
+                bu_frac_min_hash(seq.as_ptr(), 12, 0, 1, &mut invalid),
+                BU_ERR_INVALID
+            );
+            assert!(invalid.is_null());
+
+            bu_sketch_free(a);
+            bu_sketch_free(b);
+            bu_sketch_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::errors::BioError;
+use crate::nucleotide::{Strand, reverse_complement};
+use bio::alphabets::dna;
+use bio::data_structures::bwt::{BWT, Less, Occ, bwt, less};
+use bio::data_structures::fmindex::{BackwardSearchResult, FMIndex, FMIndexable};
+use bio::data_structures::suffix_array::{RawSuffixArray, suffix_array};
+
+/// Sampling rate of the occurrence table. Lower is faster but uses more memory.
+const OCC_SAMPLING_RATE: u32 = 32;
+
+/// Normalizes a base to uppercase `ACGT`, mapping everything else to `N`.
+#[inline]
+fn normalize(nt: u8) -> u8 {
+    match nt.to_ascii_uppercase() {
+        b'A' => b'A',
+        b'C' => b'C',
+        b'G' => b'G',
+        b'T' => b'T',
+        _ => b'N',
+    }
+}
+
+/// FM-index over a single DNA text, supporting exact `count` and `locate` queries.
+///
+/// The text is uppercased and non-`ACGT` bytes are stored as `N`; patterns are
+/// normalized the same way before searching. The full suffix array is kept in
+/// memory (one `usize` per base), trading memory for fast `locate`.
+///
+/// An index built with [`FmIndex::new_both_strands`] also stores the reverse
+/// complement of the text, so [`FmIndex::locate_both_strands`] finds hits on
+/// both strands with a single backward search.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::index::FmIndex;
+///
+/// let index = FmIndex::new(b"ACGTACGTAC").unwrap();
+/// assert_eq!(index.locate(b"ACG"), vec![0, 4]);
+/// assert_eq!(index.count(b"GTA"), 2);
+/// ```
+pub struct FmIndex {
+    sa: RawSuffixArray,
+    fm: FMIndex<BWT, Less, Occ>,
+    text_len: usize,
+    both_strands: bool,
+}
+
+impl FmIndex {
+    /// Builds the suffix array, BWT and occurrence table for `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `text` is empty.
+    pub fn new(text: &[u8]) -> Result<Self, BioError> {
+        Self::build(text, false)
+    }
+
+    /// Builds an index over `text` and its reverse complement, doubling memory
+    /// use in exchange for single-lookup strand-aware queries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `text` is empty.
+    pub fn new_both_strands(text: &[u8]) -> Result<Self, BioError> {
+        Self::build(text, true)
+    }
+
+    fn build(text: &[u8], both_strands: bool) -> Result<Self, BioError> {
+        if text.is_empty() {
+            return Err(BioError::InvalidParameterError(
+                "cannot build an index over an empty text.".to_string(),
+            ));
+        }
+
+        let capacity = match both_strands {
+            true => 2 * (text.len() + 1),
+            false => text.len() + 1,
+        };
+
+        let mut normalized: Vec<u8> = Vec::with_capacity(capacity);
+        normalized.extend(text.iter().map(|nt| normalize(*nt)));
+        normalized.push(b'$');
+
+        if both_strands {
+            let rc = reverse_complement(&normalized[..text.len()]);
+            normalized.extend(rc);
+            normalized.push(b'$');
+        }
+
+        let alphabet = dna::n_alphabet();
+        let sa = suffix_array(&normalized);
+        let bwt = bwt(&normalized, &sa);
+        let less = less(&bwt, &alphabet);
+        let occ = Occ::new(&bwt, OCC_SAMPLING_RATE, &alphabet);
+
+        Ok(Self {
+            sa,
+            fm: FMIndex::new(bwt, less, occ),
+            text_len: text.len(),
+            both_strands,
+        })
+    }
+
+    /// Length of the indexed text, excluding the sentinel.
+    pub fn len(&self) -> usize {
+        self.text_len
+    }
+
+    /// Always `false`, since empty texts are rejected by [`FmIndex::new`].
+    pub fn is_empty(&self) -> bool {
+        self.text_len == 0
+    }
+
+    /// Returns the suffix array interval `[lower, upper)` matching `pattern`, if any.
+    fn interval(&self, pattern: &[u8]) -> Option<(usize, usize)> {
+        if pattern.is_empty() {
+            return None;
+        }
+
+        let normalized: Vec<u8> = pattern.iter().map(|nt| normalize(*nt)).collect();
+
+        match self.fm.backward_search(normalized.iter()) {
+            BackwardSearchResult::Complete(interval) => Some((interval.lower, interval.upper)),
+            _ => None,
+        }
+    }
+
+    /// Maps a suffix array hit of a `pattern_len` long pattern to a forward strand start.
+    #[inline]
+    fn to_forward(&self, pos: usize, pattern_len: usize) -> (usize, Strand) {
+        match pos < self.text_len {
+            true => (pos, Strand::Forward),
+            // Offset within the reverse complement, mirrored back onto the forward strand.
+            false => {
+                let rc_pos = pos - self.text_len - 1;
+                (self.text_len - rc_pos - pattern_len, Strand::Reverse)
+            }
+        }
+    }
+
+    /// Returns the number of exact occurrences of `pattern` on the forward strand.
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        match self.both_strands {
+            true => self.locate(pattern).len(),
+            false => self
+                .interval(pattern)
+                .map_or(0, |(lower, upper)| upper - lower),
+        }
+    }
+
+    /// Returns the sorted start positions of all exact occurrences of `pattern`
+    /// on the forward strand.
+    ///
+    /// Unlike [`search_exact`](crate::nucleotide::search_exact), overlapping
+    /// occurrences are reported.
+    pub fn locate(&self, pattern: &[u8]) -> Vec<usize> {
+        let Some((lower, upper)) = self.interval(pattern) else {
+            return vec![];
+        };
+
+        let mut positions: Vec<usize> = self.sa[lower..upper]
+            .iter()
+            .copied()
+            .filter(|pos| *pos < self.text_len)
+            .collect();
+
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Returns `(start, strand)` for all occurrences of `pattern` or its reverse
+    /// complement, sorted by start then strand. Starts are forward strand coordinates.
+    ///
+    /// Uses a single backward search when the index was built with
+    /// [`FmIndex::new_both_strands`], and two searches otherwise.
+    pub fn locate_both_strands(&self, pattern: &[u8]) -> Vec<(usize, Strand)> {
+        let mut hits: Vec<(usize, Strand)> = match self.both_strands {
+            true => match self.interval(pattern) {
+                Some((lower, upper)) => self.sa[lower..upper]
+                    .iter()
+                    .map(|pos| self.to_forward(*pos, pattern.len()))
+                    .collect(),
+                None => vec![],
+            },
+            false => {
+                let rc = reverse_complement(pattern);
+                self.locate(pattern)
+                    .into_iter()
+                    .map(|pos| (pos, Strand::Forward))
+                    .chain(
+                        self.locate(&rc)
+                            .into_iter()
+                            .map(|pos| (pos, Strand::Reverse)),
+                    )
+                    .collect()
+            }
+        };
+
+        hits.sort_unstable();
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"GCCTTAACATTATTACGCCTA", b"TTA", vec![3, 9, 12])]
+    #[case(b"AAAA", b"AA", vec![0, 1, 2])]
+    #[case(b"ACGTACGT", b"acg", vec![0, 4])]
+    #[case(b"ACGTACGT", b"GGG", vec![])]
+    #[case(b"ACGTACGT", b"", vec![])]
+    #[case(b"ACNNRT", b"NNN", vec![2])]
+    #[case(b"ACGT", b"ACGTA", vec![])]
+    fn test_locate(#[case] text: &[u8], #[case] pattern: &[u8], #[case] expected: Vec<usize>) {
+        let index = FmIndex::new(text).unwrap();
+        assert_eq!(index.locate(pattern), expected);
+        assert_eq!(index.count(pattern), expected.len());
+    }
+
+    #[test]
+    fn test_empty_text() {
+        assert!(FmIndex::new(b"").is_err());
+    }
+
+    #[rstest]
+    #[case(b"AACGTTT", b"AAA", vec![(4, Strand::Reverse)])]
+    #[case(b"GGAATTCC", b"GAATTC", vec![(1, Strand::Forward), (1, Strand::Reverse)])]
+    #[case(b"ACCTTTGGT", b"ACC", vec![(0, Strand::Forward), (6, Strand::Reverse)])]
+    #[case(b"ACCTTTGGT", b"CCC", vec![])]
+    fn test_locate_both_strands(
+        #[case] text: &[u8],
+        #[case] pattern: &[u8],
+        #[case] expected: Vec<(usize, Strand)>,
+    ) {
+        let single = FmIndex::new(text).unwrap();
+        let double = FmIndex::new_both_strands(text).unwrap();
+
+        assert_eq!(single.locate_both_strands(pattern), expected);
+        assert_eq!(double.locate_both_strands(pattern), expected);
+        assert_eq!(single.locate(pattern), double.locate(pattern));
+        assert_eq!(single.count(pattern), double.count(pattern));
+    }
+}
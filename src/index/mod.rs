@@ -0,0 +1,8 @@
+//! Full-text indexes for repeated exact pattern queries.
+//!
+//! Building an index is a one-time cost per reference, after which each
+//! query runs in time proportional to the pattern length rather than the
+//! reference length.
+
+mod fm_index;
+pub use fm_index::*;
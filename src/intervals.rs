@@ -0,0 +1,324 @@
+//! Half-open interval arithmetic.
+//!
+//! [`Interval`] is a zero-based, half-open `[start, end)` range, convertible
+//! from and to the `(start, end)` tuples reported by e.g.
+//! [`find_n_runs`](crate::nucleotide::find_n_runs) or accepted by
+//! [`softmask_intervals`](crate::nucleotide::softmask_intervals). The set
+//! operations accept any iterator of such tuples or intervals, in any order
+//! and possibly overlapping, and return sorted, non-overlapping intervals.
+//! Empty intervals (`start >= end`) are ignored.
+
+use crate::errors::BioError;
+
+/// A zero-based, half-open `[start, end)` interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Interval {
+    /// Creates an interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `start > end`.
+    pub fn new(start: usize, end: usize) -> Result<Self, BioError> {
+        if start > end {
+            return Err(BioError::InvalidParameterError(format!(
+                "interval start {} is greater than end {}.",
+                start, end
+            )));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, pos: usize) -> bool {
+        self.start <= pos && pos < self.end
+    }
+
+    /// Whether the intervals share at least one position.
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    /// The positions shared by both intervals, if any.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+
+        match start < end {
+            true => Some(Interval { start, end }),
+            false => None,
+        }
+    }
+}
+
+impl From<(usize, usize)> for Interval {
+    fn from((start, end): (usize, usize)) -> Self {
+        Self { start, end }
+    }
+}
+
+impl From<&(usize, usize)> for Interval {
+    fn from(&(start, end): &(usize, usize)) -> Self {
+        Self { start, end }
+    }
+}
+
+impl From<Interval> for (usize, usize) {
+    fn from(interval: Interval) -> Self {
+        (interval.start, interval.end)
+    }
+}
+
+/// Merges overlapping and adjacent (book-ended) intervals.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::intervals::{Interval, merge_intervals};
+///
+/// let merged = merge_intervals([(5, 8), (0, 3), (2, 4), (4, 5), (10, 12)]);
+/// assert_eq!(merged, vec![Interval { start: 0, end: 8 }, Interval { start: 10, end: 12 }]);
+/// ```
+pub fn merge_intervals<I, T>(intervals: I) -> Vec<Interval>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<Interval>,
+{
+    let mut sorted: Vec<Interval> = intervals
+        .into_iter()
+        .map(Into::into)
+        .filter(|interval: &Interval| !interval.is_empty())
+        .collect();
+    sorted.sort_unstable();
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(sorted.len());
+    for interval in sorted {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+
+    merged
+}
+
+/// Returns the positions covered by both `a` and `b`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::intervals::{Interval, intersect_intervals};
+///
+/// let shared = intersect_intervals([(0, 10)], [(2, 4), (8, 12)]);
+/// assert_eq!(shared, vec![Interval { start: 2, end: 4 }, Interval { start: 8, end: 10 }]);
+/// ```
+pub fn intersect_intervals<A, B, T, U>(a: A, b: B) -> Vec<Interval>
+where
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = U>,
+    T: Into<Interval>,
+    U: Into<Interval>,
+{
+    let a = merge_intervals(a);
+    let b = merge_intervals(b);
+
+    let mut shared = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if let Some(intersection) = a[i].intersection(&b[j]) {
+            shared.push(intersection);
+        }
+
+        match a[i].end <= b[j].end {
+            true => i += 1,
+            false => j += 1,
+        }
+    }
+
+    shared
+}
+
+/// Returns the positions covered by `a` but not by `b`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::intervals::{Interval, subtract_intervals};
+///
+/// let remaining = subtract_intervals([(0, 10)], [(2, 4), (8, 12)]);
+/// assert_eq!(remaining, vec![Interval { start: 0, end: 2 }, Interval { start: 4, end: 8 }]);
+/// ```
+pub fn subtract_intervals<A, B, T, U>(a: A, b: B) -> Vec<Interval>
+where
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = U>,
+    T: Into<Interval>,
+    U: Into<Interval>,
+{
+    let b = merge_intervals(b);
+
+    let mut remaining = Vec::new();
+    let mut j = 0;
+
+    for interval in merge_intervals(a) {
+        let mut start = interval.start;
+
+        // Intervals in `b` ending before `start` cannot affect later intervals of `a` either.
+        while j < b.len() && b[j].end <= start {
+            j += 1;
+        }
+
+        let mut k = j;
+        while k < b.len() && b[k].start < interval.end {
+            if b[k].start > start {
+                remaining.push(Interval {
+                    start,
+                    end: b[k].start,
+                });
+            }
+            start = start.max(b[k].end);
+            k += 1;
+        }
+
+        if start < interval.end {
+            remaining.push(Interval {
+                start,
+                end: interval.end,
+            });
+        }
+    }
+
+    remaining
+}
+
+/// Returns the positions of `[0, len)` not covered by `intervals`, e.g. the
+/// unmasked regions of a sequence of length `len`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if an interval extends past `len`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::intervals::{Interval, complement_intervals};
+///
+/// let gaps = complement_intervals([(2, 4), (6, 10)], 10).unwrap();
+/// assert_eq!(gaps, vec![Interval { start: 0, end: 2 }, Interval { start: 4, end: 6 }]);
+/// ```
+pub fn complement_intervals<I, T>(intervals: I, len: usize) -> Result<Vec<Interval>, BioError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<Interval>,
+{
+    let merged = merge_intervals(intervals);
+
+    if let Some(last) = merged.last()
+        && last.end > len
+    {
+        return Err(BioError::InvalidParameterError(format!(
+            "interval [{}, {}) extends past length {}.",
+            last.start, last.end, len
+        )));
+    }
+
+    Ok(subtract_intervals([(0, len)], merged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn tuples(intervals: Vec<Interval>) -> Vec<(usize, usize)> {
+        intervals.into_iter().map(Into::into).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 6).unwrap();
+        let b = Interval::from((5, 9));
+
+        assert_eq!(a.len(), 4);
+        assert!(a.contains(2) && !a.contains(6));
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&Interval::from((6, 9))));
+        assert_eq!(a.intersection(&b), Some(Interval { start: 5, end: 6 }));
+        assert_eq!(a.intersection(&Interval::from((6, 9))), None);
+        assert!(Interval::new(3, 2).is_err());
+        assert!(Interval::from((3, 2)).is_empty());
+    }
+
+    #[rstest]
+    #[case(vec![], vec![])]
+    #[case(vec![(3, 3), (4, 2)], vec![])]
+    #[case(vec![(0, 3), (3, 5)], vec![(0, 5)])]
+    #[case(vec![(6, 9), (0, 2), (1, 4), (2, 3)], vec![(0, 4), (6, 9)])]
+    #[case(vec![(0, 10), (2, 4)], vec![(0, 10)])]
+    fn test_merge_intervals(
+        #[case] intervals: Vec<(usize, usize)>,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        assert_eq!(tuples(merge_intervals(&intervals)), expected);
+    }
+
+    #[rstest]
+    #[case(vec![(0, 5), (10, 15)], vec![(3, 12)], vec![(3, 5), (10, 12)])]
+    #[case(vec![(0, 5)], vec![(5, 10)], vec![])]
+    #[case(vec![(0, 20)], vec![(2, 4), (3, 6), (10, 25)], vec![(2, 6), (10, 20)])]
+    #[case(vec![], vec![(0, 5)], vec![])]
+    fn test_intersect_intervals(
+        #[case] a: Vec<(usize, usize)>,
+        #[case] b: Vec<(usize, usize)>,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        assert_eq!(tuples(intersect_intervals(&a, &b)), expected);
+        assert_eq!(tuples(intersect_intervals(&b, &a)), expected);
+    }
+
+    #[rstest]
+    #[case(vec![(0, 10)], vec![], vec![(0, 10)])]
+    #[case(vec![(0, 10)], vec![(0, 10)], vec![])]
+    #[case(vec![(0, 10), (12, 20)], vec![(5, 14), (16, 17)], vec![(0, 5), (14, 16), (17, 20)])]
+    #[case(vec![(5, 10)], vec![(0, 2), (3, 6), (9, 30)], vec![(6, 9)])]
+    fn test_subtract_intervals(
+        #[case] a: Vec<(usize, usize)>,
+        #[case] b: Vec<(usize, usize)>,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        assert_eq!(tuples(subtract_intervals(&a, &b)), expected);
+    }
+
+    #[rstest]
+    #[case(vec![], 5, vec![(0, 5)])]
+    #[case(vec![(0, 5)], 5, vec![])]
+    #[case(vec![(1, 2), (4, 5)], 6, vec![(0, 1), (2, 4), (5, 6)])]
+    fn test_complement_intervals(
+        #[case] intervals: Vec<(usize, usize)>,
+        #[case] len: usize,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        assert_eq!(
+            tuples(complement_intervals(&intervals, len).unwrap()),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_complement_intervals_out_of_bounds() {
+        assert!(complement_intervals([(2, 7)], 6).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::errors::BioError;
+use crate::nucleotide::windows;
+use bio::io::fasta;
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// A chunk of a FASTA record, covering `[start, end)` of the original sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeqChunk {
+    /// ID of the record the chunk was taken from.
+    pub id: String,
+    pub start: usize,
+    pub end: usize,
+    pub seq: Vec<u8>,
+}
+
+impl SeqChunk {
+    /// Returns a `samtools`-style region name `id:start-end` (one-based, inclusive).
+    pub fn name(&self) -> String {
+        format!("{}:{}-{}", self.id, self.start + 1, self.end)
+    }
+}
+
+/// Iterator over [`SeqChunk`]s of every record, created by [`chunk_records`].
+pub struct RecordChunks<B: BufRead> {
+    records: fasta::Records<B>,
+    chunk_bases: usize,
+    step: usize,
+    pending: VecDeque<SeqChunk>,
+}
+
+impl<B: BufRead> Iterator for RecordChunks<B> {
+    type Item = Result<SeqChunk, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let record = match self.records.next()? {
+                Ok(record) => record,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            let chunks = windows(record.seq(), self.chunk_bases, self.step)
+                .expect("chunk size and step are validated")
+                .map(|(start, seq)| SeqChunk {
+                    id: record.id().to_string(),
+                    start,
+                    end: start + seq.len(),
+                    seq: seq.to_vec(),
+                });
+
+            self.pending.extend(chunks);
+        }
+
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+/// Splits every record of a FASTA reader into chunks of `chunk_bases` bases,
+/// with consecutive chunks overlapping by `overlap` bases.
+///
+/// Useful for splitting long contigs before sketching or alignment. Each chunk
+/// retains its record ID and coordinates so results can be mapped back. The
+/// last chunk of a record may be shorter than `chunk_bases`, and records
+/// shorter than `chunk_bases` are returned as a single chunk. Empty records
+/// produce no chunks.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `chunk_bases` is `0` or
+/// `overlap >= chunk_bases`. The iterator yields [`BioError::IoError`] if a
+/// record cannot be read.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::io::{bio_fasta_reader_from_bytes, chunk_records};
+///
+/// let reader = bio_fasta_reader_from_bytes(b">contig\nACGTACGTAC\n").unwrap();
+/// let names: Vec<String> = chunk_records(reader, 6, 2)
+///     .unwrap()
+///     .map(|c| c.unwrap().name())
+///     .collect();
+///
+/// assert_eq!(names, vec!["contig:1-6", "contig:5-10"]);
+/// ```
+pub fn chunk_records<B: BufRead>(
+    reader: fasta::Reader<B>,
+    chunk_bases: usize,
+    overlap: usize,
+) -> Result<RecordChunks<B>, BioError> {
+    if chunk_bases == 0 || overlap >= chunk_bases {
+        return Err(BioError::InvalidParameterError(format!(
+            "overlap {} must be smaller than chunk size {}, which must be larger than 0.",
+            overlap, chunk_bases
+        )));
+    }
+
+    Ok(RecordChunks {
+        records: reader.records(),
+        chunk_bases,
+        step: chunk_bases - overlap,
+        pending: VecDeque::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::bio_fasta_reader_from_bytes;
+
+    #[test]
+    fn test_chunk_records() {
+        let reader = bio_fasta_reader_from_bytes(b">a\nACGTACGT\n>b\nAC\n>c\n\n").unwrap();
+        let chunks: Vec<SeqChunk> = chunk_records(reader, 4, 1)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let coords: Vec<(&str, usize, usize, &[u8])> = chunks
+            .iter()
+            .map(|c| (c.id.as_str(), c.start, c.end, c.seq.as_slice()))
+            .collect();
+
+        assert_eq!(
+            coords,
+            vec![
+                ("a", 0, 4, &b"ACGT"[..]),
+                ("a", 3, 7, &b"TACG"[..]),
+                ("a", 6, 8, &b"GT"[..]),
+                ("b", 0, 2, &b"AC"[..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chunk_records_invalid() {
+        let reader = bio_fasta_reader_from_bytes(b">a\nACGT\n").unwrap();
+        assert!(chunk_records(reader, 4, 4).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+use crate::errors::BioError;
+use crate::nucleotide::{
+    composition, mean_error_and_phred, nucleotide_probabilities, shannon_entropy,
+};
+use arrow_array::builder::{Float64Builder, StringBuilder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "parquet")]
+use parquet::arrow::ArrowWriter;
+
+/// Default number of rows per record batch / row group.
+pub const DEFAULT_BATCH_SIZE: usize = 65_536;
+
+/// Per-read metrics, one row of a columnar metrics table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadMetricsRow {
+    pub id: String,
+    pub length: u64,
+    /// Phred score of the mean per-base error probability. `None` for FASTA records.
+    pub mean_q: Option<f64>,
+    pub gc: f64,
+    /// Shannon entropy (bits) of the `ACGT` composition.
+    pub entropy: f64,
+}
+
+impl ReadMetricsRow {
+    /// Computes metrics for a single record.
+    pub fn from_record(id: &str, seq: &[u8], qual: Option<&[u8]>) -> Self {
+        let comp = composition(seq);
+
+        let mean_q = qual.filter(|q| !q.is_empty()).map(|q| {
+            let (error, _) = mean_error_and_phred(q);
+            -10.0 * error.log10()
+        });
+
+        Self {
+            id: id.to_string(),
+            length: seq.len() as u64,
+            mean_q,
+            gc: comp.gc_content(),
+            entropy: shannon_entropy(&nucleotide_probabilities(&comp.canonical_counts())) as f64,
+        }
+    }
+}
+
+/// Arrow schema of the per-read metrics table.
+pub fn read_metrics_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("length", DataType::UInt64, false),
+        Field::new("mean_q", DataType::Float64, true),
+        Field::new("gc", DataType::Float64, false),
+        Field::new("entropy", DataType::Float64, false),
+    ]))
+}
+
+enum Sink {
+    Ipc(FileWriter<Box<dyn Write + Send>>),
+    #[cfg(feature = "parquet")]
+    Parquet(ArrowWriter<Box<dyn Write + Send>>),
+}
+
+/// Streaming writer for per-read metric tables in Arrow IPC or Parquet format.
+///
+/// Rows are buffered and written as record batches of `batch_size` rows, so
+/// memory use is bounded regardless of the number of reads. Call
+/// [`ReadMetricsWriter::finish`] to write the file footer; dropping the
+/// writer without finishing produces an unreadable file.
+pub struct ReadMetricsWriter {
+    sink: Sink,
+    schema: SchemaRef,
+    rows: Vec<ReadMetricsRow>,
+    batch_size: usize,
+}
+
+impl ReadMetricsWriter {
+    fn with_sink(sink: Sink, schema: SchemaRef, batch_size: usize) -> Result<Self, BioError> {
+        if batch_size == 0 {
+            return Err(BioError::InvalidParameterError(
+                "batch size must be larger than 0.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            sink,
+            schema,
+            rows: Vec::with_capacity(batch_size),
+            batch_size,
+        })
+    }
+
+    /// Creates an Arrow IPC (Feather v2) file writer over any [`Write`] target.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `batch_size` is `0`, or
+    /// [`BioError::ArrowError`] if the header cannot be written.
+    pub fn arrow_ipc<W: Write + Send + 'static>(
+        writer: W,
+        batch_size: usize,
+    ) -> Result<Self, BioError> {
+        let schema = read_metrics_schema();
+        let writer: Box<dyn Write + Send> = Box::new(writer);
+        let sink = Sink::Ipc(FileWriter::try_new(writer, &schema)?);
+
+        Self::with_sink(sink, schema, batch_size)
+    }
+
+    /// Creates a Parquet file writer over any [`Write`] target, with one row
+    /// group per batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `batch_size` is `0`, or
+    /// [`BioError::ParquetError`] if the writer cannot be created.
+    #[cfg(feature = "parquet")]
+    pub fn parquet<W: Write + Send + 'static>(
+        writer: W,
+        batch_size: usize,
+    ) -> Result<Self, BioError> {
+        let schema = read_metrics_schema();
+        let writer: Box<dyn Write + Send> = Box::new(writer);
+        let sink = Sink::Parquet(ArrowWriter::try_new(writer, schema.clone(), None)?);
+
+        Self::with_sink(sink, schema, batch_size)
+    }
+
+    /// Creates a writer for `path`, choosing the format from its extension:
+    /// `.arrow`/`.feather`/`.ipc` for Arrow IPC and `.parquet` for Parquet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidFileExtensionError`] for other extensions
+    /// (or `.parquet` without the `parquet` feature), and [`BioError`] if the
+    /// file cannot be created.
+    pub fn create(path: &Path, batch_size: usize) -> Result<Self, BioError> {
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        match extension {
+            Some("arrow" | "feather" | "ipc") => {
+                Self::arrow_ipc(BufWriter::new(File::create(path)?), batch_size)
+            }
+            #[cfg(feature = "parquet")]
+            Some("parquet") => Self::parquet(BufWriter::new(File::create(path)?), batch_size),
+            _ => Err(BioError::InvalidFileExtensionError(
+                path.display().to_string(),
+            )),
+        }
+    }
+
+    /// Buffers a row, writing a batch once `batch_size` rows are buffered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError`] if writing a batch fails.
+    pub fn write(&mut self, row: ReadMetricsRow) -> Result<(), BioError> {
+        self.rows.push(row);
+
+        if self.rows.len() >= self.batch_size {
+            self.write_batch()?;
+        }
+
+        Ok(())
+    }
+
+    fn write_batch(&mut self) -> Result<(), BioError> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+
+        let mut id = StringBuilder::new();
+        let mut length = UInt64Builder::with_capacity(self.rows.len());
+        let mut mean_q = Float64Builder::with_capacity(self.rows.len());
+        let mut gc = Float64Builder::with_capacity(self.rows.len());
+        let mut entropy = Float64Builder::with_capacity(self.rows.len());
+
+        for row in self.rows.drain(..) {
+            id.append_value(&row.id);
+            length.append_value(row.length);
+            mean_q.append_option(row.mean_q);
+            gc.append_value(row.gc);
+            entropy.append_value(row.entropy);
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(id.finish()),
+            Arc::new(length.finish()),
+            Arc::new(mean_q.finish()),
+            Arc::new(gc.finish()),
+            Arc::new(entropy.finish()),
+        ];
+        let batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+
+        match &mut self.sink {
+            Sink::Ipc(writer) => writer.write(&batch)?,
+            #[cfg(feature = "parquet")]
+            Sink::Parquet(writer) => writer.write(&batch)?,
+        }
+
+        Ok(())
+    }
+
+    /// Writes any buffered rows and the file footer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError`] if writing fails.
+    pub fn finish(mut self) -> Result<(), BioError> {
+        self.write_batch()?;
+
+        match self.sink {
+            Sink::Ipc(mut writer) => {
+                writer.finish()?;
+                writer.into_inner()?.flush()?;
+            }
+            #[cfg(feature = "parquet")]
+            Sink::Parquet(writer) => {
+                writer.into_inner()?.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_ipc::reader::FileReader;
+    use tempfile::TempDir;
+
+    fn rows() -> Vec<ReadMetricsRow> {
+        vec![
+            ReadMetricsRow::from_record("r1", b"ACGT", Some(b"IIII")),
+            ReadMetricsRow::from_record("r2", b"GGGGCC", None),
+            ReadMetricsRow::from_record("r3", b"AAAA", Some(b"++++")),
+        ]
+    }
+
+    #[test]
+    fn test_read_metrics_row() {
+        let row = &rows()[0];
+        assert_eq!(row.length, 4);
+        assert!((row.mean_q.unwrap() - 40.0).abs() < 1e-6);
+        assert_eq!(row.gc, 0.5);
+        assert_eq!(row.entropy, 2.0);
+        assert_eq!(rows()[1].mean_q, None);
+    }
+
+    #[test]
+    fn test_arrow_ipc_round_trip() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("metrics.arrow");
+
+        let mut writer = ReadMetricsWriter::create(&path, 2).unwrap();
+        for row in rows() {
+            writer.write(row).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let reader = FileReader::try_new(File::open(&path).unwrap(), None).unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|b| b.unwrap()).collect();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+        assert_eq!(batches[0].schema(), read_metrics_schema());
+        assert_eq!(batches[0].column(2).null_count(), 1);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_round_trip() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("metrics.parquet");
+
+        let mut writer = ReadMetricsWriter::create(&path, DEFAULT_BATCH_SIZE).unwrap();
+        for row in rows() {
+            writer.write(row).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let num_rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(num_rows, 3);
+    }
+
+    #[test]
+    fn test_create_invalid_extension() {
+        let tmp_dir = TempDir::new().unwrap();
+        assert!(ReadMetricsWriter::create(&tmp_dir.path().join("metrics.csv"), 10).is_err());
+        assert!(ReadMetricsWriter::arrow_ipc(Vec::new(), 0).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use super::progress::CountingReader;
+use super::reader::{needletail_reader, needletail_reader_from, validate_seq_file};
+use super::types::SeqFileType;
+use crate::errors::BioError;
+use flate2::read::MultiGzDecoder;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Number of records and bases in a sequence file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordCount {
+    pub records: u64,
+    pub bases: u64,
+    /// `false` if the counts were extrapolated by [`estimate_records`].
+    pub exact: bool,
+}
+
+/// Counts records and bases in a FASTQ or FASTA file (plain or gzip) by
+/// streaming through the whole file.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the file does not exist, has an unrecognized
+/// extension, or cannot be parsed.
+pub fn count_records(path: &Path) -> Result<RecordCount, BioError> {
+    let mut reader = needletail_reader(Some(path.to_path_buf()))?;
+
+    let mut records = 0;
+    let mut bases = 0;
+
+    while let Some(record) = reader.next() {
+        let record = record
+            .map_err(|e| BioError::from(e).at_record(Some(path), records as usize + 1, None))?;
+        bases += record.num_bases() as u64;
+        records += 1;
+    }
+
+    Ok(RecordCount {
+        records,
+        bases,
+        exact: true,
+    })
+}
+
+/// Estimates records and bases in a FASTQ or FASTA file from its first
+/// `sample_records` records.
+///
+/// The mean record size of the sample is extrapolated to the file size. For
+/// gzip files, the compression ratio observed while reading the sample is used
+/// to convert record sizes to compressed bytes, so the estimate is rough for
+/// small samples or files with very uneven compressibility. If the file has at
+/// most `sample_records` records, the exact count is returned.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `sample_records` is `0`, or
+/// [`BioError`] if the file does not exist, has an unrecognized extension, or
+/// cannot be parsed.
+pub fn estimate_records(path: &Path, sample_records: u64) -> Result<RecordCount, BioError> {
+    if sample_records == 0 {
+        return Err(BioError::InvalidParameterError(
+            "sample size must be larger than 0.".to_string(),
+        ));
+    }
+
+    let (path, file_type) = validate_seq_file(path)?;
+    let file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+
+    let compressed = CountingReader::new(file);
+    let compressed_bytes = compressed.counter();
+
+    let decompressed: Box<dyn Read + Send> = match file_type {
+        SeqFileType::Gzip => Box::new(MultiGzDecoder::new(compressed)),
+        SeqFileType::Plain => Box::new(compressed),
+    };
+    let decompressed = CountingReader::new(decompressed);
+    let decompressed_bytes = decompressed.counter();
+
+    let mut reader = needletail_reader_from(decompressed)?;
+
+    let mut records = 0;
+    let mut bases = 0;
+    let mut record_bytes = 0;
+
+    while records < sample_records {
+        let Some(record) = reader.next() else {
+            return Ok(RecordCount {
+                records,
+                bases,
+                exact: true,
+            });
+        };
+
+        let record = record
+            .map_err(|e| BioError::from(e).at_record(Some(path), records as usize + 1, None))?;
+        bases += record.num_bases() as u64;
+        // `all()` excludes the final line ending.
+        record_bytes += record.all().len() as u64 + 1;
+        records += 1;
+    }
+
+    // Both counters include read-ahead buffering, which roughly cancels out in the ratio.
+    let ratio = match decompressed_bytes.bytes() {
+        0 => 1.0,
+        n => compressed_bytes.bytes() as f64 / n as f64,
+    };
+
+    let bytes_per_record = record_bytes as f64 / records as f64 * ratio;
+    let est_records = (file_size as f64 / bytes_per_record).round() as u64;
+
+    Ok(RecordCount {
+        records: est_records.max(records),
+        bases: (est_records as f64 * bases as f64 / records as f64).round() as u64,
+        exact: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SimConfig, generate_fastq};
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn write_fastq(dir: &TempDir, name: &str, num_records: usize) -> PathBuf {
+        let path = dir.path().join(name);
+        let config = SimConfig {
+            num_records,
+            ..SimConfig::default()
+        };
+
+        let mut buf = Vec::new();
+        generate_fastq(&mut buf, &config).unwrap();
+
+        let mut file = File::create(&path).unwrap();
+        match name.ends_with(".gz") {
+            true => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&buf).unwrap();
+                file.write_all(&encoder.finish().unwrap()).unwrap();
+            }
+            false => file.write_all(&buf).unwrap(),
+        }
+
+        path
+    }
+
+    #[test]
+    fn test_count_records() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fastq(&dir, "reads.fastq.gz", 50);
+
+        let count = count_records(&path).unwrap();
+        assert_eq!(
+            count,
+            RecordCount {
+                records: 50,
+                bases: 50 * 150,
+                exact: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_estimate_records_small_file_is_exact() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fastq(&dir, "reads.fastq", 20);
+
+        assert_eq!(
+            estimate_records(&path, 100).unwrap(),
+            count_records(&path).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_estimate_records_plain() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fastq(&dir, "reads.fq", 1000);
+
+        let estimate = estimate_records(&path, 100).unwrap();
+        assert!(!estimate.exact);
+        assert!(estimate.records.abs_diff(1000) <= 10);
+    }
+
+    #[test]
+    fn test_estimate_records_gzip() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fastq(&dir, "reads.fq.gz", 10_000);
+
+        let estimate = estimate_records(&path, 2000).unwrap();
+        assert!(!estimate.exact);
+        assert!(estimate.records.abs_diff(10_000) <= 2000, "{:?}", estimate);
+    }
+
+    #[test]
+    fn test_estimate_records_invalid() {
+        assert!(estimate_records(Path::new("missing.fastq"), 10).is_err());
+
+        let dir = TempDir::new().unwrap();
+        let path = write_fastq(&dir, "reads.fastq", 1);
+        assert!(estimate_records(&path, 0).is_err());
+    }
+}
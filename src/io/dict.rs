@@ -0,0 +1,143 @@
+use crate::errors::BioError;
+use bio::io::fasta;
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// One entry of a sequence dictionary, equivalent to a SAM `@SQ` header line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceDictEntry {
+    pub name: String,
+    pub length: usize,
+    /// Lowercase hex MD5 of the uppercased sequence.
+    pub md5: String,
+}
+
+/// Computes the MD5 digest of the uppercased sequence as lowercase hex, as in `samtools dict`.
+fn uppercase_md5(seq: &[u8]) -> String {
+    let mut hasher = Md5::new();
+
+    for chunk in seq.chunks(8192) {
+        hasher.update(chunk.to_ascii_uppercase());
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Builds a sequence dictionary (name, length, MD5) from a FASTA reader.
+///
+/// Equivalent to `samtools dict` / GATK `CreateSequenceDictionary`. Serialize
+/// the result with [`write_json`](super::write_json) or write it in SAM
+/// header format with [`write_sequence_dict`].
+///
+/// # Errors
+///
+/// Returns [`BioError::ParseErrorAt`] if a record cannot be read.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::io::{bio_fasta_reader_from_bytes, sequence_dictionary};
+///
+/// let reader = bio_fasta_reader_from_bytes(b">chr1 desc\nacgt\n").unwrap();
+/// let dict = sequence_dictionary(reader).unwrap();
+///
+/// assert_eq!(dict[0].name, "chr1");
+/// assert_eq!(dict[0].length, 4);
+/// assert_eq!(dict[0].md5, "f1f8f4bf413b16ad135722aa4591043e");
+/// ```
+pub fn sequence_dictionary<B: BufRead>(
+    reader: fasta::Reader<B>,
+) -> Result<Vec<SequenceDictEntry>, BioError> {
+    reader
+        .records()
+        .enumerate()
+        .map(|(i, record)| {
+            let record = record.map_err(|e| BioError::from(e).at_record(None, i + 1, None))?;
+            Ok(SequenceDictEntry {
+                name: record.id().to_string(),
+                length: record.seq().len(),
+                md5: uppercase_md5(record.seq()),
+            })
+        })
+        .collect()
+}
+
+/// Writes a sequence dictionary in the `.dict` (SAM header) format.
+///
+/// Emits an `@HD` line followed by one `@SQ` line per entry. If `uri` is
+/// provided, it is added to every `@SQ` line as the `UR` tag.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if writing fails.
+pub fn write_sequence_dict<W: Write>(
+    writer: &mut W,
+    entries: &[SequenceDictEntry],
+    uri: Option<&str>,
+) -> Result<(), BioError> {
+    writeln!(writer, "@HD\tVN:1.0\tSO:unsorted")?;
+
+    for entry in entries {
+        write!(
+            writer,
+            "@SQ\tSN:{}\tLN:{}\tM5:{}",
+            entry.name, entry.length, entry.md5
+        )?;
+
+        if let Some(uri) = uri {
+            write!(writer, "\tUR:{}", uri)?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::bio_fasta_reader_from_bytes;
+    use rstest::*;
+
+    #[rstest]
+    // md5 of "" and "ACGT".
+    #[case(b"", "d41d8cd98f00b204e9800998ecf8427e")]
+    #[case(b"ACGT", "f1f8f4bf413b16ad135722aa4591043e")]
+    #[case(b"acgT", "f1f8f4bf413b16ad135722aa4591043e")]
+    fn test_uppercase_md5(#[case] seq: &[u8], #[case] expected: &str) {
+        assert_eq!(uppercase_md5(seq), expected);
+    }
+
+    #[test]
+    fn test_sequence_dictionary() {
+        let reader = bio_fasta_reader_from_bytes(b">a\nACGT\nAC\n>b desc\nNNN\n").unwrap();
+        let dict = sequence_dictionary(reader).unwrap();
+
+        assert_eq!(dict.len(), 2);
+        assert_eq!((dict[0].name.as_str(), dict[0].length), ("a", 6));
+        assert_eq!((dict[1].name.as_str(), dict[1].length), ("b", 3));
+    }
+
+    #[test]
+    fn test_write_sequence_dict() {
+        let entries = vec![SequenceDictEntry {
+            name: "chr1".to_string(),
+            length: 4,
+            md5: "f1f8f4bf413b16ad135722aa4591043e".to_string(),
+        }];
+
+        let mut buf = Vec::new();
+        write_sequence_dict(&mut buf, &entries, Some("file:ref.fa")).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "@HD\tVN:1.0\tSO:unsorted\n@SQ\tSN:chr1\tLN:4\tM5:f1f8f4bf413b16ad135722aa4591043e\tUR:file:ref.fa\n"
+        );
+    }
+}
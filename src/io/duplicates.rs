@@ -0,0 +1,161 @@
+use crate::errors::BioError;
+use crate::kmers::{containment, frac_min_hash};
+use crate::nucleotide::{ChecksumAlgorithm, sequence_checksum};
+use bio::io::fasta;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+/// How two reference sequences were found to be redundant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DuplicateKind {
+    /// Identical after normalization (same GA4GH checksum).
+    Identical,
+    /// The query's sketch is contained in the reference's sketch.
+    Contained,
+}
+
+/// A redundant pair of records, identified by FASTA ID.
+///
+/// For [`DuplicateKind::Identical`], `query` is the later record. For
+/// [`DuplicateKind::Contained`], `query` is the record contained in `reference`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicatePair {
+    pub query: String,
+    pub reference: String,
+    pub kind: DuplicateKind,
+    pub containment: f64,
+}
+
+/// Reports identical and near-identical sequences in a multi-FASTA.
+///
+/// Exact duplicates are found via [`sequence_checksum`], so case and gap
+/// characters are ignored. Remaining sequences are sketched with
+/// [`frac_min_hash`] and a pair is reported when the containment of either
+/// sketch in the other is at least `min_containment`. Sketches use canonical
+/// k-mers, so reverse-complemented copies are also detected. Sequences shorter
+/// than `kmer_size` are only checked for exact duplicates.
+///
+/// All sequences are kept in memory as sketches, and every pair is compared.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_containment` is outside
+/// `[0.0, 1.0]` or [`frac_min_hash`] rejects `scaled`, and
+/// [`BioError::ParseErrorAt`] if a record cannot be read.
+pub fn find_duplicate_references<B: BufRead>(
+    reader: fasta::Reader<B>,
+    kmer_size: usize,
+    scaled: u64,
+    min_containment: f64,
+) -> Result<Vec<DuplicatePair>, BioError> {
+    if !(0.0..=1.0).contains(&min_containment) {
+        return Err(BioError::InvalidParameterError(format!(
+            "min containment {} must be in range 0-1.",
+            min_containment
+        )));
+    }
+
+    let mut duplicates = Vec::new();
+    let mut first_by_checksum: HashMap<String, String> = HashMap::new();
+    let mut sketches: Vec<(String, HashSet<u64>)> = Vec::new();
+
+    for (i, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| BioError::from(e).at_record(None, i + 1, None))?;
+        let id = record.id().to_string();
+        let checksum = sequence_checksum(record.seq(), ChecksumAlgorithm::Ga4gh);
+
+        if let Some(first) = first_by_checksum.get(&checksum) {
+            duplicates.push(DuplicatePair {
+                query: id,
+                reference: first.clone(),
+                kind: DuplicateKind::Identical,
+                containment: 1.0,
+            });
+            continue;
+        }
+
+        first_by_checksum.insert(checksum, id.clone());
+
+        if record.seq().len() >= kmer_size {
+            let sketch = frac_min_hash(kmer_size, scaled, record.seq())?;
+            if !sketch.is_empty() {
+                sketches.push((id, sketch));
+            }
+        }
+    }
+
+    for (i, (id_i, sketch_i)) in sketches.iter().enumerate() {
+        for (id_j, sketch_j) in &sketches[i + 1..] {
+            let c_ij = containment(sketch_i, sketch_j);
+            let c_ji = containment(sketch_j, sketch_i);
+
+            let (query, reference, c) = match c_ij >= c_ji {
+                true => (id_i, id_j, c_ij),
+                false => (id_j, id_i, c_ji),
+            };
+
+            if c >= min_containment {
+                duplicates.push(DuplicatePair {
+                    query: query.clone(),
+                    reference: reference.clone(),
+                    kind: DuplicateKind::Contained,
+                    containment: c,
+                });
+            }
+        }
+    }
+
+    Ok(duplicates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::bio_fasta_reader_from_bytes;
+    use crate::nucleotide::reverse_complement;
+
+    const SEQ: &[u8] = b"ACGGTCAGTTCAGGATCCTAGCATGCCGTAAGCTTGACTAGGCATTACG";
+
+    fn fasta(records: &[(&str, &[u8])]) -> Vec<u8> {
+        records
+            .iter()
+            .flat_map(|(id, seq)| [format!(">{}\n", id).as_bytes(), seq, b"\n"].concat())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_duplicate_references() {
+        let rc = reverse_complement(SEQ);
+        let bytes = fasta(&[
+            ("a", SEQ),
+            ("b", &SEQ.to_ascii_lowercase()),
+            ("c", &SEQ[5..40]),
+            ("d", &rc),
+            ("e", b"TTTTTTTTTTGGGGGGGGGGAAAAACCCCCCTTTTT"),
+        ]);
+        let reader = bio_fasta_reader_from_bytes(&bytes).unwrap();
+        let dups = find_duplicate_references(reader, 11, 1, 0.9).unwrap();
+
+        let pairs: Vec<(&str, &str, DuplicateKind)> = dups
+            .iter()
+            .map(|d| (d.query.as_str(), d.reference.as_str(), d.kind))
+            .collect();
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("b", "a", DuplicateKind::Identical),
+                ("c", "a", DuplicateKind::Contained),
+                ("a", "d", DuplicateKind::Contained),
+                ("c", "d", DuplicateKind::Contained),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_duplicate_references_invalid() {
+        let reader = bio_fasta_reader_from_bytes(b">a\nACGT\n").unwrap();
+        assert!(find_duplicate_references(reader, 11, 1, 1.5).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::errors::BioError;
+use bio::io::{fasta, fastq};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// What to do when a record ID is written more than once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicateIdPolicy {
+    /// Return [`BioError::DuplicateIdError`].
+    Error,
+    /// Append `_2`, `_3`, ... until the ID is unique.
+    Suffix,
+}
+
+/// Tracks written record IDs and resolves collisions according to a [`DuplicateIdPolicy`].
+///
+/// Useful on its own when writing through a custom writer; the
+/// [`GuardedFastaWriter`] and [`GuardedFastqWriter`] wrappers apply it automatically.
+#[derive(Debug)]
+pub struct IdGuard {
+    seen: HashSet<String>,
+    policy: DuplicateIdPolicy,
+}
+
+impl IdGuard {
+    pub fn new(policy: DuplicateIdPolicy) -> Self {
+        Self {
+            seen: HashSet::new(),
+            policy,
+        }
+    }
+
+    /// Registers `id` and returns the ID that should be written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::DuplicateIdError`] if `id` was already registered
+    /// and the policy is [`DuplicateIdPolicy::Error`].
+    pub fn resolve<'a>(&mut self, id: &'a str) -> Result<Cow<'a, str>, BioError> {
+        if self.seen.insert(id.to_string()) {
+            return Ok(Cow::Borrowed(id));
+        }
+
+        match self.policy {
+            DuplicateIdPolicy::Error => Err(BioError::DuplicateIdError(id.to_string())),
+            DuplicateIdPolicy::Suffix => {
+                let mut n = 2;
+                loop {
+                    let candidate = format!("{}_{}", id, n);
+                    if self.seen.insert(candidate.clone()) {
+                        return Ok(Cow::Owned(candidate));
+                    }
+                    n += 1;
+                }
+            }
+        }
+    }
+}
+
+/// A [`fasta::Writer`] that refuses or renames duplicate record IDs.
+pub struct GuardedFastaWriter<W: Write> {
+    inner: fasta::Writer<W>,
+    guard: IdGuard,
+}
+
+impl<W: Write> GuardedFastaWriter<W> {
+    pub fn new(inner: fasta::Writer<W>, policy: DuplicateIdPolicy) -> Self {
+        Self {
+            inner,
+            guard: IdGuard::new(policy),
+        }
+    }
+
+    /// Writes a record, resolving its ID through the guard first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::DuplicateIdError`] on a rejected duplicate, or
+    /// [`BioError::IoError`] if writing fails.
+    pub fn write(&mut self, id: &str, desc: Option<&str>, seq: &[u8]) -> Result<(), BioError> {
+        let id = self.guard.resolve(id)?;
+        self.inner.write(&id, desc, seq)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), BioError> {
+        self.inner.flush()?;
+        Ok(())
+    }
+}
+
+/// A [`fastq::Writer`] that refuses or renames duplicate record IDs.
+pub struct GuardedFastqWriter<W: Write> {
+    inner: fastq::Writer<W>,
+    guard: IdGuard,
+}
+
+impl<W: Write> GuardedFastqWriter<W> {
+    pub fn new(inner: fastq::Writer<W>, policy: DuplicateIdPolicy) -> Self {
+        Self {
+            inner,
+            guard: IdGuard::new(policy),
+        }
+    }
+
+    /// Writes a record, resolving its ID through the guard first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::DuplicateIdError`] on a rejected duplicate, or
+    /// [`BioError::IoError`] if writing fails.
+    pub fn write(
+        &mut self,
+        id: &str,
+        desc: Option<&str>,
+        seq: &[u8],
+        qual: &[u8],
+    ) -> Result<(), BioError> {
+        let id = self.guard.resolve(id)?;
+        self.inner.write(&id, desc, seq, qual)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), BioError> {
+        self.inner.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{bio_fasta_writer_from, bio_fastq_writer_from};
+
+    #[test]
+    fn test_id_guard_error() {
+        let mut guard = IdGuard::new(DuplicateIdPolicy::Error);
+        assert_eq!(guard.resolve("a").unwrap(), "a");
+        assert_eq!(guard.resolve("b").unwrap(), "b");
+        assert!(matches!(
+            guard.resolve("a"),
+            Err(BioError::DuplicateIdError(_))
+        ));
+    }
+
+    #[test]
+    fn test_id_guard_suffix() {
+        let mut guard = IdGuard::new(DuplicateIdPolicy::Suffix);
+        assert_eq!(guard.resolve("a").unwrap(), "a");
+        assert_eq!(guard.resolve("a_2").unwrap(), "a_2");
+        assert_eq!(guard.resolve("a").unwrap(), "a_3");
+        assert_eq!(guard.resolve("a").unwrap(), "a_4");
+    }
+
+    #[test]
+    fn test_guarded_fasta_writer() {
+        let mut buf = Vec::new();
+
+        let mut writer = GuardedFastaWriter::new(
+            bio_fasta_writer_from(&mut buf, false),
+            DuplicateIdPolicy::Suffix,
+        );
+        writer.write("s", None, b"AC").unwrap();
+        writer.write("s", None, b"GT").unwrap();
+        drop(writer);
+
+        assert_eq!(buf, b">s\nAC\n>s_2\nGT\n");
+    }
+
+    #[test]
+    fn test_guarded_fastq_writer_error() {
+        let mut buf = Vec::new();
+
+        let mut writer = GuardedFastqWriter::new(
+            bio_fastq_writer_from(&mut buf, false),
+            DuplicateIdPolicy::Error,
+        );
+        writer.write("r", None, b"AC", b"II").unwrap();
+        assert!(writer.write("r", None, b"GT", b"II").is_err());
+        drop(writer);
+
+        assert_eq!(buf, b"@r\nAC\n+\nII\n");
+    }
+}
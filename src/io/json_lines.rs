@@ -0,0 +1,139 @@
+use super::writer::{get_bufwriter, get_bufwriter_from};
+use crate::errors::BioError;
+use serde::Serialize;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+/// Streaming newline-delimited JSON (NDJSON) writer, one object per line.
+///
+/// Unlike [`write_json`](super::write_json), records are serialized as they
+/// arrive, so per-read metrics of large files never need to be collected in
+/// memory. Output is buffered and flushed when the writer is dropped.
+pub struct JsonLinesWriter<'a, T: Serialize> {
+    writer: Box<dyn Write + Send + 'a>,
+    written: usize,
+    _record: PhantomData<T>,
+}
+
+impl<T: Serialize> JsonLinesWriter<'static, T> {
+    /// Creates a writer for a file or stdout.
+    ///
+    /// Pass `Some(path)` to write to a file, or `None` for stdout. Files ending
+    /// in `.gz` are gzip-compressed automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError`] if the file cannot be created or has no extension.
+    pub fn new(outfile: Option<PathBuf>) -> Result<Self, BioError> {
+        Ok(Self {
+            writer: get_bufwriter(outfile)?,
+            written: 0,
+            _record: PhantomData,
+        })
+    }
+}
+
+impl<'a, T: Serialize> JsonLinesWriter<'a, T> {
+    /// Creates a writer over any [`Write`] target, e.g. `&mut Vec<u8>`.
+    ///
+    /// Output is gzip-compressed when `gzip` is `true`.
+    pub fn from_writer<W: Write + Send + 'a>(writer: W, gzip: bool) -> Self {
+        Self {
+            writer: get_bufwriter_from(writer, gzip),
+            written: 0,
+            _record: PhantomData,
+        }
+    }
+
+    /// Serializes `record` as a single JSON line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError`] on I/O or serialization failure.
+    pub fn write(&mut self, record: &T) -> Result<(), BioError> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")?;
+        self.written += 1;
+
+        Ok(())
+    }
+
+    /// Number of records written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Flushes buffered output. The gzip trailer is only written on drop.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if flushing fails.
+    pub fn flush(&mut self) -> Result<(), BioError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::fs::File;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    #[derive(Serialize)]
+    struct ReadMetrics {
+        id: String,
+        len: usize,
+    }
+
+    fn metrics(i: usize) -> ReadMetrics {
+        ReadMetrics {
+            id: format!("read_{}", i),
+            len: i * 10,
+        }
+    }
+
+    #[test]
+    fn test_json_lines_writer_from_vec() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = JsonLinesWriter::from_writer(&mut buf, false);
+            for i in 0..2 {
+                writer.write(&metrics(i)).unwrap();
+            }
+            assert_eq!(writer.written(), 2);
+        }
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "{\"id\":\"read_0\",\"len\":0}\n{\"id\":\"read_1\",\"len\":10}\n"
+        );
+    }
+
+    #[test]
+    fn test_json_lines_writer_gz_file() {
+        let tmp_dir = TempDir::new().unwrap();
+        let outfile = tmp_dir.path().join("metrics.jsonl.gz");
+
+        let mut writer = JsonLinesWriter::new(Some(outfile.clone())).unwrap();
+        for i in 0..100 {
+            writer.write(&metrics(i)).unwrap();
+        }
+        drop(writer);
+
+        let mut content = String::new();
+        GzDecoder::new(File::open(&outfile).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 100);
+        assert_eq!(lines[99]["len"], 990);
+    }
+}
@@ -0,0 +1,193 @@
+use super::reader::{bio_fasta_reader, bio_fastq_reader};
+use crate::errors::BioError;
+use bio::io::{fasta, fastq};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Iterator wrapper that attaches the input path, record number and record ID
+/// to every error, see [`BioError::ParseErrorAt`].
+///
+/// Wraps rust-bio FASTQ or FASTA record iterators. Records failing rust-bio's
+/// `check`, e.g. with unequal sequence and quality lengths, are reported as
+/// errors with their ID. Iteration stops after the first error.
+pub struct LocatedRecords<I> {
+    records: I,
+    path: Option<PathBuf>,
+    record: usize,
+    done: bool,
+}
+
+impl<I> LocatedRecords<I> {
+    /// Wraps `records` read from `path`, `None` for stdin or in-memory input.
+    pub fn new(records: I, path: Option<PathBuf>) -> Self {
+        Self {
+            records,
+            path,
+            record: 0,
+            done: false,
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Number of records read so far, including a failing one.
+    pub fn records_read(&self) -> usize {
+        self.record
+    }
+
+    /// Counts the next record and attaches the location to a read or check error.
+    fn locate<R>(
+        &mut self,
+        result: Result<R, BioError>,
+        id: impl Fn(&R) -> &str,
+        check: impl Fn(&R) -> Result<(), &str>,
+    ) -> Result<R, BioError> {
+        self.record += 1;
+
+        let error = match result {
+            Ok(record) => match check(&record) {
+                Ok(()) => return Ok(record),
+                Err(e) => BioError::InvalidFormatError(e.to_string()).at_record(
+                    self.path(),
+                    self.record,
+                    Some(id(&record)).filter(|id| !id.is_empty()),
+                ),
+            },
+            Err(e) => e.at_record(self.path(), self.record, None),
+        };
+
+        self.done = true;
+        Err(error)
+    }
+}
+
+impl<B: BufRead> Iterator for LocatedRecords<fastq::Records<B>> {
+    type Item = Result<fastq::Record, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.records.next()?.map_err(BioError::from);
+        Some(self.locate(result, |r| r.id(), |r| r.check()))
+    }
+}
+
+impl<B: BufRead> Iterator for LocatedRecords<fasta::Records<B>> {
+    type Item = Result<fasta::Record, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.records.next()?.map_err(BioError::from);
+        Some(self.locate(result, |r| r.id(), |r| r.check()))
+    }
+}
+
+/// Records of a FASTQ file opened by [`bio_fastq_records`].
+pub type LocatedFastqRecords = LocatedRecords<fastq::Records<BufReader<Box<dyn Read + Send>>>>;
+
+/// Records of a FASTA file opened by [`bio_fasta_records`].
+pub type LocatedFastaRecords = LocatedRecords<fasta::Records<BufReader<Box<dyn Read + Send>>>>;
+
+/// Iterates the records of a FASTQ file (plain or gzip), or stdin for `None`,
+/// with the file and record attached to every error.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the file does not exist, has an unrecognized
+/// extension, or cannot be opened.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bio_utils_rs::io::bio_fastq_records;
+/// use std::path::PathBuf;
+///
+/// for record in bio_fastq_records(Some(PathBuf::from("reads.fastq.gz"))).unwrap() {
+///     // e.g. "Parse error in reads.fastq.gz at record 1204332 (read_7): ..."
+///     let _record = record.unwrap_or_else(|e| panic!("{}", e));
+/// }
+/// ```
+pub fn bio_fastq_records(fastq: Option<PathBuf>) -> Result<LocatedFastqRecords, BioError> {
+    let records = bio_fastq_reader(fastq.clone())?.records();
+    Ok(LocatedRecords::new(records, fastq))
+}
+
+/// Iterates the records of a FASTA file (plain or gzip), or stdin for `None`,
+/// with the file and record attached to every error.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the file does not exist, has an unrecognized
+/// extension, or cannot be opened.
+pub fn bio_fasta_records(fasta: Option<PathBuf>) -> Result<LocatedFastaRecords, BioError> {
+    let records = bio_fasta_reader(fasta.clone())?.records();
+    Ok(LocatedRecords::new(records, fasta))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{bio_fasta_reader_from_bytes, bio_fastq_reader_from_bytes};
+    use crate::test_utils::temp_seq_file;
+    use rstest::*;
+
+    #[rstest]
+    // Quality line shorter than the sequence.
+    #[case(b"@r1\nACGT\n+\nIIII\n@r2\nACGT\n+\nII\n".as_slice(), 2, Some("r2"))]
+    // Missing `@` on the third record.
+    #[case(b"@r1\nA\n+\nI\n@r2\nA\n+\nI\nr3\nA\n+\nI\n".as_slice(), 3, None)]
+    fn test_located_fastq_error(
+        #[case] data: &[u8],
+        #[case] failing: usize,
+        #[case] failing_id: Option<&str>,
+    ) {
+        let reader = bio_fastq_reader_from_bytes(data).unwrap();
+        let mut records = LocatedRecords::new(reader.records(), Some(PathBuf::from("x.fastq")));
+
+        let error = records.by_ref().find_map(Result::err).unwrap();
+        match error {
+            BioError::ParseErrorAt {
+                path, record, id, ..
+            } => {
+                assert_eq!(path, Some(PathBuf::from("x.fastq")));
+                assert_eq!(record, failing);
+                assert_eq!(id.as_deref(), failing_id);
+            }
+            e => panic!("unexpected error {}", e),
+        }
+        assert!(records.next().is_none());
+        assert_eq!(records.records_read(), failing);
+    }
+
+    #[test]
+    fn test_located_fasta() {
+        let reader = bio_fasta_reader_from_bytes(b">a\nACGT\n>b\nAC\n").unwrap();
+        let ids: Vec<String> = LocatedRecords::new(reader.records(), None)
+            .map(|r| r.unwrap().id().to_string())
+            .collect();
+
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_bio_fastq_records_file() {
+        let (_tmp_dir, path) = temp_seq_file("reads.fastq");
+        std::fs::write(&path, b"@r1\nACGT\n+\nIIII\n@r2\nACGT\n+\nIII\n").unwrap();
+
+        let error = bio_fastq_records(Some(path.clone()))
+            .unwrap()
+            .find_map(Result::err)
+            .unwrap();
+        assert!(error.to_string().starts_with(&format!(
+            "Parse error in {} at record 2 (r2): ",
+            path.display()
+        )));
+    }
+}
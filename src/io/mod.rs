@@ -2,11 +2,84 @@
 //!
 //! Supports both plain-text and gzip-compressed files. When `None` is passed
 //! as the file path, functions default to stdin (readers) or stdout (writers).
+//! Readers can also be built from in-memory buffers or any [`std::io::Read`]
+//! source via the `*_from` and `*_from_bytes` constructors, and writers can
+//! target any [`std::io::Write`] such as an in-memory `Vec<u8>`. UCSC `.2bit`
+//! and `.nib` files are read into 2-bit packed sequences. With the
+//! `http` feature, remote files can be streamed from HTTP(S) and public S3 URLs.
+//! Pairwise mappings are read and written as PAF via [`PafRecord`], and
+//! alignments can be written as SAM via [`SamWriter`] for viewing in IGV.
+//! The `verbatim_*` readers keep headers and FASTQ `+` lines exactly as read,
+//! so records round-trip byte for byte. [`par_map_records`] filters or
+//! transforms records on several threads while writing them in input order.
+//! [`bio_fastq_records`] and [`bio_fasta_records`] attach the file, record
+//! number and record ID to read errors via [`BioError::ParseErrorAt`](crate::errors::BioError::ParseErrorAt).
 
 mod reader;
 pub use reader::*;
 
+mod located;
+pub use located::*;
+
 mod writer;
 pub use writer::*;
 
+mod json_lines;
+pub use json_lines::*;
+
+mod id_guard;
+pub use id_guard::*;
+
+mod skip_malformed;
+pub use skip_malformed::*;
+
+mod tolerant;
+pub use tolerant::*;
+
+mod progress;
+pub use progress::*;
+
+mod count;
+pub use count::*;
+
+mod repair;
+pub use repair::*;
+
+mod dict;
+pub use dict::*;
+
+mod duplicates;
+pub use duplicates::*;
+
+mod chunks;
+pub use chunks::*;
+
+mod two_bit;
+pub use two_bit::*;
+
+mod phred;
+pub use phred::*;
+
+mod paf;
+pub use paf::*;
+
+mod sam;
+pub use sam::*;
+
+mod verbatim;
+pub use verbatim::*;
+
+mod par_map;
+pub use par_map::*;
+
+#[cfg(feature = "http")]
+mod remote;
+#[cfg(feature = "http")]
+pub use remote::*;
+
+#[cfg(feature = "arrow")]
+mod columnar;
+#[cfg(feature = "arrow")]
+pub use columnar::*;
+
 pub mod types;
@@ -0,0 +1,270 @@
+use super::reader::decompress_if_gzip;
+use crate::cigar::Cigar;
+use crate::errors::BioError;
+use crate::nucleotide::Strand;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A single PAF (Pairwise mApping Format) line, as written by minimap2.
+///
+/// Coordinates are zero-based half-open. The `NM:i` and `cg:Z` tags are
+/// parsed into [`PafRecord::edit_distance`] and [`PafRecord::cigar`]; all
+/// other optional tags are kept verbatim in [`PafRecord::tags`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PafRecord {
+    pub query_name: String,
+    pub query_len: usize,
+    pub query_start: usize,
+    pub query_end: usize,
+    /// Relative strand of query and target.
+    pub strand: Strand,
+    pub target_name: String,
+    pub target_len: usize,
+    pub target_start: usize,
+    pub target_end: usize,
+    /// Number of matching bases.
+    pub matches: usize,
+    /// Number of bases, including gaps, in the mapping.
+    pub block_len: usize,
+    /// Mapping quality, `255` if missing.
+    pub mapq: u8,
+    /// Edit distance from the `NM:i` tag.
+    pub edit_distance: Option<usize>,
+    /// Alignment from the `cg:Z` tag.
+    pub cigar: Option<Cigar>,
+    /// Remaining `TAG:TYPE:VALUE` fields, in input order.
+    pub tags: Vec<String>,
+}
+
+impl PafRecord {
+    /// Fraction of matching bases in the mapping block, `0.0` for an empty block.
+    pub fn identity(&self) -> f64 {
+        match self.block_len {
+            0 => 0.0,
+            len => self.matches as f64 / len as f64,
+        }
+    }
+
+    /// Parses a single PAF line, without the trailing newline.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidFormatError`] if the line has fewer than 12
+    /// columns, a column cannot be parsed, or a tag is malformed.
+    pub fn parse(line: &str) -> Result<Self, BioError> {
+        let invalid =
+            |what: &str| BioError::InvalidFormatError(format!("invalid PAF {}: {}.", what, line));
+
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < 12 {
+            return Err(invalid("line, expected at least 12 columns"));
+        }
+
+        let number = |i: usize, name: &str| fields[i].parse::<usize>().map_err(|_| invalid(name));
+
+        let strand = match fields[4] {
+            "+" => Strand::Forward,
+            "-" => Strand::Reverse,
+            _ => return Err(invalid("strand")),
+        };
+
+        let mut record = Self {
+            query_name: fields[0].to_string(),
+            query_len: number(1, "query length")?,
+            query_start: number(2, "query start")?,
+            query_end: number(3, "query end")?,
+            strand,
+            target_name: fields[5].to_string(),
+            target_len: number(6, "target length")?,
+            target_start: number(7, "target start")?,
+            target_end: number(8, "target end")?,
+            matches: number(9, "match count")?,
+            block_len: number(10, "block length")?,
+            mapq: fields[11].parse().map_err(|_| invalid("mapping quality"))?,
+            edit_distance: None,
+            cigar: None,
+            tags: Vec::new(),
+        };
+
+        for tag in &fields[12..] {
+            match tag
+                .split_once(':')
+                .and_then(|(name, rest)| Some((name, rest.split_once(':')?)))
+            {
+                Some(("NM", ("i", value))) => {
+                    record.edit_distance = Some(value.parse().map_err(|_| invalid("NM tag"))?)
+                }
+                Some(("cg", ("Z", value))) => record.cigar = Some(Cigar::parse(value)?),
+                Some(_) => record.tags.push(tag.to_string()),
+                None => return Err(invalid("tag")),
+            }
+        }
+
+        Ok(record)
+    }
+}
+
+impl FromStr for PafRecord {
+    type Err = BioError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for PafRecord {
+    /// Formats the record as a PAF line without the trailing newline.
+    ///
+    /// `NM:i` is written before the other tags and `cg:Z` last, as minimap2 does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.query_name,
+            self.query_len,
+            self.query_start,
+            self.query_end,
+            self.strand.as_char(),
+            self.target_name,
+            self.target_len,
+            self.target_start,
+            self.target_end,
+            self.matches,
+            self.block_len,
+            self.mapq
+        )?;
+
+        if let Some(edit_distance) = self.edit_distance {
+            write!(f, "\tNM:i:{}", edit_distance)?;
+        }
+
+        for tag in &self.tags {
+            write!(f, "\t{}", tag)?;
+        }
+
+        if let Some(cigar) = &self.cigar {
+            write!(f, "\tcg:Z:{}", cigar)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator over the [`PafRecord`]s of a PAF stream, skipping empty lines.
+pub struct PafReader<B: BufRead> {
+    lines: std::io::Lines<B>,
+}
+
+impl<B: BufRead> PafReader<B> {
+    pub fn new(reader: B) -> Self {
+        Self {
+            lines: reader.lines(),
+        }
+    }
+}
+
+impl<B: BufRead> Iterator for PafReader<B> {
+    type Item = Result<PafRecord, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.lines.next()? {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Some(PafRecord::parse(line.trim_end_matches('\r'))),
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+}
+
+/// Creates a [`PafReader`] from any [`Read`] source, decompressing gzip input transparently.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if the first bytes cannot be read.
+pub fn paf_reader_from<'a, R: Read + Send + 'a>(
+    reader: R,
+) -> Result<PafReader<BufReader<Box<dyn Read + Send + 'a>>>, BioError> {
+    Ok(PafReader::new(BufReader::new(decompress_if_gzip(reader)?)))
+}
+
+/// Opens a PAF file (plain or gzip) for reading.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if the file cannot be opened.
+pub fn paf_reader(path: &Path) -> Result<PafReader<BufReader<Box<dyn Read + Send>>>, BioError> {
+    paf_reader_from(File::open(path)?)
+}
+
+/// Writes a single [`PafRecord`] as a line of PAF.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if writing fails.
+pub fn write_paf_record<W: Write + ?Sized>(
+    writer: &mut W,
+    record: &PafRecord,
+) -> Result<(), BioError> {
+    writeln!(writer, "{}", record)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const LINE: &str = "read1\t1000\t10\t990\t-\tchr1\t50000\t2000\t2985\t950\t990\t60\tNM:i:40\ttp:A:P\tcg:Z:500=5I480=";
+
+    #[test]
+    fn test_parse_and_format() {
+        let record = PafRecord::parse(LINE).unwrap();
+
+        assert_eq!(record.query_name, "read1");
+        assert_eq!((record.query_start, record.query_end), (10, 990));
+        assert_eq!(record.strand, Strand::Reverse);
+        assert_eq!((record.target_start, record.target_end), (2000, 2985));
+        assert_eq!(record.mapq, 60);
+        assert_eq!(record.edit_distance, Some(40));
+        assert_eq!(record.tags, vec!["tp:A:P".to_string()]);
+        assert_eq!(record.cigar.as_ref().unwrap().query_len(), 985);
+        assert!((record.identity() - 950.0 / 990.0).abs() < 1e-12);
+
+        assert_eq!(record.to_string(), LINE);
+    }
+
+    #[rstest]
+    #[case("read1\t1000\t10\t990\t+\tchr1\t50000\t2000\t2985\t950\t990")]
+    #[case("read1\t1000\t10\t990\t*\tchr1\t50000\t2000\t2985\t950\t990\t60")]
+    #[case("read1\tlong\t10\t990\t+\tchr1\t50000\t2000\t2985\t950\t990\t60")]
+    #[case("read1\t1000\t10\t990\t+\tchr1\t50000\t2000\t2985\t950\t990\t60\tNM:i:x")]
+    #[case("read1\t1000\t10\t990\t+\tchr1\t50000\t2000\t2985\t950\t990\t60\tbadtag")]
+    #[case("read1\t1000\t10\t990\t+\tchr1\t50000\t2000\t2985\t950\t990\t60\tcg:Z:5Q")]
+    fn test_parse_invalid(#[case] line: &str) {
+        assert!(matches!(
+            PafRecord::parse(line),
+            Err(BioError::InvalidFormatError(_))
+        ));
+    }
+
+    #[test]
+    fn test_reader_writer_round_trip() {
+        let input = format!("{}\n\n{}\n", LINE, LINE.replace("read1", "read2"));
+
+        let records: Vec<PafRecord> = paf_reader_from(input.as_bytes())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+
+        let mut buf = Vec::new();
+        for record in &records {
+            write_paf_record(&mut buf, record).unwrap();
+        }
+        assert_eq!(String::from_utf8(buf).unwrap(), input.replace("\n\n", "\n"));
+    }
+}
@@ -0,0 +1,272 @@
+use super::{VerbatimFastaRecord, VerbatimFastqRecord};
+use crate::errors::BioError;
+use bio::io::{fasta, fastq};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::mpsc::sync_channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Number of records sent to a worker thread at a time by [`par_map_records`].
+const BATCH_SIZE: usize = 1024;
+
+/// A destination for records of type `T`, used by [`par_map_records`].
+pub trait RecordSink<T> {
+    /// Writes a single record.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BioError`] if the record cannot be written.
+    fn write_record(&mut self, record: &T) -> Result<(), BioError>;
+}
+
+impl<W: Write> RecordSink<fastq::Record> for fastq::Writer<W> {
+    fn write_record(&mut self, record: &fastq::Record) -> Result<(), BioError> {
+        self.write_record(record)?;
+        Ok(())
+    }
+}
+
+impl<W: Write> RecordSink<fasta::Record> for fasta::Writer<W> {
+    fn write_record(&mut self, record: &fasta::Record) -> Result<(), BioError> {
+        self.write_record(record)?;
+        Ok(())
+    }
+}
+
+impl<W: Write> RecordSink<VerbatimFastqRecord> for W {
+    fn write_record(&mut self, record: &VerbatimFastqRecord) -> Result<(), BioError> {
+        record.write(self)
+    }
+}
+
+impl<W: Write> RecordSink<VerbatimFastaRecord> for W {
+    fn write_record(&mut self, record: &VerbatimFastaRecord) -> Result<(), BioError> {
+        record.write(self)
+    }
+}
+
+/// Applies `f` to every record on `threads` worker threads and writes the
+/// results to `writer` in input order.
+///
+/// Records for which `f` returns `None` are dropped, so `f` can filter, trim
+/// or tag records. Records are read on a separate thread and handed to the
+/// workers in batches; at most a few batches per thread are in flight, so
+/// memory use does not grow with the input size. The output is identical to
+/// a sequential `filter_map` regardless of `threads`. Returns the number of
+/// records written.
+///
+/// `records` is typically `reader.records()` of a rust-bio reader or a
+/// [`VerbatimFastqReader`](super::VerbatimFastqReader).
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `threads` is `0`, and the
+/// first read or write error otherwise. Processing stops at the first error.
+///
+/// # Examples
+///
+/// ```
+/// use bio::io::fastq;
+/// use bio_utils_rs::io::{bio_fastq_reader_from_bytes, par_map_records};
+///
+/// let reader = bio_fastq_reader_from_bytes(b"@r1\nACGTAC\n+\nIIIIII\n@r2\nAC\n+\nII\n").unwrap();
+/// let mut buf = Vec::new();
+/// let mut writer = fastq::Writer::new(&mut buf);
+///
+/// // Drop short reads and trim the rest to 4 bases.
+/// let written = par_map_records(reader.records(), &mut writer, 2, |r: fastq::Record| {
+///     (r.seq().len() >= 4).then(|| fastq::Record::with_attrs(r.id(), r.desc(), &r.seq()[..4], &r.qual()[..4]))
+/// })
+/// .unwrap();
+/// drop(writer);
+///
+/// assert_eq!(written, 1);
+/// assert_eq!(buf, b"@r1\nACGT\n+\nIIII\n");
+/// ```
+pub fn par_map_records<I, T, U, E, S, F>(
+    records: I,
+    writer: &mut S,
+    threads: usize,
+    f: F,
+) -> Result<usize, BioError>
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    I::IntoIter: Send,
+    T: Send,
+    U: Send,
+    E: Into<BioError>,
+    S: RecordSink<U> + ?Sized,
+    F: Fn(T) -> Option<U> + Sync,
+{
+    if threads == 0 {
+        return Err(BioError::InvalidParameterError(
+            "number of threads must be greater than 0.".to_string(),
+        ));
+    }
+
+    let records = records.into_iter();
+    let (batch_tx, batch_rx) = sync_channel::<(usize, Vec<T>)>(2 * threads);
+    let (result_tx, result_rx) = sync_channel::<(usize, Vec<U>)>(2 * threads);
+    let batch_rx = Arc::new(Mutex::new(batch_rx));
+
+    thread::scope(|s| {
+        let reader = s.spawn(move || -> Result<(), BioError> {
+            let mut batch = Vec::with_capacity(BATCH_SIZE);
+            let mut index = 0;
+
+            for record in records {
+                batch.push(record.map_err(Into::into)?);
+
+                if batch.len() == BATCH_SIZE {
+                    let full = std::mem::replace(&mut batch, Vec::with_capacity(BATCH_SIZE));
+                    if batch_tx.send((index, full)).is_err() {
+                        // The writer stopped early; its error is reported instead.
+                        return Ok(());
+                    }
+                    index += 1;
+                }
+            }
+
+            if !batch.is_empty() {
+                let _ = batch_tx.send((index, batch));
+            }
+
+            Ok(())
+        });
+
+        for _ in 0..threads {
+            let batch_rx = Arc::clone(&batch_rx);
+            let result_tx = result_tx.clone();
+            let f = &f;
+
+            s.spawn(move || {
+                loop {
+                    let next = batch_rx.lock().expect("record batch queue poisoned").recv();
+                    let Ok((index, batch)) = next else {
+                        break;
+                    };
+
+                    let mapped: Vec<U> = batch.into_iter().filter_map(f).collect();
+                    if result_tx.send((index, mapped)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // Only the workers may hold the batch receiver, so that if the writer
+        // fails and the workers exit, the reader's `send` fails instead of
+        // blocking the scope forever.
+        drop(batch_rx);
+        drop(result_tx);
+
+        let mut pending: BTreeMap<usize, Vec<U>> = BTreeMap::new();
+        let mut next_index = 0;
+        let mut written = 0;
+
+        for (index, mapped) in result_rx {
+            pending.insert(index, mapped);
+
+            while let Some(mapped) = pending.remove(&next_index) {
+                for record in &mapped {
+                    writer.write_record(record)?;
+                }
+                written += mapped.len();
+                next_index += 1;
+            }
+        }
+
+        reader.join().expect("record reader thread panicked")?;
+
+        Ok(written)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{bio_fastq_reader_from_bytes, verbatim_fastq_reader_from};
+    use rstest::*;
+
+    fn fastq_input(n: usize) -> Vec<u8> {
+        (0..n)
+            .flat_map(|i| format!("@r{} i={}\nACGT\n+\nIIII\n", i, i).into_bytes())
+            .collect()
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(3)]
+    #[case(8)]
+    fn test_par_map_preserves_order(#[case] threads: usize) {
+        let input = fastq_input(5000);
+        let reader = bio_fastq_reader_from_bytes(&input).unwrap();
+
+        let mut buf = Vec::new();
+        let mut writer = fastq::Writer::new(&mut buf);
+        let written = par_map_records(reader.records(), &mut writer, threads, |r| {
+            let i: usize = r.id()[1..].parse().unwrap();
+            (!i.is_multiple_of(3)).then_some(r)
+        })
+        .unwrap();
+
+        let expected: Vec<u8> = (0..5000)
+            .filter(|i: &usize| !i.is_multiple_of(3))
+            .flat_map(|i| format!("@r{} i={}\nACGT\n+\nIIII\n", i, i).into_bytes())
+            .collect();
+
+        drop(writer);
+
+        assert_eq!(written, 3333);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_par_map_verbatim_round_trip() {
+        let input = fastq_input(2500);
+        let reader = verbatim_fastq_reader_from(&input[..]).unwrap();
+
+        let mut out = Vec::new();
+        par_map_records(reader, &mut out, 4, Some).unwrap();
+
+        assert_eq!(out, input);
+    }
+
+    #[test]
+    fn test_par_map_read_error() {
+        let mut input = fastq_input(3000);
+        input.extend_from_slice(b"r3000\nACGT\n+\nIIII\n");
+        let reader = verbatim_fastq_reader_from(&input[..]).unwrap();
+
+        let result = par_map_records(reader, &mut Vec::new(), 2, Some);
+        assert!(matches!(result, Err(BioError::InvalidFormatError(_))));
+    }
+
+    struct FailingSink;
+
+    impl RecordSink<VerbatimFastqRecord> for FailingSink {
+        fn write_record(&mut self, _: &VerbatimFastqRecord) -> Result<(), BioError> {
+            Err(BioError::InvalidParameterError("sink closed.".to_string()))
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(4)]
+    fn test_par_map_write_error(#[case] threads: usize) {
+        // More batches than fit in the channels, so the reader is still
+        // sending when the writer fails.
+        let input = fastq_input(4 * threads * BATCH_SIZE + 1);
+        let reader = verbatim_fastq_reader_from(&input[..]).unwrap();
+
+        let result = par_map_records(reader, &mut FailingSink, threads, Some);
+        assert!(matches!(result, Err(BioError::InvalidParameterError(_))));
+    }
+
+    #[test]
+    fn test_par_map_invalid_threads() {
+        let reader = bio_fastq_reader_from_bytes(b"").unwrap();
+        let mut writer = fastq::Writer::new(Vec::new());
+        assert!(par_map_records(reader.records(), &mut writer, 0, Some).is_err());
+    }
+}
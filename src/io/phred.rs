@@ -0,0 +1,90 @@
+use crate::errors::BioError;
+use crate::nucleotide::{PhredOffset, infer_phred_offset};
+use needletail::FastxReader;
+
+/// Infers the quality encoding of a FASTQ stream from its first `max_records` records.
+///
+/// Only the sampled records are consumed from `reader`. See
+/// [`infer_phred_offset`] for the detection rules. Returns `None` if the
+/// sampled records have no qualities (e.g. FASTA) or all sampled qualities
+/// are valid in both encodings.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `max_records` is `0`,
+/// [`BioError::InvalidFormatError`] if a quality byte is not printable, or
+/// [`BioError`] if a record cannot be parsed.
+pub fn detect_phred_offset(
+    reader: &mut dyn FastxReader,
+    max_records: usize,
+) -> Result<Option<PhredOffset>, BioError> {
+    if max_records == 0 {
+        return Err(BioError::InvalidParameterError(
+            "max records must be greater than 0.".to_string(),
+        ));
+    }
+
+    let mut min = u8::MAX;
+    let mut max = u8::MIN;
+
+    for _ in 0..max_records {
+        let Some(record) = reader.next() else {
+            break;
+        };
+
+        if let Some(qual) = record?.qual() {
+            for q in qual {
+                min = min.min(*q);
+                max = max.max(*q);
+            }
+        }
+
+        // Lowest possible byte, so no further record can change the answer.
+        if min < b'@' {
+            break;
+        }
+    }
+
+    if min > max {
+        return Ok(None);
+    }
+
+    infer_phred_offset(min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::needletail_reader_from_bytes;
+    use rstest::*;
+
+    #[rstest]
+    #[case(
+        b"@r1\nACGT\n+\nIIII\n@r2\nACGT\n+\n##II\n",
+        Some(PhredOffset::Phred33)
+    )]
+    #[case(
+        b"@r1\nACGT\n+\nhhhh\n@r2\nACGT\n+\nBBhh\n",
+        Some(PhredOffset::Phred64)
+    )]
+    #[case(b"@r1\nACGT\n+\nIIII\n", None)]
+    #[case(b">r1\nACGT\n", None)]
+    fn test_detect_phred_offset(#[case] data: &[u8], #[case] expected: Option<PhredOffset>) {
+        let mut reader = needletail_reader_from_bytes(data).unwrap();
+        assert_eq!(detect_phred_offset(&mut *reader, 100).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_detect_phred_offset_samples_first_records() {
+        let data = b"@r1\nACGT\n+\nhhhh\n@r2\nACGT\n+\n####\n";
+
+        let mut reader = needletail_reader_from_bytes(data).unwrap();
+        assert_eq!(
+            detect_phred_offset(&mut *reader, 1).unwrap(),
+            Some(PhredOffset::Phred64)
+        );
+
+        let mut reader = needletail_reader_from_bytes(data).unwrap();
+        assert!(detect_phred_offset(&mut *reader, 0).is_err());
+    }
+}
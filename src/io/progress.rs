@@ -0,0 +1,256 @@
+use super::reader::{bio_fasta_reader_from, bio_fastq_reader_from, validate_seq_file};
+use crate::errors::BioError;
+use bio::io::{fasta, fastq};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Buffered, transparently decompressed source used by the progress-aware readers.
+pub type ProgressSource = BufReader<Box<dyn Read + Send>>;
+
+/// Shared count of bytes read by a [`CountingReader`].
+#[derive(Debug, Clone, Default)]
+pub struct ByteCounter(Arc<AtomicU64>);
+
+impl ByteCounter {
+    pub fn bytes(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// [`Read`] wrapper counting the bytes read from the underlying source.
+///
+/// Wrap the raw (possibly compressed) source, so the count can be compared
+/// against the file size.
+pub struct CountingReader<R: Read> {
+    inner: R,
+    counter: ByteCounter,
+}
+
+impl<R: Read> CountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            counter: ByteCounter::default(),
+        }
+    }
+
+    /// Returns a handle to the byte count that remains valid after the reader is moved.
+    pub fn counter(&self) -> ByteCounter {
+        self.counter.clone()
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.counter.0.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// Snapshot passed to progress callbacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Records parsed so far.
+    pub records: u64,
+    /// Bytes read from the source so far (compressed bytes for gzip files).
+    pub bytes_read: u64,
+    /// Size of the source, if known.
+    pub total_bytes: Option<u64>,
+}
+
+impl Progress {
+    /// Fraction of the source read, if the total size is known.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total_bytes
+            .filter(|total| *total > 0)
+            .map(|total| (self.bytes_read as f64 / total as f64).min(1.0))
+    }
+}
+
+/// Iterator wrapper that calls `callback` every `interval` items and once at the end.
+///
+/// Works with any record iterator, e.g. to drive an `indicatif` progress bar:
+/// set the bar length to `total_bytes` and its position to `bytes_read`.
+pub struct ProgressRecords<I, F> {
+    inner: I,
+    counter: ByteCounter,
+    total_bytes: Option<u64>,
+    interval: u64,
+    records: u64,
+    callback: F,
+    finished: bool,
+}
+
+impl<I: Iterator, F: FnMut(Progress)> ProgressRecords<I, F> {
+    /// Wraps `inner`, reporting bytes from `counter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `interval` is `0`.
+    pub fn new(
+        inner: I,
+        counter: ByteCounter,
+        total_bytes: Option<u64>,
+        interval: u64,
+        callback: F,
+    ) -> Result<Self, BioError> {
+        if interval == 0 {
+            return Err(BioError::InvalidParameterError(
+                "progress interval must be larger than 0.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            inner,
+            counter,
+            total_bytes,
+            interval,
+            records: 0,
+            callback,
+            finished: false,
+        })
+    }
+
+    fn report(&mut self) {
+        (self.callback)(Progress {
+            records: self.records,
+            bytes_read: self.counter.bytes(),
+            total_bytes: self.total_bytes,
+        });
+    }
+}
+
+impl<I: Iterator, F: FnMut(Progress)> Iterator for ProgressRecords<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next() {
+            Some(item) => {
+                self.records += 1;
+                if self.records.is_multiple_of(self.interval) {
+                    self.report();
+                }
+                Some(item)
+            }
+            None => {
+                if !self.finished {
+                    self.finished = true;
+                    self.report();
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Opens a validated sequence file wrapped in a [`CountingReader`].
+fn open_counting(path: &Path) -> Result<(CountingReader<File>, Option<u64>), BioError> {
+    let (path, _) = validate_seq_file(path)?;
+    let file = File::open(path)?;
+    let total_bytes = file.metadata().ok().map(|m| m.len());
+
+    Ok((CountingReader::new(file), total_bytes))
+}
+
+/// Iterates the records of a FASTQ file (plain or gzip), calling `callback`
+/// every `interval` records and once after the last record.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the file does not exist, has an unrecognized
+/// extension or cannot be opened, or if `interval` is `0`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bio_utils_rs::io::bio_fastq_records_with_progress;
+/// use std::path::Path;
+///
+/// let records = bio_fastq_records_with_progress(Path::new("reads.fastq.gz"), 100_000, |p| {
+///     eprintln!("{} records, {:.1}%", p.records, 100.0 * p.fraction().unwrap_or(0.0));
+/// })
+/// .unwrap();
+///
+/// for record in records {
+///     let _record = record.unwrap();
+/// }
+/// ```
+pub fn bio_fastq_records_with_progress<F: FnMut(Progress)>(
+    fastq: &Path,
+    interval: u64,
+    callback: F,
+) -> Result<ProgressRecords<fastq::Records<ProgressSource>, F>, BioError> {
+    let (reader, total_bytes) = open_counting(fastq)?;
+    let counter = reader.counter();
+    let records = bio_fastq_reader_from(reader)?.records();
+
+    ProgressRecords::new(records, counter, total_bytes, interval, callback)
+}
+
+/// Iterates the records of a FASTA file (plain or gzip), calling `callback`
+/// every `interval` records and once after the last record.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the file does not exist, has an unrecognized
+/// extension or cannot be opened, or if `interval` is `0`.
+pub fn bio_fasta_records_with_progress<F: FnMut(Progress)>(
+    fasta: &Path,
+    interval: u64,
+    callback: F,
+) -> Result<ProgressRecords<fasta::Records<ProgressSource>, F>, BioError> {
+    let (reader, total_bytes) = open_counting(fasta)?;
+    let counter = reader.counter();
+    let records = bio_fasta_reader_from(reader)?.records();
+
+    ProgressRecords::new(records, counter, total_bytes, interval, callback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SimConfig, generate_fastq};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_counting_reader() {
+        let mut reader = CountingReader::new(&b"ACGTACGT"[..]);
+        let counter = reader.counter();
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(counter.bytes(), 8);
+    }
+
+    #[test]
+    fn test_bio_fastq_records_with_progress() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("reads.fastq");
+        let config = SimConfig {
+            num_records: 25,
+            ..SimConfig::default()
+        };
+        generate_fastq(&mut File::create(&path).unwrap(), &config).unwrap();
+
+        let mut reports = Vec::new();
+        let records = bio_fastq_records_with_progress(&path, 10, |p| reports.push(p)).unwrap();
+        assert_eq!(records.count(), 25);
+
+        let counts: Vec<u64> = reports.iter().map(|p| p.records).collect();
+        assert_eq!(counts, vec![10, 20, 25]);
+
+        let last = reports.last().unwrap();
+        assert_eq!(last.bytes_read, last.total_bytes.unwrap());
+        assert_eq!(last.fraction(), Some(1.0));
+    }
+
+    #[test]
+    fn test_progress_invalid_interval() {
+        let records = std::iter::empty::<()>();
+        assert!(ProgressRecords::new(records, ByteCounter::default(), None, 0, |_| {}).is_err());
+    }
+}
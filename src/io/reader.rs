@@ -2,13 +2,13 @@ use super::types::SeqFileType;
 use crate::errors::BioError;
 use bio::io::fastq::Reader;
 use flate2::read::MultiGzDecoder;
-use needletail::{FastxReader, parse_fastx_file, parse_fastx_stdin};
+use needletail::{FastxReader, parse_fastx_file, parse_fastx_reader, parse_fastx_stdin};
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
 
 /// Validates that `path` exists and has a recognized sequence file extension.
-fn validate_seq_file(path: &Path) -> Result<(&Path, SeqFileType), BioError> {
+pub(super) fn validate_seq_file(path: &Path) -> Result<(&Path, SeqFileType), BioError> {
     if !path.exists() {
         return Err(BioError::FileDoesNotExistError(path.display().to_string()));
     }
@@ -100,6 +100,92 @@ pub fn needletail_reader(path: Option<PathBuf>) -> Result<Box<dyn FastxReader>,
     Ok(reader)
 }
 
+/// Wraps `reader` in a gzip decoder if it starts with the gzip magic bytes.
+pub(super) fn decompress_if_gzip<'a, R: Read + Send + 'a>(
+    reader: R,
+) -> Result<Box<dyn Read + Send + 'a>, BioError> {
+    let mut reader = BufReader::new(reader);
+
+    let reader: Box<dyn Read + Send + 'a> = match reader.fill_buf()?.starts_with(&[0x1f, 0x8b]) {
+        true => Box::new(MultiGzDecoder::new(reader)),
+        false => Box::new(reader),
+    };
+
+    Ok(reader)
+}
+
+/// Creates a [`bio::io::fastq::Reader`] from any [`Read`] source.
+///
+/// Gzip-compressed input is detected from its magic bytes and decompressed
+/// transparently.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if the first bytes cannot be read.
+pub fn bio_fastq_reader_from<'a, R: Read + Send + 'a>(
+    reader: R,
+) -> Result<Reader<BufReader<Box<dyn Read + Send + 'a>>>, BioError> {
+    Ok(Reader::new(decompress_if_gzip(reader)?))
+}
+
+/// Creates a [`bio::io::fastq::Reader`] over an in-memory buffer (plain or gzip).
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if gzip detection fails.
+pub fn bio_fastq_reader_from_bytes(
+    bytes: &[u8],
+) -> Result<Reader<BufReader<Box<dyn Read + Send + '_>>>, BioError> {
+    bio_fastq_reader_from(bytes)
+}
+
+/// Creates a [`bio::io::fasta::Reader`] from any [`Read`] source.
+///
+/// Gzip-compressed input is detected from its magic bytes and decompressed
+/// transparently.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if the first bytes cannot be read.
+pub fn bio_fasta_reader_from<'a, R: Read + Send + 'a>(
+    reader: R,
+) -> Result<bio::io::fasta::Reader<BufReader<Box<dyn Read + Send + 'a>>>, BioError> {
+    Ok(bio::io::fasta::Reader::new(decompress_if_gzip(reader)?))
+}
+
+/// Creates a [`bio::io::fasta::Reader`] over an in-memory buffer (plain or gzip).
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if gzip detection fails.
+pub fn bio_fasta_reader_from_bytes(
+    bytes: &[u8],
+) -> Result<bio::io::fasta::Reader<BufReader<Box<dyn Read + Send + '_>>>, BioError> {
+    bio_fasta_reader_from(bytes)
+}
+
+/// Creates a needletail [`FastxReader`] from any [`Read`] source.
+///
+/// Format and compression are auto-detected by needletail.
+///
+/// # Errors
+///
+/// Returns [`BioError::NeedletailParseError`] if the input is empty or not FASTA/FASTQ.
+pub fn needletail_reader_from<'a, R: Read + Send + 'a>(
+    reader: R,
+) -> Result<Box<dyn FastxReader + 'a>, BioError> {
+    Ok(parse_fastx_reader(reader)?)
+}
+
+/// Creates a needletail [`FastxReader`] over an in-memory buffer.
+///
+/// # Errors
+///
+/// Returns [`BioError::NeedletailParseError`] if the input is empty or not FASTA/FASTQ.
+pub fn needletail_reader_from_bytes(bytes: &[u8]) -> Result<Box<dyn FastxReader + '_>, BioError> {
+    needletail_reader_from(bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +221,61 @@ mod tests {
         let result = validate_seq_file(Path::new("/nonexistent/file.fastq"));
         assert!(result.is_err());
     }
+
+    const FASTQ: &[u8] = b"@r1 desc\nACGT\n+\nIIII\n@r2\nGGCC\n+\nIIII\n";
+    const FASTA: &[u8] = b">s1\nACGT\nACGT\n>s2\nGG\n";
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_bio_fastq_reader_from_bytes() {
+        let reader = bio_fastq_reader_from_bytes(FASTQ).unwrap();
+        let ids: Vec<String> = reader
+            .records()
+            .map(|r| r.unwrap().id().to_string())
+            .collect();
+        assert_eq!(ids, vec!["r1", "r2"]);
+    }
+
+    #[test]
+    fn test_bio_fastq_reader_from_gzip_bytes() {
+        let compressed = gzip(FASTQ);
+        let reader = bio_fastq_reader_from_bytes(&compressed).unwrap();
+        assert_eq!(reader.records().count(), 2);
+    }
+
+    #[test]
+    fn test_bio_fasta_reader_from_bytes() {
+        let reader = bio_fasta_reader_from_bytes(FASTA).unwrap();
+        let seqs: Vec<Vec<u8>> = reader
+            .records()
+            .map(|r| r.unwrap().seq().to_vec())
+            .collect();
+        assert_eq!(seqs, vec![b"ACGTACGT".to_vec(), b"GG".to_vec()]);
+    }
+
+    #[test]
+    fn test_needletail_reader_from_bytes() {
+        let compressed = gzip(FASTA);
+        let mut reader = needletail_reader_from_bytes(&compressed).unwrap();
+
+        let mut n = 0;
+        while let Some(record) = reader.next() {
+            record.unwrap();
+            n += 1;
+        }
+        assert_eq!(n, 2);
+    }
+
+    #[test]
+    fn test_needletail_reader_from_empty() {
+        assert!(needletail_reader_from_bytes(b"").is_err());
+    }
 }
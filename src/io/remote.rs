@@ -0,0 +1,96 @@
+use super::reader::{bio_fasta_reader_from, bio_fastq_reader_from, needletail_reader_from};
+use crate::errors::BioError;
+use bio::io::{fasta, fastq};
+use needletail::FastxReader;
+use std::io::{BufReader, Read};
+
+/// Translates a supported URL into an HTTP(S) URL.
+///
+/// `s3://bucket/key` is mapped to the virtual-hosted style
+/// `https://bucket.s3.amazonaws.com/key`, so only public objects are supported.
+fn resolve_url(url: &str) -> Result<String, BioError> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return Ok(url.to_string());
+    }
+
+    if let Some(path) = url.strip_prefix("s3://")
+        && let Some((bucket, key)) = path.split_once('/')
+        && !bucket.is_empty()
+        && !key.is_empty()
+    {
+        return Ok(format!("https://{}.s3.amazonaws.com/{}", bucket, key));
+    }
+
+    Err(BioError::InvalidParameterError(format!(
+        "unsupported url {}, expected http(s):// or s3://bucket/key.",
+        url
+    )))
+}
+
+/// Opens a remote file as a streaming reader, without downloading it to disk.
+///
+/// Supports `http://`, `https://` and public `s3://bucket/key` URLs.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] for unsupported URLs and
+/// [`BioError::HttpError`] if the request fails or returns a non-2xx status.
+pub fn open_url(url: &str) -> Result<Box<dyn Read + Send>, BioError> {
+    let response = ureq::get(&resolve_url(url)?).call()?;
+    Ok(Box::new(response.into_body().into_reader()))
+}
+
+/// Creates a rust-bio FASTQ reader streaming from a URL. Gzip is detected from the content.
+///
+/// # Errors
+///
+/// See [`open_url`].
+pub fn bio_fastq_reader_from_url(
+    url: &str,
+) -> Result<fastq::Reader<BufReader<Box<dyn Read + Send>>>, BioError> {
+    bio_fastq_reader_from(open_url(url)?)
+}
+
+/// Creates a rust-bio FASTA reader streaming from a URL. Gzip is detected from the content.
+///
+/// # Errors
+///
+/// See [`open_url`].
+pub fn bio_fasta_reader_from_url(
+    url: &str,
+) -> Result<fasta::Reader<BufReader<Box<dyn Read + Send>>>, BioError> {
+    bio_fasta_reader_from(open_url(url)?)
+}
+
+/// Creates a needletail FASTX reader streaming from a URL.
+///
+/// # Errors
+///
+/// See [`open_url`]. Also returns [`BioError::NeedletailParseError`] if the
+/// format cannot be detected.
+pub fn needletail_reader_from_url(url: &str) -> Result<Box<dyn FastxReader>, BioError> {
+    needletail_reader_from(open_url(url)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("https://example.com/a.fq.gz", "https://example.com/a.fq.gz")]
+    #[case("http://example.com/a.fa", "http://example.com/a.fa")]
+    #[case("s3://bucket/dir/a.fq", "https://bucket.s3.amazonaws.com/dir/a.fq")]
+    fn test_resolve_url(#[case] url: &str, #[case] expected: &str) {
+        assert_eq!(resolve_url(url).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("ftp://example.com/a.fa")]
+    #[case("s3://bucket")]
+    #[case("s3:///key")]
+    #[case("a.fa")]
+    fn test_resolve_url_invalid(#[case] url: &str) {
+        assert!(resolve_url(url).is_err());
+    }
+}
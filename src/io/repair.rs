@@ -0,0 +1,389 @@
+use super::reader::bio_fastq_reader;
+use super::writer::bio_fastq_writer;
+use crate::errors::BioError;
+use bio::io::fastq::{self, Record};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::hash_map::Entry;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of on-disk partitions used once the in-memory buffer overflows.
+const SPILL_PARTITIONS: usize = 16;
+
+/// Summary of a [`repair_pairs`] run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RepairStats {
+    /// Number of R1/R2 pairs written.
+    pub pairs: usize,
+    /// Number of reads without a mate.
+    pub singletons: usize,
+    /// Number of reads that were spilled to disk.
+    pub spilled: usize,
+}
+
+/// Returns the read name shared by both mates, stripping a trailing `/1` or `/2`.
+#[inline]
+fn pair_key(id: &str) -> &str {
+    id.strip_suffix("/1")
+        .or_else(|| id.strip_suffix("/2"))
+        .unwrap_or(id)
+}
+
+/// Hash-partitioned on-disk overflow for unmatched reads. The temporary
+/// directory is removed on drop, including on early returns.
+struct Spill {
+    dir: PathBuf,
+    writers: Vec<[fastq::Writer<BufWriter<File>>; 2]>,
+}
+
+impl Spill {
+    fn new() -> Result<Self, BioError> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+
+        let dir = std::env::temp_dir().join(format!(
+            "bio_utils_rs_repair_{}_{}",
+            std::process::id(),
+            nanos
+        ));
+        fs::create_dir_all(&dir)?;
+
+        let mut writers = Vec::with_capacity(SPILL_PARTITIONS);
+        for p in 0..SPILL_PARTITIONS {
+            let r1 = File::create(Self::path(&dir, p, 0))?;
+            let r2 = File::create(Self::path(&dir, p, 1))?;
+            writers.push([
+                fastq::Writer::new(BufWriter::new(r1)),
+                fastq::Writer::new(BufWriter::new(r2)),
+            ]);
+        }
+
+        Ok(Self { dir, writers })
+    }
+
+    fn path(dir: &Path, partition: usize, side: usize) -> PathBuf {
+        dir.join(format!("part_{}_r{}.fastq", partition, side + 1))
+    }
+
+    fn partition(key: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish() as usize % SPILL_PARTITIONS
+    }
+
+    fn write(&mut self, side: usize, key: &str, record: &Record) -> Result<(), BioError> {
+        self.writers[Self::partition(key)][side].write_record(record)?;
+        Ok(())
+    }
+
+    /// Writes all `pending` reads to their partitions.
+    fn write_pending(
+        &mut self,
+        pending: &mut [HashMap<String, Record>; 2],
+        stats: &mut RepairStats,
+    ) -> Result<(), BioError> {
+        for (side, map) in pending.iter_mut().enumerate() {
+            for (key, record) in map.drain() {
+                self.write(side, &key, &record)?;
+                stats.spilled += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flushes and closes all partitions and returns their R1/R2 paths.
+    fn finish(&mut self) -> Result<Vec<[PathBuf; 2]>, BioError> {
+        for [w1, w2] in self.writers.iter_mut() {
+            w1.flush()?;
+            w2.flush()?;
+        }
+        self.writers.clear();
+
+        Ok((0..SPILL_PARTITIONS)
+            .map(|p| [Self::path(&self.dir, p, 0), Self::path(&self.dir, p, 1)])
+            .collect())
+    }
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Adds an unmatched read to `pending`, keyed by its read name.
+fn insert_pending(
+    pending: &mut HashMap<String, Record>,
+    key: String,
+    record: Record,
+) -> Result<(), BioError> {
+    match pending.entry(key) {
+        Entry::Occupied(_) => Err(BioError::DuplicateIdError(record.id().to_string())),
+        Entry::Vacant(entry) => {
+            entry.insert(record);
+            Ok(())
+        }
+    }
+}
+
+/// Output writers and running statistics of a repair run.
+struct Outputs {
+    out1: fastq::Writer<Box<dyn Write>>,
+    out2: fastq::Writer<Box<dyn Write>>,
+    singletons: Option<fastq::Writer<Box<dyn Write>>>,
+    stats: RepairStats,
+}
+
+impl Outputs {
+    fn pair(&mut self, r1: &Record, r2: &Record) -> Result<(), BioError> {
+        self.out1.write_record(r1)?;
+        self.out2.write_record(r2)?;
+        self.stats.pairs += 1;
+        Ok(())
+    }
+
+    fn singleton(&mut self, record: &Record) -> Result<(), BioError> {
+        if let Some(writer) = self.singletons.as_mut() {
+            writer.write_record(record)?;
+        }
+        self.stats.singletons += 1;
+        Ok(())
+    }
+}
+
+/// Matches reads from spilled partitions, one partition in memory at a time.
+fn resolve_spill(paths: &[[PathBuf; 2]], outputs: &mut Outputs) -> Result<(), BioError> {
+    for [p1, p2] in paths {
+        let mut pending: HashMap<String, Record> = HashMap::new();
+
+        for record in fastq::Reader::new(BufReader::new(File::open(p1)?)).records() {
+            let record = record?;
+            insert_pending(&mut pending, pair_key(record.id()).to_string(), record)?;
+        }
+
+        for record in fastq::Reader::new(BufReader::new(File::open(p2)?)).records() {
+            let record = record?;
+            match pending.remove(pair_key(record.id())) {
+                Some(mate) => outputs.pair(&mate, &record)?,
+                None => outputs.singleton(&record)?,
+            }
+        }
+
+        for record in pending.values() {
+            outputs.singleton(record)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-synchronizes paired FASTQ files whose record order was broken upstream.
+///
+/// Reads `r1` and `r2` in lockstep and keeps unmatched reads in a hash map
+/// keyed by read name (ignoring a trailing `/1` or `/2`). Whenever more than
+/// `max_buffered` reads are waiting for a mate, they are spilled to
+/// hash-partitioned temporary files, which are matched partition by partition
+/// at the end so memory stays bounded. Matched pairs are written to `out1` and
+/// `out2` in the same order, and reads without a mate to `singletons` if given.
+/// Outputs are gzip-compressed, as with [`bio_fastq_writer`].
+///
+/// # Errors
+///
+/// Returns [`BioError::DuplicateIdError`] if two reads of the same file and
+/// name are waiting for a mate at the same time, and [`BioError`] if an input
+/// cannot be opened or parsed, or writing fails.
+pub fn repair_pairs(
+    r1: PathBuf,
+    r2: PathBuf,
+    out1: PathBuf,
+    out2: PathBuf,
+    singletons: Option<PathBuf>,
+    max_buffered: usize,
+) -> Result<RepairStats, BioError> {
+    let mut reads1 = bio_fastq_reader(Some(r1))?.records();
+    let mut reads2 = bio_fastq_reader(Some(r2))?.records();
+
+    let mut outputs = Outputs {
+        out1: bio_fastq_writer(Some(out1))?,
+        out2: bio_fastq_writer(Some(out2))?,
+        singletons: singletons.map(|p| bio_fastq_writer(Some(p))).transpose()?,
+        stats: RepairStats::default(),
+    };
+
+    let mut pending: [HashMap<String, Record>; 2] = [HashMap::new(), HashMap::new()];
+    let mut spill: Option<Spill> = None;
+
+    loop {
+        let next = [reads1.next(), reads2.next()];
+        if next.iter().all(|r| r.is_none()) {
+            break;
+        }
+
+        for (side, record) in next.into_iter().enumerate() {
+            let Some(record) = record else {
+                continue;
+            };
+            let record = record?;
+            let key = pair_key(record.id()).to_string();
+
+            match pending[1 - side].remove(&key) {
+                Some(mate) if side == 0 => outputs.pair(&record, &mate)?,
+                Some(mate) => outputs.pair(&mate, &record)?,
+                None => insert_pending(&mut pending[side], key, record)?,
+            }
+        }
+
+        if pending[0].len() + pending[1].len() > max_buffered {
+            let spill = match spill.as_mut() {
+                Some(spill) => spill,
+                None => spill.insert(Spill::new()?),
+            };
+            spill.write_pending(&mut pending, &mut outputs.stats)?;
+        }
+    }
+
+    match spill {
+        Some(mut spill) => {
+            spill.write_pending(&mut pending, &mut outputs.stats)?;
+            let paths = spill.finish()?;
+            resolve_spill(&paths, &mut outputs)?;
+        }
+        None => {
+            for record in pending.iter().flat_map(|map| map.values()) {
+                outputs.singleton(record)?;
+            }
+        }
+    }
+
+    outputs.out1.flush()?;
+    outputs.out2.flush()?;
+    if let Some(writer) = outputs.singletons.as_mut() {
+        writer.flush()?;
+    }
+
+    Ok(outputs.stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use tempfile::TempDir;
+
+    fn write_fastq(path: &Path, ids: &[&str]) {
+        let mut writer = fastq::Writer::new(File::create(path).unwrap());
+        for id in ids {
+            writer.write(id, None, b"ACGT", b"IIII").unwrap();
+        }
+    }
+
+    fn read_ids(path: PathBuf) -> Vec<String> {
+        bio_fastq_reader(Some(path))
+            .unwrap()
+            .records()
+            .map(|r| r.unwrap().id().to_string())
+            .collect()
+    }
+
+    #[rstest]
+    #[case(1000, 0)]
+    #[case(1, 8)]
+    fn test_repair_pairs(#[case] max_buffered: usize, #[case] expected_spilled: usize) {
+        let tmp = TempDir::new().unwrap();
+        let r1 = tmp.path().join("r1.fastq");
+        let r2 = tmp.path().join("r2.fastq");
+
+        write_fastq(&r1, &["a/1", "b/1", "c/1", "d/1"]);
+        write_fastq(&r2, &["c/2", "a/2", "e/2", "b/2"]);
+
+        let out1 = tmp.path().join("out1.fastq.gz");
+        let out2 = tmp.path().join("out2.fastq.gz");
+        let single = tmp.path().join("single.fastq.gz");
+
+        let stats = repair_pairs(
+            r1,
+            r2,
+            out1.clone(),
+            out2.clone(),
+            Some(single.clone()),
+            max_buffered,
+        )
+        .unwrap();
+
+        assert_eq!(stats.pairs, 3);
+        assert_eq!(stats.singletons, 2);
+        assert_eq!(stats.spilled, expected_spilled);
+
+        let ids1: Vec<String> = read_ids(out1)
+            .iter()
+            .map(|id| pair_key(id).to_string())
+            .collect();
+        let ids2: Vec<String> = read_ids(out2)
+            .iter()
+            .map(|id| pair_key(id).to_string())
+            .collect();
+        assert_eq!(ids1, ids2);
+
+        let mut sorted = ids1.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec!["a", "b", "c"]);
+
+        let mut singles = read_ids(single);
+        singles.sort();
+        assert_eq!(singles, vec!["d/1", "e/2"]);
+    }
+
+    #[test]
+    fn test_repair_pairs_spill_count() {
+        let tmp = TempDir::new().unwrap();
+        let (r1, r2) = (tmp.path().join("r1.fastq"), tmp.path().join("r2.fastq"));
+        write_fastq(&r1, &["a/1", "b/1", "c/1"]);
+        write_fastq(&r2, &["x/2", "y/2", "z/2"]);
+
+        let out = |name: &str| tmp.path().join(name);
+        let stats = repair_pairs(r1, r2, out("o1.fastq.gz"), out("o2.fastq.gz"), None, 3).unwrap();
+
+        // Four reads overflow the buffer, the last two are spilled at the end.
+        assert_eq!(stats.spilled, 6);
+        assert_eq!((stats.pairs, stats.singletons), (0, 6));
+    }
+
+    #[rstest]
+    #[case(1000)]
+    #[case(1)]
+    fn test_repair_pairs_duplicate_id(#[case] max_buffered: usize) {
+        let tmp = TempDir::new().unwrap();
+        let (r1, r2) = (tmp.path().join("r1.fastq"), tmp.path().join("r2.fastq"));
+        write_fastq(&r1, &["a/1", "a/1", "b/1"]);
+        write_fastq(&r2, &["b/2", "c/2", "a/2"]);
+
+        let out = |name: &str| tmp.path().join(name);
+        let result = repair_pairs(
+            r1,
+            r2,
+            out("o1.fastq.gz"),
+            out("o2.fastq.gz"),
+            None,
+            max_buffered,
+        );
+
+        assert!(matches!(result, Err(BioError::DuplicateIdError(id)) if id == "a/1"));
+    }
+
+    #[rstest]
+    #[case("read/1", "read")]
+    #[case("read/2", "read")]
+    #[case("read", "read")]
+    #[case("read/3", "read/3")]
+    fn test_pair_key(#[case] id: &str, #[case] expected: &str) {
+        assert_eq!(pair_key(id), expected);
+    }
+}
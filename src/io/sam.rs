@@ -0,0 +1,333 @@
+use super::SequenceDictEntry;
+use crate::cigar::Cigar;
+use crate::errors::BioError;
+use crate::nucleotide::Strand;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// SAM flag: the segment is unmapped.
+pub const SAM_FLAG_UNMAPPED: u16 = 0x4;
+/// SAM flag: the sequence is reverse complemented.
+pub const SAM_FLAG_REVERSE: u16 = 0x10;
+/// SAM flag: secondary alignment.
+pub const SAM_FLAG_SECONDARY: u16 = 0x100;
+/// SAM flag: supplementary alignment.
+pub const SAM_FLAG_SUPPLEMENTARY: u16 = 0x800;
+
+/// A single-end SAM alignment record.
+///
+/// `pos` is zero-based and written one-based. Mate fields (`RNEXT`, `PNEXT`,
+/// `TLEN`) are always written as `*`, `0` and `0`. An empty `seq` or `cigar`
+/// is written as `*`, and `qual` holds Phred+33 encoded bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamRecord {
+    pub qname: String,
+    pub flag: u16,
+    /// Reference name, `None` for unmapped records.
+    pub rname: Option<String>,
+    pub pos: usize,
+    pub mapq: u8,
+    pub cigar: Cigar,
+    pub seq: Vec<u8>,
+    pub qual: Option<Vec<u8>>,
+    /// Optional `TAG:TYPE:VALUE` fields, written verbatim.
+    pub tags: Vec<String>,
+}
+
+impl SamRecord {
+    /// Creates a primary alignment of `seq` to `rname` starting at zero-based `pos`.
+    ///
+    /// For [`Strand::Reverse`] the reverse flag is set; as in SAM, `seq` and
+    /// `cigar` are expected in reference orientation.
+    pub fn mapped(
+        qname: &str,
+        rname: &str,
+        pos: usize,
+        strand: Strand,
+        mapq: u8,
+        cigar: Cigar,
+        seq: &[u8],
+    ) -> Self {
+        Self {
+            qname: qname.to_string(),
+            flag: match strand {
+                Strand::Forward => 0,
+                Strand::Reverse => SAM_FLAG_REVERSE,
+            },
+            rname: Some(rname.to_string()),
+            pos,
+            mapq,
+            cigar,
+            seq: seq.to_vec(),
+            qual: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Creates an unmapped record.
+    pub fn unmapped(qname: &str, seq: &[u8]) -> Self {
+        Self {
+            qname: qname.to_string(),
+            flag: SAM_FLAG_UNMAPPED,
+            rname: None,
+            pos: 0,
+            mapq: 0,
+            cigar: Cigar::new(),
+            seq: seq.to_vec(),
+            qual: None,
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn with_qual(mut self, qual: &[u8]) -> Self {
+        self.qual = Some(qual.to_vec());
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    pub fn is_unmapped(&self) -> bool {
+        self.flag & SAM_FLAG_UNMAPPED != 0
+    }
+}
+
+/// Writes SAM files with a header built from a sequence dictionary.
+///
+/// Records are validated against the header: the reference must be declared,
+/// the alignment must fit inside it, and the CIGAR, sequence and quality
+/// lengths must agree.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::cigar::Cigar;
+/// use bio_utils_rs::io::{SamRecord, SamWriter, SequenceDictEntry};
+/// use bio_utils_rs::nucleotide::Strand;
+///
+/// let dict = vec![SequenceDictEntry { name: "chr1".to_string(), length: 100, md5: String::new() }];
+/// let mut writer = SamWriter::new(Vec::new(), &dict).unwrap();
+///
+/// let cigar: Cigar = "4=".parse().unwrap();
+/// let record = SamRecord::mapped("read1", "chr1", 9, Strand::Forward, 60, cigar, b"ACGT");
+/// writer.write_record(&record).unwrap();
+///
+/// let sam = String::from_utf8(writer.into_inner()).unwrap();
+/// assert!(sam.ends_with("read1\t0\tchr1\t10\t60\t4=\t*\t0\t0\tACGT\t*\n"));
+/// ```
+pub struct SamWriter<W: Write> {
+    writer: W,
+    lengths: HashMap<String, usize>,
+}
+
+impl<W: Write> SamWriter<W> {
+    /// Creates a writer and writes the `@HD`, `@SQ` and `@PG` header lines.
+    ///
+    /// `M5` tags are only written for entries with a non-empty `md5`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::DuplicateIdError`] if a sequence name occurs more
+    /// than once, or [`BioError::IoError`] if writing fails.
+    pub fn new(mut writer: W, dict: &[SequenceDictEntry]) -> Result<Self, BioError> {
+        let mut lengths = HashMap::with_capacity(dict.len());
+
+        writeln!(writer, "@HD\tVN:1.6\tSO:unsorted")?;
+        for entry in dict {
+            if lengths.insert(entry.name.clone(), entry.length).is_some() {
+                return Err(BioError::DuplicateIdError(entry.name.clone()));
+            }
+
+            write!(writer, "@SQ\tSN:{}\tLN:{}", entry.name, entry.length)?;
+            if !entry.md5.is_empty() {
+                write!(writer, "\tM5:{}", entry.md5)?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(
+            writer,
+            "@PG\tID:{0}\tPN:{0}\tVN:{1}",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        )?;
+
+        Ok(Self { writer, lengths })
+    }
+
+    fn validate(&self, record: &SamRecord) -> Result<(), BioError> {
+        let invalid = |msg: String| {
+            BioError::InvalidParameterError(format!("SAM record {} {}.", record.qname, msg))
+        };
+
+        if let Some(qual) = &record.qual
+            && qual.len() != record.seq.len()
+        {
+            return Err(invalid(format!(
+                "has sequence length {} but quality length {}",
+                record.seq.len(),
+                qual.len()
+            )));
+        }
+
+        if !record.seq.is_empty()
+            && !record.cigar.is_empty()
+            && record.cigar.query_len() != record.seq.len()
+        {
+            return Err(invalid(format!(
+                "has sequence length {} but CIGAR query length {}",
+                record.seq.len(),
+                record.cigar.query_len()
+            )));
+        }
+
+        if let Some(rname) = &record.rname {
+            let Some(&length) = self.lengths.get(rname) else {
+                return Err(invalid(format!("references unknown sequence {}", rname)));
+            };
+
+            if record.pos + record.cigar.target_len() > length {
+                return Err(invalid(format!(
+                    "extends past the end of {} (length {})",
+                    rname, length
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a single alignment record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if the record is
+    /// inconsistent with itself or the header, or [`BioError::IoError`] if
+    /// writing fails.
+    pub fn write_record(&mut self, record: &SamRecord) -> Result<(), BioError> {
+        self.validate(record)?;
+
+        let (rname, pos) = match &record.rname {
+            Some(rname) => (rname.as_str(), record.pos + 1),
+            None => ("*", 0),
+        };
+
+        write!(
+            self.writer,
+            "{}\t{}\t{}\t{}\t{}\t{}\t*\t0\t0\t",
+            record.qname, record.flag, rname, pos, record.mapq, record.cigar
+        )?;
+
+        match record.seq.is_empty() {
+            true => self.writer.write_all(b"*")?,
+            false => self.writer.write_all(&record.seq)?,
+        }
+        self.writer.write_all(b"\t")?;
+
+        match &record.qual {
+            Some(qual) if !qual.is_empty() => self.writer.write_all(qual)?,
+            _ => self.writer.write_all(b"*")?,
+        }
+
+        for tag in &record.tags {
+            write!(self.writer, "\t{}", tag)?;
+        }
+
+        writeln!(self.writer)?;
+        Ok(())
+    }
+
+    /// Flushes and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if flushing fails.
+    pub fn finish(mut self) -> Result<W, BioError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Returns the underlying writer without flushing.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn dict() -> Vec<SequenceDictEntry> {
+        vec![
+            SequenceDictEntry {
+                name: "chr1".to_string(),
+                length: 20,
+                md5: "f1f8f4bf413b16ad135722aa4591043e".to_string(),
+            },
+            SequenceDictEntry {
+                name: "chr2".to_string(),
+                length: 10,
+                md5: String::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_sam_writer() {
+        let mut writer = SamWriter::new(Vec::new(), &dict()).unwrap();
+
+        let cigar = Cigar::parse("2S3=1X1D2=").unwrap();
+        let mapped = SamRecord::mapped("r1", "chr2", 3, Strand::Reverse, 30, cigar, b"TTACGTAC")
+            .with_qual(b"IIIIIIII")
+            .with_tag("NM:i:2");
+        writer.write_record(&mapped).unwrap();
+        writer
+            .write_record(&SamRecord::unmapped("r2", b"ACGT"))
+            .unwrap();
+
+        let sam = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let lines: Vec<&str> = sam.lines().collect();
+
+        assert_eq!(lines[0], "@HD\tVN:1.6\tSO:unsorted");
+        assert_eq!(
+            lines[1],
+            "@SQ\tSN:chr1\tLN:20\tM5:f1f8f4bf413b16ad135722aa4591043e"
+        );
+        assert_eq!(lines[2], "@SQ\tSN:chr2\tLN:10");
+        assert!(lines[3].starts_with("@PG\tID:bio_utils_rs"));
+        assert_eq!(
+            lines[4],
+            "r1\t16\tchr2\t4\t30\t2S3=1X1D2=\t*\t0\t0\tTTACGTAC\tIIIIIIII\tNM:i:2"
+        );
+        assert_eq!(lines[5], "r2\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\t*");
+    }
+
+    #[rstest]
+    // Unknown reference.
+    #[case(SamRecord::mapped("r", "chr3", 0, Strand::Forward, 60, Cigar::parse("4M").unwrap(), b"ACGT"))]
+    // Alignment extends past the reference end.
+    #[case(SamRecord::mapped("r", "chr2", 7, Strand::Forward, 60, Cigar::parse("4M").unwrap(), b"ACGT"))]
+    // CIGAR and sequence lengths differ.
+    #[case(SamRecord::mapped("r", "chr1", 0, Strand::Forward, 60, Cigar::parse("5M").unwrap(), b"ACGT"))]
+    // Quality and sequence lengths differ.
+    #[case(SamRecord::unmapped("r", b"ACGT").with_qual(b"II"))]
+    fn test_sam_writer_invalid(#[case] record: SamRecord) {
+        let mut writer = SamWriter::new(Vec::new(), &dict()).unwrap();
+        assert!(matches!(
+            writer.write_record(&record),
+            Err(BioError::InvalidParameterError(_))
+        ));
+    }
+
+    #[test]
+    fn test_sam_writer_duplicate_sequence() {
+        let mut entries = dict();
+        entries.push(entries[0].clone());
+        assert!(matches!(
+            SamWriter::new(Vec::new(), &entries),
+            Err(BioError::DuplicateIdError(_))
+        ));
+    }
+}
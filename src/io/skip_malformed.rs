@@ -0,0 +1,144 @@
+use crate::errors::BioError;
+use bio::io::fastq;
+use std::io::{BufRead, ErrorKind};
+
+/// Checks a parsed record for problems the bio parser does not catch.
+fn validate_fastq_record(record: &fastq::Record) -> Result<(), String> {
+    record.check().map_err(|e| e.to_string())?;
+
+    if let Some(pos) = record.seq().iter().position(|b| !b.is_ascii_alphabetic()) {
+        return Err(format!(
+            "illegal sequence character {:?} at position {}.",
+            record.seq()[pos] as char,
+            pos
+        ));
+    }
+
+    if let Some(pos) = record
+        .qual()
+        .iter()
+        .position(|b| !(b'!'..=b'~').contains(b))
+    {
+        return Err(format!("illegal quality character at position {}.", pos));
+    }
+
+    Ok(())
+}
+
+/// Iterator over FASTQ records that skips malformed records instead of aborting.
+///
+/// Records with a missing `@`, incomplete lines, non UTF-8 content, unequal
+/// sequence/quality lengths or illegal characters are logged at `warn` level
+/// and skipped. Genuine I/O errors are still returned, after which iteration stops.
+pub struct SkipMalformedRecords<B: BufRead> {
+    records: fastq::Records<B>,
+    record_index: usize,
+    skipped: usize,
+    done: bool,
+}
+
+impl<B: BufRead> SkipMalformedRecords<B> {
+    /// Number of malformed records skipped so far.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    fn skip(&mut self, reason: &str) {
+        log::warn!(
+            "skipping malformed record #{}: {}",
+            self.record_index,
+            reason
+        );
+        self.skipped += 1;
+    }
+}
+
+impl<B: BufRead> Iterator for SkipMalformedRecords<B> {
+    type Item = Result<fastq::Record, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let result = self.records.next()?;
+            self.record_index += 1;
+
+            match result {
+                Ok(record) => match validate_fastq_record(&record) {
+                    Ok(()) => return Some(Ok(record)),
+                    Err(reason) => self.skip(&reason),
+                },
+                Err(fastq::Error::ReadError(e)) if e.kind() != ErrorKind::InvalidData => {
+                    self.done = true;
+                    return Some(Err(BioError::IoError(e)));
+                }
+                Err(e) => self.skip(&e.to_string()),
+            }
+        }
+    }
+}
+
+/// Wraps a FASTQ reader so that malformed records are skipped rather than
+/// aborting the stream. Query [`SkipMalformedRecords::skipped`] afterwards
+/// for the number of discarded records.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::io::{bio_fastq_reader_from_bytes, skip_malformed};
+///
+/// let data = b"@r1\nACGT\n+\nIIII\n@r2\nACGT\n+\nII\n@r3\nGG\n+\nII\n";
+/// let mut records = skip_malformed(bio_fastq_reader_from_bytes(data).unwrap());
+///
+/// let ids: Vec<String> = records
+///     .by_ref()
+///     .map(|r| r.unwrap().id().to_string())
+///     .collect();
+///
+/// assert_eq!(ids, vec!["r1", "r3"]);
+/// assert_eq!(records.skipped(), 1);
+/// ```
+pub fn skip_malformed<B: BufRead>(reader: fastq::Reader<B>) -> SkipMalformedRecords<B> {
+    SkipMalformedRecords {
+        records: reader.records(),
+        record_index: 0,
+        skipped: 0,
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::bio_fastq_reader_from_bytes;
+    use rstest::*;
+
+    fn collect_ids(data: &[u8]) -> (Vec<String>, usize) {
+        let mut records = skip_malformed(bio_fastq_reader_from_bytes(data).unwrap());
+        let ids = records
+            .by_ref()
+            .map(|r| r.unwrap().id().to_string())
+            .collect();
+        (ids, records.skipped())
+    }
+
+    #[rstest]
+    #[case(b"@r1\nACGT\n+\nIIII\n", vec!["r1"], 0)]
+    // quality length mismatch
+    #[case(b"@r1\nACGT\n+\nIII\n@r2\nAC\n+\nII\n", vec!["r2"], 1)]
+    // illegal sequence character
+    #[case(b"@r1\nAC1T\n+\nIIII\n@r2\nAC\n+\nII\n", vec!["r2"], 1)]
+    // missing '@', parser resyncs on the next header line
+    #[case(b"r1\n@r2\nAC\n+\nII\n", vec!["r2"], 1)]
+    fn test_skip_malformed(
+        #[case] data: &[u8],
+        #[case] expected_ids: Vec<&str>,
+        #[case] expected_skipped: usize,
+    ) {
+        let (ids, skipped) = collect_ids(data);
+        assert_eq!(ids, expected_ids);
+        assert_eq!(skipped, expected_skipped);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::errors::BioError;
+use bio::io::fastq;
+use std::io::BufRead;
+
+/// Iterator over FASTQ records that stops cleanly at a truncated or corrupt tail.
+///
+/// Every complete record is yielded. At the first read, decompression or parse
+/// error, or at a record whose sequence and quality lengths differ (the usual
+/// symptom of a file cut mid-record), a single
+/// [`BioError::TruncatedFileError`] describing what was salvaged is yielded and
+/// iteration stops.
+pub struct TolerantRecords<B: BufRead> {
+    records: fastq::Records<B>,
+    records_salvaged: usize,
+    bytes_salvaged: u64,
+    done: bool,
+}
+
+impl<B: BufRead> TolerantRecords<B> {
+    /// Number of complete records yielded so far.
+    pub fn records_salvaged(&self) -> usize {
+        self.records_salvaged
+    }
+
+    /// Uncompressed size in bytes of the complete records yielded so far,
+    /// assuming four-line records with an empty `+` line.
+    pub fn bytes_salvaged(&self) -> u64 {
+        self.bytes_salvaged
+    }
+
+    fn truncated(&mut self, reason: String) -> BioError {
+        self.done = true;
+
+        BioError::TruncatedFileError {
+            records: self.records_salvaged,
+            bytes: self.bytes_salvaged,
+            reason,
+        }
+    }
+}
+
+/// Size of a record in FASTQ text format: `@id desc\nseq\n+\nqual\n`.
+fn record_text_len(record: &fastq::Record) -> u64 {
+    let header = 1 + record.id().len() + record.desc().map_or(0, |d| d.len() + 1);
+    (header + record.seq().len() + record.qual().len() + 5) as u64
+}
+
+impl<B: BufRead> Iterator for TolerantRecords<B> {
+    type Item = Result<fastq::Record, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.records.next()? {
+            Ok(record) => match record.check() {
+                Ok(()) => {
+                    self.records_salvaged += 1;
+                    self.bytes_salvaged += record_text_len(&record);
+                    Some(Ok(record))
+                }
+                Err(e) => Some(Err(self.truncated(format!(
+                    "incomplete record {}: {}",
+                    record.id(),
+                    e
+                )))),
+            },
+            Err(e) => Some(Err(self.truncated(e.to_string()))),
+        }
+    }
+}
+
+/// Wraps a FASTQ reader so that a truncated or corrupt file (e.g. an
+/// interrupted `.fastq.gz` transfer) yields all complete records followed by
+/// a typed [`BioError::TruncatedFileError`], instead of an opaque I/O error.
+///
+/// A clean end of file ends iteration without an error.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::errors::BioError;
+/// use bio_utils_rs::io::{bio_fastq_reader_from_bytes, tolerant_records};
+///
+/// let data = b"@r1\nACGT\n+\nIIII\n@r2\nACGT\n+\nII";
+/// let mut records = tolerant_records(bio_fastq_reader_from_bytes(data).unwrap());
+///
+/// assert_eq!(records.next().unwrap().unwrap().id(), "r1");
+/// assert!(matches!(
+///     records.next(),
+///     Some(Err(BioError::TruncatedFileError { records: 1, .. }))
+/// ));
+/// assert!(records.next().is_none());
+/// ```
+pub fn tolerant_records<B: BufRead>(reader: fastq::Reader<B>) -> TolerantRecords<B> {
+    TolerantRecords {
+        records: reader.records(),
+        records_salvaged: 0,
+        bytes_salvaged: 0,
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::bio_fastq_reader_from_bytes;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    fn fastq(num_records: usize) -> Vec<u8> {
+        (0..num_records)
+            .flat_map(|i| format!("@read_{}\nACGTACGTAC\n+\nIIIIIIIIII\n", i).into_bytes())
+            .collect()
+    }
+
+    fn collect(data: &[u8]) -> (usize, Option<BioError>, u64) {
+        let mut records = tolerant_records(bio_fastq_reader_from_bytes(data).unwrap());
+        let mut ok = 0;
+        let mut error = None;
+
+        for r in records.by_ref() {
+            match r {
+                Ok(_) => ok += 1,
+                Err(e) => error = Some(e),
+            }
+        }
+
+        (ok, error, records.bytes_salvaged())
+    }
+
+    #[test]
+    fn test_tolerant_records_complete() {
+        let data = fastq(3);
+        let (ok, error, bytes) = collect(&data);
+
+        assert_eq!(ok, 3);
+        assert!(error.is_none());
+        assert_eq!(bytes, data.len() as u64);
+    }
+
+    #[test]
+    fn test_tolerant_records_truncated_plain() {
+        let data = fastq(3);
+        let (ok, error, _) = collect(&data[..data.len() - 5]);
+
+        assert_eq!(ok, 2);
+        assert!(matches!(
+            error,
+            Some(BioError::TruncatedFileError { records: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_tolerant_records_truncated_gzip() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(&fastq(1000)).unwrap();
+        let gz = encoder.finish().unwrap();
+
+        let (ok, error, bytes) = collect(&gz[..gz.len() / 2]);
+
+        assert!(ok > 0 && ok < 1000);
+        assert!(matches!(error, Some(BioError::TruncatedFileError { .. })));
+        assert_eq!(bytes, fastq(ok).len() as u64);
+    }
+}
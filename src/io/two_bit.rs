@@ -0,0 +1,645 @@
+use crate::errors::BioError;
+use crate::nucleotide::PackedSeq;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// `.2bit` file signature.
+const TWO_BIT_SIGNATURE: u32 = 0x1A41_2743;
+
+/// `.nib` file signature.
+const NIB_SIGNATURE: u32 = 0x6BE9_3D3A;
+
+/// Size in bytes of the `.2bit` file header.
+const TWO_BIT_HEADER_SIZE: u64 = 16;
+
+/// `.2bit` base codes (`T=0, C=1, A=2, G=3`) to [`PackedSeq`] codes (`A=0, C=1, G=2, T=3`).
+const TWO_BIT_TO_CODE: [u8; 4] = [3, 1, 0, 2];
+
+/// [`PackedSeq`] codes to `.2bit` base codes.
+const CODE_TO_TWO_BIT: [u8; 4] = [2, 1, 3, 0];
+
+/// `.nib` nibble values (`T, C, A, G, N`) to uppercase ASCII.
+const NIB_TO_NT: [u8; 5] = [b'T', b'C', b'A', b'G', b'N'];
+
+/// Bit flagging a soft-masked base in a `.nib` nibble.
+const NIB_MASK_BIT: u8 = 0x8;
+
+/// Reads a `u32`, byte-swapped if the file was written on a machine of the other endianness.
+fn read_u32<R: Read>(reader: &mut R, swapped: bool) -> Result<u32, BioError> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+
+    Ok(match swapped {
+        true => u32::from_be_bytes(buf),
+        false => u32::from_le_bytes(buf),
+    })
+}
+
+fn read_u64<R: Read>(reader: &mut R, swapped: bool) -> Result<u64, BioError> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+
+    Ok(match swapped {
+        true => u64::from_be_bytes(buf),
+        false => u64::from_le_bytes(buf),
+    })
+}
+
+/// Reads a signature and returns whether the remaining fields are byte-swapped.
+fn read_signature<R: Read>(reader: &mut R, signature: u32, format: &str) -> Result<bool, BioError> {
+    match read_u32(reader, false)? {
+        s if s == signature => Ok(false),
+        s if s == signature.swap_bytes() => Ok(true),
+        s => Err(BioError::InvalidFormatError(format!(
+            "invalid {} signature {:#010x}.",
+            format, s
+        ))),
+    }
+}
+
+/// Reads exactly `len` bytes. Memory grows with the bytes actually read, so
+/// a corrupt length fails at the end of the stream instead of allocating it.
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, BioError> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+
+    match bytes.len() == len {
+        true => Ok(bytes),
+        false => Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into()),
+    }
+}
+
+/// Reads `count` block starts followed by `count` block sizes.
+fn read_blocks<R: Read>(reader: &mut R, swapped: bool) -> Result<Vec<(usize, usize)>, BioError> {
+    let count = read_u32(reader, swapped)? as usize;
+
+    // `count` is untrusted, so the lists grow as blocks are read.
+    let mut starts = Vec::new();
+    for _ in 0..count {
+        starts.push(read_u32(reader, swapped)? as usize);
+    }
+
+    let mut blocks = Vec::new();
+    for start in starts {
+        blocks.push((start, read_u32(reader, swapped)? as usize));
+    }
+
+    Ok(blocks)
+}
+
+fn to_u32(value: usize, what: &str) -> Result<u32, BioError> {
+    u32::try_from(value).map_err(|_| {
+        BioError::InvalidParameterError(format!(
+            "{} {} exceeds the .2bit limit of {}.",
+            what,
+            value,
+            u32::MAX
+        ))
+    })
+}
+
+/// Layout of a single sequence record in a `.2bit` file.
+struct TwoBitRecord {
+    len: usize,
+    n_runs: Vec<(usize, usize)>,
+    mask_runs: Vec<(usize, usize)>,
+    /// Absolute file offset of the packed bases.
+    dna_offset: u64,
+}
+
+/// Random-access reader for UCSC `.2bit` files.
+///
+/// Only the header and sequence index are read on construction. Sequences
+/// are then loaded on demand, either whole as a [`PackedSeq`] or as an ASCII
+/// region that only reads the bytes it covers. Both little- and big-endian
+/// files, and both version 0 (32-bit offsets) and version 1 (64-bit offsets),
+/// are supported.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::io::{TwoBitReader, write_two_bit};
+/// use bio_utils_rs::nucleotide::PackedSeq;
+/// use std::io::Cursor;
+///
+/// let chr1 = PackedSeq::from_ascii(b"ACGTNNacgt");
+/// let mut buf = Vec::new();
+/// write_two_bit(&mut buf, &[("chr1", &chr1)]).unwrap();
+///
+/// let mut reader = TwoBitReader::new(Cursor::new(buf)).unwrap();
+/// assert_eq!(reader.names(), vec!["chr1"]);
+/// assert_eq!(reader.read_region("chr1", 2, 8).unwrap(), b"GTNNac");
+/// ```
+pub struct TwoBitReader<R: Read + Seek> {
+    inner: R,
+    swapped: bool,
+    index: Vec<(String, u64)>,
+}
+
+impl<R: Read + Seek> TwoBitReader<R> {
+    /// Parses the header and sequence index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidFormatError`] if the signature or version is
+    /// not recognized, or [`BioError::IoError`] if the index cannot be read.
+    pub fn new(mut inner: R) -> Result<Self, BioError> {
+        inner.seek(SeekFrom::Start(0))?;
+        let swapped = read_signature(&mut inner, TWO_BIT_SIGNATURE, ".2bit")?;
+
+        let version = read_u32(&mut inner, swapped)?;
+        if version > 1 {
+            return Err(BioError::InvalidFormatError(format!(
+                "unsupported .2bit version {}.",
+                version
+            )));
+        }
+
+        let count = read_u32(&mut inner, swapped)? as usize;
+        let _reserved = read_u32(&mut inner, swapped)?;
+
+        // `count` is untrusted, so the index grows as entries are read.
+        let mut index = Vec::new();
+        for _ in 0..count {
+            let mut name_len = [0u8; 1];
+            inner.read_exact(&mut name_len)?;
+
+            let mut name = vec![0u8; name_len[0] as usize];
+            inner.read_exact(&mut name)?;
+            let name = String::from_utf8(name).map_err(|e| {
+                BioError::InvalidFormatError(format!("non UTF-8 sequence name: {}.", e))
+            })?;
+
+            let offset = match version {
+                0 => read_u32(&mut inner, swapped)? as u64,
+                _ => read_u64(&mut inner, swapped)?,
+            };
+
+            index.push((name, offset));
+        }
+
+        Ok(Self {
+            inner,
+            swapped,
+            index,
+        })
+    }
+
+    /// Sequence names, in file order.
+    pub fn names(&self) -> Vec<&str> {
+        self.index.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    fn record(&mut self, name: &str) -> Result<TwoBitRecord, BioError> {
+        let offset = self
+            .index
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, offset)| *offset)
+            .ok_or_else(|| {
+                BioError::InvalidParameterError(format!(
+                    "sequence {} not found in .2bit file.",
+                    name
+                ))
+            })?;
+
+        self.inner.seek(SeekFrom::Start(offset))?;
+
+        let len = read_u32(&mut self.inner, self.swapped)? as usize;
+        let n_runs = read_blocks(&mut self.inner, self.swapped)?;
+        let mask_runs = read_blocks(&mut self.inner, self.swapped)?;
+        let _reserved = read_u32(&mut self.inner, self.swapped)?;
+
+        Ok(TwoBitRecord {
+            len,
+            n_runs,
+            mask_runs,
+            dna_offset: self.inner.stream_position()?,
+        })
+    }
+
+    /// Length of sequence `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `name` is not in the file.
+    pub fn sequence_len(&mut self, name: &str) -> Result<usize, BioError> {
+        Ok(self.record(name)?.len)
+    }
+
+    /// Loads sequence `name` as a [`PackedSeq`], keeping `N` and soft-mask runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `name` is not in the file,
+    /// or [`BioError::InvalidFormatError`] if its block lists are inconsistent.
+    pub fn read_packed(&mut self, name: &str) -> Result<PackedSeq, BioError> {
+        let record = self.record(name)?;
+
+        let bytes = read_bytes(&mut self.inner, record.len.div_ceil(4))?;
+
+        let mut words = vec![0u64; record.len.div_ceil(32)];
+        for i in 0..record.len {
+            let two_bit = (bytes[i / 4] >> (6 - 2 * (i % 4))) & 3;
+            words[i / 32] |= (TWO_BIT_TO_CODE[two_bit as usize] as u64) << ((i % 32) * 2);
+        }
+
+        PackedSeq::from_parts(words, record.len, record.n_runs, record.mask_runs)
+            .map_err(|e| BioError::InvalidFormatError(format!("sequence {}: {}", name, e)))
+    }
+
+    /// Loads sequence `name` as ASCII.
+    ///
+    /// # Errors
+    ///
+    /// See [`TwoBitReader::read_packed`].
+    pub fn read_sequence(&mut self, name: &str) -> Result<Vec<u8>, BioError> {
+        Ok(self.read_packed(name)?.to_ascii())
+    }
+
+    /// Reads the 0-based, half-open region `[start, end)` of sequence `name` as
+    /// ASCII, only loading the packed bytes it covers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `name` is not in the file
+    /// or the region is out of bounds.
+    pub fn read_region(
+        &mut self,
+        name: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<Vec<u8>, BioError> {
+        let record = self.record(name)?;
+
+        if start > end || end > record.len {
+            return Err(BioError::InvalidParameterError(format!(
+                "region [{}, {}) is invalid for sequence {} of length {}.",
+                start, end, name, record.len
+            )));
+        }
+
+        let first_byte = start / 4;
+        let mut bytes = vec![0u8; end.div_ceil(4) - first_byte];
+        self.inner
+            .seek(SeekFrom::Start(record.dna_offset + first_byte as u64))?;
+        self.inner.read_exact(&mut bytes)?;
+
+        let mut seq: Vec<u8> = (start..end)
+            .map(|i| {
+                let two_bit = (bytes[i / 4 - first_byte] >> (6 - 2 * (i % 4))) & 3;
+                b"TCAG"[two_bit as usize]
+            })
+            .collect();
+
+        for &(run_start, run_len) in &record.n_runs {
+            let (s, e) = (run_start.max(start), (run_start + run_len).min(end));
+            if s < e {
+                seq[s - start..e - start].fill(b'N');
+            }
+        }
+
+        for &(run_start, run_len) in &record.mask_runs {
+            let (s, e) = (run_start.max(start), (run_start + run_len).min(end));
+            if s < e {
+                seq[s - start..e - start].make_ascii_lowercase();
+            }
+        }
+
+        Ok(seq)
+    }
+}
+
+/// Opens a `.2bit` file for random access.
+///
+/// # Errors
+///
+/// Returns [`BioError::FileDoesNotExistError`] if `path` does not exist, or
+/// any error of [`TwoBitReader::new`].
+pub fn two_bit_reader(path: &Path) -> Result<TwoBitReader<BufReader<File>>, BioError> {
+    if !path.exists() {
+        return Err(BioError::FileDoesNotExistError(path.display().to_string()));
+    }
+
+    TwoBitReader::new(BufReader::new(File::open(path)?))
+}
+
+fn write_blocks<W: Write>(writer: &mut W, runs: &[(usize, usize)]) -> Result<(), BioError> {
+    writer.write_all(&to_u32(runs.len(), "block count")?.to_le_bytes())?;
+
+    for &(start, _) in runs {
+        writer.write_all(&to_u32(start, "block start")?.to_le_bytes())?;
+    }
+    for &(_, len) in runs {
+        writer.write_all(&to_u32(len, "block size")?.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Writes named packed sequences as a little-endian UCSC `.2bit` file.
+///
+/// Version 0 is written unless the file exceeds 4 GiB, in which case version 1
+/// (64-bit offsets) is used.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if a name is longer than 255
+/// bytes or a sequence is longer than `u32::MAX`, or [`BioError::IoError`] if
+/// writing fails.
+pub fn write_two_bit<W: Write>(
+    writer: &mut W,
+    records: &[(&str, &PackedSeq)],
+) -> Result<(), BioError> {
+    for (name, seq) in records {
+        if name.len() > u8::MAX as usize {
+            return Err(BioError::InvalidParameterError(format!(
+                "sequence name {} exceeds 255 bytes.",
+                name
+            )));
+        }
+        to_u32(seq.len(), "sequence length")?;
+    }
+
+    let record_size = |seq: &PackedSeq| -> u64 {
+        16 + 8 * (seq.n_runs().len() + seq.mask_runs().len()) as u64 + seq.len().div_ceil(4) as u64
+    };
+    let index_size = |offset_size: u64| -> u64 {
+        records
+            .iter()
+            .map(|(name, _)| 1 + name.len() as u64 + offset_size)
+            .sum()
+    };
+
+    let data_size: u64 = records.iter().map(|(_, seq)| record_size(seq)).sum();
+    let version: u32 = match TWO_BIT_HEADER_SIZE + index_size(4) + data_size > u32::MAX as u64 {
+        true => 1,
+        false => 0,
+    };
+
+    writer.write_all(&TWO_BIT_SIGNATURE.to_le_bytes())?;
+    writer.write_all(&version.to_le_bytes())?;
+    writer.write_all(&to_u32(records.len(), "sequence count")?.to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+
+    let mut offset = TWO_BIT_HEADER_SIZE + index_size(4 + 4 * version as u64);
+    for (name, seq) in records {
+        writer.write_all(&[name.len() as u8])?;
+        writer.write_all(name.as_bytes())?;
+
+        match version {
+            0 => writer.write_all(&(offset as u32).to_le_bytes())?,
+            _ => writer.write_all(&offset.to_le_bytes())?,
+        }
+
+        offset += record_size(seq);
+    }
+
+    for (_, seq) in records {
+        writer.write_all(&(seq.len() as u32).to_le_bytes())?;
+        write_blocks(writer, seq.n_runs())?;
+        write_blocks(writer, seq.mask_runs())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+
+        let mut bytes = vec![0u8; seq.len().div_ceil(4)];
+        for i in 0..seq.len() {
+            bytes[i / 4] |= CODE_TO_TWO_BIT[seq.code(i) as usize] << (6 - 2 * (i % 4));
+        }
+        writer.write_all(&bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Reads a single-sequence UCSC `.nib` file (4 bits per base).
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidFormatError`] if the signature is not recognized
+/// or a nibble is not a valid base, or [`BioError::IoError`] if reading fails.
+pub fn read_nib<R: Read>(mut reader: R) -> Result<PackedSeq, BioError> {
+    let swapped = read_signature(&mut reader, NIB_SIGNATURE, ".nib")?;
+    let len = read_u32(&mut reader, swapped)? as usize;
+
+    let bytes = read_bytes(&mut reader, len.div_ceil(2))?;
+
+    let seq = (0..len)
+        .map(|i| {
+            let nibble = match i % 2 {
+                0 => bytes[i / 2] >> 4,
+                _ => bytes[i / 2] & 0xF,
+            };
+
+            let nt = *NIB_TO_NT
+                .get((nibble & !NIB_MASK_BIT) as usize)
+                .ok_or_else(|| {
+                    BioError::InvalidFormatError(format!(
+                        "invalid .nib base {:#x} at position {}.",
+                        nibble, i
+                    ))
+                })?;
+
+            Ok(match nibble & NIB_MASK_BIT {
+                0 => nt,
+                _ => nt.to_ascii_lowercase(),
+            })
+        })
+        .collect::<Result<Vec<u8>, BioError>>()?;
+
+    Ok(PackedSeq::from_ascii(&seq))
+}
+
+/// Writes a packed sequence as a little-endian UCSC `.nib` file.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if the sequence is longer than
+/// `u32::MAX`, or [`BioError::IoError`] if writing fails.
+pub fn write_nib<W: Write>(writer: &mut W, seq: &PackedSeq) -> Result<(), BioError> {
+    writer.write_all(&NIB_SIGNATURE.to_le_bytes())?;
+    writer.write_all(&to_u32(seq.len(), "sequence length")?.to_le_bytes())?;
+
+    let mut bytes = vec![0u8; seq.len().div_ceil(2)];
+    for (i, nt) in seq.to_ascii().into_iter().enumerate() {
+        let mut nibble = NIB_TO_NT
+            .iter()
+            .position(|&n| n == nt.to_ascii_uppercase())
+            .unwrap_or(4) as u8;
+
+        if nt.is_ascii_lowercase() {
+            nibble |= NIB_MASK_BIT;
+        }
+
+        bytes[i / 2] |= match i % 2 {
+            0 => nibble << 4,
+            _ => nibble,
+        };
+    }
+
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::io::Cursor;
+    use tempfile::TempDir;
+
+    fn two_bit_bytes(records: &[(&str, &[u8])]) -> Vec<u8> {
+        let packed: Vec<(&str, PackedSeq)> = records
+            .iter()
+            .map(|(name, seq)| (*name, PackedSeq::from_ascii(seq)))
+            .collect();
+        let refs: Vec<(&str, &PackedSeq)> = packed.iter().map(|(n, s)| (*n, s)).collect();
+
+        let mut buf = Vec::new();
+        write_two_bit(&mut buf, &refs).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_write_two_bit_layout() {
+        let buf = two_bit_bytes(&[("a", b"ACGT")]);
+
+        #[rustfmt::skip]
+        let expected: Vec<u8> = vec![
+            0x43, 0x27, 0x41, 0x1A, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+            1, b'a', 22, 0, 0, 0,
+            4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            // A=10 C=01 G=11 T=00
+            0b1001_1100,
+        ];
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_two_bit_round_trip() {
+        let records: &[(&str, &[u8])] = &[
+            ("chr1", b"NNNNacgtACGTTTGCAtgcaNNGATTACA"),
+            ("chr2", b""),
+            ("chrM", b"ACGTACGTACGTACGTACGTACGTACGTACGTACGTAcgtN"),
+        ];
+
+        let mut reader = TwoBitReader::new(Cursor::new(two_bit_bytes(records))).unwrap();
+        assert_eq!(reader.names(), vec!["chr1", "chr2", "chrM"]);
+
+        for (name, seq) in records {
+            assert_eq!(reader.sequence_len(name).unwrap(), seq.len());
+            assert_eq!(reader.read_sequence(name).unwrap(), *seq);
+        }
+        assert!(reader.read_sequence("chrX").is_err());
+    }
+
+    #[rstest]
+    #[case(0, 30, b"NNNNacgtACGTTTGCAtgcaNNGATTACA")]
+    #[case(3, 9, b"NacgtA")]
+    #[case(21, 24, b"NNG")]
+    #[case(5, 5, b"")]
+    fn test_read_region(#[case] start: usize, #[case] end: usize, #[case] expected: &[u8]) {
+        let buf = two_bit_bytes(&[("chr1", b"NNNNacgtACGTTTGCAtgcaNNGATTACA")]);
+        let mut reader = TwoBitReader::new(Cursor::new(buf)).unwrap();
+
+        assert_eq!(reader.read_region("chr1", start, end).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_read_region_invalid() {
+        let buf = two_bit_bytes(&[("chr1", b"ACGT")]);
+        let mut reader = TwoBitReader::new(Cursor::new(buf)).unwrap();
+
+        assert!(reader.read_region("chr1", 2, 5).is_err());
+        assert!(reader.read_region("chr1", 3, 2).is_err());
+    }
+
+    #[test]
+    fn test_two_bit_big_endian() {
+        // Same layout as `test_write_two_bit_layout`, byte-swapped.
+        #[rustfmt::skip]
+        let buf: Vec<u8> = vec![
+            0x1A, 0x41, 0x27, 0x43, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0,
+            1, b'a', 0, 0, 0, 22,
+            0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0b1001_1100,
+        ];
+
+        let mut reader = TwoBitReader::new(Cursor::new(buf)).unwrap();
+        assert_eq!(reader.read_sequence("a").unwrap(), b"ACGT");
+    }
+
+    #[test]
+    fn test_two_bit_invalid_signature() {
+        let result = TwoBitReader::new(Cursor::new(vec![0u8; 16]));
+        assert!(matches!(result, Err(BioError::InvalidFormatError(_))));
+    }
+
+    #[rstest]
+    // Sequence count.
+    #[case(8, u32::MAX)]
+    // Sequence length, N block count and mask block count of `a`.
+    #[case(22, u32::MAX)]
+    #[case(26, u32::MAX)]
+    #[case(30, u32::MAX)]
+    fn test_two_bit_corrupt_counts(#[case] offset: usize, #[case] value: u32) {
+        let mut buf = two_bit_bytes(&[("a", b"ACGT")]);
+        buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+
+        let result = TwoBitReader::new(Cursor::new(buf)).and_then(|mut r| r.read_packed("a"));
+        assert!(matches!(result, Err(BioError::IoError(_))));
+    }
+
+    #[test]
+    fn test_two_bit_reader_path() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("ref.2bit");
+        std::fs::write(&path, two_bit_bytes(&[("chr1", b"ACGTN")])).unwrap();
+
+        let mut reader = two_bit_reader(&path).unwrap();
+        assert_eq!(reader.read_sequence("chr1").unwrap(), b"ACGTN");
+
+        assert!(matches!(
+            two_bit_reader(&tmp.path().join("missing.2bit")),
+            Err(BioError::FileDoesNotExistError(_))
+        ));
+    }
+
+    #[rstest]
+    #[case(b"")]
+    #[case(b"ACGTN")]
+    #[case(b"acgtNNACGTtt")]
+    fn test_nib_round_trip(#[case] seq: &[u8]) {
+        let mut buf = Vec::new();
+        write_nib(&mut buf, &PackedSeq::from_ascii(seq)).unwrap();
+
+        assert_eq!(buf.len(), 8 + seq.len().div_ceil(2));
+        assert_eq!(read_nib(Cursor::new(buf)).unwrap().to_ascii(), seq);
+    }
+
+    #[test]
+    fn test_nib_truncated() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&NIB_SIGNATURE.to_le_bytes());
+        buf.extend_from_slice(&u32::MAX.to_le_bytes());
+        buf.push(0x12);
+
+        assert!(matches!(
+            read_nib(Cursor::new(buf)),
+            Err(BioError::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_nib_invalid_base() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&NIB_SIGNATURE.to_le_bytes());
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.push(0x70);
+
+        assert!(matches!(
+            read_nib(Cursor::new(buf)),
+            Err(BioError::InvalidFormatError(_))
+        ));
+    }
+}
@@ -0,0 +1,398 @@
+use super::reader::decompress_if_gzip;
+use super::writer::write_fasta_record;
+use crate::errors::BioError;
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// Reads one line into `buf` without its `\n` or `\r\n` terminator.
+/// Returns `false` at end of input.
+fn read_line<B: BufRead>(reader: &mut B, buf: &mut String) -> Result<bool, BioError> {
+    buf.clear();
+    if reader.read_line(buf)? == 0 {
+        return Ok(false);
+    }
+
+    if buf.ends_with('\n') {
+        buf.pop();
+        if buf.ends_with('\r') {
+            buf.pop();
+        }
+    }
+
+    Ok(true)
+}
+
+/// Reads lines until a non-empty one is found. Returns `false` at end of input.
+fn read_non_empty_line<B: BufRead>(reader: &mut B, buf: &mut String) -> Result<bool, BioError> {
+    while read_line(reader, buf)? {
+        if !buf.is_empty() {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Splits a header into ID and description at the first whitespace, as rust-bio does.
+fn split_header(header: &str) -> (&str, Option<&str>) {
+    match header.split_once(char::is_whitespace) {
+        Some((id, desc)) => (id, Some(desc)),
+        None => (header, None),
+    }
+}
+
+/// A FASTQ record that keeps its header and `+` line exactly as read.
+///
+/// rust-bio splits the header into ID and description, trimming trailing
+/// whitespace and normalizing the separator, and drops the content of the
+/// `+` line. This record stores both verbatim so that
+/// [`VerbatimFastqRecord::write`] reproduces the input byte for byte, apart
+/// from `\r\n` line endings which are written as `\n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerbatimFastqRecord {
+    /// Header line without the leading `@`.
+    pub header: String,
+    pub seq: Vec<u8>,
+    /// Content of the separator line after the `+`, usually empty.
+    pub plus: String,
+    pub qual: Vec<u8>,
+}
+
+impl VerbatimFastqRecord {
+    /// Header up to the first whitespace.
+    pub fn id(&self) -> &str {
+        split_header(&self.header).0
+    }
+
+    /// Header after the first whitespace, if any.
+    pub fn desc(&self) -> Option<&str> {
+        split_header(&self.header).1
+    }
+
+    /// Writes the record as four FASTQ lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if writing fails.
+    pub fn write<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), BioError> {
+        writer.write_all(b"@")?;
+        writer.write_all(self.header.as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.write_all(&self.seq)?;
+        writer.write_all(b"\n+")?;
+        writer.write_all(self.plus.as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.write_all(&self.qual)?;
+        writer.write_all(b"\n")?;
+
+        Ok(())
+    }
+}
+
+/// A FASTA record that keeps its header exactly as read.
+///
+/// Sequence lines are joined; `line_width` holds the length of the first
+/// sequence line if the sequence spans several lines, so that
+/// [`VerbatimFastaRecord::write`] reproduces consistently wrapped input byte
+/// for byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerbatimFastaRecord {
+    /// Header line without the leading `>`.
+    pub header: String,
+    pub seq: Vec<u8>,
+    pub line_width: Option<usize>,
+}
+
+impl VerbatimFastaRecord {
+    /// Header up to the first whitespace.
+    pub fn id(&self) -> &str {
+        split_header(&self.header).0
+    }
+
+    /// Header after the first whitespace, if any.
+    pub fn desc(&self) -> Option<&str> {
+        split_header(&self.header).1
+    }
+
+    /// Writes the record, wrapping the sequence at `line_width` if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::IoError`] if writing fails.
+    pub fn write<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), BioError> {
+        write_fasta_record(writer, &self.header, None, &self.seq, self.line_width)
+    }
+}
+
+/// Iterator over [`VerbatimFastqRecord`]s, created by [`verbatim_fastq_reader_from`].
+///
+/// Records must have single-line sequence and quality. Empty lines between
+/// records are skipped.
+pub struct VerbatimFastqReader<B: BufRead> {
+    reader: B,
+    line: String,
+}
+
+impl<B: BufRead> VerbatimFastqReader<B> {
+    pub fn new(reader: B) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+        }
+    }
+
+    fn read_record(&mut self) -> Result<Option<VerbatimFastqRecord>, BioError> {
+        if !read_non_empty_line(&mut self.reader, &mut self.line)? {
+            return Ok(None);
+        }
+
+        let Some(header) = self.line.strip_prefix('@') else {
+            return Err(BioError::InvalidFormatError(format!(
+                "expected FASTQ header starting with '@', got {}.",
+                self.line
+            )));
+        };
+        let header = header.to_string();
+
+        let mut next_line = |what: &str| -> Result<String, BioError> {
+            match read_line(&mut self.reader, &mut self.line)? {
+                true => Ok(self.line.clone()),
+                false => Err(BioError::InvalidFormatError(format!(
+                    "FASTQ record {} is missing its {} line.",
+                    header, what
+                ))),
+            }
+        };
+
+        let seq = next_line("sequence")?;
+        let plus = next_line("separator")?;
+        let qual = next_line("quality")?;
+
+        let Some(plus) = plus.strip_prefix('+') else {
+            return Err(BioError::InvalidFormatError(format!(
+                "FASTQ record {} has separator line {} not starting with '+'.",
+                header, plus
+            )));
+        };
+
+        if seq.len() != qual.len() {
+            return Err(BioError::InvalidFormatError(format!(
+                "FASTQ record {} has sequence length {} but quality length {}.",
+                header,
+                seq.len(),
+                qual.len()
+            )));
+        }
+
+        Ok(Some(VerbatimFastqRecord {
+            header,
+            seq: seq.into_bytes(),
+            plus: plus.to_string(),
+            qual: qual.into_bytes(),
+        }))
+    }
+}
+
+impl<B: BufRead> Iterator for VerbatimFastqReader<B> {
+    type Item = Result<VerbatimFastqRecord, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Iterator over [`VerbatimFastaRecord`]s, created by [`verbatim_fasta_reader_from`].
+///
+/// Empty lines are skipped.
+pub struct VerbatimFastaReader<B: BufRead> {
+    reader: B,
+    line: String,
+    next_header: Option<String>,
+}
+
+impl<B: BufRead> VerbatimFastaReader<B> {
+    pub fn new(reader: B) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            next_header: None,
+        }
+    }
+
+    fn read_record(&mut self) -> Result<Option<VerbatimFastaRecord>, BioError> {
+        let header = match self.next_header.take() {
+            Some(header) => header,
+            None => {
+                if !read_non_empty_line(&mut self.reader, &mut self.line)? {
+                    return Ok(None);
+                }
+
+                match self.line.strip_prefix('>') {
+                    Some(header) => header.to_string(),
+                    None => {
+                        return Err(BioError::InvalidFormatError(format!(
+                            "expected FASTA header starting with '>', got {}.",
+                            self.line
+                        )));
+                    }
+                }
+            }
+        };
+
+        let mut seq = Vec::new();
+        let mut line_width = None;
+        let mut num_lines = 0;
+
+        while read_line(&mut self.reader, &mut self.line)? {
+            if let Some(next_header) = self.line.strip_prefix('>') {
+                self.next_header = Some(next_header.to_string());
+                break;
+            }
+
+            if self.line.is_empty() {
+                continue;
+            }
+
+            num_lines += 1;
+            if num_lines == 1 {
+                line_width = Some(self.line.len());
+            }
+            seq.extend_from_slice(self.line.as_bytes());
+        }
+
+        Ok(Some(VerbatimFastaRecord {
+            header,
+            seq,
+            line_width: line_width.filter(|_| num_lines > 1),
+        }))
+    }
+}
+
+impl<B: BufRead> Iterator for VerbatimFastaReader<B> {
+    type Item = Result<VerbatimFastaRecord, BioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Creates a [`VerbatimFastqReader`] from any [`Read`] source, decompressing gzip input transparently.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if the first bytes cannot be read.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::io::verbatim_fastq_reader_from;
+///
+/// let fastq = b"@read1  sample=a\tlane=1 \nACGT\n+read1\nIIII\n";
+///
+/// let mut out = Vec::new();
+/// for record in verbatim_fastq_reader_from(&fastq[..]).unwrap() {
+///     let record = record.unwrap();
+///     assert_eq!(record.id(), "read1");
+///     record.write(&mut out).unwrap();
+/// }
+/// assert_eq!(out, fastq);
+/// ```
+pub fn verbatim_fastq_reader_from<'a, R: Read + Send + 'a>(
+    reader: R,
+) -> Result<VerbatimFastqReader<BufReader<Box<dyn Read + Send + 'a>>>, BioError> {
+    Ok(VerbatimFastqReader::new(BufReader::new(
+        decompress_if_gzip(reader)?,
+    )))
+}
+
+/// Creates a [`VerbatimFastaReader`] from any [`Read`] source, decompressing gzip input transparently.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if the first bytes cannot be read.
+pub fn verbatim_fasta_reader_from<'a, R: Read + Send + 'a>(
+    reader: R,
+) -> Result<VerbatimFastaReader<BufReader<Box<dyn Read + Send + 'a>>>, BioError> {
+    Ok(VerbatimFastaReader::new(BufReader::new(
+        decompress_if_gzip(reader)?,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn round_trip_fastq(input: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for record in verbatim_fastq_reader_from(input).unwrap() {
+            record.unwrap().write(&mut out).unwrap();
+        }
+        out
+    }
+
+    fn round_trip_fasta(input: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for record in verbatim_fasta_reader_from(input).unwrap() {
+            record.unwrap().write(&mut out).unwrap();
+        }
+        out
+    }
+
+    #[rstest]
+    #[case(b"@r1\nACGT\n+\nIIII\n")]
+    #[case(b"@r1 desc with  two spaces\nACGT\n+r1 desc with  two spaces\nIIII\n")]
+    #[case(b"@r1\tBC:Z:ACGT \nAC\n+\nII\n@r2\n\n+\n\n")]
+    fn test_fastq_round_trip(#[case] input: &[u8]) {
+        assert_eq!(round_trip_fastq(input), input);
+    }
+
+    #[test]
+    fn test_fastq_round_trip_crlf() {
+        assert_eq!(
+            round_trip_fastq(b"@r1 d\r\nAC\r\n+\r\nII\r\n"),
+            b"@r1 d\nAC\n+\nII\n"
+        );
+    }
+
+    #[test]
+    fn test_fastq_id_and_desc() {
+        let record = verbatim_fastq_reader_from(&b"@r1\tlane=1  x\nA\n+\nI\n"[..])
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(record.id(), "r1");
+        assert_eq!(record.desc(), Some("lane=1  x"));
+    }
+
+    #[rstest]
+    #[case(b"r1\nACGT\n+\nIIII\n")]
+    #[case(b"@r1\nACGT\n-\nIIII\n")]
+    #[case(b"@r1\nACGT\n+\nIII\n")]
+    #[case(b"@r1\nACGT\n+\n")]
+    fn test_fastq_invalid(#[case] input: &[u8]) {
+        let mut reader = verbatim_fastq_reader_from(input).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(BioError::InvalidFormatError(_)))
+        ));
+    }
+
+    #[rstest]
+    #[case(b">s1\nACGT\n")]
+    #[case(b">s1 a  b \nACGT\nACGT\nAC\n>s2\nAAA\n")]
+    #[case(b">s1\n\n>s2 x\nAC\n")]
+    fn test_fasta_round_trip(#[case] input: &[u8]) {
+        assert_eq!(round_trip_fasta(input), input);
+    }
+
+    #[test]
+    fn test_fasta_invalid() {
+        let mut reader = verbatim_fasta_reader_from(&b"s1\nACGT\n"[..]).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(BioError::InvalidFormatError(_)))
+        ));
+    }
+}
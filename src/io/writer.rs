@@ -23,6 +23,72 @@ pub fn write_json<T: Serialize>(outfile: Option<PathBuf>, s: T) -> Result<(), Bi
     Ok(())
 }
 
+/// Serializes `rows` as delimited text with a header row derived from the field names.
+fn write_delimited<I>(outfile: Option<PathBuf>, rows: I, delimiter: u8) -> Result<(), BioError>
+where
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(get_bufwriter(outfile)?);
+
+    for row in rows {
+        writer.serialize(row)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Serializes `rows` as tab-separated values to a file or stdout.
+///
+/// Each row must serialize to a flat record, e.g. a struct of scalars. The
+/// header is taken from the field names of the first row. Pass `Some(path)`
+/// to write to a file, or `None` for stdout. Files ending in `.gz` are
+/// gzip-compressed automatically.
+///
+/// # Errors
+///
+/// Returns [`BioError`] on I/O or serialization failure.
+pub fn write_tsv<I>(outfile: Option<PathBuf>, rows: I) -> Result<(), BioError>
+where
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    write_delimited(outfile, rows, b'\t')
+}
+
+/// Serializes `rows` as comma-separated values to a file or stdout.
+///
+/// See [`write_tsv`] for the row and output conventions.
+///
+/// # Errors
+///
+/// Returns [`BioError`] on I/O or serialization failure.
+pub fn write_csv<I>(outfile: Option<PathBuf>, rows: I) -> Result<(), BioError>
+where
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    write_delimited(outfile, rows, b',')
+}
+
+/// Wraps any [`Write`] target in a buffered writer, optionally gzip-compressing the output.
+///
+/// This is the code path shared by all file writers, so in-memory targets such
+/// as `&mut Vec<u8>` produce byte-identical output. The gzip trailer and buffer
+/// are flushed when the returned writer is dropped.
+pub fn get_bufwriter_from<'a, W: Write + Send + 'a>(
+    writer: W,
+    gzip: bool,
+) -> Box<dyn Write + Send + 'a> {
+    match gzip {
+        true => Box::new(BufWriter::new(GzEncoder::new(writer, Compression::fast()))),
+        false => Box::new(BufWriter::new(writer)),
+    }
+}
+
 /// Creates a buffered writer for a file or stdout.
 ///
 /// Files ending in `.gz` are wrapped in a gzip encoder with fast compression.
@@ -40,18 +106,9 @@ pub fn get_bufwriter(outfile: Option<PathBuf>) -> Result<Box<dyn Write + Send>,
                 BioError::InvalidFileExtensionError(outfile.display().to_string()),
             )?;
 
-            let writer = match extension.as_str() {
-                "gz" => Box::new(BufWriter::new(GzEncoder::new(f, Compression::fast())))
-                    as Box<dyn Write + Send>,
-                _ => Box::new(BufWriter::new(f)) as Box<dyn Write + Send>,
-            };
-
-            Ok(writer)
-        }
-        None => {
-            let writer = BufWriter::new(std::io::stdout());
-            Ok(Box::new(writer))
+            Ok(get_bufwriter_from(f, extension == "gz"))
         }
+        None => Ok(get_bufwriter_from(std::io::stdout(), false)),
     }
 }
 
@@ -64,15 +121,23 @@ pub fn get_bufwriter(outfile: Option<PathBuf>) -> Result<Box<dyn Write + Send>,
 ///
 /// Returns [`BioError`] if the output file cannot be created.
 pub fn bio_fastq_writer(outfile: Option<PathBuf>) -> Result<Writer<Box<dyn Write>>, BioError> {
-    let writer: Box<dyn Write> = match outfile {
-        Some(path) => {
-            let f = File::create(path)?;
-            Box::new(BufWriter::new(GzEncoder::new(f, Compression::fast())))
-        }
-        None => Box::new(BufWriter::new(std::io::stdout())),
+    let writer = match outfile {
+        Some(path) => bio_fastq_writer_from(File::create(path)?, true),
+        None => bio_fastq_writer_from(std::io::stdout(), false),
     };
 
-    Ok(Writer::new(writer))
+    Ok(writer)
+}
+
+/// Creates a [`bio::io::fastq::Writer`] over any [`Write`] target, e.g. `&mut Vec<u8>`.
+///
+/// Output is gzip-compressed when `gzip` is `true`. Drop the writer before
+/// reading the target so that buffered and compressed data is flushed.
+pub fn bio_fastq_writer_from<'a, W: Write + Send + 'a>(
+    writer: W,
+    gzip: bool,
+) -> Writer<Box<dyn Write + 'a>> {
+    Writer::new(get_bufwriter_from(writer, gzip))
 }
 
 /// Creates a [`bio::io::fasta::Writer`] for writing FASTA records.
@@ -86,15 +151,136 @@ pub fn bio_fastq_writer(outfile: Option<PathBuf>) -> Result<Writer<Box<dyn Write
 pub fn bio_fasta_writer(
     outfile: Option<PathBuf>,
 ) -> Result<bio::io::fasta::Writer<Box<dyn Write>>, BioError> {
-    let writer: Box<dyn Write> = match outfile {
-        Some(path) => {
-            let f = File::create(path)?;
-            Box::new(BufWriter::new(GzEncoder::new(f, Compression::fast())))
-        }
-        None => Box::new(BufWriter::new(std::io::stdout())),
+    let writer = match outfile {
+        Some(path) => bio_fasta_writer_from(File::create(path)?, true),
+        None => bio_fasta_writer_from(std::io::stdout(), false),
     };
 
-    Ok(bio::io::fasta::Writer::new(writer))
+    Ok(writer)
+}
+
+/// Creates a [`bio::io::fasta::Writer`] over any [`Write`] target, e.g. `&mut Vec<u8>`.
+///
+/// Output is gzip-compressed when `gzip` is `true`. Drop the writer before
+/// reading the target so that buffered and compressed data is flushed.
+pub fn bio_fasta_writer_from<'a, W: Write + Send + 'a>(
+    writer: W,
+    gzip: bool,
+) -> bio::io::fasta::Writer<Box<dyn Write + 'a>> {
+    bio::io::fasta::Writer::new(get_bufwriter_from(writer, gzip))
+}
+
+fn write_header<W: Write + ?Sized>(
+    writer: &mut W,
+    marker: u8,
+    id: &str,
+    desc: Option<&str>,
+) -> Result<(), BioError> {
+    writer.write_all(&[marker])?;
+    writer.write_all(id.as_bytes())?;
+    if let Some(desc) = desc {
+        writer.write_all(b" ")?;
+        writer.write_all(desc.as_bytes())?;
+    }
+    writer.write_all(b"\n")?;
+
+    Ok(())
+}
+
+/// Writes a single FASTQ record to any [`Write`] target.
+///
+/// Produces the same four-line layout as [`bio_fastq_writer`] without going
+/// through the rust-bio writer types. Combine with [`get_bufwriter`] or
+/// [`get_bufwriter_from`] for buffered, optionally gzip-compressed output.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `seq` and `qual` differ in
+/// length, or [`BioError::IoError`] if writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::io::write_fastq_record;
+///
+/// let mut buf = Vec::new();
+/// write_fastq_record(&mut buf, "read1", Some("sample=a"), b"ACGT", b"IIII").unwrap();
+/// assert_eq!(buf, b"@read1 sample=a\nACGT\n+\nIIII\n");
+/// ```
+pub fn write_fastq_record<W: Write + ?Sized>(
+    writer: &mut W,
+    id: &str,
+    desc: Option<&str>,
+    seq: &[u8],
+    qual: &[u8],
+) -> Result<(), BioError> {
+    if seq.len() != qual.len() {
+        return Err(BioError::InvalidParameterError(format!(
+            "sequence and quality length differ for {}: {} vs {}.",
+            id,
+            seq.len(),
+            qual.len()
+        )));
+    }
+
+    write_header(writer, b'@', id, desc)?;
+    writer.write_all(seq)?;
+    writer.write_all(b"\n+\n")?;
+    writer.write_all(qual)?;
+    writer.write_all(b"\n")?;
+
+    Ok(())
+}
+
+/// Writes a single FASTA record to any [`Write`] target.
+///
+/// The sequence is written on a single line when `line_width` is `None`, and
+/// wrapped every `line_width` bases otherwise (e.g. `Some(60)` or `Some(80)`).
+/// Empty sequences produce an empty sequence line.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `line_width` is `Some(0)`,
+/// or [`BioError::IoError`] if writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::io::write_fasta_record;
+///
+/// let mut buf = Vec::new();
+/// write_fasta_record(&mut buf, "seq1", None, b"ACGTACGTAC", Some(4)).unwrap();
+/// assert_eq!(buf, b">seq1\nACGT\nACGT\nAC\n");
+/// ```
+pub fn write_fasta_record<W: Write + ?Sized>(
+    writer: &mut W,
+    id: &str,
+    desc: Option<&str>,
+    seq: &[u8],
+    line_width: Option<usize>,
+) -> Result<(), BioError> {
+    if line_width == Some(0) {
+        return Err(BioError::InvalidParameterError(
+            "line width must be greater than 0.".to_string(),
+        ));
+    }
+
+    write_header(writer, b'>', id, desc)?;
+
+    match line_width {
+        Some(width) if seq.len() > width => {
+            for line in seq.chunks(width) {
+                writer.write_all(line)?;
+                writer.write_all(b"\n")?;
+            }
+        }
+        _ => {
+            writer.write_all(seq)?;
+            writer.write_all(b"\n")?;
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -121,6 +307,54 @@ mod tests {
         assert_eq!(parsed["num"], 42);
     }
 
+    #[derive(Serialize)]
+    struct Row {
+        name: &'static str,
+        value: f64,
+    }
+
+    #[test]
+    fn test_write_tsv_and_csv() {
+        let tmp_dir = TempDir::new().unwrap();
+        let rows = [
+            Row {
+                name: "a",
+                value: 0.5,
+            },
+            Row {
+                name: "b,c",
+                value: 1.0,
+            },
+        ];
+
+        let tsv = tmp_dir.path().join("report.tsv");
+        write_tsv(Some(tsv.clone()), &rows).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&tsv).unwrap(),
+            "name\tvalue\na\t0.5\nb,c\t1.0\n"
+        );
+
+        let csv = tmp_dir.path().join("report.csv");
+        write_csv(Some(csv.clone()), &rows).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&csv).unwrap(),
+            "name,value\na,0.5\n\"b,c\",1.0\n"
+        );
+    }
+
+    #[test]
+    fn test_write_tsv_gz() {
+        let tmp_dir = TempDir::new().unwrap();
+        let outfile = tmp_dir.path().join("report.tsv.gz");
+        write_tsv(Some(outfile.clone()), [("a", 1)]).unwrap();
+
+        let mut content = String::new();
+        flate2::read::GzDecoder::new(File::open(&outfile).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "a\t1\n");
+    }
+
     #[test]
     fn test_get_bufwriter_plain_file() {
         let tmp_dir = TempDir::new().unwrap();
@@ -177,4 +411,67 @@ mod tests {
         let metadata = std::fs::metadata(&outfile).unwrap();
         assert!(metadata.len() > 0);
     }
+
+    #[test]
+    fn test_bio_fastq_writer_from_vec() {
+        let mut buf = Vec::new();
+
+        let mut writer = bio_fastq_writer_from(&mut buf, false);
+        writer.write("read1", None, b"ACGT", b"IIII").unwrap();
+        drop(writer);
+
+        assert_eq!(buf, b"@read1\nACGT\n+\nIIII\n");
+    }
+
+    #[test]
+    fn test_bio_fasta_writer_from_vec_gzip() {
+        let mut buf = Vec::new();
+
+        let mut writer = bio_fasta_writer_from(&mut buf, true);
+        writer.write("seq1", None, b"ACGT").unwrap();
+        drop(writer);
+
+        let mut content = String::new();
+        flate2::read::MultiGzDecoder::new(&buf[..])
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, ">seq1\nACGT\n");
+    }
+
+    #[test]
+    fn test_write_fastq_record_matches_bio_writer() {
+        let mut expected = Vec::new();
+        let mut writer = bio_fastq_writer_from(&mut expected, false);
+        writer
+            .write("read1", Some("x=1"), b"ACGT", b"IIII")
+            .unwrap();
+        writer.write("read2", None, b"", b"").unwrap();
+        drop(writer);
+
+        let mut buf = Vec::new();
+        write_fastq_record(&mut buf, "read1", Some("x=1"), b"ACGT", b"IIII").unwrap();
+        write_fastq_record(&mut buf, "read2", None, b"", b"").unwrap();
+        assert_eq!(buf, expected);
+
+        assert!(write_fastq_record(&mut buf, "read3", None, b"ACGT", b"III").is_err());
+    }
+
+    #[test]
+    fn test_write_fasta_record() {
+        let mut expected = Vec::new();
+        let mut writer = bio_fasta_writer_from(&mut expected, false);
+        writer.write("seq1", Some("chr1"), b"ACGTACGT").unwrap();
+        drop(writer);
+
+        let mut buf = Vec::new();
+        write_fasta_record(&mut buf, "seq1", Some("chr1"), b"ACGTACGT", None).unwrap();
+        assert_eq!(buf, expected);
+
+        let mut buf = Vec::new();
+        write_fasta_record(&mut buf, "seq1", None, b"ACGTACGT", Some(4)).unwrap();
+        write_fasta_record(&mut buf, "seq2", None, b"", Some(4)).unwrap();
+        assert_eq!(buf, b">seq1\nACGT\nACGT\n>seq2\n\n");
+
+        assert!(write_fasta_record(&mut buf, "seq3", None, b"ACGT", Some(0)).is_err());
+    }
 }
@@ -0,0 +1,190 @@
+use super::canonical::{CanonicalKmerIter, validate_kmer_size};
+use crate::errors::BioError;
+use crate::intervals::{Interval, merge_intervals};
+use crate::nucleotide::{hardmask_intervals, softmask_intervals};
+use std::collections::HashSet;
+
+/// How [`mask_blacklisted`] masks bases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskMode {
+    /// Lowercase the bases.
+    Soft,
+    /// Replace the bases with the given byte, e.g. `b'N'`.
+    Hard(u8),
+}
+
+/// A set of canonical k-mers to mask, such as those of adapters, vectors or
+/// a contaminant genome.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::KmerBlacklist;
+///
+/// let blacklist = KmerBlacklist::from_seqs([b"AGATCGGAAGAGC".as_slice()], 11).unwrap();
+/// assert_eq!(blacklist.len(), 3);
+/// assert!(blacklist.contains_kmer(b"AGATCGGAAGA"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KmerBlacklist {
+    kmer_size: usize,
+    kmers: HashSet<u64>,
+}
+
+impl KmerBlacklist {
+    /// Creates an empty blacklist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `kmer_size` is `0` or
+    /// greater than [`MAX_KMER_SIZE`](super::MAX_KMER_SIZE).
+    pub fn new(kmer_size: usize) -> Result<Self, BioError> {
+        validate_kmer_size(kmer_size)?;
+
+        Ok(Self {
+            kmer_size,
+            kmers: HashSet::new(),
+        })
+    }
+
+    /// Creates a blacklist of every k-mer of `seqs`.
+    ///
+    /// # Errors
+    ///
+    /// See [`KmerBlacklist::new`].
+    pub fn from_seqs<'a, I>(seqs: I, kmer_size: usize) -> Result<Self, BioError>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let mut blacklist = Self::new(kmer_size)?;
+        for seq in seqs {
+            blacklist.add_seq(seq);
+        }
+
+        Ok(blacklist)
+    }
+
+    /// Adds every k-mer of `seq`, in both orientations.
+    pub fn add_seq(&mut self, seq: &[u8]) {
+        let kmers = CanonicalKmerIter::new(seq, self.kmer_size).expect("kmer size is validated");
+        self.kmers.extend(kmers.map(|(_, _, canonical)| canonical));
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+
+    /// Number of distinct canonical k-mers.
+    pub fn len(&self) -> usize {
+        self.kmers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kmers.is_empty()
+    }
+
+    /// Whether `kmer` or its reverse complement is blacklisted.
+    pub fn contains_kmer(&self, kmer: &[u8]) -> bool {
+        kmer.len() == self.kmer_size
+            && CanonicalKmerIter::new(kmer, self.kmer_size)
+                .expect("kmer size is validated")
+                .next()
+                .is_some_and(|(_, _, canonical)| self.kmers.contains(&canonical))
+    }
+}
+
+/// Returns the merged intervals of `seq` covered by blacklisted k-mers, in
+/// either orientation.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::intervals::Interval;
+/// use bio_utils_rs::kmers::{KmerBlacklist, blacklisted_intervals};
+///
+/// let blacklist = KmerBlacklist::from_seqs([b"GATTACA".as_slice()], 5).unwrap();
+/// let intervals = blacklisted_intervals(b"CCGATTACACCTGTAATCCC", &blacklist);
+/// assert_eq!(intervals, vec![Interval { start: 2, end: 9 }, Interval { start: 11, end: 18 }]);
+/// ```
+pub fn blacklisted_intervals(seq: &[u8], blacklist: &KmerBlacklist) -> Vec<Interval> {
+    let k = blacklist.kmer_size();
+    let kmers = CanonicalKmerIter::new(seq, k).expect("kmer size is validated");
+
+    merge_intervals(
+        kmers
+            .filter(|(_, _, canonical)| blacklist.kmers.contains(canonical))
+            .map(|(pos, _, _)| (pos, pos + k)),
+    )
+}
+
+/// Masks every base of `seq` covered by a blacklisted k-mer, see
+/// [`blacklisted_intervals`]. Returns the masked sequence and the number of
+/// masked bases.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::{KmerBlacklist, MaskMode, mask_blacklisted};
+///
+/// let blacklist = KmerBlacklist::from_seqs([b"GATTACA".as_slice()], 5).unwrap();
+///
+/// let (masked, n) = mask_blacklisted(b"CCGATTACACC", &blacklist, MaskMode::Soft);
+/// assert_eq!((masked.as_slice(), n), (b"CCgattacaCC".as_slice(), 7));
+///
+/// let (masked, _) = mask_blacklisted(b"CCGATTACACC", &blacklist, MaskMode::Hard(b'N'));
+/// assert_eq!(masked, b"CCNNNNNNNCC");
+/// ```
+pub fn mask_blacklisted(seq: &[u8], blacklist: &KmerBlacklist, mode: MaskMode) -> (Vec<u8>, usize) {
+    let intervals: Vec<(usize, usize)> = blacklisted_intervals(seq, blacklist)
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let masked_bases = intervals.iter().map(|(start, end)| end - start).sum();
+
+    let masked = match mode {
+        MaskMode::Soft => softmask_intervals(seq, &intervals),
+        MaskMode::Hard(mask) => hardmask_intervals(seq, &intervals, mask),
+    }
+    .expect("blacklisted intervals lie within the sequence");
+
+    (masked, masked_bases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"ACGTACGTACGT".as_slice(), vec![])]
+    // Overlapping and book-ended hits are merged.
+    #[case(b"TTGATTACAGATTACATT".as_slice(), vec![(2, 16)])]
+    // Ambiguous bases break k-mers.
+    #[case(b"GATTNACA".as_slice(), vec![])]
+    #[case(b"gattaca".as_slice(), vec![(0, 7)])]
+    fn test_blacklisted_intervals(#[case] seq: &[u8], #[case] expected: Vec<(usize, usize)>) {
+        let blacklist = KmerBlacklist::from_seqs([b"GATTACAGATTACA".as_slice()], 5).unwrap();
+        let intervals: Vec<(usize, usize)> = blacklisted_intervals(seq, &blacklist)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        assert_eq!(intervals, expected);
+    }
+
+    #[test]
+    fn test_mask_blacklisted_empty() {
+        let blacklist = KmerBlacklist::new(5).unwrap();
+        assert_eq!(
+            mask_blacklisted(b"ACGTACGT", &blacklist, MaskMode::Soft),
+            (b"ACGTACGT".to_vec(), 0)
+        );
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(33)]
+    fn test_invalid_kmer_size(#[case] kmer_size: usize) {
+        assert!(KmerBlacklist::new(kmer_size).is_err());
+    }
+}
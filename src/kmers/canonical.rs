@@ -0,0 +1,161 @@
+use crate::errors::BioError;
+use crate::nucleotide::NT_LOOKUP;
+
+/// Largest k-mer size that fits a 2-bit encoded `u64`.
+pub const MAX_KMER_SIZE: usize = 32;
+
+/// Checks that `kmer_size` fits a 2-bit encoded `u64`.
+pub(super) fn validate_kmer_size(kmer_size: usize) -> Result<(), BioError> {
+    if kmer_size == 0 || kmer_size > MAX_KMER_SIZE {
+        return Err(BioError::InvalidParameterError(format!(
+            "kmer size {} must be in range 1-{}.",
+            kmer_size, MAX_KMER_SIZE
+        )));
+    }
+
+    Ok(())
+}
+
+/// Rolling 2-bit encoder over the valid k-mers of a DNA sequence.
+///
+/// Yields `(position, forward, canonical)` for every k-mer without ambiguous
+/// bases, where `position` is the 0-based start of the k-mer, `forward` its
+/// 2-bit encoding (`A=0, C=1, G=2, T=3`, first base in the highest bits) and
+/// `canonical` the smaller of the forward and reverse complement encodings.
+///
+/// Ambiguous bases (anything not `A`/`C`/`G`/`T`/`a`/`c`/`g`/`t`/`u`/`U`)
+/// reset the current k-mer window.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::CanonicalKmerIter;
+///
+/// let kmers: Vec<(usize, u64, u64)> = CanonicalKmerIter::new(b"ACGNTT", 2).unwrap().collect();
+///
+/// // AC = 0b0001, CG = 0b0110, TT = 0b1111 (canonical AA).
+/// assert_eq!(kmers, vec![(0, 0b0001, 0b0001), (1, 0b0110, 0b0110), (4, 0b1111, 0b0000)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CanonicalKmerIter<'a> {
+    seq: &'a [u8],
+    kmer_size: usize,
+    pos: usize,
+    valid: usize,
+    forward: u64,
+    reverse: u64,
+    mask: u64,
+    shift: usize,
+}
+
+impl<'a> CanonicalKmerIter<'a> {
+    /// Creates an iterator over the canonical k-mers of `seq`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `kmer_size` is `0` or
+    /// greater than [`MAX_KMER_SIZE`].
+    pub fn new(seq: &'a [u8], kmer_size: usize) -> Result<Self, BioError> {
+        validate_kmer_size(kmer_size)?;
+
+        Ok(Self {
+            seq,
+            kmer_size,
+            pos: 0,
+            valid: 0,
+            forward: 0,
+            reverse: 0,
+            mask: u64::MAX >> (64 - 2 * kmer_size),
+            shift: (kmer_size - 1) * 2,
+        })
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+}
+
+impl Iterator for CanonicalKmerIter<'_> {
+    type Item = (usize, u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.seq.len() {
+            let nt = NT_LOOKUP[self.seq[self.pos] as usize] as u64;
+            self.pos += 1;
+
+            if nt >= 4 {
+                self.valid = 0;
+                self.forward = 0;
+                self.reverse = 0;
+                continue;
+            }
+
+            self.forward = (self.forward << 2 | nt) & self.mask;
+            self.reverse = self.reverse >> 2 | (3 - nt) << self.shift;
+            self.valid += 1;
+
+            if self.valid >= self.kmer_size {
+                return Some((
+                    self.pos - self.kmer_size,
+                    self.forward,
+                    self.forward.min(self.reverse),
+                ));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.seq.len() - self.pos;
+        let pending = self.kmer_size - 1 - (self.valid.min(self.kmer_size - 1));
+        (0, Some(remaining.saturating_sub(pending)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nucleotide::reverse_complement;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"", 3, vec![])]
+    #[case(b"AC", 3, vec![])]
+    #[case(b"ACGT", 3, vec![0, 1])]
+    #[case(b"ACNGTAC", 2, vec![0, 3, 4, 5])]
+    #[case(b"acgu", 4, vec![0])]
+    fn test_positions(#[case] seq: &[u8], #[case] kmer_size: usize, #[case] expected: Vec<usize>) {
+        let positions: Vec<usize> = CanonicalKmerIter::new(seq, kmer_size)
+            .unwrap()
+            .map(|(pos, _, _)| pos)
+            .collect();
+        assert_eq!(positions, expected);
+    }
+
+    #[rstest]
+    #[case(b"GATTACAGATTACAGATTACAGATTACAGATTACA", 5)]
+    #[case(b"GATTACAGATTACAGATTACAGATTACAGATTACA", 32)]
+    fn test_canonical_strand_independent(#[case] seq: &[u8], #[case] kmer_size: usize) {
+        let mut fwd: Vec<u64> = CanonicalKmerIter::new(seq, kmer_size)
+            .unwrap()
+            .map(|(_, _, c)| c)
+            .collect();
+        let rc = reverse_complement(seq);
+        let mut rev: Vec<u64> = CanonicalKmerIter::new(&rc, kmer_size)
+            .unwrap()
+            .map(|(_, _, c)| c)
+            .collect();
+
+        fwd.sort();
+        rev.sort();
+        assert_eq!(fwd, rev);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(33)]
+    fn test_invalid_kmer_size(#[case] kmer_size: usize) {
+        assert!(CanonicalKmerIter::new(b"ACGT", kmer_size).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use super::canonical::CanonicalKmerIter;
+use super::count::TrustedKmers;
+use bio::io::fastq;
+
+/// A single base substitution made by [`correct_read`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Correction {
+    /// 0-based position in the read.
+    pub pos: usize,
+    pub from: u8,
+    pub to: u8,
+}
+
+/// A read corrected by [`correct_read`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrectedRead {
+    pub seq: Vec<u8>,
+    pub corrections: Vec<Correction>,
+}
+
+/// Reads corrected by [`correct_reads`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrectedReads {
+    /// Corrected records, in input order.
+    pub reads: Vec<fastq::Record>,
+    /// Corrections as `(read index, correction)`, in input order.
+    pub log: Vec<(usize, Correction)>,
+}
+
+/// Number of k-mers covering `pos` and how many of them are trusted.
+fn covering_support(seq: &[u8], pos: usize, trusted: &TrustedKmers) -> (usize, usize) {
+    let k = trusted.kmer_size();
+    let start = (pos + 1).saturating_sub(k);
+    let end = (pos + k).min(seq.len());
+    let covering = (end - start).saturating_sub(k - 1);
+
+    let supported = CanonicalKmerIter::new(&seq[start..end], k)
+        .expect("kmer size is validated")
+        .filter(|&(_, _, canonical)| trusted.contains(canonical))
+        .count();
+
+    (covering, supported)
+}
+
+/// Corrects single-base errors in `seq` using a trusted k-mer spectrum.
+///
+/// Positions where every covering k-mer is untrusted are error candidates.
+/// A candidate is substituted if exactly one alternative base makes all
+/// covering k-mers trusted; candidates with no or several such bases are
+/// left unchanged. Candidates are visited left to right, so a correction is
+/// seen by the positions after it. Ambiguous bases, such as `N`, are
+/// candidates like any other base.
+///
+/// Reads needing more than `max_corrections` corrections most likely come
+/// from a region missing from `trusted` and are returned unchanged, without
+/// corrections. Corrected bases are uppercase.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::{KmerCounts, correct_read};
+///
+/// let genome = b"GATTACAGGCTTCGAACCTGA";
+/// let trusted = KmerCounts::from_seqs([genome.as_slice()], 7).unwrap().trusted(1);
+///
+/// let corrected = correct_read(b"GATTACAGGCTACGAACCTGA", &trusted, 4);
+/// assert_eq!(corrected.seq, genome);
+/// assert_eq!(corrected.corrections.len(), 1);
+/// assert_eq!(corrected.corrections[0].pos, 11);
+/// ```
+pub fn correct_read(seq: &[u8], trusted: &TrustedKmers, max_corrections: usize) -> CorrectedRead {
+    let mut corrected = seq.to_vec();
+    let mut corrections = Vec::new();
+
+    for pos in 0..corrected.len() {
+        let (covering, supported) = covering_support(&corrected, pos, trusted);
+        if covering == 0 || supported > 0 {
+            continue;
+        }
+
+        let from = corrected[pos];
+        let fixes: Vec<u8> = b"ACGT"
+            .iter()
+            .copied()
+            .filter(|&alternative| alternative != from.to_ascii_uppercase())
+            .filter(|&alternative| {
+                corrected[pos] = alternative;
+                covering_support(&corrected, pos, trusted) == (covering, covering)
+            })
+            .collect();
+
+        match fixes.as_slice() {
+            [to] => {
+                corrected[pos] = *to;
+                corrections.push(Correction { pos, from, to: *to });
+            }
+            _ => corrected[pos] = from,
+        }
+    }
+
+    match corrections.len() > max_corrections {
+        true => CorrectedRead {
+            seq: seq.to_vec(),
+            corrections: Vec::new(),
+        },
+        false => CorrectedRead {
+            seq: corrected,
+            corrections,
+        },
+    }
+}
+
+/// Corrects every record with [`correct_read`] and logs the corrections.
+///
+/// Qualities, ids and descriptions are kept as is.
+///
+/// # Examples
+///
+/// ```
+/// use bio::io::fastq;
+/// use bio_utils_rs::kmers::{KmerCounts, correct_reads};
+///
+/// let reads = vec![
+///     fastq::Record::with_attrs("r1", None, b"GATTACAGGCTTCGAACCTGA", &[b'I'; 21]),
+///     fastq::Record::with_attrs("r2", None, b"GATTACAGGCTTCGAACCTGA", &[b'I'; 21]),
+///     fastq::Record::with_attrs("r3", None, b"GATTACAGGCTACGAACCTGA", &[b'I'; 21]),
+/// ];
+/// let counts = KmerCounts::from_seqs(reads.iter().map(|r| r.seq()), 7).unwrap();
+///
+/// let corrected = correct_reads(&reads, &counts.trusted(2), 4);
+/// assert_eq!(corrected.reads[2].seq(), reads[0].seq());
+/// assert_eq!(corrected.log.len(), 1);
+/// assert_eq!(corrected.log[0].0, 2);
+/// ```
+pub fn correct_reads(
+    records: &[fastq::Record],
+    trusted: &TrustedKmers,
+    max_corrections: usize,
+) -> CorrectedReads {
+    let mut reads = Vec::with_capacity(records.len());
+    let mut log = Vec::new();
+
+    for (i, record) in records.iter().enumerate() {
+        let corrected = correct_read(record.seq(), trusted, max_corrections);
+        log.extend(corrected.corrections.into_iter().map(|c| (i, c)));
+        reads.push(fastq::Record::with_attrs(
+            record.id(),
+            record.desc(),
+            &corrected.seq,
+            record.qual(),
+        ));
+    }
+
+    CorrectedReads { reads, log }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmers::KmerCounts;
+    use crate::rng::SplitMix64;
+    use crate::test_utils::random_sequence;
+    use rstest::*;
+
+    fn trusted(genome: &[u8]) -> TrustedKmers {
+        KmerCounts::from_seqs([genome], 15).unwrap().trusted(1)
+    }
+
+    #[rstest]
+    // Interior, read start, read end.
+    #[case(&[75])]
+    #[case(&[0])]
+    #[case(&[149])]
+    #[case(&[20, 60, 100])]
+    fn test_correct_substitutions(#[case] errors: &[usize]) {
+        let genome = random_sequence(&mut SplitMix64::new(1), 5000, 0.5);
+        let trusted = trusted(&genome);
+        let truth = &genome[1000..1150];
+
+        let mut read = truth.to_vec();
+        for &pos in errors {
+            read[pos] = match read[pos] {
+                b'A' => b'C',
+                _ => b'A',
+            };
+        }
+
+        let corrected = correct_read(&read, &trusted, 10);
+        assert_eq!(corrected.seq, truth);
+        let positions: Vec<usize> = corrected.corrections.iter().map(|c| c.pos).collect();
+        assert_eq!(positions, errors);
+    }
+
+    #[test]
+    fn test_correct_n() {
+        let genome = random_sequence(&mut SplitMix64::new(2), 2000, 0.5);
+        let mut read = genome[500..600].to_vec();
+        read[50] = b'N';
+
+        let corrected = correct_read(&read, &trusted(&genome), 10);
+        assert_eq!(corrected.seq, &genome[500..600]);
+        assert_eq!(corrected.corrections[0].from, b'N');
+    }
+
+    #[test]
+    fn test_correct_unrelated_read_unchanged() {
+        let genome = random_sequence(&mut SplitMix64::new(3), 2000, 0.5);
+        let read = random_sequence(&mut SplitMix64::new(4), 150, 0.5);
+
+        let corrected = correct_read(&read, &trusted(&genome), 5);
+        assert_eq!(corrected.seq, read);
+        assert!(corrected.corrections.is_empty());
+    }
+
+    #[test]
+    fn test_correct_adjacent_errors_unchanged() {
+        let genome = random_sequence(&mut SplitMix64::new(5), 2000, 0.5);
+        let mut read = genome[500..650].to_vec();
+        read[70] = if read[70] == b'A' { b'C' } else { b'A' };
+        read[72] = if read[72] == b'A' { b'C' } else { b'A' };
+
+        let corrected = correct_read(&read, &trusted(&genome), 10);
+        assert!(corrected.corrections.is_empty());
+    }
+}
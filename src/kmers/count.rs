@@ -0,0 +1,258 @@
+use super::canonical::{CanonicalKmerIter, validate_kmer_size};
+use crate::errors::BioError;
+use crate::stats::Histogram;
+use std::collections::{HashMap, HashSet};
+
+/// Canonical k-mer counts over one or more sequences.
+///
+/// K-mers are stored by their canonical 2-bit encoding, as yielded by
+/// [`CanonicalKmerIter`], so a k-mer and its reverse complement share a
+/// count. K-mers containing ambiguous bases are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::KmerCounts;
+///
+/// let counts = KmerCounts::from_seqs([b"ACGTAC".as_slice(), b"GTACG"], 3).unwrap();
+///
+/// // ACG is counted together with its reverse complement CGT.
+/// assert_eq!(counts.count(b"ACG"), 3);
+/// assert_eq!(counts.count(b"CGT"), 3);
+/// assert_eq!(counts.count(b"TAC"), 4);
+/// assert_eq!(counts.count(b"AAA"), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KmerCounts {
+    kmer_size: usize,
+    counts: HashMap<u64, u32>,
+}
+
+impl KmerCounts {
+    /// Creates an empty counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `kmer_size` is `0` or
+    /// greater than [`MAX_KMER_SIZE`](super::MAX_KMER_SIZE).
+    pub fn new(kmer_size: usize) -> Result<Self, BioError> {
+        validate_kmer_size(kmer_size)?;
+
+        Ok(Self {
+            kmer_size,
+            counts: HashMap::new(),
+        })
+    }
+
+    /// Counts the k-mers of all `seqs`.
+    ///
+    /// # Errors
+    ///
+    /// See [`KmerCounts::new`].
+    pub fn from_seqs<'a, I>(seqs: I, kmer_size: usize) -> Result<Self, BioError>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let mut counts = Self::new(kmer_size)?;
+        for seq in seqs {
+            counts.add(seq);
+        }
+
+        Ok(counts)
+    }
+
+    /// Adds the k-mers of `seq`. Counts saturate at `u32::MAX`.
+    pub fn add(&mut self, seq: &[u8]) {
+        let kmers = CanonicalKmerIter::new(seq, self.kmer_size).expect("kmer size is validated");
+
+        for (_, _, canonical) in kmers {
+            let count = self.counts.entry(canonical).or_insert(0);
+            *count = count.saturating_add(1);
+        }
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+
+    /// Number of distinct canonical k-mers.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Count of a canonical k-mer encoding.
+    pub fn get(&self, canonical: u64) -> u32 {
+        self.counts.get(&canonical).copied().unwrap_or(0)
+    }
+
+    /// Count of `kmer` or its reverse complement.
+    ///
+    /// Returns `0` if `kmer` has the wrong length or contains ambiguous bases.
+    pub fn count(&self, kmer: &[u8]) -> u32 {
+        if kmer.len() != self.kmer_size {
+            return 0;
+        }
+
+        CanonicalKmerIter::new(kmer, self.kmer_size)
+            .expect("kmer size is validated")
+            .next()
+            .map_or(0, |(_, _, canonical)| self.get(canonical))
+    }
+
+    /// Iterates over `(canonical, count)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, u32)> + '_ {
+        self.counts.iter().map(|(&kmer, &count)| (kmer, count))
+    }
+
+    /// The k-mer spectrum: number of distinct k-mers per count.
+    pub fn histogram(&self) -> Histogram {
+        let mut histogram = Histogram::default();
+        histogram.extend(self.counts.values().map(|&count| count as u64));
+
+        histogram
+    }
+
+    /// Suggests a minimum count for [`KmerCounts::trusted`] from the first
+    /// valley of the k-mer spectrum.
+    ///
+    /// Erroneous k-mers dominate the lowest counts, so the spectrum falls
+    /// from count `1` until it rises towards the coverage peak. Returns the
+    /// count after which it first rises, or `None` if it never does, e.g.
+    /// at low coverage.
+    pub fn suggested_min_count(&self) -> Option<u32> {
+        let histogram = self.histogram();
+        let frequency = |count: u32| histogram.count_at(count as u64);
+        let (max_count, _) = histogram.bins().next_back()?;
+
+        (1..max_count as u32).find(|&count| frequency(count) < frequency(count + 1))
+    }
+
+    /// Returns the k-mers seen at least `min_count` times.
+    pub fn trusted(&self, min_count: u32) -> TrustedKmers {
+        TrustedKmers {
+            kmer_size: self.kmer_size,
+            kmers: self
+                .iter()
+                .filter(|&(_, count)| count >= min_count)
+                .map(|(kmer, _)| kmer)
+                .collect(),
+        }
+    }
+}
+
+/// A set of canonical k-mers considered error free, typically built with
+/// [`KmerCounts::trusted`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedKmers {
+    kmer_size: usize,
+    kmers: HashSet<u64>,
+}
+
+impl TrustedKmers {
+    /// Creates a set from canonical k-mer encodings, e.g. from a reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `kmer_size` is `0` or
+    /// greater than [`MAX_KMER_SIZE`](super::MAX_KMER_SIZE).
+    pub fn new<I>(kmer_size: usize, kmers: I) -> Result<Self, BioError>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        validate_kmer_size(kmer_size)?;
+
+        Ok(Self {
+            kmer_size,
+            kmers: kmers.into_iter().collect(),
+        })
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.kmers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kmers.is_empty()
+    }
+
+    /// Whether a canonical k-mer encoding is trusted.
+    pub fn contains(&self, canonical: u64) -> bool {
+        self.kmers.contains(&canonical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nucleotide::reverse_complement;
+    use rstest::*;
+
+    #[test]
+    fn test_counts_strand_independent() {
+        let seq = b"GATTACAGATTACA";
+        let forward = KmerCounts::from_seqs([seq.as_slice()], 4).unwrap();
+        let rc = reverse_complement(seq);
+        let reverse = KmerCounts::from_seqs([rc.as_slice()], 4).unwrap();
+
+        assert_eq!(forward, reverse);
+        assert_eq!(forward.count(b"GATT"), 2);
+        assert_eq!(forward.count(b"AATC"), 2);
+    }
+
+    #[rstest]
+    #[case(b"ACG".as_slice(), 1)]
+    #[case(b"AC".as_slice(), 0)]
+    #[case(b"ANG".as_slice(), 0)]
+    fn test_count(#[case] kmer: &[u8], #[case] expected: u32) {
+        let counts = KmerCounts::from_seqs([b"ACGNAC".as_slice()], 3).unwrap();
+        assert_eq!(counts.count(kmer), expected);
+    }
+
+    #[test]
+    fn test_histogram_and_trusted() {
+        let counts = KmerCounts::from_seqs([b"AAAAA".as_slice(), b"AAAAA", b"CCAGT"], 3).unwrap();
+
+        // AAA 6x, CCA, CAG and AGT once each.
+        assert_eq!(
+            counts.histogram().bins().collect::<Vec<_>>(),
+            vec![(1, 3), (6, 1)]
+        );
+
+        let trusted = counts.trusted(2);
+        assert_eq!(trusted.len(), 1);
+        assert_eq!(trusted.kmer_size(), 3);
+    }
+
+    #[rstest]
+    #[case(&[(1, 100), (2, 10), (3, 5), (4, 20), (5, 40)], Some(3))]
+    #[case(&[(1, 100), (2, 10), (3, 5)], None)]
+    #[case(&[], None)]
+    fn test_suggested_min_count(#[case] spectrum: &[(u32, usize)], #[case] expected: Option<u32>) {
+        let mut counts = KmerCounts::new(5).unwrap();
+        let mut kmer = 0;
+        for &(count, n) in spectrum {
+            for _ in 0..n {
+                counts.counts.insert(kmer, count);
+                kmer += 1;
+            }
+        }
+
+        assert_eq!(counts.suggested_min_count(), expected);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(33)]
+    fn test_invalid_kmer_size(#[case] kmer_size: usize) {
+        assert!(KmerCounts::new(kmer_size).is_err());
+        assert!(TrustedKmers::new(kmer_size, []).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use super::sketch::Sketcher;
+use crate::errors::BioError;
+use std::collections::{BTreeMap, HashSet};
+
+/// Selection statistics of a [`Sketcher`] on one sequence, computed by
+/// [`sketch_diagnostics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SketchDiagnostics {
+    pub seq_len: usize,
+    /// Number of selected positions, counting repeated values.
+    pub selected: usize,
+    /// Number of distinct selected values, i.e. the sketch size.
+    pub distinct: usize,
+    /// Selected positions per base. Compare with the expected density of the
+    /// scheme, e.g. `2 / (w + 1)` for random minimizers or `1 / scaled` for
+    /// FracMinHash.
+    pub density: f64,
+    /// Fraction of selected positions whose value was already selected, from
+    /// repeats or hash collisions. `0.0` if nothing is selected.
+    pub duplicate_rate: f64,
+    /// Histogram of distances between consecutive selected positions.
+    pub gaps: BTreeMap<usize, usize>,
+    /// Longest distance between consecutive selected positions, `0` if fewer
+    /// than two positions are selected.
+    pub max_gap: usize,
+}
+
+impl SketchDiagnostics {
+    /// Mean distance between consecutive selected positions, `NaN` if fewer
+    /// than two positions are selected.
+    pub fn mean_gap(&self) -> f64 {
+        let (count, total) = self.gaps.iter().fold((0, 0), |(count, total), (gap, n)| {
+            (count + n, total + gap * n)
+        });
+
+        total as f64 / count as f64
+    }
+}
+
+/// Reports the achieved density, the gap length distribution between
+/// selected positions and the duplicate value rate of `sketcher` on `seq`,
+/// for tuning `k`, `w` or `s` empirically.
+///
+/// Long gaps leave regions unsampled, which matters for mapping and
+/// containment of short queries, while a high duplicate rate points at
+/// repetitive sequence or too small a `k`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `sketcher` does not report
+/// selected positions, see [`Sketcher::positions`].
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::{FracMinHashSketch, sketch_diagnostics};
+///
+/// // Every 3-mer is selected with scaled = 1.
+/// let sketcher = FracMinHashSketch::new(3, 1).unwrap();
+/// let diagnostics = sketch_diagnostics(&sketcher, b"ACGTACGT").unwrap();
+///
+/// assert_eq!((diagnostics.selected, diagnostics.distinct), (6, 2));
+/// assert_eq!(diagnostics.density, 0.75);
+/// assert_eq!(diagnostics.max_gap, 1);
+/// ```
+pub fn sketch_diagnostics<S: Sketcher + ?Sized>(
+    sketcher: &S,
+    seq: &[u8],
+) -> Result<SketchDiagnostics, BioError> {
+    let mut positions = sketcher.positions(seq).ok_or_else(|| {
+        BioError::InvalidParameterError("sketcher does not report selected positions.".to_string())
+    })?;
+
+    // Canonical minimizers may report a position more than once, out of order.
+    positions.sort_unstable();
+    positions.dedup();
+
+    let distinct = positions
+        .iter()
+        .map(|&(_, value)| value)
+        .collect::<HashSet<u64>>()
+        .len();
+
+    let mut gaps = BTreeMap::new();
+    for pair in positions.windows(2) {
+        *gaps.entry(pair[1].0 - pair[0].0).or_insert(0) += 1;
+    }
+
+    let selected = positions.len();
+
+    Ok(SketchDiagnostics {
+        seq_len: seq.len(),
+        selected,
+        distinct,
+        density: match seq.is_empty() {
+            true => 0.0,
+            false => selected as f64 / seq.len() as f64,
+        },
+        duplicate_rate: match selected {
+            0 => 0.0,
+            _ => 1.0 - distinct as f64 / selected as f64,
+        },
+        max_gap: gaps.keys().next_back().copied().unwrap_or(0),
+        gaps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmers::{FracMinHashSketch, Sketch};
+    use crate::rng::SplitMix64;
+    use rstest::*;
+
+    #[test]
+    fn test_sketch_diagnostics_density() {
+        let mut rng = SplitMix64::new(11);
+        let seq: Vec<u8> = (0..200_000).map(|_| b"ACGT"[rng.next_below(4)]).collect();
+
+        let sketcher = FracMinHashSketch::new(21, 10).unwrap();
+        let diagnostics = sketch_diagnostics(&sketcher, &seq).unwrap();
+
+        assert!((diagnostics.density - 0.1).abs() < 0.01);
+        assert!((diagnostics.mean_gap() - 10.0).abs() < 1.0);
+        assert!(diagnostics.max_gap > 50);
+        assert!(diagnostics.duplicate_rate < 1e-3);
+        assert_eq!(diagnostics.distinct, sketcher.sketch(&seq).len());
+    }
+
+    #[rstest]
+    #[case(b"AAAAAAAA".as_slice(), 6, 1, 5.0 / 6.0)]
+    #[case(b"AAAA".as_slice(), 2, 1, 0.5)]
+    #[case(b"AC".as_slice(), 0, 0, 0.0)]
+    fn test_sketch_diagnostics_duplicates(
+        #[case] seq: &[u8],
+        #[case] selected: usize,
+        #[case] distinct: usize,
+        #[case] duplicate_rate: f64,
+    ) {
+        let sketcher = FracMinHashSketch::new(3, 1).unwrap();
+        let diagnostics = sketch_diagnostics(&sketcher, seq).unwrap();
+
+        assert_eq!(
+            (diagnostics.selected, diagnostics.distinct),
+            (selected, distinct)
+        );
+        assert!((diagnostics.duplicate_rate - duplicate_rate).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sketch_diagnostics_gaps() {
+        // Ambiguous bases break k-mers and leave a gap.
+        let sketcher = FracMinHashSketch::new(3, 1).unwrap();
+        let diagnostics = sketch_diagnostics(&sketcher, b"ACGTNNACGT").unwrap();
+
+        assert_eq!(diagnostics.gaps, BTreeMap::from([(1, 2), (5, 1)]));
+        assert_eq!(diagnostics.max_gap, 5);
+        assert!((diagnostics.mean_gap() - 7.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sketch_diagnostics_unsupported() {
+        struct NoPositions;
+
+        impl Sketcher for NoPositions {
+            fn sketch(&self, _seq: &[u8]) -> Sketch {
+                Sketch::new()
+            }
+        }
+
+        assert!(sketch_diagnostics(&NoPositions, b"ACGT").is_err());
+    }
+}
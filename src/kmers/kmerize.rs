@@ -1,27 +1,36 @@
+use super::canonical::CanonicalKmerIter;
 use super::hash::mm_hash64;
+use super::sketch::Sketch;
 use crate::errors::BioError;
-use crate::nucleotide::NT_LOOKUP;
-use std::collections::HashSet;
+use crate::nucleotide::{AmbigPolicy, apply_ambig_policy};
+
+pub(super) fn validate_scaled(scaled: u64) -> Result<(), BioError> {
+    if scaled == 0 {
+        return Err(BioError::InvalidParameterError(
+            "scaled must be greater than 0.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
 
 /// Computes a FracMinHash sketch of canonical k-mers from a DNA sequence.
 ///
-/// Encodes each k-mer as a 2-bit packed `u64`, selects the canonical
-/// (lexicographically smaller) orientation of forward/reverse complement,
-/// and retains hashes that fall below `u64::MAX / ds_factor`.
-///
-/// Ambiguous bases (anything not `A`/`C`/`G`/`T`/`a`/`c`/`g`/`t`/`u`/`U`)
-/// reset the current k-mer window.
+/// Encodes each k-mer as a 2-bit packed `u64` with [`CanonicalKmerIter`],
+/// selects the canonical (lexicographically smaller) orientation of
+/// forward/reverse complement, hashes it with `mm_hash64` and retains hashes
+/// `h <= u64::MAX / scaled`, so on average one in `scaled` distinct k-mers is
+/// kept. As in sourmash, `scaled = 1` keeps every k-mer and sketches computed
+/// with the same `kmer_size` and `scaled` are directly comparable. Ambiguous
+/// bases reset the current k-mer window.
 ///
 /// # Errors
 ///
 /// Returns [`BioError::InvalidParameterError`] if:
 /// - `kmer_size` exceeds `seq.len()`
-/// - `ds_factor` is `0` or greater than `200`
-pub fn frac_min_hash(
-    kmer_size: usize,
-    ds_factor: u64,
-    seq: &[u8],
-) -> Result<HashSet<u64>, BioError> {
+/// - `kmer_size` is `0` or greater than [`MAX_KMER_SIZE`](super::MAX_KMER_SIZE)
+/// - `scaled` is `0`
+pub fn frac_min_hash(kmer_size: usize, scaled: u64, seq: &[u8]) -> Result<Sketch, BioError> {
     if kmer_size > seq.len() {
         return Err(BioError::InvalidParameterError(format!(
             "kmer size {} cannot be longer than sequence len {}.",
@@ -30,62 +39,46 @@ pub fn frac_min_hash(
         )));
     }
 
-    if ds_factor == 0 || ds_factor > 200 {
-        return Err(BioError::InvalidParameterError(format!(
-            "downsampling factor {} must be in range 1-200.",
-            ds_factor
-        )));
-    }
-
-    // -- fwd strand
-    let mut kmer_forward: u64 = 0;
-    let nbits = kmer_size << 1;
-    let mask: u64 = (1 << nbits) - 1;
-
-    // -- rev strand
-    let mut kmer_reverse: u64 = 0;
-    let shift = ((kmer_size - 1) * 2) as u64;
-
-    // -- hashes.
-    let mut canonical_hashes: HashSet<u64> = HashSet::with_capacity(seq.len() - kmer_size + 1);
-    let mut valid_kmer_index: usize = 0;
+    validate_scaled(scaled)?;
 
-    seq.iter().for_each(|nt_char| {
-        let nt = NT_LOOKUP[*nt_char as usize] as u64;
+    let max_hash = u64::MAX / scaled;
+    let mut canonical_hashes: Sketch =
+        Sketch::with_capacity((seq.len() - kmer_size + 1) / scaled as usize + 1);
 
-        if nt >= 4 {
-            valid_kmer_index = 0;
-            kmer_forward = 0;
-            kmer_reverse = 0;
-            return;
+    for (_, _, canonical) in CanonicalKmerIter::new(seq, kmer_size)? {
+        let hash = mm_hash64(canonical);
+        if hash <= max_hash {
+            canonical_hashes.insert(hash);
         }
-
-        // -- fwd strand
-        kmer_forward = (kmer_forward << 2 | nt) & mask;
-
-        // -- rev strand
-        let nt_rev = 3 - nt;
-        kmer_reverse = kmer_reverse >> 2 | nt_rev << shift;
-
-        if valid_kmer_index >= kmer_size - 1 {
-            let canonical = match kmer_forward < kmer_reverse {
-                true => kmer_forward,
-                false => kmer_reverse,
-            };
-            if canonical <= u64::MAX / ds_factor {
-                canonical_hashes.insert(mm_hash64(canonical));
-            }
-        }
-
-        valid_kmer_index += 1;
-    });
+    }
 
     Ok(canonical_hashes)
 }
 
+/// Like [`frac_min_hash`], but handles ambiguous bases according to `policy`.
+///
+/// [`AmbigPolicy::Skip`] and [`AmbigPolicy::MapToN`] both drop k-mers
+/// overlapping an ambiguous base, as [`frac_min_hash`] does, while
+/// [`AmbigPolicy::Random`] resolves them to compatible bases first.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] for invalid parameters as in
+/// [`frac_min_hash`], or [`BioError::InvalidSymbolError`] for the first
+/// ambiguous base under [`AmbigPolicy::Error`].
+pub fn frac_min_hash_with_policy(
+    kmer_size: usize,
+    scaled: u64,
+    seq: &[u8],
+    policy: AmbigPolicy,
+) -> Result<Sketch, BioError> {
+    frac_min_hash(kmer_size, scaled, &apply_ambig_policy(seq, policy)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rng::SplitMix64;
     use rstest::*;
 
     #[rstest]
@@ -111,4 +104,37 @@ mod tests {
 
         assert_eq!(result1, result2);
     }
+
+    #[rstest]
+    #[case(10)]
+    #[case(1000)]
+    fn test_scaled_fraction(#[case] scaled: u64) {
+        let mut rng = SplitMix64::new(7);
+        let seq: Vec<u8> = (0..200_000).map(|_| b"ACGT"[rng.next_below(4)]).collect();
+        let all = frac_min_hash(21, 1, &seq).unwrap();
+        let sketch = frac_min_hash(21, scaled, &seq).unwrap();
+
+        assert!(sketch.iter().all(|h| *h <= u64::MAX / scaled));
+        assert!(sketch.is_subset(&all));
+
+        let expected = all.len() as f64 / scaled as f64;
+        assert!((sketch.len() as f64 - expected).abs() < 4.0 * expected.sqrt());
+    }
+
+    #[test]
+    fn test_with_policy() {
+        let seq = b"ACGTRACGT";
+        let skip = frac_min_hash_with_policy(4, 1, seq, AmbigPolicy::Skip).unwrap();
+        let random = frac_min_hash_with_policy(4, 1, seq, AmbigPolicy::Random { seed: 1 }).unwrap();
+
+        assert_eq!(skip, frac_min_hash(4, 1, seq).unwrap());
+        assert!(random.len() > skip.len());
+        assert!(skip.is_subset(&random));
+        assert!(frac_min_hash_with_policy(4, 1, seq, AmbigPolicy::Error).is_err());
+    }
+
+    #[test]
+    fn test_invalid_scaled() {
+        assert!(frac_min_hash(3, 0, b"ACGT").is_err());
+    }
 }
@@ -1,8 +1,56 @@
 //! K-mer encoding and sketching.
 //!
-//! Provides a FracMinHash implementation for generating compact sequence
-//! sketches from canonical (strand-aware) k-mers.
+//! Provides a reusable canonical k-mer iterator ([`CanonicalKmerIter`]), an
+//! ntHash rolling hasher ([`NtHashIter`]), canonical k-mer counting
+//! ([`KmerCounts`]) with reference-free per-read error estimates
+//! ([`kmer_support`]) and spectral error correction ([`correct_read`]),
+//! blacklist-based masking ([`mask_blacklisted`]), a FracMinHash
+//! implementation for generating compact sequence sketches from canonical
+//! (strand-aware) k-mers, sketch similarity measures, and a k-mer size sweep
+//! and sketch diagnostics ([`sketch_diagnostics`]) for choosing parameters
+//! empirically.
+//!
+//! All sketching schemes share the [`Sketch`] type and [`Sketcher`] trait, so
+//! [`FracMinHashSketch`] and the minimizer/syncmer sketchers of the `sketch`
+//! feature are interchangeable, including for chunked input via [`StreamingSketch`].
+
+mod canonical;
+pub use canonical::*;
 
 mod hash;
+#[cfg(feature = "perf")]
+pub use hash::mm_hash64;
+#[cfg(not(feature = "perf"))]
+pub(crate) use hash::mm_hash64;
+mod nthash;
+pub use nthash::*;
+
+mod count;
+pub use count::*;
+
+mod spectrum;
+pub use spectrum::*;
+
+mod correct;
+pub use correct::*;
+
+mod blacklist;
+pub use blacklist::*;
+
 mod kmerize;
-pub use kmerize::frac_min_hash;
+pub use kmerize::{frac_min_hash, frac_min_hash_with_policy};
+
+mod sketch;
+pub use sketch::*;
+
+mod streaming;
+pub use streaming::*;
+
+mod similarity;
+pub use similarity::*;
+
+mod diagnostics;
+pub use diagnostics::*;
+
+mod sweep;
+pub use sweep::*;
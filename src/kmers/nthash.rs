@@ -0,0 +1,215 @@
+use crate::errors::BioError;
+use crate::nucleotide::NT_LOOKUP;
+
+/// ntHash seeds for `A`, `C`, `G` and `T`, indexed by [`NT_LOOKUP`] code.
+const NTHASH_SEEDS: [u64; 4] = [
+    0x3c8b_fbb3_95c6_0474,
+    0x3193_c185_62a0_2b4c,
+    0x2032_3ed0_8257_2324,
+    0x2955_49f5_4be2_4456,
+];
+
+/// Seed of the complement of the base with [`NT_LOOKUP`] code `code`.
+#[inline]
+fn complement_seed(code: usize) -> u64 {
+    NTHASH_SEEDS[3 - code]
+}
+
+/// Rolling [ntHash](https://doi.org/10.1093/bioinformatics/btw397) over the valid k-mers of a DNA sequence.
+///
+/// Yields `(position, forward, canonical)` for every k-mer without ambiguous
+/// bases, where `position` is the 0-based start of the k-mer, `forward` the
+/// ntHash of the forward strand and `canonical` the smaller of the forward
+/// and reverse complement hashes. Unlike [`CanonicalKmerIter`](super::CanonicalKmerIter)
+/// with `mm_hash64`, the hash is updated in constant
+/// time per base for any `k`, including `k > 32`.
+///
+/// Windows containing an ambiguous base (anything not `A`/`C`/`G`/`T`/`a`/`c`/`g`/`t`/`u`/`U`)
+/// are skipped, and hashing resumes at the next valid window.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::NtHashIter;
+/// use bio_utils_rs::nucleotide::reverse_complement;
+///
+/// let seq = b"ACGTTGCANACGTTGCA";
+/// let fwd: Vec<u64> = NtHashIter::new(seq, 8).unwrap().map(|(_, _, c)| c).collect();
+/// let rev: Vec<u64> = NtHashIter::new(&reverse_complement(seq), 8)
+///     .unwrap()
+///     .map(|(_, _, c)| c)
+///     .collect();
+///
+/// assert_eq!(fwd.len(), 2);
+/// assert_eq!(fwd, rev.into_iter().rev().collect::<Vec<u64>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct NtHashIter<'a> {
+    seq: &'a [u8],
+    kmer_size: usize,
+    pos: usize,
+    valid: usize,
+    forward: u64,
+    reverse: u64,
+}
+
+impl<'a> NtHashIter<'a> {
+    /// Creates an ntHash iterator over `seq`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `kmer_size` is `0`.
+    pub fn new(seq: &'a [u8], kmer_size: usize) -> Result<Self, BioError> {
+        if kmer_size == 0 {
+            return Err(BioError::InvalidParameterError(
+                "kmer size must be greater than 0.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            seq,
+            kmer_size,
+            pos: 0,
+            valid: 0,
+            forward: 0,
+            reverse: 0,
+        })
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+
+    /// Computes the forward and reverse complement hashes of the k-mer starting at `start`.
+    fn hash_window(&self, start: usize) -> (u64, u64) {
+        let k = self.kmer_size;
+
+        self.seq[start..start + k]
+            .iter()
+            .enumerate()
+            .fold((0, 0), |(fwd, rev), (i, nt)| {
+                let code = NT_LOOKUP[*nt as usize] as usize;
+                (
+                    fwd ^ NTHASH_SEEDS[code].rotate_left((k - 1 - i) as u32),
+                    rev ^ complement_seed(code).rotate_left(i as u32),
+                )
+            })
+    }
+}
+
+impl Iterator for NtHashIter<'_> {
+    type Item = (usize, u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let k = self.kmer_size;
+
+        while self.pos < self.seq.len() {
+            let code = NT_LOOKUP[self.seq[self.pos] as usize] as usize;
+            self.pos += 1;
+
+            if code >= 4 {
+                self.valid = 0;
+                continue;
+            }
+
+            self.valid += 1;
+            let start = self.pos - k.min(self.pos);
+
+            match self.valid {
+                v if v < k => continue,
+                v if v == k => (self.forward, self.reverse) = self.hash_window(start),
+                _ => {
+                    let out = NT_LOOKUP[self.seq[start - 1] as usize] as usize;
+
+                    self.forward = self.forward.rotate_left(1)
+                        ^ NTHASH_SEEDS[out].rotate_left(k as u32)
+                        ^ NTHASH_SEEDS[code];
+                    self.reverse = self.reverse.rotate_right(1)
+                        ^ complement_seed(out).rotate_right(1)
+                        ^ complement_seed(code).rotate_left((k - 1) as u32);
+                }
+            }
+
+            return Some((start, self.forward, self.forward.min(self.reverse)));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nucleotide::reverse_complement;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"ACGTACGTTGCAGGCTAAGC", 4)]
+    #[case(
+        b"ACGTACGTTGCAGGCTAAGCACGTACGTTGCAGGCTAAGCACGTACGTTGCAGGCTAAGCACGTACGTTGCAGGCTAAGC",
+        70
+    )]
+    #[case(b"acgtNNACGTTGCAGnGCTAAGC", 3)]
+    fn test_rolling_matches_direct(#[case] seq: &[u8], #[case] kmer_size: usize) {
+        let iter = NtHashIter::new(seq, kmer_size).unwrap();
+
+        for (pos, fwd, canonical) in iter.clone() {
+            let (direct_fwd, direct_rev) = iter.hash_window(pos);
+            assert_eq!(fwd, direct_fwd);
+            assert_eq!(canonical, direct_fwd.min(direct_rev));
+        }
+    }
+
+    #[rstest]
+    #[case(b"ACGNTTGCA", 3, vec![0, 4, 5, 6])]
+    #[case(b"NNNN", 2, vec![])]
+    #[case(b"AC", 3, vec![])]
+    fn test_positions(#[case] seq: &[u8], #[case] kmer_size: usize, #[case] expected: Vec<usize>) {
+        let positions: Vec<usize> = NtHashIter::new(seq, kmer_size)
+            .unwrap()
+            .map(|(pos, _, _)| pos)
+            .collect();
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn test_canonical_strand_independent() {
+        let seq = b"GATTACAGATTACAGGGCATTACAGATTACA";
+        let rc = reverse_complement(seq);
+
+        let mut fwd: Vec<u64> = NtHashIter::new(seq, 7)
+            .unwrap()
+            .map(|(_, _, c)| c)
+            .collect();
+        let mut rev: Vec<u64> = NtHashIter::new(&rc, 7)
+            .unwrap()
+            .map(|(_, _, c)| c)
+            .collect();
+
+        fwd.sort();
+        rev.sort();
+        assert_eq!(fwd, rev);
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let upper: Vec<_> = NtHashIter::new(b"ACGTACGT", 4).unwrap().collect();
+        let lower: Vec<_> = NtHashIter::new(b"acgtacgt", 4).unwrap().collect();
+        assert_eq!(upper, lower);
+    }
+
+    #[test]
+    fn test_invalid_kmer_size() {
+        assert!(NtHashIter::new(b"ACGT", 0).is_err());
+    }
+
+    #[test]
+    fn test_reference_value() {
+        // Canonical hash of TGCAG (k=5) from the reference ntHash implementation.
+        let hashes: Vec<u64> = NtHashIter::new(b"TGCAG", 5)
+            .unwrap()
+            .map(|(_, _, c)| c)
+            .collect();
+        assert_eq!(hashes, vec![0x0baf_a672_8fc6_dabf]);
+    }
+}
@@ -0,0 +1,88 @@
+use super::sketch::Sketch;
+
+/// Similarity measures between two hash sketches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimilarityMetric {
+    /// `|A ∩ B| / |A ∪ B|`. Symmetric.
+    Jaccard,
+    /// `|A ∩ B| / |A|`, i.e. the fraction of the query contained in the reference.
+    Containment,
+}
+
+impl SimilarityMetric {
+    /// Returns `true` if `compute(a, b) == compute(b, a)` for all sketches.
+    pub fn is_symmetric(&self) -> bool {
+        match self {
+            SimilarityMetric::Jaccard => true,
+            SimilarityMetric::Containment => false,
+        }
+    }
+
+    /// Computes the similarity between `query` and `reference`.
+    pub fn compute(&self, query: &Sketch, reference: &Sketch) -> f64 {
+        match self {
+            SimilarityMetric::Jaccard => jaccard(query, reference),
+            SimilarityMetric::Containment => containment(query, reference),
+        }
+    }
+}
+
+/// Computes the Jaccard index between two sketches.
+///
+/// Returns `0.0` if both sketches are empty.
+#[inline]
+pub fn jaccard(a: &Sketch, b: &Sketch) -> f64 {
+    let (small, large) = match a.len() < b.len() {
+        true => (a, b),
+        false => (b, a),
+    };
+
+    let intersection = small.iter().filter(|h| large.contains(h)).count();
+    let union = a.len() + b.len() - intersection;
+
+    match union {
+        0 => 0.0,
+        _ => intersection as f64 / union as f64,
+    }
+}
+
+/// Computes the fraction of `query` hashes that are present in `reference`.
+///
+/// Returns `0.0` if `query` is empty.
+#[inline]
+pub fn containment(query: &Sketch, reference: &Sketch) -> f64 {
+    if query.is_empty() {
+        return 0.0;
+    }
+
+    let intersection = query.iter().filter(|h| reference.contains(h)).count();
+    intersection as f64 / query.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn set(hashes: &[u64]) -> Sketch {
+        hashes.iter().copied().collect()
+    }
+
+    #[rstest]
+    #[case(&[], &[], 0.0)]
+    #[case(&[1, 2], &[1, 2], 1.0)]
+    #[case(&[1, 2], &[3, 4], 0.0)]
+    #[case(&[1, 2, 3], &[2, 3, 4], 0.5)]
+    fn test_jaccard(#[case] a: &[u64], #[case] b: &[u64], #[case] expected: f64) {
+        assert_eq!(jaccard(&set(a), &set(b)), expected);
+        assert_eq!(jaccard(&set(b), &set(a)), expected);
+    }
+
+    #[rstest]
+    #[case(&[], &[1], 0.0)]
+    #[case(&[1, 2], &[1, 2, 3, 4], 1.0)]
+    #[case(&[1, 2, 3, 4], &[1, 2], 0.5)]
+    fn test_containment(#[case] query: &[u64], #[case] reference: &[u64], #[case] expected: f64) {
+        assert_eq!(containment(&set(query), &set(reference)), expected);
+    }
+}
@@ -0,0 +1,195 @@
+use super::canonical::{CanonicalKmerIter, validate_kmer_size};
+use super::hash::mm_hash64;
+use super::kmerize::{frac_min_hash, validate_scaled};
+use super::streaming::StreamingSketch;
+use crate::errors::BioError;
+use crate::nucleotide::{AmbigPolicy, apply_ambig_policy, split_on_ambiguous};
+use std::collections::HashSet;
+
+/// Set of hashes (or k-mer values) selected from a sequence by a [`Sketcher`].
+pub type Sketch = HashSet<u64>;
+
+/// Common interface of all sketching schemes.
+///
+/// Implemented by [`FracMinHashSketch`] and, with the `sketch` feature, by the
+/// minimizer and syncmer sketchers in `simd_sketch`.
+pub trait Sketcher: Send + Sync {
+    fn sketch(&self, seq: &[u8]) -> Sketch;
+
+    /// Number of consecutive bases that determine whether a k-mer is selected,
+    /// e.g. `kmer_size + window_size - 1` for minimizers.
+    ///
+    /// Used by [`StreamingSketch`] to overlap chunks. The default of
+    /// `usize::MAX` is always correct but buffers the whole sequence.
+    fn context_len(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Returns the selected k-mers of `seq` as `(position, value)`, keeping
+    /// repeated values, or `None` if the sketcher does not track positions.
+    /// Values are those of [`Sketcher::sketch`].
+    ///
+    /// Used by [`sketch_diagnostics`](super::sketch_diagnostics). The default
+    /// returns `None`.
+    fn positions(&self, _seq: &[u8]) -> Option<Vec<(usize, u64)>> {
+        None
+    }
+
+    /// Sketches `seq` with ambiguous bases handled according to `policy`.
+    ///
+    /// Under [`AmbigPolicy::Skip`] and [`AmbigPolicy::MapToN`] the sequence
+    /// is sketched as its maximal runs of `ACGT`, so no selected k-mer
+    /// overlaps an ambiguous base regardless of how the sketcher itself
+    /// encodes them. [`AmbigPolicy::Random`] resolves ambiguous bases to
+    /// compatible bases first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidSymbolError`] for the first ambiguous base
+    /// under [`AmbigPolicy::Error`].
+    fn sketch_with_policy(&self, seq: &[u8], policy: AmbigPolicy) -> Result<Sketch, BioError> {
+        let seq = apply_ambig_policy(seq, policy)?;
+
+        match policy {
+            AmbigPolicy::Skip | AmbigPolicy::MapToN => {
+                let mut sketch = Sketch::new();
+                for (_, run) in split_on_ambiguous(&seq, 1) {
+                    sketch.extend(self.sketch(run));
+                }
+                Ok(sketch)
+            }
+            AmbigPolicy::Error | AmbigPolicy::Random { .. } => Ok(self.sketch(&seq)),
+        }
+    }
+
+    /// Wraps the sketcher for incremental sketching of chunked input.
+    fn streaming(self) -> StreamingSketch<Self>
+    where
+        Self: Sized,
+    {
+        StreamingSketch::new(self)
+    }
+}
+
+impl<S: Sketcher + ?Sized> Sketcher for Box<S> {
+    fn sketch(&self, seq: &[u8]) -> Sketch {
+        (**self).sketch(seq)
+    }
+
+    fn context_len(&self) -> usize {
+        (**self).context_len()
+    }
+
+    fn positions(&self, seq: &[u8]) -> Option<Vec<(usize, u64)>> {
+        (**self).positions(seq)
+    }
+}
+
+/// FracMinHash sketcher, see [`frac_min_hash`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FracMinHashSketch {
+    kmer_size: usize,
+    scaled: u64,
+}
+
+impl FracMinHashSketch {
+    /// Creates a FracMinHash sketcher with validated parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `kmer_size` is `0` or
+    /// greater than [`MAX_KMER_SIZE`](super::MAX_KMER_SIZE), or `scaled` is `0`.
+    pub fn new(kmer_size: usize, scaled: u64) -> Result<Self, BioError> {
+        validate_kmer_size(kmer_size)?;
+        validate_scaled(scaled)?;
+
+        Ok(Self { kmer_size, scaled })
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+
+    pub fn scaled(&self) -> u64 {
+        self.scaled
+    }
+}
+
+impl Sketcher for FracMinHashSketch {
+    /// Sequences shorter than `kmer_size` yield an empty sketch.
+    fn sketch(&self, seq: &[u8]) -> Sketch {
+        if seq.len() < self.kmer_size {
+            return Sketch::new();
+        }
+
+        frac_min_hash(self.kmer_size, self.scaled, seq).expect("parameters are validated in new")
+    }
+
+    fn context_len(&self) -> usize {
+        self.kmer_size
+    }
+
+    fn positions(&self, seq: &[u8]) -> Option<Vec<(usize, u64)>> {
+        let kmers =
+            CanonicalKmerIter::new(seq, self.kmer_size).expect("parameters are validated in new");
+        let max_hash = u64::MAX / self.scaled;
+
+        Some(
+            kmers
+                .map(|(pos, _, canonical)| (pos, mm_hash64(canonical)))
+                .filter(|&(_, hash)| hash <= max_hash)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(33, 1)]
+    #[case(21, 0)]
+    fn test_frac_min_hash_sketch_invalid(#[case] kmer_size: usize, #[case] scaled: u64) {
+        assert!(FracMinHashSketch::new(kmer_size, scaled).is_err());
+    }
+
+    #[test]
+    fn test_frac_min_hash_sketch() {
+        let sketcher = FracMinHashSketch::new(5, 1).unwrap();
+        let seq = b"ACGTTGCAGGCTAAGCTTAG";
+
+        assert_eq!((sketcher.kmer_size(), sketcher.scaled()), (5, 1));
+        assert_eq!(sketcher.sketch(seq), frac_min_hash(5, 1, seq).unwrap());
+        assert!(sketcher.sketch(b"ACG").is_empty());
+    }
+
+    #[test]
+    fn test_boxed_sketcher() {
+        let sketcher: Box<dyn Sketcher> = Box::new(FracMinHashSketch::new(5, 1).unwrap());
+        let seq = b"ACGTTGCAGGCTAAGCTTAG";
+
+        assert_eq!(sketcher.context_len(), 5);
+        assert_eq!(sketcher.sketch(seq), frac_min_hash(5, 1, seq).unwrap());
+    }
+
+    #[rstest]
+    #[case(AmbigPolicy::Skip)]
+    #[case(AmbigPolicy::MapToN)]
+    fn test_sketch_with_policy_skips_ambiguous(#[case] policy: AmbigPolicy) {
+        let sketcher = FracMinHashSketch::new(5, 1).unwrap();
+        let seq = b"ACGTTGCAGGNCTAAGCTTAGYACG";
+
+        assert_eq!(
+            sketcher.sketch_with_policy(seq, policy).unwrap(),
+            sketcher.sketch(seq)
+        );
+        assert!(
+            sketcher
+                .sketch_with_policy(seq, AmbigPolicy::Error)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use super::canonical::CanonicalKmerIter;
+use super::count::TrustedKmers;
+
+/// K-mer support of a read, computed by [`kmer_support`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KmerSupport {
+    pub kmer_size: usize,
+    /// Number of valid k-mers in the read.
+    pub kmers: usize,
+    /// Number of those k-mers found in the trusted set.
+    pub trusted: usize,
+}
+
+impl KmerSupport {
+    /// Fraction of the read's k-mers that are trusted, or `0.0` for a read
+    /// without valid k-mers.
+    pub fn fraction_trusted(&self) -> f64 {
+        match self.kmers {
+            0 => 0.0,
+            n => self.trusted as f64 / n as f64,
+        }
+    }
+
+    /// Estimated per-base error rate of the read.
+    ///
+    /// Assuming independent errors at rate `e` and a trusted set containing
+    /// every error-free k-mer, a k-mer is trusted with probability
+    /// `(1 - e)^k`, so `e = 1 - fraction_trusted^(1/k)`. Returns `None` for a
+    /// read without valid k-mers.
+    pub fn estimated_error_rate(&self) -> Option<f64> {
+        match self.kmers {
+            0 => None,
+            _ => Some(1.0 - self.fraction_trusted().powf(1.0 / self.kmer_size as f64)),
+        }
+    }
+}
+
+/// Counts how many k-mers of `seq` are in `trusted`, a quick reference-free
+/// quality proxy that does not rely on the read's Phred scores.
+///
+/// `trusted` is typically built from high-count k-mers of the whole read
+/// set with [`KmerCounts::trusted`](super::KmerCounts::trusted), or from a
+/// reference. K-mers with ambiguous bases are not counted.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::{KmerCounts, kmer_support};
+///
+/// let reads: [&[u8]; 3] = [b"GATTACAGGCT", b"GATTACAGGCT", b"GATTTCAGGCT"];
+/// let trusted = KmerCounts::from_seqs(reads, 5).unwrap().trusted(2);
+///
+/// assert_eq!(kmer_support(reads[0], &trusted).fraction_trusted(), 1.0);
+///
+/// // The substitution hides every k-mer that covers it.
+/// let support = kmer_support(reads[2], &trusted);
+/// assert_eq!((support.trusted, support.kmers), (2, 7));
+/// assert!(support.estimated_error_rate().unwrap() > 0.1);
+/// ```
+pub fn kmer_support(seq: &[u8], trusted: &TrustedKmers) -> KmerSupport {
+    let mut support = KmerSupport {
+        kmer_size: trusted.kmer_size(),
+        kmers: 0,
+        trusted: 0,
+    };
+
+    let kmers = CanonicalKmerIter::new(seq, trusted.kmer_size()).expect("kmer size is validated");
+    for (_, _, canonical) in kmers {
+        support.kmers += 1;
+        support.trusted += trusted.contains(canonical) as usize;
+    }
+
+    support
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmers::KmerCounts;
+    use crate::rng::SplitMix64;
+    use crate::test_utils::random_sequence;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0.01)]
+    #[case(0.05)]
+    #[case(0.1)]
+    fn test_estimated_error_rate(#[case] error_rate: f64) {
+        let genome = random_sequence(&mut SplitMix64::new(1), 50_000, 0.5);
+        let trusted = KmerCounts::from_seqs([genome.as_slice()], 15)
+            .unwrap()
+            .trusted(1);
+
+        let mut rng = SplitMix64::new(2);
+        let read: Vec<u8> = genome[..20_000]
+            .iter()
+            .map(|&nt| match rng.next_f64() < error_rate {
+                true => b"ACGT"[(b"ACGT".iter().position(|&b| b == nt).unwrap() + 1) % 4],
+                false => nt,
+            })
+            .collect();
+
+        let estimate = kmer_support(&read, &trusted)
+            .estimated_error_rate()
+            .unwrap();
+        assert!((estimate - error_rate).abs() < 0.2 * error_rate);
+    }
+
+    #[rstest]
+    #[case(b"".as_slice(), 0, 0)]
+    #[case(b"ACNGT".as_slice(), 0, 0)]
+    #[case(b"ACGTT".as_slice(), 3, 2)]
+    fn test_kmer_support(#[case] seq: &[u8], #[case] kmers: usize, #[case] supported: usize) {
+        let trusted = KmerCounts::from_seqs([b"ACGT".as_slice()], 3)
+            .unwrap()
+            .trusted(1);
+
+        let support = kmer_support(seq, &trusted);
+        assert_eq!((support.kmers, support.trusted), (kmers, supported));
+
+        if kmers == 0 {
+            assert_eq!(support.fraction_trusted(), 0.0);
+            assert_eq!(support.estimated_error_rate(), None);
+        }
+    }
+}
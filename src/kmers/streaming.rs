@@ -0,0 +1,152 @@
+use super::sketch::{Sketch, Sketcher};
+
+/// Incremental sketch of a sequence that arrives in chunks.
+///
+/// Each chunk is sketched together with the last `context_len - 1` bases of
+/// the previous one, so every k-mer window lies entirely within one sketched
+/// buffer and the result equals sketching the concatenated sequence at once,
+/// while only one chunk plus the carried-over context is held in memory.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::kmers::{FracMinHashSketch, Sketcher};
+///
+/// let seq = b"ACGTTGCAGGCTAAGCTTAGCCATG".repeat(200);
+/// let sketcher = FracMinHashSketch::new(21, 10).unwrap();
+/// let expected = sketcher.sketch(&seq);
+///
+/// let mut stream = sketcher.streaming();
+/// for chunk in seq.chunks(1000) {
+///     stream.sketch_streaming(chunk);
+/// }
+///
+/// assert_eq!(stream.finalize(), expected);
+/// ```
+pub struct StreamingSketch<S: Sketcher> {
+    sketcher: S,
+    carry: Vec<u8>,
+    hashes: Sketch,
+    sketched: bool,
+    bases: u64,
+}
+
+impl<S: Sketcher> StreamingSketch<S> {
+    pub fn new(sketcher: S) -> Self {
+        Self {
+            sketcher,
+            carry: Vec::new(),
+            hashes: Sketch::new(),
+            sketched: false,
+            bases: 0,
+        }
+    }
+
+    /// Number of bases consumed so far.
+    pub fn bases(&self) -> u64 {
+        self.bases
+    }
+
+    /// Adds the next chunk of the sequence.
+    pub fn sketch_streaming(&mut self, chunk: &[u8]) {
+        self.bases += chunk.len() as u64;
+        self.carry.extend_from_slice(chunk);
+
+        let context_len = self.sketcher.context_len();
+        if self.carry.len() < context_len {
+            return;
+        }
+
+        self.hashes.extend(self.sketcher.sketch(&self.carry));
+        self.sketched = true;
+
+        let keep = context_len - 1;
+        self.carry.drain(..self.carry.len() - keep);
+    }
+
+    /// Sketches any remaining bases and returns the sketch of the full sequence.
+    pub fn finalize(mut self) -> Sketch {
+        // Once a buffer has been sketched, the carried-over bases were covered by it.
+        if !self.sketched {
+            self.hashes.extend(self.sketcher.sketch(&self.carry));
+        }
+
+        self.hashes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use rstest::*;
+
+    /// Window minimizers over a plain rolling hash, to test chunking without SIMD.
+    struct NaiveMinimizers {
+        kmer_size: usize,
+        window_size: usize,
+    }
+
+    impl Sketcher for NaiveMinimizers {
+        fn sketch(&self, seq: &[u8]) -> Sketch {
+            let hashes: Vec<u64> = seq
+                .windows(self.kmer_size)
+                .map(|w| {
+                    w.iter()
+                        .fold(0u64, |h, b| h.wrapping_mul(0x100000001b3) ^ *b as u64)
+                })
+                .collect();
+
+            hashes
+                .windows(self.window_size)
+                .filter_map(|w| w.iter().min().copied())
+                .collect()
+        }
+
+        fn context_len(&self) -> usize {
+            self.kmer_size + self.window_size - 1
+        }
+    }
+
+    struct Buffered;
+
+    impl Sketcher for Buffered {
+        fn sketch(&self, seq: &[u8]) -> Sketch {
+            Sketch::from([seq.len() as u64])
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(7)]
+    #[case(25)]
+    #[case(1000)]
+    #[case(20_000)]
+    fn test_streaming_matches_full(#[case] chunk_size: usize) {
+        let mut rng = SplitMix64::new(11);
+        let seq: Vec<u8> = (0..10_000).map(|_| b"ACGT"[rng.next_below(4)]).collect();
+
+        let sketcher = NaiveMinimizers {
+            kmer_size: 15,
+            window_size: 10,
+        };
+        let expected = sketcher.sketch(&seq);
+
+        let mut stream = sketcher.streaming();
+        for chunk in seq.chunks(chunk_size) {
+            stream.sketch_streaming(chunk);
+        }
+
+        assert_eq!(stream.bases(), 10_000);
+        assert_eq!(stream.finalize(), expected);
+    }
+
+    #[test]
+    fn test_streaming_default_context_buffers() {
+        let mut stream = Buffered.streaming();
+        stream.sketch_streaming(b"ACGT");
+        stream.sketch_streaming(b"ACG");
+
+        assert_eq!(stream.finalize(), Sketch::from([7]));
+    }
+}
@@ -0,0 +1,173 @@
+use super::kmerize::frac_min_hash;
+use super::similarity::SimilarityMetric;
+use super::sketch::Sketch;
+use crate::errors::BioError;
+use std::io::Write;
+
+/// A single row of a k-mer size sweep.
+///
+/// `query` and `reference` are indices into the input sequence slice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KSweepRecord {
+    pub kmer_size: usize,
+    pub query: usize,
+    pub reference: usize,
+    pub similarity: f64,
+}
+
+/// Sketches every sequence for one k-mer size and compares all pairs.
+fn sweep_single_k(
+    seqs: &[&[u8]],
+    kmer_size: usize,
+    scaled: u64,
+    metric: SimilarityMetric,
+) -> Result<Vec<KSweepRecord>, BioError> {
+    let sketches = seqs
+        .iter()
+        .map(|seq| frac_min_hash(kmer_size, scaled, seq))
+        .collect::<Result<Vec<Sketch>, BioError>>()?;
+
+    let mut records = Vec::new();
+
+    for (i, query) in sketches.iter().enumerate() {
+        for (j, reference) in sketches.iter().enumerate() {
+            if i == j || (metric.is_symmetric() && j < i) {
+                continue;
+            }
+
+            records.push(KSweepRecord {
+                kmer_size,
+                query: i,
+                reference: j,
+                similarity: metric.compute(query, reference),
+            });
+        }
+    }
+
+    Ok(records)
+}
+
+/// Sketches `seqs` at every k-mer size in `ks` and reports pairwise similarities.
+///
+/// Each k-mer size is processed on its own thread, or sequentially on
+/// `wasm32` targets without thread support. For symmetric metrics only
+/// pairs with `query < reference` are reported, otherwise all ordered pairs are.
+/// Records are returned in the order of `ks`, then by `(query, reference)`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `ks` is empty or if
+/// [`frac_min_hash`] rejects a k-mer size or `scaled`.
+pub fn k_sweep(
+    seqs: &[&[u8]],
+    ks: &[usize],
+    scaled: u64,
+    metric: SimilarityMetric,
+) -> Result<Vec<KSweepRecord>, BioError> {
+    if ks.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "at least one kmer size is required.".to_string(),
+        ));
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    let results: Vec<Result<Vec<KSweepRecord>, BioError>> = std::thread::scope(|s| {
+        let handles: Vec<_> = ks
+            .iter()
+            .map(|&k| s.spawn(move || sweep_single_k(seqs, k, scaled, metric)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("k sweep thread panicked"))
+            .collect()
+    });
+
+    #[cfg(target_family = "wasm")]
+    let results: Vec<Result<Vec<KSweepRecord>, BioError>> = ks
+        .iter()
+        .map(|&k| sweep_single_k(seqs, k, scaled, metric))
+        .collect();
+
+    let mut records = Vec::new();
+    for result in results {
+        records.extend(result?);
+    }
+
+    Ok(records)
+}
+
+/// Writes k sweep records as a tab-separated table with a header line.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if writing fails.
+pub fn write_k_sweep_tsv<W: Write>(
+    writer: &mut W,
+    records: &[KSweepRecord],
+) -> Result<(), BioError> {
+    writeln!(writer, "kmer_size\tquery\treference\tsimilarity")?;
+
+    for r in records {
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{:.6}",
+            r.kmer_size, r.query, r.reference, r.similarity
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_k_sweep_jaccard_pairs() {
+        let seqs: Vec<&[u8]> = vec![b"ACGTACGTTTGA", b"ACGTACGTTTGA", b"GGGGCCCCAAAT"];
+        let records = k_sweep(&seqs, &[3, 5], 1, SimilarityMetric::Jaccard).unwrap();
+
+        // 3 unordered pairs per k.
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[0].kmer_size, 3);
+        assert_eq!(records[3].kmer_size, 5);
+        assert_eq!((records[0].query, records[0].reference), (0, 1));
+        assert_eq!(records[0].similarity, 1.0);
+    }
+
+    #[test]
+    fn test_k_sweep_containment_ordered_pairs() {
+        let seqs: Vec<&[u8]> = vec![b"ACGTACGTTTGA", b"ACGTACGTTTGACCCATA"];
+        let records = k_sweep(&seqs, &[4], 1, SimilarityMetric::Containment).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].similarity, 1.0);
+        assert!(records[1].similarity < 1.0);
+    }
+
+    #[test]
+    fn test_k_sweep_invalid() {
+        let seqs: Vec<&[u8]> = vec![b"ACGT"];
+        assert!(k_sweep(&seqs, &[], 1, SimilarityMetric::Jaccard).is_err());
+        assert!(k_sweep(&seqs, &[10], 1, SimilarityMetric::Jaccard).is_err());
+    }
+
+    #[test]
+    fn test_write_k_sweep_tsv() {
+        let records = vec![KSweepRecord {
+            kmer_size: 21,
+            query: 0,
+            reference: 1,
+            similarity: 0.5,
+        }];
+
+        let mut buf = Vec::new();
+        write_k_sweep_tsv(&mut buf, &records).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "kmer_size\tquery\treference\tsimilarity\n21\t0\t1\t0.500000\n"
+        );
+    }
+}
@@ -3,21 +3,61 @@
 //! # Modules
 //!
 //! - [`io`] — Readers and writers for FASTQ/FASTA files (plain and gzip-compressed).
+//! - [`pipeline`] — Composable filter/trim/tag stages streamed over FASTQ/FASTA records (`io` feature).
 //! - [`nucleotide`] — Sequence operations, quality metrics, entropy, homopolymer detection, and pattern search.
-//! - [`aminoacid`] - Nucleotide to aminoacid translations.
-//! - [`kmers`] — K-mer encoding and FracMinHash sketching.
+//! - [`aminoacid`] - Nucleotide to aminoacid translations and protein FASTA output.
+//! - [`alignment`] — Edit distance, identity and gap-affine wavefront alignment of sequence pairs.
+//! - [`cigar`] — CIGAR parsing and formatting, alignment lengths, identity and coordinate liftover.
+//! - [`alphabet`] — DNA, IUPAC, RNA and protein alphabet validation.
+//! - [`kmers`] — K-mer encoding, counting and FracMinHash sketching.
+//! - [`index`] — FM-index for repeated exact pattern queries against a reference.
+//! - [`primer`] — Primer melting temperature, GC clamp, dimer and hairpin checks.
+//! - [`qc`] — Run/sample QC statistics and configurable pass/warn/fail policies.
+//! - [`assembly`] — Scaffold gap statistics and splitting scaffolds into contigs at N runs.
+//! - [`intervals`] — Merge, intersect, subtract and complement half-open intervals.
+//! - [`coverage`] — Per-base depth, mean depth and breadth from intervals, bedGraph/WIG track writers, GC-bias reports and depth normalization.
+//! - [`simd_sketch`] — SIMD minimizer/syncmer sketching, sketch databases, screening, taxonomic classification, read overlaps and chimera detection (`sketch` feature).
+//! - [`taxonomy`] — Taxonomy trees, lineages and lowest common ancestor queries.
+//! - [`sampling`] — Seeded subsampling of reads or records by fraction or count.
+//! - [`stats`] — Fixed-width and logarithmic histograms with merging and quantiles.
+//! - [`rng`] — The [`Seedable`](rng::Seedable) seeding convention of stochastic utilities.
+//! - `python` — PyO3 bindings of common sequence functions (`python` feature).
+//! - `ffi` — C ABI of core sequence functions and sketching, see `include/bio_utils_rs.h` (`ffi` feature).
 //! - [`errors`] — Shared error types used across the crate.
+//! - `test_utils` — Seeded FASTQ/FASTA generators for tests and benchmarks (`test-utils` feature).
 
+pub mod alignment;
+pub mod alphabet;
 pub mod aminoacid;
+pub mod assembly;
+pub mod cigar;
+pub mod coverage;
 pub mod errors;
+pub mod index;
+pub mod intervals;
 pub mod kmers;
 pub mod nucleotide;
+pub mod primer;
+pub mod qc;
+pub mod rng;
+pub mod sampling;
+pub mod stats;
+pub mod taxonomy;
 
 #[cfg(feature = "io")]
 pub mod io;
 
-#[cfg(feature = "simd")]
+#[cfg(feature = "io")]
+pub mod pipeline;
+
+#[cfg(feature = "sketch")]
 pub mod simd_sketch;
 
-#[cfg(test)]
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
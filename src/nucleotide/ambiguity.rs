@@ -0,0 +1,260 @@
+use super::statics::NT_LOOKUP;
+use crate::alphabet::Alphabet;
+use crate::errors::BioError;
+use crate::rng::{Seedable, SplitMix64};
+use std::borrow::Cow;
+
+/// How to treat bases other than `ACGT` (e.g. `N` or IUPAC codes).
+///
+/// Accepted by [`frac_min_hash_with_policy`](crate::kmers::frac_min_hash_with_policy),
+/// [`Sketcher::sketch_with_policy`](crate::kmers::Sketcher::sketch_with_policy)
+/// and [`translate_with_policy`](crate::aminoacid::translate_with_policy), so
+/// k-mer, sketching and translation code handle ambiguity the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmbigPolicy {
+    /// Drop every k-mer or codon that overlaps an ambiguous base.
+    #[default]
+    Skip,
+    /// Fail on the first ambiguous base.
+    Error,
+    /// Replace every ambiguous base with `N`, so IUPAC codes and invalid
+    /// bytes are treated alike. Codons containing `N` translate to `X`.
+    MapToN,
+    /// Replace every ambiguous base with a random compatible base, e.g. `R`
+    /// with `A` or `G` and `N` with any of `ACGT`. Seeded for reproducibility.
+    Random { seed: u64 },
+}
+
+impl Seedable for AmbigPolicy {
+    fn seed(&self) -> Option<u64> {
+        match self {
+            AmbigPolicy::Random { seed } => Some(*seed),
+            _ => None,
+        }
+    }
+
+    fn with_seed(self, seed: u64) -> Self {
+        match self {
+            AmbigPolicy::Random { .. } => AmbigPolicy::Random { seed },
+            policy => policy,
+        }
+    }
+}
+
+/// Bases compatible with an IUPAC code, with anything unknown treated as `N`.
+#[inline]
+fn iupac_bases(nt: u8) -> &'static [u8] {
+    match nt.to_ascii_uppercase() {
+        b'R' => b"AG",
+        b'Y' => b"CT",
+        b'S' => b"CG",
+        b'W' => b"AT",
+        b'K' => b"GT",
+        b'M' => b"AC",
+        b'B' => b"CGT",
+        b'D' => b"AGT",
+        b'H' => b"ACT",
+        b'V' => b"ACG",
+        _ => b"ACGT",
+    }
+}
+
+#[inline]
+fn is_ambiguous(nt: u8) -> bool {
+    NT_LOOKUP[nt as usize] > 3
+}
+
+/// Applies `policy` to `seq`, returning a sequence in which every remaining
+/// non-`ACGT` base should be skipped by downstream code.
+///
+/// [`AmbigPolicy::Skip`] and [`AmbigPolicy::Error`] never copy `seq`. `U` is
+/// treated as `T`, and softmasking is preserved.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidSymbolError`] for the first ambiguous base
+/// under [`AmbigPolicy::Error`].
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{AmbigPolicy, apply_ambig_policy};
+///
+/// assert_eq!(&*apply_ambig_policy(b"ACRT", AmbigPolicy::MapToN).unwrap(), b"ACNT");
+/// assert!(apply_ambig_policy(b"ACRT", AmbigPolicy::Error).is_err());
+///
+/// let resolved = apply_ambig_policy(b"ACRT", AmbigPolicy::Random { seed: 1 }).unwrap();
+/// assert!(resolved[2] == b'A' || resolved[2] == b'G');
+/// ```
+pub fn apply_ambig_policy(seq: &[u8], policy: AmbigPolicy) -> Result<Cow<'_, [u8]>, BioError> {
+    let Some(pos) = seq.iter().position(|nt| is_ambiguous(*nt)) else {
+        return Ok(Cow::Borrowed(seq));
+    };
+
+    match policy {
+        AmbigPolicy::Skip => Ok(Cow::Borrowed(seq)),
+        AmbigPolicy::Error => Err(BioError::InvalidSymbolError {
+            pos,
+            symbol: seq[pos] as char,
+            alphabet: Alphabet::Dna.as_str(),
+        }),
+        AmbigPolicy::MapToN => Ok(Cow::Owned(
+            seq.iter()
+                .map(|nt| match is_ambiguous(*nt) {
+                    true => b'N',
+                    false => *nt,
+                })
+                .collect(),
+        )),
+        AmbigPolicy::Random { seed } => {
+            let mut rng = SplitMix64::new(seed);
+            Ok(Cow::Owned(
+                seq.iter()
+                    .map(|nt| {
+                        if !is_ambiguous(*nt) {
+                            return *nt;
+                        }
+
+                        let bases = iupac_bases(*nt);
+                        let base = bases[rng.next_below(bases.len())];
+                        match nt.is_ascii_lowercase() {
+                            true => base.to_ascii_lowercase(),
+                            false => base,
+                        }
+                    })
+                    .collect(),
+            ))
+        }
+    }
+}
+
+/// Iterator over the maximal unambiguous runs of a sequence, see [`split_on_ambiguous`].
+#[derive(Debug, Clone)]
+pub struct AmbiguousSplit<'a> {
+    seq: &'a [u8],
+    pos: usize,
+    min_len: usize,
+}
+
+impl<'a> Iterator for AmbiguousSplit<'a> {
+    type Item = (usize, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.seq.len() {
+            let start = self.seq[self.pos..]
+                .iter()
+                .position(|nt| !is_ambiguous(*nt))
+                .map(|offset| self.pos + offset)?;
+            let end = self.seq[start..]
+                .iter()
+                .position(|nt| is_ambiguous(*nt))
+                .map_or(self.seq.len(), |offset| start + offset);
+
+            self.pos = end;
+            if end - start >= self.min_len {
+                return Some((start, &self.seq[start..end]));
+            }
+        }
+
+        None
+    }
+}
+
+/// Splits `seq` into its maximal runs of `ACGT` (case insensitive, `U`
+/// counting as `T`) that are at least `k` bases long.
+///
+/// Yields `(start, run)` with `start` the offset of `run` in `seq`. Every
+/// k-mer of `seq` without an ambiguous base lies in exactly one run, so
+/// k-mer counting, sketching or packing code can iterate over the runs
+/// instead of resetting on ambiguous bases itself. Empty runs are never
+/// yielded, including for `k = 0`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::split_on_ambiguous;
+///
+/// let runs: Vec<_> = split_on_ambiguous(b"ACGTNACNNGTTACRA", 3).collect();
+/// assert_eq!(runs, vec![(0, b"ACGT".as_slice()), (9, b"GTTAC".as_slice())]);
+/// ```
+pub fn split_on_ambiguous(seq: &[u8], k: usize) -> AmbiguousSplit<'_> {
+    AmbiguousSplit {
+        seq,
+        pos: 0,
+        min_len: k.max(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(AmbigPolicy::Skip)]
+    #[case(AmbigPolicy::Error)]
+    #[case(AmbigPolicy::MapToN)]
+    #[case(AmbigPolicy::Random { seed: 0 })]
+    fn test_unambiguous_is_borrowed(#[case] policy: AmbigPolicy) {
+        let seq = b"ACGTacguU";
+        assert!(matches!(
+            apply_ambig_policy(seq, policy).unwrap(),
+            Cow::Borrowed(s) if s == seq
+        ));
+    }
+
+    #[test]
+    fn test_error_position() {
+        match apply_ambig_policy(b"ACGTYA", AmbigPolicy::Error) {
+            Err(BioError::InvalidSymbolError { pos, symbol, .. }) => {
+                assert_eq!((pos, symbol), (4, 'Y'))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_map_to_n() {
+        assert_eq!(
+            &*apply_ambig_policy(b"AcRy-N", AmbigPolicy::MapToN).unwrap(),
+            b"AcNNNN"
+        );
+    }
+
+    #[test]
+    fn test_random_compatible() {
+        let seq = b"RYSWKMBDHVNnr".repeat(50);
+        let resolved = apply_ambig_policy(&seq, AmbigPolicy::Random { seed: 7 }).unwrap();
+
+        assert_eq!(resolved.len(), seq.len());
+        for (nt, base) in seq.iter().zip(resolved.iter()) {
+            assert!(iupac_bases(*nt).contains(&base.to_ascii_uppercase()));
+            assert_eq!(nt.is_ascii_lowercase(), base.is_ascii_lowercase());
+        }
+        assert_eq!(
+            resolved,
+            apply_ambig_policy(&seq, AmbigPolicy::Random { seed: 7 }).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(b"", 1, vec![])]
+    #[case(b"NNN", 1, vec![])]
+    #[case(b"ACGT", 0, vec![(0, 4)])]
+    #[case(b"ACGT", 5, vec![])]
+    #[case(b"NacgNNtuAR", 2, vec![(1, 4), (6, 9)])]
+    #[case(b"ANCNGNT", 1, vec![(0, 1), (2, 3), (4, 5), (6, 7)])]
+    fn test_split_on_ambiguous(
+        #[case] seq: &[u8],
+        #[case] k: usize,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        let runs: Vec<(usize, usize)> = split_on_ambiguous(seq, k)
+            .map(|(start, run)| {
+                assert_eq!(run, &seq[start..start + run.len()]);
+                (start, start + run.len())
+            })
+            .collect();
+        assert_eq!(runs, expected);
+    }
+}
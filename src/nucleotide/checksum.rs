@@ -0,0 +1,102 @@
+use md5::Md5;
+use sha2::{Digest, Sha512};
+
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Checksum algorithm for [`sequence_checksum`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// Lowercase hex MD5, as used by refget and the SAM `M5` tag.
+    Md5,
+    /// Lowercase hex of the first 24 bytes of SHA-512 (refget v1 `trunc512`).
+    Trunc512,
+    /// GA4GH sequence identifier: `SQ.` followed by the base64url encoded
+    /// first 24 bytes of SHA-512 (refget v2 `sha512t24u`).
+    Ga4gh,
+}
+
+/// Encodes `bytes` as hex.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Encodes `bytes` as unpadded base64url. `bytes.len()` must be a multiple of 3.
+fn to_base64_url(bytes: &[u8]) -> String {
+    bytes
+        .chunks_exact(3)
+        .flat_map(|c| {
+            let n = (c[0] as u32) << 16 | (c[1] as u32) << 8 | c[2] as u32;
+            [18, 12, 6, 0].map(|shift| BASE64_URL[(n >> shift & 0x3f) as usize] as char)
+        })
+        .collect()
+}
+
+/// Normalizes a sequence for checksumming: drops every byte that is not an
+/// ASCII letter (gaps, whitespace, `*`) and uppercases the rest.
+pub fn normalize_for_checksum(seq: &[u8]) -> Vec<u8> {
+    seq.iter()
+        .filter(|b| b.is_ascii_alphabetic())
+        .map(|b| b.to_ascii_uppercase())
+        .collect()
+}
+
+/// Computes a registry-compatible checksum of `seq` after [`normalize_for_checksum`].
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{ChecksumAlgorithm, sequence_checksum};
+///
+/// assert_eq!(
+///     sequence_checksum(b"ac-gt", ChecksumAlgorithm::Ga4gh),
+///     "SQ.aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2"
+/// );
+/// ```
+pub fn sequence_checksum(seq: &[u8], algo: ChecksumAlgorithm) -> String {
+    let normalized = normalize_for_checksum(seq);
+
+    match algo {
+        ChecksumAlgorithm::Md5 => to_hex(&Md5::digest(&normalized)),
+        ChecksumAlgorithm::Trunc512 => to_hex(&Sha512::digest(&normalized)[..24]),
+        ChecksumAlgorithm::Ga4gh => {
+            format!("SQ.{}", to_base64_url(&Sha512::digest(&normalized)[..24]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"ACGT", ChecksumAlgorithm::Md5, "f1f8f4bf413b16ad135722aa4591043e")]
+    #[case(
+        b"ACGT",
+        ChecksumAlgorithm::Trunc512,
+        "68a178f7c740c5c240aa67ba41843b119d3bf9f8b0f0ac36"
+    )]
+    #[case(
+        b"ACGT",
+        ChecksumAlgorithm::Ga4gh,
+        "SQ.aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2"
+    )]
+    #[case(b"", ChecksumAlgorithm::Ga4gh, "SQ.z4PhNX7vuL3xVChQ1m2AB9Yg5AULVxXc")]
+    #[case(
+        b"a c.g-t\n",
+        ChecksumAlgorithm::Md5,
+        "f1f8f4bf413b16ad135722aa4591043e"
+    )]
+    fn test_sequence_checksum(
+        #[case] seq: &[u8],
+        #[case] algo: ChecksumAlgorithm,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(sequence_checksum(seq, algo), expected);
+    }
+
+    #[test]
+    fn test_normalize_for_checksum() {
+        assert_eq!(normalize_for_checksum(b"ac-GT*n "), b"ACGTN");
+    }
+}
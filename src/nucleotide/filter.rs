@@ -0,0 +1,224 @@
+use super::record_metrics::RecordMetricsBuilder;
+
+/// Reason a read was rejected by a [`ReadFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterReason {
+    TooShort,
+    TooLong,
+    LowQuality,
+    LowComplexity,
+    TooManyN,
+}
+
+/// Ready-made [`ReadFilter`] thresholds for common workflows.
+///
+/// | Preset             | Length      | Mean Phred | Entropy (bits) | N fraction |
+/// |--------------------|-------------|------------|----------------|------------|
+/// | `OntAmplicon`      | 100-10000   | 10         | 1.0            | 0.05       |
+/// | `OntWgs`           | ≥ 1000      | 10         | 1.0            | 0.05       |
+/// | `PacBioHifi`       | ≥ 1000      | 20         | 1.0            | 0.01       |
+/// | `IlluminaAmplicon` | ≥ 50        | 20         | -              | 0.05       |
+/// | `IlluminaWgs`      | ≥ 50        | 20         | 1.0            | 0.05       |
+///
+/// Amplicon presets bound the length to drop concatemers, and the Illumina
+/// amplicon preset does not filter on entropy since amplicons can be low
+/// complexity by design.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterPreset {
+    OntAmplicon,
+    OntWgs,
+    PacBioHifi,
+    IlluminaAmplicon,
+    IlluminaWgs,
+}
+
+/// Length, quality and complexity thresholds for single reads.
+///
+/// Unset thresholds are not checked, so the default filter passes every
+/// read. Start from a [`FilterPreset`] and override fields as needed.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{FilterPreset, FilterReason, ReadFilter};
+///
+/// let filter = ReadFilter {
+///     min_len: Some(4),
+///     ..ReadFilter::from(FilterPreset::IlluminaWgs)
+/// };
+///
+/// assert!(filter.passes(b"ACGTAC", Some(b"IIIIII")));
+/// assert_eq!(filter.rejection(b"ACG", Some(b"III")), Some(FilterReason::TooShort));
+/// assert_eq!(filter.rejection(b"ACGTAC", Some(b"++++++")), Some(FilterReason::LowQuality));
+/// assert_eq!(filter.rejection(b"GGGGGG", Some(b"IIIIII")), Some(FilterReason::LowComplexity));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReadFilter {
+    pub min_len: Option<usize>,
+    pub max_len: Option<usize>,
+    /// Minimum Phred score of the mean error probability. Not checked for
+    /// reads without qualities.
+    pub min_mean_phred: Option<u8>,
+    /// Minimum Shannon entropy (bits) of the `ACGT` composition.
+    pub min_entropy: Option<f32>,
+    /// Maximum fraction of `N`/`n` bases.
+    pub max_n_fraction: Option<f64>,
+}
+
+impl From<FilterPreset> for ReadFilter {
+    fn from(preset: FilterPreset) -> Self {
+        match preset {
+            FilterPreset::OntAmplicon => Self {
+                min_len: Some(100),
+                max_len: Some(10_000),
+                min_mean_phred: Some(10),
+                min_entropy: Some(1.0),
+                max_n_fraction: Some(0.05),
+            },
+            FilterPreset::OntWgs => Self {
+                min_len: Some(1000),
+                max_len: None,
+                min_mean_phred: Some(10),
+                min_entropy: Some(1.0),
+                max_n_fraction: Some(0.05),
+            },
+            FilterPreset::PacBioHifi => Self {
+                min_len: Some(1000),
+                max_len: None,
+                min_mean_phred: Some(20),
+                min_entropy: Some(1.0),
+                max_n_fraction: Some(0.01),
+            },
+            FilterPreset::IlluminaAmplicon => Self {
+                min_len: Some(50),
+                max_len: None,
+                min_mean_phred: Some(20),
+                min_entropy: None,
+                max_n_fraction: Some(0.05),
+            },
+            FilterPreset::IlluminaWgs => Self {
+                min_len: Some(50),
+                max_len: None,
+                min_mean_phred: Some(20),
+                min_entropy: Some(1.0),
+                max_n_fraction: Some(0.05),
+            },
+        }
+    }
+}
+
+impl ReadFilter {
+    /// Metrics needed by the configured thresholds.
+    fn metrics_builder(&self) -> RecordMetricsBuilder {
+        let mut builder = RecordMetricsBuilder::new();
+        if self.min_mean_phred.is_some() {
+            builder = builder.quality();
+        }
+        if self.min_entropy.is_some() {
+            builder = builder.entropy();
+        }
+        if self.max_n_fraction.is_some() {
+            builder = builder.n_count();
+        }
+
+        builder
+    }
+
+    /// Returns the first threshold `seq` violates, checked in the order of
+    /// [`FilterReason`], or `None` if the read passes.
+    ///
+    /// `qual` is assumed to be Phred+33 encoded. Only the metrics needed by
+    /// the configured thresholds are computed.
+    pub fn rejection(&self, seq: &[u8], qual: Option<&[u8]>) -> Option<FilterReason> {
+        if self.min_len.is_some_and(|min| seq.len() < min) {
+            return Some(FilterReason::TooShort);
+        }
+        if self.max_len.is_some_and(|max| seq.len() > max) {
+            return Some(FilterReason::TooLong);
+        }
+
+        let metrics = self.metrics_builder().compute(seq, qual);
+
+        if let (Some(min), Some(phred)) = (self.min_mean_phred, metrics.mean_phred)
+            && phred < min
+        {
+            return Some(FilterReason::LowQuality);
+        }
+        if let (Some(min), Some(entropy)) = (self.min_entropy, metrics.entropy)
+            && entropy < min
+        {
+            return Some(FilterReason::LowComplexity);
+        }
+        if let (Some(max), Some(n_count)) = (self.max_n_fraction, metrics.n_count)
+            && !seq.is_empty()
+            && n_count as f64 / seq.len() as f64 > max
+        {
+            return Some(FilterReason::TooManyN);
+        }
+
+        None
+    }
+
+    /// Whether `seq` passes every configured threshold.
+    pub fn passes(&self, seq: &[u8], qual: Option<&[u8]>) -> bool {
+        self.rejection(seq, qual).is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"ACGTACGTAC", Some(b"IIIIIIIIII".as_slice()), None)]
+    #[case(b"ACGTACGTAC", None, None)]
+    #[case(b"ACGTA", None, Some(FilterReason::TooShort))]
+    #[case(b"ACGTACGTACGTACGTACGTA", None, Some(FilterReason::TooLong))]
+    #[case(b"ACGTACGTAC", Some(b"++++++++++".as_slice()), Some(FilterReason::LowQuality))]
+    #[case(b"ATATATATAT", None, Some(FilterReason::LowComplexity))]
+    #[case(b"ACGTNNNNAC", None, Some(FilterReason::TooManyN))]
+    // Length is checked before quality.
+    #[case(b"ACG", Some(b"+++".as_slice()), Some(FilterReason::TooShort))]
+    fn test_rejection(
+        #[case] seq: &[u8],
+        #[case] qual: Option<&[u8]>,
+        #[case] expected: Option<FilterReason>,
+    ) {
+        let filter = ReadFilter {
+            min_len: Some(8),
+            max_len: Some(20),
+            min_mean_phred: Some(20),
+            min_entropy: Some(1.5),
+            max_n_fraction: Some(0.2),
+        };
+
+        assert_eq!(filter.rejection(seq, qual), expected);
+        assert_eq!(filter.passes(seq, qual), expected.is_none());
+    }
+
+    #[test]
+    fn test_default_passes_everything() {
+        assert!(ReadFilter::default().passes(b"", Some(b"")));
+        assert!(ReadFilter::default().passes(b"NNNN", Some(b"!!!!")));
+    }
+
+    #[rstest]
+    #[case(FilterPreset::OntAmplicon, 1500, 12, true)]
+    #[case(FilterPreset::OntAmplicon, 20_000, 12, false)]
+    #[case(FilterPreset::OntWgs, 500, 12, false)]
+    #[case(FilterPreset::PacBioHifi, 5000, 15, false)]
+    #[case(FilterPreset::IlluminaWgs, 150, 30, true)]
+    #[case(FilterPreset::IlluminaWgs, 150, 15, false)]
+    fn test_presets(
+        #[case] preset: FilterPreset,
+        #[case] len: usize,
+        #[case] phred: u8,
+        #[case] expected: bool,
+    ) {
+        let seq: Vec<u8> = b"ACGT".iter().copied().cycle().take(len).collect();
+        let qual = vec![phred + 33; len];
+
+        assert_eq!(ReadFilter::from(preset).passes(&seq, Some(&qual)), expected);
+    }
+}
@@ -1,4 +1,15 @@
+use super::statics::NT_LOOKUP;
+use crate::alphabet::Alphabet;
 use crate::errors::BioError;
+use std::io::Write;
+
+#[cfg(feature = "io")]
+use crate::io::bio_fasta_reader_from;
+#[cfg(feature = "io")]
+use std::io::Read;
+
+/// Maximum value of the BED score column.
+const BED_MAX_SCORE: usize = 1000;
 
 /// Checks whether the region for seq: &[u8] at `[i, j)` qualifies as a homopolymer.
 #[inline]
@@ -66,6 +77,213 @@ pub fn find_homopolymers(
     Ok(hps)
 }
 
+/// Configures [`find_homopolymers_with`].
+///
+/// Minimum lengths are set per base, since e.g. polishers handle GC and AT
+/// homopolymers differently. Only `ACGT` runs are considered (`U` counts as
+/// `T`); runs of `N` or other symbols are never reported.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{HomopolymerOptions, find_homopolymers_with};
+///
+/// let options = HomopolymerOptions::new(4).min_len_gc(3).max_len(6);
+/// let hps = find_homopolymers_with(b"GGGTAAATTTTTTTTCCCC", &options);
+/// assert_eq!(hps, vec![(0, 3, b'G', 3), (15, 19, b'C', 4)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomopolymerOptions {
+    /// Minimum run length for `A`, `C`, `G` and `T`.
+    min_len: [usize; 4],
+    max_len: Option<usize>,
+    bases: [bool; 4],
+    include_softmask: bool,
+}
+
+impl HomopolymerOptions {
+    /// Reports runs of any `ACGT` base at least `min_len` long, including
+    /// softmasked runs. A `min_len` of `0` is treated as `1`.
+    pub fn new(min_len: usize) -> Self {
+        Self {
+            min_len: [min_len.max(1); 4],
+            max_len: None,
+            bases: [true; 4],
+            include_softmask: true,
+        }
+    }
+
+    /// Sets the minimum run length of `base` (case insensitive).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidSymbolError`] if `base` is not one of `ACGTU`.
+    pub fn min_len_for(mut self, base: u8, min_len: usize) -> Result<Self, BioError> {
+        self.min_len[base_index(base, 0)?] = min_len.max(1);
+        Ok(self)
+    }
+
+    /// Sets the minimum run length of `G` and `C`.
+    pub fn min_len_gc(mut self, min_len: usize) -> Self {
+        self.min_len[1] = min_len.max(1);
+        self.min_len[2] = min_len.max(1);
+        self
+    }
+
+    /// Sets the minimum run length of `A` and `T`.
+    pub fn min_len_at(mut self, min_len: usize) -> Self {
+        self.min_len[0] = min_len.max(1);
+        self.min_len[3] = min_len.max(1);
+        self
+    }
+
+    /// Skips runs longer than `max_len`, e.g. to leave long low-complexity
+    /// stretches to a dedicated masker.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Restricts reporting to runs of the bases in `bases` (case insensitive).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidSymbolError`] if `bases` contains anything
+    /// other than `ACGTU`.
+    pub fn bases(mut self, bases: &[u8]) -> Result<Self, BioError> {
+        self.bases = [false; 4];
+        for (pos, nt) in bases.iter().enumerate() {
+            self.bases[base_index(*nt, pos)?] = true;
+        }
+
+        Ok(self)
+    }
+
+    /// Whether to report lowercase runs, `true` by default.
+    pub fn include_softmask(mut self, include_softmask: bool) -> Self {
+        self.include_softmask = include_softmask;
+        self
+    }
+}
+
+fn base_index(nt: u8, pos: usize) -> Result<usize, BioError> {
+    match NT_LOOKUP[nt as usize] {
+        idx @ 0..=3 => Ok(idx as usize),
+        _ => Err(BioError::InvalidSymbolError {
+            pos,
+            symbol: nt as char,
+            alphabet: Alphabet::Dna.as_str(),
+        }),
+    }
+}
+
+/// Finds homopolymer regions as configured by `options`.
+///
+/// Returns `(start, end, nucleotide, length)` tuples like
+/// [`find_homopolymers`], and is case sensitive in the same way.
+pub fn find_homopolymers_with(
+    seq: &[u8],
+    options: &HomopolymerOptions,
+) -> Vec<(usize, usize, u8, usize)> {
+    let mut hps: Vec<(usize, usize, u8, usize)> = Vec::new();
+    let mut i = 0;
+
+    while i < seq.len() {
+        let nt = seq[i];
+        let mut j = i + 1;
+        while j < seq.len() && seq[j] == nt {
+            j += 1;
+        }
+
+        let len = j - i;
+        let idx = NT_LOOKUP[nt as usize] as usize;
+
+        let reported = idx < 4
+            && options.bases[idx]
+            && len >= options.min_len[idx]
+            && options.max_len.is_none_or(|max_len| len <= max_len)
+            && (options.include_softmask || nt.is_ascii_uppercase());
+
+        if reported {
+            hps.push((i, j, nt, len));
+        }
+
+        i = j;
+    }
+
+    hps
+}
+
+/// Writes the homopolymers of `seq` found by [`find_homopolymers_with`] as
+/// BED6 lines for `chrom`.
+///
+/// The name column holds the nucleotide and the score column the run length,
+/// capped at the BED maximum of 1000. Strand is always `.`. Returns the number
+/// of lines written.
+///
+/// # Errors
+///
+/// Returns [`BioError::IoError`] if writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{HomopolymerOptions, write_homopolymer_bed};
+///
+/// let mut buf = Vec::new();
+/// let options = HomopolymerOptions::new(3);
+/// write_homopolymer_bed(&mut buf, "chr1", b"ACGGGT", &options).unwrap();
+/// assert_eq!(buf, b"chr1\t2\t5\tG\t3\t.\n");
+/// ```
+pub fn write_homopolymer_bed<W: Write + ?Sized>(
+    writer: &mut W,
+    chrom: &str,
+    seq: &[u8],
+    options: &HomopolymerOptions,
+) -> Result<usize, BioError> {
+    let hps = find_homopolymers_with(seq, options);
+
+    for (start, end, nt, len) in &hps {
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}\t.",
+            chrom,
+            start,
+            end,
+            *nt as char,
+            (*len).min(BED_MAX_SCORE)
+        )?;
+    }
+
+    Ok(hps.len())
+}
+
+/// Writes the homopolymers of every record in a FASTA stream (plain or gzip)
+/// as BED6, see [`write_homopolymer_bed`], e.g. as a mask for variant calling.
+///
+/// The record ID is used as the chromosome name. Returns the total number of
+/// lines written.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the input cannot be read or parsed, or writing fails.
+#[cfg(feature = "io")]
+pub fn homopolymer_bed<R: Read + Send, W: Write + ?Sized>(
+    reader: R,
+    writer: &mut W,
+    options: &HomopolymerOptions,
+) -> Result<usize, BioError> {
+    let mut written = 0;
+
+    for record in bio_fasta_reader_from(reader)?.records() {
+        let record = record?;
+        written += write_homopolymer_bed(writer, record.id(), record.seq(), options)?;
+    }
+
+    writer.flush()?;
+    Ok(written)
+}
+
 /// Inspired by https://github.com/bluenote-1577/myloasm/blob/main/src/kmer_comp.rs#L22
 ///
 /// Applies hard homopolymer compression for a given input nt sequence.
@@ -99,6 +317,7 @@ pub fn homopolymer_compression(seq: &[u8]) -> Vec<u8> {
 ///
 /// TODO - try find an empirical value for the capacity for c_seq.
 /// Query some genomes from NCBI and calculate before/after to find a suitable empirical value.
+#[allow(dead_code)] // Only used in tests until it is exposed.
 fn homopolymer_compression_soft(seq: &[u8], max_len: usize) -> Vec<u8> {
     let mut hp_comp: Vec<u8> = Vec::new();
 
@@ -129,6 +348,100 @@ fn homopolymer_compression_soft(seq: &[u8], max_len: usize) -> Vec<u8> {
     hp_comp
 }
 
+/// A tandem repeat as `(start, end, motif, copies)`.
+pub type TandemRepeat = (usize, usize, Vec<u8>, usize);
+
+/// Returns `true` if `motif` is not itself a repeat of a shorter unit (e.g. `AT` but not `ATAT`).
+#[inline]
+fn is_primitive_motif(motif: &[u8]) -> bool {
+    let len = motif.len();
+
+    !(1..len)
+        .filter(|p| len.is_multiple_of(*p))
+        .any(|p| motif.chunks_exact(p).all(|chunk| chunk == &motif[..p]))
+}
+
+/// Finds short tandem repeats (microsatellites) in a DNA sequence.
+///
+/// Scans every motif length in `min_period..=max_period` and returns a list of
+/// `(start, end, motif, copies)` tuples for every run of at least `min_copies`
+/// complete copies of a motif. Coordinates are zero-based half-open intervals
+/// `[start, end)` spanning the complete copies only. Motifs that are repeats of
+/// a shorter unit (e.g. `ATAT`) or contain ambiguous bases are skipped, so a
+/// repeat is only reported at its smallest period. Homopolymers are the
+/// `period = 1` case. Results are sorted by start, then by motif length.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_period` is `0`,
+/// `min_period > max_period` or `min_copies < 2`.
+///
+/// NOTE - is case sensitive, like [`find_homopolymers`].
+pub fn find_tandem_repeats(
+    seq: &[u8],
+    min_period: usize,
+    max_period: usize,
+    min_copies: usize,
+) -> Result<Vec<TandemRepeat>, BioError> {
+    if min_period == 0 || min_period > max_period {
+        return Err(BioError::InvalidParameterError(format!(
+            "invalid motif length range {}-{}.",
+            min_period, max_period
+        )));
+    }
+
+    if min_copies < 2 {
+        return Err(BioError::InvalidParameterError(format!(
+            "min copies {} must be at least 2.",
+            min_copies
+        )));
+    }
+
+    let mut repeats: Vec<TandemRepeat> = Vec::new();
+    let seq_len = seq.len();
+
+    for period in min_period..=max_period {
+        let min_span = period * min_copies;
+        if seq_len < min_span {
+            break;
+        }
+
+        let mut i = 0;
+        while i + min_span <= seq_len {
+            // Extend while each base equals the one a full period earlier.
+            let mut j = i + period;
+            while j < seq_len && seq[j] == seq[j - period] {
+                j += 1;
+            }
+
+            let copies = (j - i) / period;
+            let motif = &seq[i..i + period];
+
+            let valid_motif =
+                motif.iter().all(|nt| NT_LOOKUP[*nt as usize] < 4) && is_primitive_motif(motif);
+
+            if copies >= min_copies && valid_motif {
+                let end = i + copies * period;
+                repeats.push((i, end, motif.to_vec(), copies));
+                i = end;
+                continue;
+            }
+
+            // Every later start with at least `min_copies` copies left in this
+            // run has a rotation of the same motif, which is just as invalid.
+            if copies >= min_copies {
+                i = j - min_span + 1;
+                continue;
+            }
+
+            i += 1;
+        }
+    }
+
+    repeats.sort_by_key(|(start, _, motif, _)| (*start, motif.len()));
+    Ok(repeats)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +463,59 @@ mod tests {
         assert_eq!(hps, expected);
     }
 
+    #[rstest]
+    #[case(HomopolymerOptions::new(4), vec![(3, 7, b'A', 4), (7, 12, b'T', 5)])]
+    #[case(HomopolymerOptions::new(4).min_len_gc(3), vec![(0, 3, b'G', 3), (3, 7, b'A', 4), (7, 12, b'T', 5), (16, 19, b'c', 3)])]
+    #[case(HomopolymerOptions::new(3).max_len(4), vec![(0, 3, b'G', 3), (3, 7, b'A', 4), (16, 19, b'c', 3)])]
+    #[case(HomopolymerOptions::new(3).bases(b"gc").unwrap(), vec![(0, 3, b'G', 3), (16, 19, b'c', 3)])]
+    #[case(HomopolymerOptions::new(3).include_softmask(false), vec![(0, 3, b'G', 3), (3, 7, b'A', 4), (7, 12, b'T', 5)])]
+    #[case(HomopolymerOptions::new(2).min_len_for(b'u', 6).unwrap().min_len_at(5), vec![(0, 3, b'G', 3), (7, 12, b'T', 5), (16, 19, b'c', 3)])]
+    fn test_find_homopolymers_with(
+        #[case] options: HomopolymerOptions,
+        #[case] expected: Vec<(usize, usize, u8, usize)>,
+    ) {
+        assert_eq!(
+            find_homopolymers_with(b"GGGAAAATTTTTNNNNccc", &options),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_homopolymer_options_invalid() {
+        assert!(HomopolymerOptions::new(3).bases(b"GCN").is_err());
+        assert!(HomopolymerOptions::new(3).min_len_for(b'N', 2).is_err());
+    }
+
+    #[test]
+    fn test_write_homopolymer_bed() {
+        let mut buf = Vec::new();
+        let options = HomopolymerOptions::new(4).min_len_gc(3);
+        let seq = [b"ACCCGT".as_slice(), &[b'A'; 1200]].concat();
+
+        assert_eq!(
+            write_homopolymer_bed(&mut buf, "chr2", &seq, &options).unwrap(),
+            2
+        );
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "chr2\t1\t4\tC\t3\t.\nchr2\t6\t1206\tA\t1000\t.\n"
+        );
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn test_homopolymer_bed() {
+        let fasta = b">s1 desc\nAAAACG\n>s2\nCGTTTT\n>s3\nACGT\n";
+        let mut buf = Vec::new();
+
+        let written = homopolymer_bed(&fasta[..], &mut buf, &HomopolymerOptions::new(4)).unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "s1\t0\t4\tA\t4\t.\ns2\t2\t6\tT\t4\t.\n"
+        );
+    }
+
     #[rstest]
     #[case(b"", b"")]
     #[case(b"T", b"T")]
@@ -157,7 +523,7 @@ mod tests {
     #[case(b"CCCGTTT", b"CGT")]
     #[case(b"AAANNGGT", b"ANGT")]
     fn test_homopolymer_compression(#[case] seq: &[u8], #[case] expected: &[u8]) {
-        let c_seq = homopolymer_compression(&seq[..]);
+        let c_seq = homopolymer_compression(seq);
         assert_eq!(&c_seq[..], expected);
     }
 
@@ -175,4 +541,52 @@ mod tests {
         let hp_comp = homopolymer_compression_soft(seq, max_len);
         assert_eq!(&hp_comp[..], expected);
     }
+
+    #[rstest]
+    #[case(b"", 2, 6, 3, vec![])]
+    #[case(b"ACGTACGT", 2, 6, 3, vec![])]
+    #[case(b"GGCACACACAGG", 2, 6, 3, vec![(2, 10, b"CA".to_vec(), 4)])]
+    #[case(b"TTAGGTTAGGTTAGGC", 2, 6, 3, vec![(0, 15, b"TTAGG".to_vec(), 3)])]
+    #[case(b"ATATATAT", 2, 6, 2, vec![(0, 8, b"AT".to_vec(), 4)])]
+    #[case(b"AAAAAA", 1, 2, 3, vec![(0, 6, b"A".to_vec(), 6)])]
+    #[case(b"AAAAAA", 2, 3, 2, vec![])]
+    #[case(b"NANANANA", 2, 2, 2, vec![])]
+    #[case(b"CAGCAGCAGTTCTTCTTC", 3, 3, 3, vec![(0, 9, b"CAG".to_vec(), 3), (9, 18, b"TTC".to_vec(), 3)])]
+    fn test_find_tandem_repeats(
+        #[case] seq: &[u8],
+        #[case] min_period: usize,
+        #[case] max_period: usize,
+        #[case] min_copies: usize,
+        #[case] expected: Vec<TandemRepeat>,
+    ) {
+        let repeats = find_tandem_repeats(seq, min_period, max_period, min_copies)
+            .expect("expected valid result");
+        assert_eq!(repeats, expected);
+    }
+
+    #[rstest]
+    #[case(b"A", vec![(0, 100_000, b"A".to_vec(), 100_000)])]
+    #[case(b"AT", vec![(0, 100_000, b"AT".to_vec(), 50_000)])]
+    #[case(b"N", vec![])]
+    fn test_find_tandem_repeats_long_run(#[case] unit: &[u8], #[case] expected: Vec<TandemRepeat>) {
+        let seq = unit.repeat(100_000 / unit.len());
+
+        let start = std::time::Instant::now();
+        let repeats = find_tandem_repeats(&seq, 1, 6, 3).expect("expected valid result");
+
+        assert_eq!(repeats, expected);
+        assert!(start.elapsed().as_secs() < 2);
+    }
+
+    #[rstest]
+    #[case(0, 6, 3)]
+    #[case(4, 2, 3)]
+    #[case(2, 6, 1)]
+    fn test_find_tandem_repeats_invalid(
+        #[case] min_period: usize,
+        #[case] max_period: usize,
+        #[case] min_copies: usize,
+    ) {
+        assert!(find_tandem_repeats(b"ACACAC", min_period, max_period, min_copies).is_err());
+    }
 }
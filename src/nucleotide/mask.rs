@@ -0,0 +1,118 @@
+use crate::errors::BioError;
+
+/// Returns `seq` with all bases uppercased, removing softmasking.
+#[inline]
+pub fn to_uppercase_seq(seq: &[u8]) -> Vec<u8> {
+    seq.to_ascii_uppercase()
+}
+
+/// Checks that every `[start, end)` interval lies within a sequence of length `len`.
+fn validate_intervals(len: usize, intervals: &[(usize, usize)]) -> Result<(), BioError> {
+    for &(start, end) in intervals {
+        if start > end || end > len {
+            return Err(BioError::InvalidParameterError(format!(
+                "interval [{}, {}) is invalid for sequence of length {}.",
+                start, end, len
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies `f` to every base covered by `intervals` in a copy of `seq`.
+fn mask_with<F: Fn(u8) -> u8>(
+    seq: &[u8],
+    intervals: &[(usize, usize)],
+    f: F,
+) -> Result<Vec<u8>, BioError> {
+    validate_intervals(seq.len(), intervals)?;
+
+    let mut masked = seq.to_vec();
+    for &(start, end) in intervals {
+        masked[start..end].iter_mut().for_each(|nt| *nt = f(*nt));
+    }
+
+    Ok(masked)
+}
+
+/// Softmasks (lowercases) the bases in BED-style `[start, end)` intervals.
+///
+/// Intervals may overlap and need not be sorted, so the `(start, end)` of
+/// regions reported by detectors such as [`find_homopolymers`](super::find_homopolymers)
+/// or [`find_n_runs`](super::find_n_runs) can be used as is.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if an interval has
+/// `start > end` or extends past the end of `seq`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::softmask_intervals;
+///
+/// let masked = softmask_intervals(b"ACGTACGT", &[(1, 3), (6, 8)]).unwrap();
+/// assert_eq!(masked, b"AcgTACgt");
+/// ```
+pub fn softmask_intervals(seq: &[u8], intervals: &[(usize, usize)]) -> Result<Vec<u8>, BioError> {
+    mask_with(seq, intervals, |nt| nt.to_ascii_lowercase())
+}
+
+/// Replaces the bases in BED-style `[start, end)` intervals with `mask`, e.g. `b'N'`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if an interval has
+/// `start > end` or extends past the end of `seq`.
+pub fn hardmask_intervals(
+    seq: &[u8],
+    intervals: &[(usize, usize)],
+    mask: u8,
+) -> Result<Vec<u8>, BioError> {
+    mask_with(seq, intervals, |_| mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_to_uppercase_seq() {
+        assert_eq!(to_uppercase_seq(b"acGTn"), b"ACGTN");
+    }
+
+    #[rstest]
+    #[case(b"ACGTACGT", vec![], b"ACGTACGT")]
+    #[case(b"ACGTACGT", vec![(0, 8)], b"acgtacgt")]
+    #[case(b"ACGTACGT", vec![(4, 6), (2, 5), (7, 7)], b"ACgtacGT")]
+    #[case(b"ACgtACGT", vec![(0, 1)], b"aCgtACGT")]
+    fn test_softmask_intervals(
+        #[case] seq: &[u8],
+        #[case] intervals: Vec<(usize, usize)>,
+        #[case] expected: &[u8],
+    ) {
+        assert_eq!(softmask_intervals(seq, &intervals).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(b"ACGTACGT", vec![(1, 3)], b'N', b"ANNTACGT")]
+    #[case(b"ACGTACGT", vec![(6, 8), (0, 1)], b'X', b"XCGTACXX")]
+    fn test_hardmask_intervals(
+        #[case] seq: &[u8],
+        #[case] intervals: Vec<(usize, usize)>,
+        #[case] mask: u8,
+        #[case] expected: &[u8],
+    ) {
+        assert_eq!(hardmask_intervals(seq, &intervals, mask).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(vec![(3, 2)])]
+    #[case(vec![(0, 9)])]
+    fn test_invalid_intervals(#[case] intervals: Vec<(usize, usize)>) {
+        assert!(softmask_intervals(b"ACGTACGT", &intervals).is_err());
+        assert!(hardmask_intervals(b"ACGTACGT", &intervals, b'N').is_err());
+    }
+}
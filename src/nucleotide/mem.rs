@@ -0,0 +1,230 @@
+use crate::errors::BioError;
+use std::collections::HashMap;
+
+/// A state in a suffix automaton.
+struct SamState {
+    len: usize,
+    link: Option<usize>,
+    /// End position (inclusive) of the first occurrence of this state in the text.
+    first_pos: usize,
+    next: HashMap<u8, usize>,
+}
+
+/// Builds a suffix automaton over `text` in `O(n)` amortized time.
+fn suffix_automaton(text: &[u8]) -> Vec<SamState> {
+    let mut states = Vec::with_capacity(text.len() * 2 + 1);
+    states.push(SamState {
+        len: 0,
+        link: None,
+        first_pos: 0,
+        next: HashMap::new(),
+    });
+
+    let mut last = 0;
+
+    for (pos, &c) in text.iter().enumerate() {
+        let cur = states.len();
+        states.push(SamState {
+            len: states[last].len + 1,
+            link: None,
+            first_pos: pos,
+            next: HashMap::new(),
+        });
+
+        let mut p = Some(last);
+        while let Some(pi) = p {
+            if states[pi].next.contains_key(&c) {
+                break;
+            }
+            states[pi].next.insert(c, cur);
+            p = states[pi].link;
+        }
+
+        match p {
+            None => states[cur].link = Some(0),
+            Some(pi) => {
+                let q = states[pi].next[&c];
+
+                if states[pi].len + 1 == states[q].len {
+                    states[cur].link = Some(q);
+                } else {
+                    let clone = states.len();
+                    states.push(SamState {
+                        len: states[pi].len + 1,
+                        link: states[q].link,
+                        first_pos: states[q].first_pos,
+                        next: states[q].next.clone(),
+                    });
+
+                    let mut p = Some(pi);
+                    while let Some(pi) = p {
+                        if states[pi].next.get(&c) != Some(&q) {
+                            break;
+                        }
+                        states[pi].next.insert(c, clone);
+                        p = states[pi].link;
+                    }
+
+                    states[q].link = Some(clone);
+                    states[cur].link = Some(clone);
+                }
+            }
+        }
+
+        last = cur;
+    }
+
+    states
+}
+
+/// Finds the longest common substring between `a` and `b` using a suffix automaton.
+///
+/// Returns `Some((a_start, b_start, len))` for the first longest shared substring
+/// found while scanning `b`, or `None` if the sequences share no bytes.
+/// Runs in `O(len(a) + len(b))` time.
+///
+/// NOTE - is case sensitive.
+pub fn longest_common_substring(a: &[u8], b: &[u8]) -> Option<(usize, usize, usize)> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+
+    let states = suffix_automaton(a);
+
+    let mut v = 0;
+    let mut l = 0;
+    let mut best: Option<(usize, usize, usize)> = None;
+
+    for (j, c) in b.iter().enumerate() {
+        while v != 0 && !states[v].next.contains_key(c) {
+            v = states[v].link.unwrap_or(0);
+            l = states[v].len;
+        }
+
+        if let Some(&next) = states[v].next.get(c) {
+            v = next;
+            l += 1;
+        }
+
+        if l > 0 && best.is_none_or(|(_, _, len)| l > len) {
+            let a_start = states[v].first_pos + 1 - l;
+            best = Some((a_start, j + 1 - l, l));
+        }
+    }
+
+    best
+}
+
+/// Finds maximal exact matches (MEMs) of at least `min_len` bases between `a` and `b`.
+///
+/// Seeds are exact `min_len`-mers from `a`, which are extended in both
+/// directions. Only matches that cannot be extended further to the left or right
+/// are reported, each exactly once. Returns a vec of `(a_start, b_start, len)`,
+/// sorted by `b_start` then `a_start`.
+///
+/// Highly repetitive sequences produce many MEMs; this is intended for quick
+/// overlap detection rather than whole-genome comparison.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_len` is `0`.
+///
+/// NOTE - is case sensitive.
+pub fn find_mems(
+    a: &[u8],
+    b: &[u8],
+    min_len: usize,
+) -> Result<Vec<(usize, usize, usize)>, BioError> {
+    if min_len == 0 {
+        return Err(BioError::InvalidParameterError(
+            "min MEM length must be larger than 0.".to_string(),
+        ));
+    }
+
+    let mut mems = Vec::new();
+
+    if a.len() < min_len || b.len() < min_len {
+        return Ok(mems);
+    }
+
+    let mut seeds: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (i, kmer) in a.windows(min_len).enumerate() {
+        seeds.entry(kmer).or_default().push(i);
+    }
+
+    for (j, kmer) in b.windows(min_len).enumerate() {
+        let Some(positions) = seeds.get(kmer) else {
+            continue;
+        };
+
+        for &i in positions {
+            // Not left-maximal, the MEM was already reported from an earlier seed.
+            if i > 0 && j > 0 && a[i - 1] == b[j - 1] {
+                continue;
+            }
+
+            let mut len = min_len;
+            while i + len < a.len() && j + len < b.len() && a[i + len] == b[j + len] {
+                len += 1;
+            }
+
+            mems.push((i, j, len));
+        }
+    }
+
+    mems.sort_by_key(|(a_start, b_start, _)| (*b_start, *a_start));
+    Ok(mems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"", b"ACGT", None)]
+    #[case(b"AAAA", b"CCCC", None)]
+    #[case(b"ACGT", b"ACGT", Some((0, 0, 4)))]
+    #[case(b"TTTACGTAA", b"GGACGTG", Some((3, 2, 4)))]
+    #[case(b"ACGTTTTTGCA", b"CCTTTTTGCAA", Some((3, 2, 8)))]
+    fn test_longest_common_substring(
+        #[case] a: &[u8],
+        #[case] b: &[u8],
+        #[case] expected: Option<(usize, usize, usize)>,
+    ) {
+        assert_eq!(longest_common_substring(a, b), expected);
+    }
+
+    #[rstest]
+    #[case(b"ACGT", b"TTTT", 3, vec![])]
+    #[case(b"AACCGGTT", b"CCGG", 3, vec![(2, 0, 4)])]
+    #[case(b"ACGTNNNNACGT", b"ACGT", 4, vec![(0, 0, 4), (8, 0, 4)])]
+    #[case(b"TTACGTAAGGCATT", b"ACGTCCGGCA", 4, vec![(2, 0, 4), (8, 6, 4)])]
+    fn test_find_mems(
+        #[case] a: &[u8],
+        #[case] b: &[u8],
+        #[case] min_len: usize,
+        #[case] expected: Vec<(usize, usize, usize)>,
+    ) {
+        assert_eq!(find_mems(a, b, min_len).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_find_mems_invalid() {
+        assert!(find_mems(b"ACGT", b"ACGT", 0).is_err());
+    }
+
+    #[test]
+    fn test_lcs_matches_mems() {
+        let a = b"GATTACAGATTACCAGGT";
+        let b = b"CCAGATTACCAGTT";
+        let (_, _, lcs_len) = longest_common_substring(a, b).unwrap();
+        let max_mem = find_mems(a, b, 3)
+            .unwrap()
+            .into_iter()
+            .map(|(_, _, len)| len)
+            .max()
+            .unwrap();
+        assert_eq!(lcs_len, max_mem);
+    }
+}
@@ -0,0 +1,487 @@
+use super::seq::reverse_complement;
+use super::statics::PHRED_OFFSET;
+use crate::errors::BioError;
+use bio::io::fastq;
+
+/// Highest Phred score assigned to a merged base, the Illumina 1.8+ maximum.
+const MAX_MERGED_PHRED: u8 = 41;
+
+/// Lowest Phred score assigned to a merged base where the reads disagree.
+const MIN_DISAGREE_PHRED: u8 = 2;
+
+/// Parameters for [`merge_pair`] and [`merge_pairs`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeParams {
+    /// Minimum number of overlapping bases.
+    pub min_overlap: usize,
+    /// Maximum fraction of mismatching bases in the overlap.
+    pub max_mismatch_rate: f64,
+    /// Whether to merge pairs whose insert is shorter than a read, so that the
+    /// read runs into adapter. The read-through is trimmed and only the
+    /// insert is kept.
+    pub allow_staggered: bool,
+}
+
+impl Default for MergeParams {
+    fn default() -> Self {
+        Self {
+            min_overlap: 10,
+            max_mismatch_rate: 0.1,
+            allow_staggered: true,
+        }
+    }
+}
+
+impl MergeParams {
+    fn validate(&self) -> Result<(), BioError> {
+        if self.min_overlap == 0 {
+            return Err(BioError::InvalidParameterError(
+                "minimum overlap must be greater than 0.".to_string(),
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&self.max_mismatch_rate) {
+            return Err(BioError::InvalidParameterError(format!(
+                "max mismatch rate must be in [0, 1], got {}.",
+                self.max_mismatch_rate
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// A read pair merged into a single read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergedPair {
+    pub seq: Vec<u8>,
+    /// Phred+33 encoded qualities.
+    pub qual: Vec<u8>,
+    /// Number of overlapping bases.
+    pub overlap: usize,
+    /// Number of mismatching bases in the overlap.
+    pub mismatches: usize,
+}
+
+/// Read pair merging statistics, reported by [`merge_pairs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeStats {
+    pub pairs: usize,
+    pub merged: usize,
+    /// Sum of overlap lengths over merged pairs.
+    pub total_overlap: usize,
+    /// Sum of mismatches over merged pairs.
+    pub total_mismatches: usize,
+}
+
+impl MergeStats {
+    /// Fraction of pairs merged, `0.0` if there are no pairs.
+    pub fn merge_rate(&self) -> f64 {
+        match self.pairs {
+            0 => 0.0,
+            pairs => self.merged as f64 / pairs as f64,
+        }
+    }
+
+    /// Mean overlap length of merged pairs, `0.0` if none were merged.
+    pub fn mean_overlap(&self) -> f64 {
+        match self.merged {
+            0 => 0.0,
+            merged => self.total_overlap as f64 / merged as f64,
+        }
+    }
+
+    fn add(&mut self, merged: Option<&MergedPair>) {
+        self.pairs += 1;
+
+        if let Some(merged) = merged {
+            self.merged += 1;
+            self.total_overlap += merged.overlap;
+            self.total_mismatches += merged.mismatches;
+        }
+    }
+}
+
+/// Counts mismatches between two equally long slices, ignoring `N`s and case.
+fn count_mismatches(a: &[u8], b: &[u8]) -> usize {
+    a.iter()
+        .zip(b)
+        .filter(|(x, y)| {
+            let (x, y) = (x.to_ascii_uppercase(), y.to_ascii_uppercase());
+            x != y && x != b'N' && y != b'N'
+        })
+        .count()
+}
+
+/// Consensus base and Phred+33 quality of two overlapping bases.
+///
+/// Agreeing bases get the sum of their Phred scores, capped at Q41. For
+/// disagreeing bases the higher quality base wins with the difference of
+/// the Phred scores (at least Q2), and an `N` defers to the other base.
+fn consensus(a: u8, qa: u8, b: u8, qb: u8) -> (u8, u8) {
+    let offset = PHRED_OFFSET as u8;
+    let (pa, pb) = (qa.saturating_sub(offset), qb.saturating_sub(offset));
+
+    let (base, phred) = match (a.to_ascii_uppercase(), b.to_ascii_uppercase()) {
+        (x, y) if x == y => (a, (pa + pb).min(MAX_MERGED_PHRED)),
+        (b'N', _) => (b, pb),
+        (_, b'N') => (a, pa),
+        _ => match pa >= pb {
+            true => (a, (pa - pb).max(MIN_DISAGREE_PHRED)),
+            false => (b, (pb - pa).max(MIN_DISAGREE_PHRED)),
+        },
+    };
+
+    (base, phred + offset)
+}
+
+/// Merges a read pair whose 3' ends overlap into a single read.
+///
+/// `r2` is reverse complemented and every placement relative to `r1` with at
+/// least `min_overlap` overlapping bases is scored. The placement with the
+/// lowest mismatch rate (ties broken by the longest overlap) is used if its
+/// rate is at most `max_mismatch_rate`. The merged read spans the insert,
+/// from the start of `r1` to the end of the reverse complemented `r2`; bases
+/// of either read outside it are adapter read-through and trimmed.
+/// Non-overlapping parts are copied from the reads, overlapping bases get a
+/// consensus base and recalculated quality. Returns `None` if no placement
+/// qualifies.
+///
+/// Qualities are Phred+33 encoded.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if a read's sequence and
+/// quality differ in length or `params` is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{MergeParams, merge_pair, reverse_complement};
+///
+/// // A 16 bp insert sequenced with 12 bp reads, overlapping by 8 bases.
+/// let insert = b"ACGTTGCAAGGCTTAC";
+/// let r1 = &insert[..12];
+/// let r2 = reverse_complement(&insert[4..]);
+///
+/// let params = MergeParams { min_overlap: 5, ..Default::default() };
+/// let merged = merge_pair(r1, &[b'I'; 12], &r2, &[b'I'; 12], &params).unwrap().unwrap();
+///
+/// assert_eq!(merged.seq, insert);
+/// assert_eq!(merged.overlap, 8);
+/// ```
+pub fn merge_pair(
+    r1_seq: &[u8],
+    r1_qual: &[u8],
+    r2_seq: &[u8],
+    r2_qual: &[u8],
+    params: &MergeParams,
+) -> Result<Option<MergedPair>, BioError> {
+    params.validate()?;
+
+    if r1_seq.len() != r1_qual.len() || r2_seq.len() != r2_qual.len() {
+        return Err(BioError::InvalidParameterError(
+            "read sequence and quality lengths differ.".to_string(),
+        ));
+    }
+
+    // `reverse_complement` maps soft-masked bases to `N`, which would match
+    // anything in `count_mismatches`.
+    let r2_seq = reverse_complement(&r2_seq.to_ascii_uppercase());
+    let r2_qual: Vec<u8> = r2_qual.iter().rev().copied().collect();
+    let (len1, len2) = (r1_seq.len() as isize, r2_seq.len() as isize);
+    let min_overlap = params.min_overlap as isize;
+
+    // `shift` is the position of the first reverse complemented r2 base on r1.
+    let min_shift = match params.allow_staggered {
+        true => min_overlap - len2,
+        false => 0,
+    };
+
+    // (mismatches, overlap, shift) of the best placement.
+    let mut best: Option<(usize, usize, isize)> = None;
+    for shift in min_shift..=len1 - min_overlap {
+        let start = shift.max(0);
+        let end = len1.min(shift + len2);
+        if end - start < min_overlap {
+            continue;
+        }
+
+        // R1 running past the end of R2 is read-through as well.
+        if !params.allow_staggered && shift + len2 < len1 {
+            continue;
+        }
+
+        let overlap = (end - start) as usize;
+        let mismatches = count_mismatches(
+            &r1_seq[start as usize..end as usize],
+            &r2_seq[(start - shift) as usize..(end - shift) as usize],
+        );
+
+        if mismatches as f64 > params.max_mismatch_rate * overlap as f64 {
+            continue;
+        }
+
+        // Compare mismatch rates without division: m1 / o1 < m2 / o2.
+        let better = match best {
+            None => true,
+            Some((m, o, _)) => {
+                let (lhs, rhs) = (mismatches * o, m * overlap);
+                lhs < rhs || (lhs == rhs && overlap > o)
+            }
+        };
+        if better {
+            best = Some((mismatches, overlap, shift));
+        }
+    }
+
+    let Some((mismatches, overlap, shift)) = best else {
+        return Ok(None);
+    };
+
+    // The insert starts with r1 and ends with the reverse complemented r2.
+    let end = shift + len2;
+
+    let mut seq = Vec::with_capacity(end as usize);
+    let mut qual = Vec::with_capacity(end as usize);
+    for i in 0..end {
+        let j = (i - shift) as usize;
+
+        let (base, q) = match (i < len1, i >= shift) {
+            (true, true) => consensus(
+                r1_seq[i as usize],
+                r1_qual[i as usize],
+                r2_seq[j],
+                r2_qual[j],
+            ),
+            (true, false) => (r1_seq[i as usize], r1_qual[i as usize]),
+            _ => (r2_seq[j], r2_qual[j]),
+        };
+
+        seq.push(base);
+        qual.push(q);
+    }
+
+    Ok(Some(MergedPair {
+        seq,
+        qual,
+        overlap,
+        mismatches,
+    }))
+}
+
+/// Output of [`merge_pairs`].
+#[derive(Debug, Clone, Default)]
+pub struct MergedPairs {
+    /// Merged reads, named after their R1 mate.
+    pub merged: Vec<fastq::Record>,
+    /// Pairs that could not be merged, unchanged.
+    pub unmerged: Vec<(fastq::Record, fastq::Record)>,
+    pub stats: MergeStats,
+}
+
+/// Merges read pairs with [`merge_pair`], as FLASH and PEAR do for amplicons.
+///
+/// `r1` and `r2` must hold the mates in the same order. Merged reads take the
+/// ID and description of the R1 mate; pairs that cannot be merged are
+/// returned unchanged. Both keep the input order.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `r1` and `r2` differ in
+/// length, or any error from [`merge_pair`].
+pub fn merge_pairs(
+    r1: &[fastq::Record],
+    r2: &[fastq::Record],
+    params: &MergeParams,
+) -> Result<MergedPairs, BioError> {
+    if r1.len() != r2.len() {
+        return Err(BioError::InvalidParameterError(format!(
+            "got {} R1 reads but {} R2 reads.",
+            r1.len(),
+            r2.len()
+        )));
+    }
+
+    let mut output = MergedPairs::default();
+
+    for (a, b) in r1.iter().zip(r2) {
+        let pair = merge_pair(a.seq(), a.qual(), b.seq(), b.qual(), params)?;
+        output.stats.add(pair.as_ref());
+
+        match pair {
+            Some(pair) => output.merged.push(fastq::Record::with_attrs(
+                a.id(),
+                a.desc(),
+                &pair.seq,
+                &pair.qual,
+            )),
+            None => output.unmerged.push((a.clone(), b.clone())),
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn params(min_overlap: usize) -> MergeParams {
+        MergeParams {
+            min_overlap,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_with_mismatch() {
+        let insert = b"ACGTTGCAAGGCTTACGGATCCAT";
+        let r1 = insert[..16].to_vec();
+        let mut r2 = reverse_complement(&insert[6..]);
+
+        // Low quality mismatch in R2 within the overlap, at insert position 10.
+        let r2_pos = r2.len() - 1 - (10 - 6);
+        r2[r2_pos] = b'T';
+        let mut r2_qual = vec![b'I'; r2.len()];
+        r2_qual[r2_pos] = b'#';
+
+        let merged = merge_pair(&r1, &[b'5'; 16], &r2, &r2_qual, &params(5))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(merged.seq, insert);
+        assert_eq!((merged.overlap, merged.mismatches), (10, 1));
+        // Q20 + Q40 agreeing bases cap at Q41, the mismatch keeps Q20 - Q2 = Q18.
+        assert_eq!(merged.qual[6], b'J');
+        assert_eq!(merged.qual[10], b'3');
+        assert_eq!(merged.qual[0], b'5');
+        assert_eq!(merged.qual[23], b'I');
+    }
+
+    #[rstest]
+    #[case(true, Some(b"GGCTTACG".to_vec()))]
+    #[case(false, None)]
+    fn test_merge_staggered(#[case] allow_staggered: bool, #[case] expected: Option<Vec<u8>>) {
+        // An 8 bp insert read through into 4 bp of adapter on each side.
+        let insert = b"GGCTTACG";
+        let r1 = [insert.as_slice(), b"AGAT"].concat();
+        let r2 = [reverse_complement(insert).as_slice(), b"CTGT"].concat();
+
+        let params = MergeParams {
+            min_overlap: 6,
+            allow_staggered,
+            ..Default::default()
+        };
+        let merged = merge_pair(&r1, &[b'I'; 12], &r2, &[b'I'; 12], &params).unwrap();
+
+        assert_eq!(merged.map(|m| m.seq), expected);
+    }
+
+    #[test]
+    fn test_merge_short_r1() {
+        // R1 covers only the start of a 20 bp insert, R2 reads into adapter.
+        let insert = b"ACGTTGCAAGGCTTACGGAT";
+        let r2 = [reverse_complement(insert).as_slice(), b"AG"].concat();
+        let merged = merge_pair(&insert[..8], &[b'I'; 8], &r2, &[b'I'; 22], &params(5))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(merged.seq, insert);
+        assert_eq!(merged.overlap, 8);
+    }
+
+    #[rstest]
+    #[case(true, Some(b"ACGTTGCAAGGCTTACGGAT".to_vec()))]
+    #[case(false, None)]
+    fn test_merge_r1_read_through(
+        #[case] allow_staggered: bool,
+        #[case] expected: Option<Vec<u8>>,
+    ) {
+        // R1 runs 10 bp into adapter past the end of the insert.
+        let insert = b"ACGTTGCAAGGCTTACGGAT";
+        let r1 = [insert.as_slice(), b"AGATCGGAAG"].concat();
+        let r2 = reverse_complement(&insert[10..]);
+
+        let params = MergeParams {
+            min_overlap: 5,
+            allow_staggered,
+            ..Default::default()
+        };
+        let merged = merge_pair(&r1, &[b'I'; 30], &r2, &[b'I'; 10], &params).unwrap();
+
+        assert_eq!(merged.map(|m| m.seq), expected);
+    }
+
+    #[test]
+    fn test_merge_no_overlap() {
+        let r1 = b"AAAAAAAAAACCCCCCCCCC";
+        let r2 = b"GTGTGTGTGTGTGTGTGTGT";
+        let merged = merge_pair(r1, &[b'I'; 20], r2, &[b'I'; 20], &params(10)).unwrap();
+        assert!(merged.is_none());
+    }
+
+    #[test]
+    fn test_merge_lowercase_r2() {
+        let insert = b"ACGTTGCAAGGCTTAC";
+        let r2 = reverse_complement(&insert[4..]).to_ascii_lowercase();
+        let merged = merge_pair(&insert[..12], &[b'I'; 12], &r2, &[b'I'; 12], &params(5))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(merged.seq, insert);
+        assert_eq!((merged.overlap, merged.mismatches), (8, 0));
+
+        let r2 = b"gtgtgtgtgtgtgtgtgtgt";
+        let merged = merge_pair(
+            b"AAAAAAAAAACCCCCCCCCC",
+            &[b'I'; 20],
+            r2,
+            &[b'I'; 20],
+            &params(10),
+        );
+        assert!(merged.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_merge_pairs_stats() {
+        let insert = b"ACGTTGCAAGGCTTAC";
+        let r1 = vec![
+            fastq::Record::with_attrs("p1", Some("x"), &insert[..12], &[b'I'; 12]),
+            fastq::Record::with_attrs("p2", None, b"AAAAAAAAAAAA", &[b'I'; 12]),
+        ];
+        let r2 = vec![
+            fastq::Record::with_attrs("p1", None, &reverse_complement(&insert[4..]), &[b'I'; 12]),
+            fastq::Record::with_attrs("p2", None, b"GTGTGTGTGTGT", &[b'I'; 12]),
+        ];
+
+        let MergedPairs {
+            merged,
+            unmerged,
+            stats,
+        } = merge_pairs(&r1, &r2, &params(5)).unwrap();
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!((merged[0].id(), merged[0].desc()), ("p1", Some("x")));
+        assert_eq!(merged[0].seq(), insert);
+        assert_eq!(unmerged.len(), 1);
+        assert_eq!(unmerged[0].0.id(), "p2");
+        assert_eq!(stats.merge_rate(), 0.5);
+        assert_eq!(stats.mean_overlap(), 8.0);
+
+        assert!(merge_pairs(&r1, &r2[..1], &params(5)).is_err());
+    }
+
+    #[rstest]
+    #[case(0, 0.1)]
+    #[case(10, 1.5)]
+    fn test_merge_invalid_params(#[case] min_overlap: usize, #[case] max_mismatch_rate: f64) {
+        let params = MergeParams {
+            min_overlap,
+            max_mismatch_rate,
+            ..Default::default()
+        };
+        assert!(merge_pair(b"ACGT", b"IIII", b"ACGT", b"IIII", &params).is_err());
+    }
+}
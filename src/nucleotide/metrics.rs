@@ -1,27 +1,53 @@
-use super::seq::error_to_phred;
-use super::statics::PHRED_TO_ERROR;
-use memchr::memchr_iter;
+use super::seq::{error_to_phred, qual_to_error};
+use crate::errors::BioError;
 
 /// Computes the mean error probability and corresponding Phred score for a quality string.
 ///
 /// Each byte in `qual` is treated as a raw Phred+33 encoded quality score and
-/// looked up in [`PHRED_TO_ERROR`](super::statics::PHRED_TO_ERROR).
-/// Returns `(0.0, 0)` for empty input.
+/// looked up in [`PHRED_TO_ERROR`](super::statics::PHRED_TO_ERROR). For legacy
+/// Phred+64 data use [`mean_error_and_phred_with_offset`](super::mean_error_and_phred_with_offset).
+/// Returns `(0.0, 0)` for empty input. Never panics: bytes outside Phred+33
+/// are saturated by [`qual_to_error`](super::qual_to_error), use
+/// [`checked_mean_error_and_phred`] to reject them.
 #[inline]
 pub fn mean_error_and_phred(qual: &[u8]) -> (f64, u8) {
     if qual.is_empty() {
         return (0.0, 0);
     }
 
-    let error_sum: f64 = qual
-        .iter()
-        .map(|phred| PHRED_TO_ERROR[*phred as usize])
-        .sum::<f64>();
+    let error_sum: f64 = qual.iter().map(|q| qual_to_error(*q)).sum::<f64>();
 
     let error_mean = error_sum / qual.len() as f64;
     (error_mean, error_to_phred(error_mean))
 }
 
+/// Like [`mean_error_and_phred`], but rejects bytes outside the printable
+/// Phred+33 range `!`..=`~`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidSymbolError`] for the first invalid byte.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::checked_mean_error_and_phred;
+///
+/// assert_eq!(checked_mean_error_and_phred(b"++++").unwrap().1, 10);
+/// assert!(checked_mean_error_and_phred(b"II\tI").is_err());
+/// ```
+pub fn checked_mean_error_and_phred(qual: &[u8]) -> Result<(f64, u8), BioError> {
+    if let Some(pos) = qual.iter().position(|q| !(b'!'..=b'~').contains(q)) {
+        return Err(BioError::InvalidSymbolError {
+            pos,
+            symbol: qual[pos] as char,
+            alphabet: "Phred+33",
+        });
+    }
+
+    Ok(mean_error_and_phred(qual))
+}
+
 /// Returns the truncated mean of a slice of lengths.
 ///
 /// Returns `0` for empty input.
@@ -34,6 +60,96 @@ pub fn mean_len(lengths: &[usize]) -> usize {
     lengths.iter().sum::<usize>() / lengths.len()
 }
 
+/// Class of each byte in [`composition`]: `A`, `C`, `G`, `T` = 0-3, `a`, `c`, `g`, `t` = 4-7, other = 8.
+const COMPOSITION_CLASS: [u8; 256] = {
+    let mut table = [8u8; 256];
+    table[b'A' as usize] = 0;
+    table[b'C' as usize] = 1;
+    table[b'G' as usize] = 2;
+    table[b'T' as usize] = 3;
+    table[b'a' as usize] = 4;
+    table[b'c' as usize] = 5;
+    table[b'g' as usize] = 6;
+    table[b't' as usize] = 7;
+    table
+};
+
+/// Base composition of a DNA sequence, as computed by [`composition`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Composition {
+    /// Uppercase `[A, C, G, T]` counts.
+    pub counts: [usize; 4],
+    /// Lowercase (softmasked) `[a, c, g, t]` counts.
+    pub softmasked: [usize; 4],
+    /// Everything that is not `ACGTacgt`, e.g. `N` or IUPAC codes.
+    pub ambiguous: usize,
+}
+
+impl Composition {
+    /// Total number of bases.
+    pub fn len(&self) -> usize {
+        self.counts.iter().sum::<usize>() + self.softmasked_count() + self.ambiguous
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn softmasked_count(&self) -> usize {
+        self.softmasked.iter().sum()
+    }
+
+    /// Case-insensitive `[A, C, G, T]` counts.
+    pub fn canonical_counts(&self) -> [usize; 4] {
+        std::array::from_fn(|i| self.counts[i] + self.softmasked[i])
+    }
+
+    /// Number of `G`, `C`, `g` and `c` bases.
+    pub fn gc_count(&self) -> usize {
+        self.counts[1] + self.counts[2] + self.softmasked[1] + self.softmasked[2]
+    }
+
+    /// GC fraction in `[0.0, 1.0]` over all bases, `0.0` if empty.
+    pub fn gc_content(&self) -> f64 {
+        match self.gc_count() {
+            0 => 0.0,
+            gc => gc as f64 / self.len() as f64,
+        }
+    }
+}
+
+/// Computes the base composition of a DNA sequence in a single pass.
+///
+/// Counts upper and lowercase `ACGT` separately along with everything else,
+/// from which counts, GC content, softmasking and ambiguity can all be
+/// derived without rescanning `seq`. [`nucleotide_counts`] and [`gc_content`]
+/// are thin wrappers around it.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::composition;
+///
+/// let comp = composition(b"AACGttNN");
+/// assert_eq!(comp.counts, [2, 1, 1, 0]);
+/// assert_eq!(comp.softmasked_count(), 2);
+/// assert_eq!(comp.ambiguous, 2);
+/// assert_eq!(comp.gc_content(), 0.25);
+/// ```
+#[inline]
+pub fn composition(seq: &[u8]) -> Composition {
+    let mut classes = [0usize; 9];
+    for &nt in seq {
+        classes[COMPOSITION_CLASS[nt as usize] as usize] += 1;
+    }
+
+    Composition {
+        counts: [classes[0], classes[1], classes[2], classes[3]],
+        softmasked: [classes[4], classes[5], classes[6], classes[7]],
+        ambiguous: classes[8],
+    }
+}
+
 /// Counts canonical nucleotides in a DNA sequence.
 ///
 /// Returns a tuple of:
@@ -41,6 +157,9 @@ pub fn mean_len(lengths: &[usize]) -> usize {
 /// - `usize` — number of softmasked bases (`a`, `c`, `g`, `t`).
 /// - `usize` — number of ambiguous/unknown bases (everything else).
 ///
+/// Use [`composition`] directly when more than one of these, or the GC
+/// content, is needed.
+///
 /// # Examples
 ///
 /// ```
@@ -53,49 +172,17 @@ pub fn mean_len(lengths: &[usize]) -> usize {
 /// ```
 #[inline]
 pub fn nucleotide_counts(seq: &[u8]) -> ([usize; 4], usize, usize) {
-    if seq.is_empty() {
-        return ([0; 4], 0, 0);
-    }
-
-    let mut canonical = [0usize; 4]; // A=0, C=1, G=2, T=3
-
-    let mut softmasked_count: usize = 0;
-    let mut ambiguous_count: usize = 0;
-
-    for &nt in seq {
-        match nt {
-            b'A' => canonical[0] += 1,
-            b'C' => canonical[1] += 1,
-            b'G' => canonical[2] += 1,
-            b'T' => canonical[3] += 1,
-            b'a' | b'c' | b'g' | b't' => softmasked_count += 1,
-            _ => ambiguous_count += 1,
-        }
-    }
-
-    (canonical, softmasked_count, ambiguous_count)
+    let comp = composition(seq);
+    (comp.counts, comp.softmasked_count(), comp.ambiguous)
 }
 
 /// Computes the GC content of a DNA sequence as a fraction in `[0.0, 1.0]`.
 ///
 /// Counts both uppercase (`G`, `C`) and lowercase (`g`, `c`) bases.
-/// Uses SIMD-accelerated byte search via [`memchr`].
 /// Returns `0.0` for empty input.
 #[inline]
 pub fn gc_content(seq: &[u8]) -> f64 {
-    if seq.is_empty() {
-        return 0.0;
-    }
-
-    let gc_count = memchr_iter(b'G', seq).count()
-        + memchr_iter(b'C', seq).count()
-        + memchr_iter(b'g', seq).count()
-        + memchr_iter(b'c', seq).count();
-
-    match gc_count {
-        0 => 0.0,
-        _ => gc_count as f64 / seq.len() as f64,
-    }
+    composition(seq).gc_content()
 }
 
 #[cfg(test)]
@@ -103,6 +190,29 @@ mod tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    #[case(b"", 0)]
+    #[case(b"++++", 10)]
+    #[case(b"5555", 20)]
+    // Phred 93 (HiFi) is kept and non-ASCII bytes saturate at it instead of panicking.
+    #[case(b"~~~~", 93)]
+    #[case(&[255, 255], 93)]
+    fn test_mean_error_and_phred(#[case] qual: &[u8], #[case] expected: u8) {
+        assert_eq!(mean_error_and_phred(qual).1, expected);
+    }
+
+    #[rstest]
+    #[case(b"II II", Some(2))]
+    #[case(&[b'I', 200], Some(1))]
+    #[case(b"!~", None)]
+    fn test_checked_mean_error_and_phred(#[case] qual: &[u8], #[case] invalid_pos: Option<usize>) {
+        match checked_mean_error_and_phred(qual) {
+            Err(BioError::InvalidSymbolError { pos, .. }) => assert_eq!(Some(pos), invalid_pos),
+            Ok(_) => assert_eq!(invalid_pos, None),
+            Err(e) => panic!("unexpected error {}", e),
+        }
+    }
+
     #[rstest]
     #[case(b"", 0.0_f64)]
     #[case(b"A", 0.0_f64)]
@@ -137,4 +247,20 @@ mod tests {
         assert_eq!(softmasked, expected_softmasked);
         assert_eq!(ambiguous, expected_ambiguous);
     }
+
+    #[test]
+    fn test_composition_large() {
+        let seq: Vec<u8> = b"ACGTacgtNN"
+            .repeat(30_000)
+            .into_iter()
+            .chain(*b"GGc")
+            .collect();
+        let comp = composition(&seq);
+
+        assert_eq!(comp.counts, [30_000, 30_000, 30_002, 30_000]);
+        assert_eq!(comp.softmasked, [30_000, 30_001, 30_000, 30_000]);
+        assert_eq!(comp.ambiguous, 60_000);
+        assert_eq!(comp.len(), seq.len());
+        assert_eq!(comp.canonical_counts(), [60_000, 60_001, 60_002, 60_000]);
+    }
 }
@@ -2,26 +2,109 @@
 //!
 //! Provides functions for:
 //! - Reverse complement and base conversions ([`reverse_complement`], [`error_to_phred`])
-//! - Quality and composition metrics ([`gc_content`], [`nucleotide_counts`], [`mean_error_and_phred`])
+//! - RNA transcription and reverse complement ([`transcribe`], [`back_transcribe`], [`reverse_complement_rna`])
+//! - Quality and composition metrics ([`composition`], [`gc_content`], [`nucleotide_counts`], [`mean_error_and_phred`])
+//! - Per-record metrics with selectable, single-pass computation ([`RecordMetricsBuilder`])
+//! - Length, quality and complexity read filters with workflow presets ([`ReadFilter`], [`FilterPreset`])
 //! - Shannon entropy ([`shannon_entropy`], [`nucleotide_probabilities`])
-//! - Homopolymer detection ([`find_homopolymers`])
-//! - Exact and fuzzy pattern search ([`search_exact`], [`search_fuzzy`])
+//! - Case conversion and interval soft/hard masking ([`softmask_intervals`], [`hardmask_intervals`])
+//! - N content and N-run based scaffold splitting ([`n_content`], [`split_on_n_runs`])
+//! - Homopolymer and tandem repeat detection ([`find_homopolymers`], [`find_homopolymers_with`], [`find_tandem_repeats`])
+//! - Homopolymer BED export ([`write_homopolymer_bed`])
+//! - Mismatch-tolerant poly(A)/poly(T) tail detection and trimming ([`find_poly_a_tail`], [`find_poly_t_head`], [`trim_poly_a`])
+//! - Inverted and direct repeat detection ([`find_inverted_repeats`], [`find_direct_repeats`])
+//! - Telomeric repeat detection at contig ends ([`find_telomeres`])
+//! - Exact, multi-pattern and fuzzy pattern search ([`search_exact`], [`search_exact_both_strands`], [`search_exact_multi`], [`search_fuzzy`])
+//! - Search results with span, strand, edits and pattern index ([`Match`], [`search_fuzzy_matches`])
+//! - Quality-aware fuzzy search ([`search_fuzzy_quality`])
+//! - Seed-and-extend fuzzy search for long patterns ([`search_fuzzy_seeded`])
+//! - VecScreen-style vector and adapter contamination screening with trim recommendations ([`screen_vector`])
+//! - Overlap-based read pair merging with consensus qualities ([`merge_pair`], [`merge_pairs`])
+//! - cDNA read orientation and primer trimming from SSP/VNP primers ([`detect_orientation`])
+//! - Shared substring detection ([`longest_common_substring`], [`find_mems`])
+//! - Seeded mononucleotide, dinucleotide and codon shuffling ([`shuffle_sequence`])
+//! - Seeded error injection with a variant list ([`mutate`])
+//! - MD5 and refget/GA4GH sequence checksums ([`sequence_checksum`])
+//! - Overlapping sequence windows ([`windows`]) and per-window metrics ([`window_values`])
+//! - 2-bit packed sequences with N and soft-mask runs ([`PackedSeq`])
+//! - Phred+33/+64 quality encoding detection and conversion ([`PhredOffset`], [`infer_phred_offset`])
+//! - Consistent handling of ambiguous bases ([`AmbigPolicy`], [`apply_ambig_policy`], [`split_on_ambiguous`])
 //! - Static lookup tables ([`NT_LOOKUP`], [`PHRED_TO_ERROR`])
 
 mod seq;
 pub use seq::*;
 
+mod ambiguity;
+pub use ambiguity::*;
+
+mod checksum;
+pub use checksum::*;
+
 mod metrics;
 pub use metrics::*;
 
 mod statics;
 pub use statics::*;
 
+mod record_metrics;
+pub use record_metrics::*;
+
+mod filter;
+pub use filter::*;
+
 mod entropy;
 pub use entropy::*;
 
 mod homopolymer;
 pub use homopolymer::*;
 
+mod mask;
+pub use mask::*;
+
+mod poly_tail;
+pub use poly_tail::*;
+
+mod mutate;
+pub use mutate::*;
+
+mod n_runs;
+pub use n_runs::*;
+
+mod packed;
+pub use packed::*;
+
+mod phred;
+pub use phred::*;
+
+mod repeats;
+pub use repeats::*;
+
+mod telomere;
+pub use telomere::*;
+
 mod search;
 pub use search::*;
+
+mod shuffle;
+pub use shuffle::*;
+
+mod windows;
+pub use windows::*;
+
+mod quality_search;
+pub use quality_search::*;
+
+mod seeded_search;
+pub use seeded_search::*;
+
+mod vector_screen;
+pub use vector_screen::*;
+
+mod orientation;
+pub use orientation::*;
+
+mod mem;
+pub use mem::*;
+
+mod merge_pairs;
+pub use merge_pairs::*;
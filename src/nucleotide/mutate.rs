@@ -0,0 +1,165 @@
+use crate::errors::BioError;
+use crate::rng::SplitMix64;
+
+const BASES: [u8; 4] = [b'A', b'C', b'G', b'T'];
+
+/// A variant introduced by [`mutate`].
+///
+/// `pos` is the zero-based position in the original sequence. Insertions are
+/// placed immediately before the base at `pos`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Substitution { pos: usize, ref_nt: u8, alt_nt: u8 },
+    Insertion { pos: usize, alt_nt: u8 },
+    Deletion { pos: usize, ref_nt: u8 },
+}
+
+/// Randomly introduces substitutions, insertions and deletions into `seq`.
+///
+/// Each base independently gets a substitution with probability `sub_rate`,
+/// an insertion before it with probability `ins_rate`, or is deleted with
+/// probability `del_rate`. Substituted and inserted bases are drawn uniformly
+/// from `ACGT`, substitutions always change the base. Output is fully
+/// determined by `seed`.
+///
+/// Returns the mutated sequence and the introduced variants in position order.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if a rate is outside `[0.0, 1.0]`
+/// or the rates sum to more than `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::mutate;
+///
+/// let (mutated, variants) = mutate(b"ACGTACGTACGT", 0.1, 0.0, 0.0, 42).unwrap();
+/// assert_eq!(mutated.len(), 12);
+/// assert_eq!(variants.len(), mutated.iter().zip(b"ACGTACGTACGT").filter(|(a, b)| a != b).count());
+/// ```
+pub fn mutate(
+    seq: &[u8],
+    sub_rate: f64,
+    ins_rate: f64,
+    del_rate: f64,
+    seed: u64,
+) -> Result<(Vec<u8>, Vec<Variant>), BioError> {
+    for (name, rate) in [
+        ("substitution", sub_rate),
+        ("insertion", ins_rate),
+        ("deletion", del_rate),
+    ] {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(BioError::InvalidParameterError(format!(
+                "{} rate {} must be in range 0-1.",
+                name, rate
+            )));
+        }
+    }
+
+    if sub_rate + ins_rate + del_rate > 1.0 {
+        return Err(BioError::InvalidParameterError(format!(
+            "sum of mutation rates {} cannot exceed 1.",
+            sub_rate + ins_rate + del_rate
+        )));
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut mutated = Vec::with_capacity(seq.len());
+    let mut variants = Vec::new();
+
+    for (pos, &ref_nt) in seq.iter().enumerate() {
+        let r = rng.next_f64();
+
+        if r < sub_rate {
+            let alt_nt = match BASES.iter().position(|b| *b == ref_nt.to_ascii_uppercase()) {
+                // Skip over the reference base so the substitution always changes it.
+                Some(ref_idx) => BASES[(ref_idx + 1 + rng.next_below(3)) % 4],
+                None => BASES[rng.next_below(4)],
+            };
+            mutated.push(alt_nt);
+            variants.push(Variant::Substitution {
+                pos,
+                ref_nt,
+                alt_nt,
+            });
+        } else if r < sub_rate + ins_rate {
+            let alt_nt = BASES[rng.next_below(4)];
+            mutated.push(alt_nt);
+            mutated.push(ref_nt);
+            variants.push(Variant::Insertion { pos, alt_nt });
+        } else if r < sub_rate + ins_rate + del_rate {
+            variants.push(Variant::Deletion { pos, ref_nt });
+        } else {
+            mutated.push(ref_nt);
+        }
+    }
+
+    Ok((mutated, variants))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const SEQ: &[u8] = b"ACGTACGTACGTACGTACGTACGTACGTACGTACGTACGTACGTACGTACGT";
+
+    #[test]
+    fn test_mutate_no_rates() {
+        let (mutated, variants) = mutate(SEQ, 0.0, 0.0, 0.0, 1).unwrap();
+        assert_eq!(mutated, SEQ);
+        assert!(variants.is_empty());
+    }
+
+    #[test]
+    fn test_mutate_deterministic() {
+        let a = mutate(SEQ, 0.1, 0.05, 0.05, 7).unwrap();
+        assert_eq!(a, mutate(SEQ, 0.1, 0.05, 0.05, 7).unwrap());
+        assert_ne!(a, mutate(SEQ, 0.1, 0.05, 0.05, 8).unwrap());
+    }
+
+    #[rstest]
+    #[case(1.0, 0.0, 0.0)]
+    #[case(0.0, 1.0, 0.0)]
+    #[case(0.0, 0.0, 1.0)]
+    #[case(0.2, 0.2, 0.2)]
+    fn test_mutate_variants_consistent(
+        #[case] sub_rate: f64,
+        #[case] ins_rate: f64,
+        #[case] del_rate: f64,
+    ) {
+        let (mutated, variants) = mutate(SEQ, sub_rate, ins_rate, del_rate, 3).unwrap();
+
+        let ins = variants
+            .iter()
+            .filter(|v| matches!(v, Variant::Insertion { .. }))
+            .count();
+        let del = variants
+            .iter()
+            .filter(|v| matches!(v, Variant::Deletion { .. }))
+            .count();
+        assert_eq!(mutated.len(), SEQ.len() + ins - del);
+
+        for v in &variants {
+            if let Variant::Substitution {
+                pos,
+                ref_nt,
+                alt_nt,
+            } = *v
+            {
+                assert_eq!(SEQ[pos], ref_nt);
+                assert_ne!(ref_nt, alt_nt);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(-0.1, 0.0, 0.0)]
+    #[case(0.0, 1.5, 0.0)]
+    #[case(0.5, 0.3, 0.3)]
+    fn test_mutate_invalid(#[case] sub_rate: f64, #[case] ins_rate: f64, #[case] del_rate: f64) {
+        assert!(mutate(SEQ, sub_rate, ins_rate, del_rate, 0).is_err());
+    }
+}
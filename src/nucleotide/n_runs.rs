@@ -0,0 +1,181 @@
+use crate::errors::BioError;
+
+#[inline]
+fn is_n(nt: u8) -> bool {
+    nt == b'N' || nt == b'n'
+}
+
+/// N content of a sequence, as computed by [`n_content`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NContent {
+    /// Number of `N`/`n` bases.
+    pub n_count: usize,
+    /// Fraction of `N`/`n` bases in `[0.0, 1.0]`, `0.0` for empty input.
+    pub fraction: f64,
+    /// Length of the longest contiguous `N`/`n` run.
+    pub longest_run: usize,
+}
+
+/// Computes the fraction of `N`/`n` bases and the longest contiguous N run.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::n_content;
+///
+/// let n = n_content(b"ACNNNGTnA");
+/// assert_eq!(n.n_count, 4);
+/// assert_eq!(n.longest_run, 3);
+/// ```
+pub fn n_content(seq: &[u8]) -> NContent {
+    let mut n_count = 0;
+    let mut longest_run = 0;
+    let mut run = 0;
+
+    for &nt in seq {
+        if is_n(nt) {
+            n_count += 1;
+            run += 1;
+            longest_run = longest_run.max(run);
+        } else {
+            run = 0;
+        }
+    }
+
+    NContent {
+        n_count,
+        fraction: match seq.len() {
+            0 => 0.0,
+            len => n_count as f64 / len as f64,
+        },
+        longest_run,
+    }
+}
+
+fn validate_min_run(min_run: usize) -> Result<(), BioError> {
+    if min_run == 0 {
+        return Err(BioError::InvalidParameterError(
+            "min N run length must be greater than 0.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Finds all runs of `N`/`n` bases at least `min_run` long.
+///
+/// Returns zero-based half-open `(start, end)` intervals.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_run` is `0`.
+pub fn find_n_runs(seq: &[u8], min_run: usize) -> Result<Vec<(usize, usize)>, BioError> {
+    validate_min_run(min_run)?;
+
+    let mut runs = Vec::new();
+    let mut i = 0;
+
+    while i < seq.len() {
+        if !is_n(seq[i]) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < seq.len() && is_n(seq[i]) {
+            i += 1;
+        }
+
+        if i - start >= min_run {
+            runs.push((start, i));
+        }
+    }
+
+    Ok(runs)
+}
+
+/// Splits a scaffold into contigs at N runs at least `min_run` long.
+///
+/// Returns zero-based half-open `(start, end)` intervals of the contigs.
+/// Shorter N runs are kept inside the contigs, and leading or trailing gaps
+/// do not produce empty contigs.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_run` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::split_on_n_runs;
+///
+/// let contigs = split_on_n_runs(b"ACGTNNNNACNGTNNNN", 3).unwrap();
+/// assert_eq!(contigs, vec![(0, 4), (8, 13)]);
+/// ```
+pub fn split_on_n_runs(seq: &[u8], min_run: usize) -> Result<Vec<(usize, usize)>, BioError> {
+    let mut contigs = Vec::new();
+    let mut start = 0;
+
+    for (gap_start, gap_end) in find_n_runs(seq, min_run)? {
+        if gap_start > start {
+            contigs.push((start, gap_start));
+        }
+        start = gap_end;
+    }
+
+    if start < seq.len() {
+        contigs.push((start, seq.len()));
+    }
+
+    Ok(contigs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"", 0, 0.0, 0)]
+    #[case(b"ACGT", 0, 0.0, 0)]
+    #[case(b"NNNN", 4, 1.0, 4)]
+    #[case(b"NnAcNNNg", 5, 0.625, 3)]
+    fn test_n_content(
+        #[case] seq: &[u8],
+        #[case] n_count: usize,
+        #[case] fraction: f64,
+        #[case] longest_run: usize,
+    ) {
+        assert_eq!(
+            n_content(seq),
+            NContent {
+                n_count,
+                fraction,
+                longest_run
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(b"ACGT", 1, vec![(0, 4)])]
+    #[case(b"NNACGTNNNNAAnnnCC", 3, vec![(0, 6), (10, 12), (15, 17)])]
+    #[case(b"NNACGTNNNNAAnnnCC", 4, vec![(0, 6), (10, 17)])]
+    #[case(b"NNNN", 1, vec![])]
+    #[case(b"", 1, vec![])]
+    fn test_split_on_n_runs(
+        #[case] seq: &[u8],
+        #[case] min_run: usize,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        assert_eq!(split_on_n_runs(seq, min_run).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_find_n_runs() {
+        assert_eq!(
+            find_n_runs(b"NACNNGTnnnN", 2).unwrap(),
+            vec![(3, 5), (7, 11)]
+        );
+        assert!(find_n_runs(b"ACGT", 0).is_err());
+    }
+}
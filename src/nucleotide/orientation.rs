@@ -0,0 +1,225 @@
+use super::search::{Match, search_fuzzy_matches};
+use super::seq::{Strand, reverse_complement};
+use crate::errors::BioError;
+use std::cmp::{Ordering, Reverse};
+
+/// Primers flanking full-length cDNA reads, used by [`detect_orientation`].
+///
+/// Forward reads start with the strand switching primer (SSP) and end with
+/// the reverse complement of the VN primer (VNP); reverse reads start with
+/// the VNP and end with the reverse complement of the SSP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CdnaPrimers {
+    pub ssp: Vec<u8>,
+    pub vnp: Vec<u8>,
+    /// Maximum edit distance of a primer match.
+    pub max_edits: u8,
+    /// Number of bases at each read end searched for primers.
+    pub search_len: usize,
+}
+
+impl Default for CdnaPrimers {
+    /// ONT PCS109 primers, as shipped with pychopper.
+    fn default() -> Self {
+        Self {
+            ssp: b"TTTCTGTTGGTGCTGATATTGCTGGG".to_vec(),
+            vnp: b"ACTTGCCTGTCGCTCTATCTTC".to_vec(),
+            max_edits: 4,
+            search_len: 200,
+        }
+    }
+}
+
+/// Orientation of a full-length cDNA read, reported by [`detect_orientation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOrientation {
+    /// Strand of the transcript relative to the read.
+    pub strand: Strand,
+    /// Start of the read after the 5' primer.
+    pub start: usize,
+    /// End of the read before the 3' primer (exclusive).
+    pub end: usize,
+    /// Sum of the edit distances of both primer matches.
+    pub edits: u8,
+}
+
+/// Best match of `primer` in `window`, by lowest edit distance and then
+/// closest to the read end (`at_start` for the 5' end).
+fn best_primer_match(
+    window: &[u8],
+    primer: &[u8],
+    max_edits: u8,
+    at_start: bool,
+) -> Result<Option<Match>, BioError> {
+    let hits = search_fuzzy_matches(window, primer, max_edits)?;
+
+    Ok(match at_start {
+        true => hits.into_iter().min_by_key(|m| (m.edits, m.start)),
+        false => hits.into_iter().min_by_key(|m| (m.edits, Reverse(m.end))),
+    })
+}
+
+/// Detects the orientation of a cDNA read from its flanking primers, as
+/// pychopper does, and returns the strand and the read coordinates between
+/// the primers.
+///
+/// The first and last `search_len` bases are searched with Myers fuzzy
+/// search for both primer configurations: SSP at the start and reverse
+/// complemented VNP at the end (forward), or VNP at the start and reverse
+/// complemented SSP at the end (reverse). Only configurations with primers
+/// at both ends qualify; if both do, the one with fewer edits wins. Returns
+/// `None` if no configuration qualifies, both have the same number of edits,
+/// or the primers overlap. Matching is case sensitive.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if a primer is empty or
+/// `search_len` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{CdnaPrimers, Strand, detect_orientation, reverse_complement};
+///
+/// let primers = CdnaPrimers::default();
+/// let transcript = b"ATGGCGTACCGATTAGCCATAGGCAAAAAAAAAA";
+/// let read = [primers.ssp.as_slice(), transcript, &reverse_complement(&primers.vnp)].concat();
+///
+/// let orientation = detect_orientation(&read, &primers).unwrap().unwrap();
+/// assert_eq!(orientation.strand, Strand::Forward);
+/// assert_eq!(&read[orientation.start..orientation.end], transcript);
+///
+/// let orientation = detect_orientation(&reverse_complement(&read), &primers).unwrap().unwrap();
+/// assert_eq!(orientation.strand, Strand::Reverse);
+/// ```
+pub fn detect_orientation(
+    seq: &[u8],
+    primers: &CdnaPrimers,
+) -> Result<Option<ReadOrientation>, BioError> {
+    if primers.ssp.is_empty() || primers.vnp.is_empty() || primers.search_len == 0 {
+        return Err(BioError::InvalidParameterError(
+            "cDNA primers must be non-empty and the search length greater than 0.".to_string(),
+        ));
+    }
+
+    let head = &seq[..primers.search_len.min(seq.len())];
+    let tail_offset = seq.len().saturating_sub(primers.search_len);
+    let tail = &seq[tail_offset..];
+
+    let configurations = [
+        (Strand::Forward, &primers.ssp, &primers.vnp),
+        (Strand::Reverse, &primers.vnp, &primers.ssp),
+    ];
+
+    let mut candidates: Vec<ReadOrientation> = Vec::with_capacity(2);
+    for (strand, first, second) in configurations {
+        let Some(start) = best_primer_match(head, first, primers.max_edits, true)? else {
+            continue;
+        };
+
+        let second_rc = reverse_complement(second);
+        let Some(end) = best_primer_match(tail, &second_rc, primers.max_edits, false)? else {
+            continue;
+        };
+
+        let (start_pos, end_pos) = (start.end, tail_offset + end.start);
+        if start_pos <= end_pos {
+            candidates.push(ReadOrientation {
+                strand,
+                start: start_pos,
+                end: end_pos,
+                edits: start.edits + end.edits,
+            });
+        }
+    }
+
+    Ok(match candidates.as_slice() {
+        [single] => Some(*single),
+        [a, b] => match a.edits.cmp(&b.edits) {
+            Ordering::Less => Some(*a),
+            Ordering::Greater => Some(*b),
+            Ordering::Equal => None,
+        },
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::test_utils::random_sequence;
+    use rstest::*;
+
+    fn full_length_read(transcript: &[u8]) -> Vec<u8> {
+        let primers = CdnaPrimers::default();
+        [
+            b"GTAC".as_slice(),
+            &primers.ssp,
+            transcript,
+            &reverse_complement(&primers.vnp),
+            b"CCA",
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_detect_orientation_forward_and_reverse() {
+        let transcript = random_sequence(&mut SplitMix64::new(7), 500, 0.5);
+        let read = full_length_read(&transcript);
+        let primers = CdnaPrimers::default();
+
+        let forward = detect_orientation(&read, &primers).unwrap().unwrap();
+        assert_eq!(forward.strand, Strand::Forward);
+        assert_eq!((forward.start, forward.end), (30, 530));
+        assert_eq!(forward.edits, 0);
+
+        let rc = reverse_complement(&read);
+        let reverse = detect_orientation(&rc, &primers).unwrap().unwrap();
+        assert_eq!(reverse.strand, Strand::Reverse);
+        assert_eq!(
+            reverse_complement(&rc[reverse.start..reverse.end]),
+            transcript
+        );
+    }
+
+    #[test]
+    fn test_detect_orientation_with_primer_errors() {
+        let transcript = random_sequence(&mut SplitMix64::new(8), 300, 0.5);
+        let mut read = full_length_read(&transcript);
+        // Substitution in the SSP and a deletion in the VNP.
+        read[10] = if read[10] == b'A' { b'C' } else { b'A' };
+        read.remove(read.len() - 10);
+
+        let orientation = detect_orientation(&read, &CdnaPrimers::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(orientation.strand, Strand::Forward);
+        assert_eq!(orientation.edits, 2);
+    }
+
+    #[rstest]
+    // Missing 3' primer.
+    #[case(true)]
+    // No primers.
+    #[case(false)]
+    fn test_detect_orientation_none(#[case] with_ssp: bool) {
+        let primers = CdnaPrimers::default();
+        let transcript = random_sequence(&mut SplitMix64::new(9), 300, 0.5);
+        let read = match with_ssp {
+            true => [primers.ssp.as_slice(), &transcript].concat(),
+            false => transcript,
+        };
+
+        assert_eq!(detect_orientation(&read, &primers).unwrap(), None);
+    }
+
+    #[test]
+    fn test_detect_orientation_invalid() {
+        let primers = CdnaPrimers {
+            search_len: 0,
+            ..Default::default()
+        };
+        assert!(detect_orientation(b"ACGT", &primers).is_err());
+    }
+}
@@ -0,0 +1,383 @@
+use super::statics::NT_LOOKUP;
+use crate::errors::BioError;
+
+/// Bases stored per `u64` word.
+const BASES_PER_WORD: usize = 32;
+
+/// 2-bit code to uppercase ASCII, in [`NT_LOOKUP`] order.
+const CODE_TO_NT: [u8; 4] = [b'A', b'C', b'G', b'T'];
+
+/// Collects `[start, start + len)` runs of positions for which `pred` holds.
+fn collect_runs(len: usize, pred: impl Fn(usize) -> bool) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+
+    for i in (0..len).filter(|&i| pred(i)) {
+        match runs.last_mut() {
+            Some((start, run_len)) if *start + *run_len == i => *run_len += 1,
+            _ => runs.push((i, 1)),
+        }
+    }
+
+    runs
+}
+
+/// Returns `true` if `pos` lies within one of the sorted, non-overlapping `runs`.
+#[inline]
+fn in_runs(runs: &[(usize, usize)], pos: usize) -> bool {
+    let idx = runs.partition_point(|(start, _)| *start <= pos);
+    idx > 0 && pos < runs[idx - 1].0 + runs[idx - 1].1
+}
+
+/// Clips `runs` to `[start, end)` and shifts them to start at `0`.
+fn slice_runs(runs: &[(usize, usize)], start: usize, end: usize) -> Vec<(usize, usize)> {
+    runs.iter()
+        .filter_map(|&(s, len)| {
+            let (s2, e2) = (s.max(start), (s + len).min(end));
+            (s2 < e2).then(|| (s2 - start, e2 - s2))
+        })
+        .collect()
+}
+
+/// Merges adjacent runs and drops empty ones.
+fn coalesce_runs(runs: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(runs.len());
+
+    for (start, len) in runs.into_iter().filter(|&(_, len)| len > 0) {
+        match merged.last_mut() {
+            Some((prev, prev_len)) if *prev + *prev_len == start => *prev_len += len,
+            _ => merged.push((start, len)),
+        }
+    }
+
+    merged
+}
+
+/// Mirrors `runs` for a reversed sequence of length `len`.
+fn reverse_runs(runs: &[(usize, usize)], len: usize) -> Vec<(usize, usize)> {
+    runs.iter()
+        .rev()
+        .map(|&(start, run_len)| (len - start - run_len, run_len))
+        .collect()
+}
+
+/// DNA sequence packed at 2 bits per base.
+///
+/// Positions that are not `ACGT` are stored as `N` runs and lowercase
+/// (soft-masked) positions as mask runs, both as sorted `(start, len)` lists,
+/// similar to the UCSC `.2bit` layout. Other IUPAC codes are not preserved
+/// and decode to `N`. `U` decodes to `T`.
+///
+/// Codes under `N` runs and past the end are always stored as `0` and runs
+/// are merged, so sequences that decode to the same ASCII compare equal.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::PackedSeq;
+///
+/// let packed = PackedSeq::from_ascii(b"ACGTNNacgt");
+/// assert_eq!(packed.to_ascii(), b"ACGTNNacgt");
+/// assert_eq!(packed.reverse_complement().to_ascii(), b"acgtNNACGT");
+/// assert_eq!(packed.n_runs(), &[(4, 2)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PackedSeq {
+    words: Vec<u64>,
+    len: usize,
+    n_runs: Vec<(usize, usize)>,
+    mask_runs: Vec<(usize, usize)>,
+}
+
+impl PackedSeq {
+    /// Packs an ASCII sequence.
+    pub fn from_ascii(seq: &[u8]) -> Self {
+        let mut words = vec![0u64; seq.len().div_ceil(BASES_PER_WORD)];
+
+        for (i, nt) in seq.iter().enumerate() {
+            // `N` positions are stored as code 0 and tracked in `n_runs`.
+            let code = match NT_LOOKUP[*nt as usize] {
+                code @ 0..=3 => code as u64,
+                _ => 0,
+            };
+            words[i / BASES_PER_WORD] |= code << ((i % BASES_PER_WORD) * 2);
+        }
+
+        Self {
+            words,
+            len: seq.len(),
+            n_runs: collect_runs(seq.len(), |i| NT_LOOKUP[seq[i] as usize] >= 4),
+            mask_runs: collect_runs(seq.len(), |i| seq[i].is_ascii_lowercase()),
+        }
+    }
+
+    /// Builds a packed sequence from its raw parts, e.g. when reading `.2bit` files.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `words` holds fewer than
+    /// `len` bases, or a run is unsorted, overlapping or out of bounds.
+    pub fn from_parts(
+        words: Vec<u64>,
+        len: usize,
+        n_runs: Vec<(usize, usize)>,
+        mask_runs: Vec<(usize, usize)>,
+    ) -> Result<Self, BioError> {
+        if words.len() < len.div_ceil(BASES_PER_WORD) {
+            return Err(BioError::InvalidParameterError(format!(
+                "{} words cannot hold {} bases.",
+                words.len(),
+                len
+            )));
+        }
+
+        for runs in [&n_runs, &mask_runs] {
+            let mut prev_end = 0;
+            for &(start, run_len) in runs {
+                if start < prev_end || start + run_len > len {
+                    return Err(BioError::InvalidParameterError(format!(
+                        "run ({}, {}) is unsorted, overlapping or exceeds length {}.",
+                        start, run_len, len
+                    )));
+                }
+                prev_end = start + run_len;
+            }
+        }
+
+        Ok(Self {
+            words,
+            len,
+            n_runs,
+            mask_runs,
+        }
+        .normalized())
+    }
+
+    /// Zeroes the codes under `N` runs and past `len`, drops unused words and
+    /// merges adjacent runs, as [`from_ascii`](Self::from_ascii) stores them.
+    fn normalized(mut self) -> Self {
+        self.words.truncate(self.len.div_ceil(BASES_PER_WORD));
+
+        for &(start, run_len) in &self.n_runs {
+            for i in start..start + run_len {
+                self.words[i / BASES_PER_WORD] &= !(3 << ((i % BASES_PER_WORD) * 2));
+            }
+        }
+
+        let tail = self.len % BASES_PER_WORD;
+        if let (true, Some(last)) = (tail > 0, self.words.last_mut()) {
+            *last &= (1 << (tail * 2)) - 1;
+        }
+
+        self.n_runs = coalesce_runs(self.n_runs);
+        self.mask_runs = coalesce_runs(self.mask_runs);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Packed words, 32 bases per word with the first base in the lowest bits.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Sorted `(start, len)` runs of `N`.
+    pub fn n_runs(&self) -> &[(usize, usize)] {
+        &self.n_runs
+    }
+
+    /// Sorted `(start, len)` runs of soft-masked (lowercase) bases.
+    pub fn mask_runs(&self) -> &[(usize, usize)] {
+        &self.mask_runs
+    }
+
+    /// Returns the 2-bit code (`A=0, C=1, G=2, T=3`) at `pos`, ignoring `N` runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos >= len`.
+    #[inline]
+    pub fn code(&self, pos: usize) -> u8 {
+        assert!(pos < self.len, "position {} out of bounds", pos);
+        ((self.words[pos / BASES_PER_WORD] >> ((pos % BASES_PER_WORD) * 2)) & 3) as u8
+    }
+
+    /// Returns the ASCII base at `pos`, including `N` and soft-masking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos >= len`.
+    pub fn base(&self, pos: usize) -> u8 {
+        let nt = match in_runs(&self.n_runs, pos) {
+            true => b'N',
+            false => CODE_TO_NT[self.code(pos) as usize],
+        };
+
+        match in_runs(&self.mask_runs, pos) {
+            true => nt.to_ascii_lowercase(),
+            false => nt,
+        }
+    }
+
+    /// Decodes the full sequence to ASCII.
+    pub fn to_ascii(&self) -> Vec<u8> {
+        let mut seq: Vec<u8> = (0..self.len)
+            .map(|i| CODE_TO_NT[self.code(i) as usize])
+            .collect();
+
+        for &(start, len) in &self.n_runs {
+            seq[start..start + len].fill(b'N');
+        }
+
+        for &(start, len) in &self.mask_runs {
+            seq[start..start + len].make_ascii_lowercase();
+        }
+
+        seq
+    }
+
+    /// Returns the packed subsequence `[start, end)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `start > end` or `end > len`.
+    pub fn slice(&self, start: usize, end: usize) -> Result<PackedSeq, BioError> {
+        if start > end || end > self.len {
+            return Err(BioError::InvalidParameterError(format!(
+                "slice [{}, {}) is invalid for sequence of length {}.",
+                start, end, self.len
+            )));
+        }
+
+        let len = end - start;
+        let mut words = vec![0u64; len.div_ceil(BASES_PER_WORD)];
+
+        for i in 0..len {
+            words[i / BASES_PER_WORD] |=
+                (self.code(start + i) as u64) << ((i % BASES_PER_WORD) * 2);
+        }
+
+        Ok(PackedSeq {
+            words,
+            len,
+            n_runs: slice_runs(&self.n_runs, start, end),
+            mask_runs: slice_runs(&self.mask_runs, start, end),
+        }
+        .normalized())
+    }
+
+    /// Returns the reverse complement, computed in packed space.
+    pub fn reverse_complement(&self) -> PackedSeq {
+        let mut words = vec![0u64; self.words.len()];
+
+        for i in 0..self.len {
+            let code = 3 - self.code(self.len - 1 - i) as u64;
+            words[i / BASES_PER_WORD] |= code << ((i % BASES_PER_WORD) * 2);
+        }
+
+        PackedSeq {
+            words,
+            len: self.len,
+            n_runs: reverse_runs(&self.n_runs, self.len),
+            mask_runs: reverse_runs(&self.mask_runs, self.len),
+        }
+        .normalized()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nucleotide::reverse_complement;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"")]
+    #[case(b"ACGT")]
+    #[case(b"NNNNacgtNNACGTTTGCAtgcaNN")]
+    #[case(b"ACGTACGTACGTACGTACGTACGTACGTACGTACGTAcgtN")]
+    fn test_round_trip(#[case] seq: &[u8]) {
+        let packed = PackedSeq::from_ascii(seq);
+        assert_eq!(packed.to_ascii(), seq);
+        assert_eq!(packed.len(), seq.len());
+        assert_eq!(
+            (0..seq.len()).map(|i| packed.base(i)).collect::<Vec<u8>>(),
+            seq
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_and_rna() {
+        let packed = PackedSeq::from_ascii(b"ARYU");
+        assert_eq!(packed.to_ascii(), b"ANNT");
+        assert_eq!(packed.n_runs(), &[(1, 2)]);
+    }
+
+    #[rstest]
+    #[case(b"ACGTNNacgt")]
+    #[case(b"AAAACCCCGGGGTTTTAAAACCCCGGGGTTTTAC")]
+    fn test_reverse_complement(#[case] seq: &[u8]) {
+        let packed = PackedSeq::from_ascii(seq);
+        let expected = reverse_complement(&seq.to_ascii_uppercase());
+
+        assert_eq!(
+            packed.reverse_complement().to_ascii().to_ascii_uppercase(),
+            expected
+        );
+        assert_eq!(packed.reverse_complement().reverse_complement(), packed);
+    }
+
+    #[rstest]
+    #[case(0, 4, b"ACGT")]
+    #[case(3, 8, b"TNNac")]
+    #[case(5, 5, b"")]
+    fn test_slice(#[case] start: usize, #[case] end: usize, #[case] expected: &[u8]) {
+        let packed = PackedSeq::from_ascii(b"ACGTNNacgt");
+        assert_eq!(packed.slice(start, end).unwrap().to_ascii(), expected);
+    }
+
+    #[test]
+    fn test_slice_invalid() {
+        let packed = PackedSeq::from_ascii(b"ACGT");
+        assert!(packed.slice(3, 2).is_err());
+        assert!(packed.slice(0, 5).is_err());
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let packed = PackedSeq::from_ascii(b"ACGTNNacgt");
+        let rebuilt = PackedSeq::from_parts(
+            packed.words().to_vec(),
+            packed.len(),
+            packed.n_runs().to_vec(),
+            packed.mask_runs().to_vec(),
+        )
+        .unwrap();
+        assert_eq!(rebuilt, packed);
+
+        assert!(PackedSeq::from_parts(vec![], 4, vec![], vec![]).is_err());
+        assert!(PackedSeq::from_parts(vec![0], 4, vec![(2, 1), (1, 1)], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_eq_ignores_codes_under_n() {
+        let n = PackedSeq::from_ascii(b"N");
+        assert_eq!(n.reverse_complement(), n);
+
+        let packed = PackedSeq::from_ascii(b"ACNNNT");
+        assert_eq!(packed.reverse_complement().reverse_complement(), packed);
+        assert_eq!(packed.slice(1, 4).unwrap(), PackedSeq::from_ascii(b"CNN"));
+
+        // Arbitrary codes under `N`, past the end, split runs and extra words.
+        let rebuilt =
+            PackedSeq::from_parts(vec![u64::MAX, 7], 6, vec![(2, 1), (3, 2)], vec![(0, 0)])
+                .unwrap();
+        assert_eq!(rebuilt.to_ascii(), b"TTNNNT");
+        assert_eq!(rebuilt, PackedSeq::from_ascii(b"TTNNNT"));
+    }
+}
@@ -0,0 +1,118 @@
+use super::metrics::mean_error_and_phred;
+use super::statics::PHRED_OFFSET;
+use crate::errors::BioError;
+
+/// ASCII offset of a FASTQ quality encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhredOffset {
+    /// Sanger and Illumina 1.8+, `!` = Phred 0.
+    Phred33,
+    /// Illumina 1.3-1.7 (and Solexa), `@` = Phred 0.
+    Phred64,
+}
+
+impl PhredOffset {
+    pub fn value(&self) -> u8 {
+        match self {
+            PhredOffset::Phred33 => PHRED_OFFSET as u8,
+            PhredOffset::Phred64 => 64,
+        }
+    }
+
+    /// Re-encodes `qual` as Phred+33, so it can be passed to functions that assume it.
+    ///
+    /// Phred+64 scores below 0 (Solexa) are clamped to Phred 0.
+    pub fn to_phred33(&self, qual: &[u8]) -> Vec<u8> {
+        let shift = self.value() - PHRED_OFFSET as u8;
+        qual.iter()
+            .map(|q| q.saturating_sub(shift).max(PHRED_OFFSET as u8))
+            .collect()
+    }
+}
+
+/// Infers the quality encoding from the smallest and largest quality bytes seen.
+///
+/// Any byte below `@` (64) can only be Phred+33. If all bytes are at least
+/// `@` and some exceed `J` (Phred+33 Q41, the Illumina 1.8+ maximum) the data
+/// is Phred+64. Returns `None` when all bytes fall in `@..=J`, which is valid
+/// in both encodings.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min > max`, or
+/// [`BioError::InvalidFormatError`] if either byte is outside the printable
+/// quality range `!..=~`.
+pub fn infer_phred_offset(min: u8, max: u8) -> Result<Option<PhredOffset>, BioError> {
+    if min > max {
+        return Err(BioError::InvalidParameterError(format!(
+            "min quality byte {} cannot be larger than max quality byte {}.",
+            min, max
+        )));
+    }
+
+    if min < b'!' || max > b'~' {
+        return Err(BioError::InvalidFormatError(format!(
+            "quality bytes {}-{} are outside the printable range 33-126.",
+            min, max
+        )));
+    }
+
+    let offset = match (min, max) {
+        (min, _) if min < b'@' => Some(PhredOffset::Phred33),
+        (_, max) if max > b'J' => Some(PhredOffset::Phred64),
+        _ => None,
+    };
+
+    Ok(offset)
+}
+
+/// Like [`mean_error_and_phred`], but for qualities encoded with `offset`.
+#[inline]
+pub fn mean_error_and_phred_with_offset(qual: &[u8], offset: PhredOffset) -> (f64, u8) {
+    match offset {
+        PhredOffset::Phred33 => mean_error_and_phred(qual),
+        PhredOffset::Phred64 => mean_error_and_phred(&offset.to_phred33(qual)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b'#', b'J', Some(PhredOffset::Phred33))]
+    #[case(b'5', b'~', Some(PhredOffset::Phred33))]
+    #[case(b'B', b'h', Some(PhredOffset::Phred64))]
+    #[case(b'@', b'J', None)]
+    #[case(b'I', b'I', None)]
+    fn test_infer_phred_offset(
+        #[case] min: u8,
+        #[case] max: u8,
+        #[case] expected: Option<PhredOffset>,
+    ) {
+        assert_eq!(infer_phred_offset(min, max).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(b'J', b'#')]
+    #[case(b' ', b'J')]
+    #[case(b'#', 127)]
+    fn test_infer_phred_offset_invalid(#[case] min: u8, #[case] max: u8) {
+        assert!(infer_phred_offset(min, max).is_err());
+    }
+
+    #[test]
+    fn test_to_phred33() {
+        assert_eq!(PhredOffset::Phred64.to_phred33(b"@Th;"), b"!5I!");
+        assert_eq!(PhredOffset::Phred33.to_phred33(b"!5I"), b"!5I");
+    }
+
+    #[test]
+    fn test_mean_error_and_phred_with_offset() {
+        assert_eq!(
+            mean_error_and_phred_with_offset(b"^^^^", PhredOffset::Phred64),
+            mean_error_and_phred(b"????")
+        );
+    }
+}
@@ -0,0 +1,156 @@
+/// Whether `nt` matches the tail base `base` (`A` or `T`), ignoring case; `U` matches `T`.
+#[inline]
+fn is_tail_base(nt: u8, base: u8) -> bool {
+    match nt.to_ascii_uppercase() {
+        b'U' => base == b'T',
+        nt => nt == base,
+    }
+}
+
+/// Scans `bases` (ordered from the read end inwards) for a run of `base`
+/// with at most `max_mismatch` other bases, and returns its length.
+///
+/// The run starts at the read end, which may itself be a mismatch, and its
+/// inner boundary is always a `base`.
+fn anchored_run_len<'a, I>(bases: I, base: u8, max_mismatch: usize) -> usize
+where
+    I: Iterator<Item = &'a u8>,
+{
+    let mut mismatches = 0;
+    let mut len = 0;
+
+    for (i, nt) in bases.enumerate() {
+        match is_tail_base(*nt, base) {
+            true => len = i + 1,
+            false => {
+                mismatches += 1;
+                if mismatches > max_mismatch {
+                    break;
+                }
+            }
+        }
+    }
+
+    len
+}
+
+/// Finds a poly(A) tail anchored at the 3' end of `seq`.
+///
+/// Unlike [`find_homopolymers`](super::find_homopolymers), the tail may be
+/// interrupted by up to `max_mismatch` non-`A` bases, as is common in ONT
+/// cDNA and direct RNA reads, and the last bases of the read may be
+/// mismatches too. The tail always starts at an `A`. Returns `[start, end)`
+/// with `end == seq.len()`, or `None` if the tail is shorter than `min_len`.
+/// Matching is case insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::find_poly_a_tail;
+///
+/// let read = b"ACGTTGCAAAAAAGAAAAAAAACAAAAAAAA";
+/// assert_eq!(find_poly_a_tail(read, 15, 2), Some((7, 31)));
+/// assert_eq!(find_poly_a_tail(read, 15, 0), None);
+/// ```
+pub fn find_poly_a_tail(seq: &[u8], min_len: usize, max_mismatch: usize) -> Option<(usize, usize)> {
+    let len = anchored_run_len(seq.iter().rev(), b'A', max_mismatch);
+
+    match len >= min_len.max(1) {
+        true => Some((seq.len() - len, seq.len())),
+        false => None,
+    }
+}
+
+/// Finds a poly(T) head anchored at the 5' start of `seq`, the reverse
+/// complement of a poly(A) tail as seen in reverse strand cDNA reads.
+///
+/// See [`find_poly_a_tail`]; `U` counts as `T`. Returns `[0, end)`, or
+/// `None` if the head is shorter than `min_len`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::find_poly_t_head;
+///
+/// assert_eq!(find_poly_t_head(b"TTTTCTTTTTTGCAGT", 8, 1), Some((0, 11)));
+/// ```
+pub fn find_poly_t_head(seq: &[u8], min_len: usize, max_mismatch: usize) -> Option<(usize, usize)> {
+    let len = anchored_run_len(seq.iter(), b'T', max_mismatch);
+
+    match len >= min_len.max(1) {
+        true => Some((0, len)),
+        false => None,
+    }
+}
+
+/// Returns the `[start, end)` range of `seq` left after removing a 5' poly(T)
+/// head and a 3' poly(A) tail, found with [`find_poly_t_head`] and
+/// [`find_poly_a_tail`].
+///
+/// The range can be applied to both sequence and quality. A read consisting
+/// only of tail gives an empty range.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::trim_poly_a;
+///
+/// let read = b"TTTTTTTTGACCGTAGGCAAAAAAAGAAAA";
+/// let (start, end) = trim_poly_a(read, 8, 1);
+/// assert_eq!(&read[start..end], b"GACCGTAGGC");
+/// ```
+pub fn trim_poly_a(seq: &[u8], min_len: usize, max_mismatch: usize) -> (usize, usize) {
+    let start = find_poly_t_head(seq, min_len, max_mismatch).map_or(0, |(_, end)| end);
+    let end = find_poly_a_tail(&seq[start..], min_len, max_mismatch)
+        .map_or(seq.len(), |(tail_start, _)| start + tail_start);
+
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"ACGTAAAAAAAAAA", 10, 0, Some((4, 14)))]
+    #[case(b"ACGTAAAAAAAAA", 10, 0, None)]
+    #[case(b"ACGTAAAAAaaaaa", 10, 0, Some((4, 14)))]
+    // Mismatches at the read end are included, leading mismatches are not.
+    #[case(b"ACGTCAAAAAAAAAG", 10, 1, Some((5, 15)))]
+    #[case(b"GGGGAAAAAAAAAA", 5, 3, Some((4, 14)))]
+    #[case(b"AAAAAAAAAA", 10, 0, Some((0, 10)))]
+    #[case(b"CCCC", 1, 2, None)]
+    #[case(b"", 0, 0, None)]
+    fn test_find_poly_a_tail(
+        #[case] seq: &[u8],
+        #[case] min_len: usize,
+        #[case] max_mismatch: usize,
+        #[case] expected: Option<(usize, usize)>,
+    ) {
+        assert_eq!(find_poly_a_tail(seq, min_len, max_mismatch), expected);
+    }
+
+    #[rstest]
+    #[case(b"TTTTTTTTTTACGT", 10, 0, Some((0, 10)))]
+    #[case(b"UUUUUGUUUUUACGU", 10, 1, Some((0, 11)))]
+    #[case(b"ATTTTTTTTTTC", 10, 0, None)]
+    #[case(b"ATTTTTTTTTTC", 10, 1, Some((0, 11)))]
+    fn test_find_poly_t_head(
+        #[case] seq: &[u8],
+        #[case] min_len: usize,
+        #[case] max_mismatch: usize,
+        #[case] expected: Option<(usize, usize)>,
+    ) {
+        assert_eq!(find_poly_t_head(seq, min_len, max_mismatch), expected);
+    }
+
+    #[rstest]
+    #[case(b"ACGTACGT", (0, 8))]
+    #[case(b"ACGTAAAAAA", (0, 4))]
+    #[case(b"TTTTTTACGT", (6, 10))]
+    #[case(b"TTTTTTAAAAAA", (6, 6))]
+    fn test_trim_poly_a(#[case] seq: &[u8], #[case] expected: (usize, usize)) {
+        assert_eq!(trim_poly_a(seq, 6, 0), expected);
+    }
+}
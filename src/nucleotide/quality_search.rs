@@ -0,0 +1,157 @@
+use super::seq::qual_to_error;
+use crate::errors::BioError;
+
+/// Cost of inserting or deleting a base, regardless of quality.
+const INDEL_COST: f64 = 1.0;
+
+/// Returns `true` if the (possibly IUPAC ambiguous) `pattern_nt` covers `seq_nt`.
+#[inline]
+fn iupac_matches(pattern_nt: u8, seq_nt: u8) -> bool {
+    let seq_nt = seq_nt.to_ascii_uppercase();
+
+    let allowed: &[u8] = match pattern_nt.to_ascii_uppercase() {
+        b'N' => b"ACGT",
+        b'R' => b"AG",
+        b'Y' => b"CT",
+        b'S' => b"GC",
+        b'W' => b"AT",
+        b'K' => b"GT",
+        b'M' => b"AC",
+        b'B' => b"CGT",
+        b'D' => b"AGT",
+        b'H' => b"ACT",
+        b'V' => b"ACG",
+        b'U' => b"T",
+        nt => return nt == seq_nt,
+    };
+
+    allowed.contains(&seq_nt)
+}
+
+/// Cost of a mismatch against a base with the given error probability.
+///
+/// A base with error `0.75` is indistinguishable from a random base and
+/// mismatches against it are free, while a perfect base costs a full edit.
+#[inline]
+fn mismatch_cost(error: f64) -> f64 {
+    (1.0 - error * 4.0 / 3.0).max(0.0)
+}
+
+/// Searches `seq` for approximate matches of `pattern`, weighting mismatches by base quality.
+///
+/// Computes a semi-global alignment (the whole pattern, any substring of
+/// `seq`) where a mismatch against a base with error probability `e` costs
+/// `max(0, 1 - 4e/3)`, i.e. the probability that the observed base is not
+/// just random noise. Insertions and deletions cost `1.0`. Quality bytes are
+/// Phred+33. IUPAC codes in the pattern are honored and matching is case
+/// insensitive.
+///
+/// Returns `(end_position, cost)` for every end position in `seq` (inclusive)
+/// where the alignment cost is at most `max_cost`. The cost can be read as the
+/// expected number of genuine edits, so low-quality ONT bases no longer push
+/// true primer/barcode hits over a flat edit distance cutoff.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `pattern` is empty or
+/// `qual` and `seq` differ in length.
+pub fn search_fuzzy_quality(
+    seq: &[u8],
+    qual: &[u8],
+    pattern: &[u8],
+    max_cost: f64,
+) -> Result<Vec<(usize, f64)>, BioError> {
+    if pattern.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "fuzzy search pattern cannot be empty.".to_string(),
+        ));
+    }
+
+    if seq.len() != qual.len() {
+        return Err(BioError::InvalidParameterError(format!(
+            "sequence length {} does not match quality length {}.",
+            seq.len(),
+            qual.len()
+        )));
+    }
+
+    let m = pattern.len();
+
+    // Column of costs for pattern prefixes of length 0..=m against the current text end.
+    let mut prev: Vec<f64> = (0..=m).map(|i| i as f64 * INDEL_COST).collect();
+    let mut curr: Vec<f64> = vec![0.0; m + 1];
+
+    let mut hits = Vec::new();
+
+    for (j, (&nt, &q)) in seq.iter().zip(qual).enumerate() {
+        let error = qual_to_error(q);
+        let sub = mismatch_cost(error);
+
+        // Free start anywhere in seq.
+        curr[0] = 0.0;
+
+        for i in 1..=m {
+            let diag = prev[i - 1]
+                + match iupac_matches(pattern[i - 1], nt) {
+                    true => 0.0,
+                    false => sub,
+                };
+            let up = curr[i - 1] + INDEL_COST;
+            let left = prev[i] + INDEL_COST;
+
+            curr[i] = diag.min(up).min(left);
+        }
+
+        if curr[m] <= max_cost {
+            hits.push((j, curr[m]));
+        }
+
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_match() {
+        let hits = search_fuzzy_quality(b"AACGTAA", b"IIIIIII", b"CGT", 0.0).unwrap();
+        assert_eq!(hits, vec![(4, 0.0)]);
+    }
+
+    #[test]
+    fn test_low_quality_mismatch_is_cheap() {
+        // Mismatch at position 3 (`C` instead of `G`).
+        let seq = b"AACCTAA";
+
+        let high = search_fuzzy_quality(seq, b"IIIIIII", b"CGT", 0.5).unwrap();
+        assert!(high.is_empty());
+
+        // Quality `#` = Phred 2, error ~0.63.
+        let low = search_fuzzy_quality(seq, b"III#III", b"CGT", 0.5).unwrap();
+        assert_eq!(low.len(), 1);
+        assert_eq!(low[0].0, 4);
+        assert!(low[0].1 < 0.5);
+    }
+
+    #[test]
+    fn test_iupac_pattern() {
+        let hits = search_fuzzy_quality(b"AACGTAA", b"IIIIIII", b"CNT", 0.0).unwrap();
+        assert_eq!(hits, vec![(4, 0.0)]);
+    }
+
+    #[test]
+    fn test_indel() {
+        let hits = search_fuzzy_quality(b"AACGGTAA", b"IIIIIIII", b"CGT", 1.0).unwrap();
+        assert!(hits.iter().any(|(end, cost)| *end == 5 && *cost <= 1.0));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(search_fuzzy_quality(b"ACGT", b"III", b"CG", 1.0).is_err());
+        assert!(search_fuzzy_quality(b"ACGT", b"IIII", b"", 1.0).is_err());
+    }
+}
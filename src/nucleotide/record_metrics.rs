@@ -0,0 +1,228 @@
+use super::entropy::{nucleotide_probabilities, shannon_entropy};
+use super::metrics::mean_error_and_phred;
+
+/// Metrics for a single sequence record, computed by [`RecordMetricsBuilder`].
+///
+/// Metrics that were not selected in the builder are `None`, as are the
+/// quality metrics of records without qualities.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordMetrics {
+    pub length: usize,
+    /// GC fraction, counting both upper and lowercase bases.
+    pub gc: Option<f64>,
+    pub mean_error: Option<f64>,
+    /// Phred score of [`RecordMetrics::mean_error`].
+    pub mean_phred: Option<u8>,
+    /// Shannon entropy (bits) of the case-insensitive `ACGT` composition.
+    pub entropy: Option<f32>,
+    /// Number of `N`/`n` bases.
+    pub n_count: Option<usize>,
+    /// Number of runs of identical bases at least the configured length long.
+    pub homopolymer_count: Option<usize>,
+}
+
+/// Selects which [`RecordMetrics`] to compute.
+///
+/// All selected sequence metrics share a single pass over the sequence, and
+/// quality metrics a single pass over the qualities. Nothing is selected by
+/// default; use [`RecordMetricsBuilder::all`] to compute everything.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::RecordMetricsBuilder;
+///
+/// let builder = RecordMetricsBuilder::new().gc().n_count();
+/// let metrics = builder.compute(b"ACGGNN", None);
+///
+/// assert_eq!(metrics.length, 6);
+/// assert_eq!(metrics.gc, Some(0.5));
+/// assert_eq!(metrics.n_count, Some(2));
+/// assert_eq!(metrics.entropy, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordMetricsBuilder {
+    gc: bool,
+    quality: bool,
+    entropy: bool,
+    n_count: bool,
+    homopolymer_min_len: Option<usize>,
+}
+
+impl RecordMetricsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects every metric, counting homopolymers of at least `homopolymer_min_len` bases.
+    pub fn all(homopolymer_min_len: usize) -> Self {
+        Self::new()
+            .gc()
+            .quality()
+            .entropy()
+            .n_count()
+            .homopolymers(homopolymer_min_len)
+    }
+
+    pub fn gc(mut self) -> Self {
+        self.gc = true;
+        self
+    }
+
+    /// Selects `mean_error` and `mean_phred`.
+    pub fn quality(mut self) -> Self {
+        self.quality = true;
+        self
+    }
+
+    pub fn entropy(mut self) -> Self {
+        self.entropy = true;
+        self
+    }
+
+    pub fn n_count(mut self) -> Self {
+        self.n_count = true;
+        self
+    }
+
+    /// Counts runs of identical bases at least `min_len` long (case sensitive).
+    ///
+    /// A `min_len` of `0` is treated as `1`.
+    pub fn homopolymers(mut self, min_len: usize) -> Self {
+        self.homopolymer_min_len = Some(min_len.max(1));
+        self
+    }
+
+    /// Computes the selected metrics for a record.
+    ///
+    /// `qual` is assumed to be Phred+33 encoded.
+    pub fn compute(&self, seq: &[u8], qual: Option<&[u8]>) -> RecordMetrics {
+        let mut metrics = RecordMetrics {
+            length: seq.len(),
+            ..RecordMetrics::default()
+        };
+
+        if self.gc || self.entropy || self.n_count || self.homopolymer_min_len.is_some() {
+            self.compute_seq(seq, &mut metrics);
+        }
+
+        if self.quality
+            && let Some(qual) = qual.filter(|q| !q.is_empty())
+        {
+            let (mean_error, mean_phred) = mean_error_and_phred(qual);
+            metrics.mean_error = Some(mean_error);
+            metrics.mean_phred = Some(mean_phred);
+        }
+
+        metrics
+    }
+
+    fn compute_seq(&self, seq: &[u8], metrics: &mut RecordMetrics) {
+        let mut counts = [0usize; 4];
+        let mut n_count = 0;
+
+        let min_hp_len = self.homopolymer_min_len.unwrap_or(usize::MAX);
+        let mut hp_count = 0;
+        let mut run_len = 0;
+        let mut last: Option<u8> = None;
+
+        for &nt in seq {
+            match nt {
+                b'A' | b'a' => counts[0] += 1,
+                b'C' | b'c' => counts[1] += 1,
+                b'G' | b'g' => counts[2] += 1,
+                b'T' | b't' => counts[3] += 1,
+                b'N' | b'n' => n_count += 1,
+                _ => {}
+            }
+
+            if last == Some(nt) {
+                run_len += 1;
+            } else {
+                hp_count += (run_len >= min_hp_len) as usize;
+                run_len = 1;
+                last = Some(nt);
+            }
+        }
+        hp_count += (run_len >= min_hp_len) as usize;
+
+        if self.gc {
+            metrics.gc = Some(match seq.len() {
+                0 => 0.0,
+                len => (counts[1] + counts[2]) as f64 / len as f64,
+            });
+        }
+
+        if self.entropy {
+            metrics.entropy = Some(shannon_entropy(&nucleotide_probabilities(&counts)));
+        }
+
+        if self.n_count {
+            metrics.n_count = Some(n_count);
+        }
+
+        if self.homopolymer_min_len.is_some() {
+            metrics.homopolymer_count = Some(hp_count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nucleotide::{find_homopolymers, gc_content, nucleotide_counts};
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"", None)]
+    #[case(b"ACGTNNacgt", Some(b"IIII##IIII".as_slice()))]
+    #[case(b"AAAAcccGGGGGGtN", Some(b"5555555555555+5".as_slice()))]
+    #[case(b"ACGTTTTTTTTGCA", None)]
+    fn test_matches_individual_metrics(#[case] seq: &[u8], #[case] qual: Option<&[u8]>) {
+        let metrics = RecordMetricsBuilder::all(3).compute(seq, qual);
+        let (counts, _, _) = nucleotide_counts(&seq.to_ascii_uppercase());
+
+        assert_eq!(metrics.length, seq.len());
+        assert_eq!(metrics.gc, Some(gc_content(seq)));
+        assert_eq!(
+            metrics.entropy,
+            Some(shannon_entropy(&nucleotide_probabilities(&counts)))
+        );
+        assert_eq!(
+            metrics.n_count,
+            Some(
+                seq.iter()
+                    .filter(|nt| nt.eq_ignore_ascii_case(&b'N'))
+                    .count()
+            )
+        );
+        assert_eq!(
+            metrics.homopolymer_count,
+            Some(find_homopolymers(seq, 3, true).unwrap().len())
+        );
+        assert_eq!(
+            (metrics.mean_error, metrics.mean_phred),
+            match qual {
+                Some(q) => {
+                    let (e, p) = mean_error_and_phred(q);
+                    (Some(e), Some(p))
+                }
+                None => (None, None),
+            }
+        );
+    }
+
+    #[test]
+    fn test_only_selected_metrics() {
+        let metrics = RecordMetricsBuilder::new()
+            .quality()
+            .compute(b"ACGT", Some(b"IIII"));
+
+        assert_eq!(metrics.length, 4);
+        assert_eq!(metrics.mean_phred, Some(40));
+        assert_eq!(metrics.gc, None);
+        assert_eq!(metrics.entropy, None);
+        assert_eq!(metrics.n_count, None);
+        assert_eq!(metrics.homopolymer_count, None);
+    }
+}
@@ -0,0 +1,437 @@
+use super::seq::reverse_complement;
+use super::statics::NT_LOOKUP;
+use crate::errors::BioError;
+use crate::kmers::mm_hash64;
+use std::collections::{HashMap, HashSet};
+
+/// Largest anchor k-mer size used by [`find_direct_repeats`].
+const DIRECT_REPEAT_MAX_KMER: usize = 15;
+
+/// Largest minimizer window used by [`find_direct_repeats`].
+const DIRECT_REPEAT_MAX_WINDOW: usize = 8;
+
+/// Anchor k-mers occurring more often than this are ignored as low complexity.
+const DIRECT_REPEAT_MAX_OCC: usize = 64;
+
+/// An inverted repeat: two reverse-complementary arms separated by a loop.
+///
+/// The left arm is `[start, start + arm_len)` and the right arm is
+/// `[end - arm_len, end)`, both zero-based half-open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvertedRepeat {
+    pub start: usize,
+    pub end: usize,
+    pub arm_len: usize,
+    pub loop_len: usize,
+    /// Number of non-complementary pairs within the arms.
+    pub mismatches: usize,
+}
+
+/// Checks whether two bases form a Watson-Crick pair. Ambiguous bases never pair.
+#[inline]
+fn pairs(a: u8, b: u8) -> bool {
+    matches!(
+        (a, b),
+        (b'A', b'T') | (b'T', b'A') | (b'C', b'G') | (b'G', b'C')
+    )
+}
+
+/// Finds inverted repeats (hairpins and, for `max_loop = 0`, palindromes) in `seq`.
+///
+/// Exact seeds of length `min_arm / (max_mismatch + 1)` are matched against
+/// the reverse complement, so every arm with at most `max_mismatch` mismatches
+/// contains at least one seed. Seeds are extended inwards without mismatches
+/// to find the loop, then outwards allowing up to `max_mismatch` mismatches.
+/// Arms never end in a mismatch.
+///
+/// Returns repeats with `arm_len >= min_arm` and `loop_len <= max_loop`,
+/// sorted by `(start, end)`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_arm` is `0`.
+///
+/// NOTE - is case insensitive.
+pub fn find_inverted_repeats(
+    seq: &[u8],
+    min_arm: usize,
+    max_loop: usize,
+    max_mismatch: usize,
+) -> Result<Vec<InvertedRepeat>, BioError> {
+    if min_arm == 0 {
+        return Err(BioError::InvalidParameterError(
+            "min arm length must be larger than 0.".to_string(),
+        ));
+    }
+
+    let seed_len = (min_arm / (max_mismatch + 1)).max(1);
+    let n = seq.len();
+
+    if n < 2 * min_arm {
+        return Ok(vec![]);
+    }
+
+    let upper = seq.to_ascii_uppercase();
+    let rc = reverse_complement(&upper);
+
+    let mut seeds: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (i, kmer) in upper.windows(seed_len).enumerate() {
+        if kmer
+            .iter()
+            .all(|nt| matches!(nt, b'A' | b'C' | b'G' | b'T'))
+        {
+            seeds.entry(kmer).or_default().push(i);
+        }
+    }
+
+    // Keyed on the outer boundaries, keeping the longest arm.
+    let mut found: HashMap<(usize, usize), InvertedRepeat> = HashMap::new();
+
+    for (r, kmer) in rc.windows(seed_len).enumerate() {
+        let Some(positions) = seeds.get(kmer) else {
+            continue;
+        };
+
+        // upper[j..j + seed_len] is the reverse complement of `kmer`.
+        let j = n - r - seed_len;
+
+        for &i in positions {
+            if i + seed_len > j {
+                continue;
+            }
+
+            // Innermost pair, extended towards the loop.
+            let (mut p, mut q) = (i + seed_len - 1, j);
+            while p + 1 < q - 1 && pairs(upper[p + 1], upper[q - 1]) {
+                p += 1;
+                q -= 1;
+            }
+
+            let loop_len = q - p - 1;
+            if loop_len > max_loop {
+                continue;
+            }
+
+            // Outermost pair, extended away from the loop.
+            let (mut l, mut r) = (i, j + seed_len - 1);
+            let (mut best_l, mut best_r, mut best_mm) = (l, r, 0);
+            let mut mismatches = 0;
+
+            while l > 0 && r + 1 < n {
+                l -= 1;
+                r += 1;
+
+                if pairs(upper[l], upper[r]) {
+                    (best_l, best_r, best_mm) = (l, r, mismatches);
+                } else {
+                    mismatches += 1;
+                    if mismatches > max_mismatch {
+                        break;
+                    }
+                }
+            }
+
+            let arm_len = p - best_l + 1;
+            if arm_len < min_arm {
+                continue;
+            }
+
+            let repeat = InvertedRepeat {
+                start: best_l,
+                end: best_r + 1,
+                arm_len,
+                loop_len,
+                mismatches: best_mm,
+            };
+
+            found
+                .entry((repeat.start, repeat.end))
+                .and_modify(|existing| {
+                    if repeat.arm_len > existing.arm_len {
+                        *existing = repeat.clone();
+                    }
+                })
+                .or_insert(repeat);
+        }
+    }
+
+    let mut repeats: Vec<InvertedRepeat> = found.into_values().collect();
+    repeats.sort_by_key(|r| (r.start, r.end));
+
+    Ok(repeats)
+}
+
+/// A pair of directly repeated segments `[start, start + len)` and
+/// `[copy_start, copy_start + len)`, with `start < copy_start`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectRepeat {
+    pub start: usize,
+    pub copy_start: usize,
+    pub len: usize,
+    /// Fraction of identical bases between the two copies.
+    pub identity: f64,
+}
+
+/// Returns `(position, kmer)` window minimizers of `seq`, in position order.
+///
+/// K-mers containing ambiguous bases are never selected.
+fn minimizers(seq: &[u8], kmer_size: usize, window_size: usize) -> Vec<(usize, u64)> {
+    let mask: u64 = (1 << (kmer_size << 1)) - 1;
+    let mut kmer: u64 = 0;
+    let mut valid = 0;
+
+    let hashed: Vec<Option<(u64, u64)>> = seq
+        .iter()
+        .map(|nt_char| {
+            let nt = NT_LOOKUP[*nt_char as usize] as u64;
+            if nt >= 4 {
+                valid = 0;
+                return None;
+            }
+
+            kmer = (kmer << 2 | nt) & mask;
+            valid += 1;
+            (valid >= kmer_size).then(|| (mm_hash64(kmer), kmer))
+        })
+        .skip(kmer_size - 1)
+        .collect();
+
+    let mut selected: Vec<(usize, u64)> = Vec::new();
+
+    for (start, window) in hashed.windows(window_size).enumerate() {
+        let best = window
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, h)| h.map(|(hash, kmer)| (hash, start + offset, kmer)))
+            .min();
+
+        if let Some((_, pos, kmer)) = best
+            && selected.last().is_none_or(|(last, _)| *last != pos)
+        {
+            selected.push((pos, kmer));
+        }
+    }
+
+    selected
+}
+
+/// Finds directly repeated segments (same orientation) of at least `min_len` bases.
+///
+/// Window minimizers of the sequence form a self-sketch; shared minimizers
+/// anchor candidate copies, which are extended to maximal exact matches.
+/// Exact matches on the same diagonal separated by at most `max_gap`
+/// mismatching bases are merged, so diverged copies are reported as one
+/// repeat with `identity < 1.0`, provided each exact piece is long enough to
+/// contain an anchor (at most 22 bases). Indels split a repeat.
+///
+/// A segment with more than two copies is reported once per pair of copies.
+/// Copies may overlap (tandem duplications). Returns repeats sorted by
+/// `(start, copy_start)`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_len` is `0`.
+///
+/// NOTE - is case insensitive.
+pub fn find_direct_repeats(
+    seq: &[u8],
+    min_len: usize,
+    max_gap: usize,
+) -> Result<Vec<DirectRepeat>, BioError> {
+    if min_len == 0 {
+        return Err(BioError::InvalidParameterError(
+            "min repeat length must be larger than 0.".to_string(),
+        ));
+    }
+
+    let kmer_size = min_len.min(DIRECT_REPEAT_MAX_KMER);
+    // Every exact copy of `min_len` bases contains at least one full window.
+    let window_size = (min_len - kmer_size + 1).min(DIRECT_REPEAT_MAX_WINDOW);
+
+    if seq.len() < min_len + 1 {
+        return Ok(vec![]);
+    }
+
+    let upper = seq.to_ascii_uppercase();
+    let n = upper.len();
+
+    let mut anchors: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (pos, kmer) in minimizers(&upper, kmer_size, window_size) {
+        anchors.entry(kmer).or_default().push(pos);
+    }
+
+    // Maximal exact matches as (diagonal, start, len).
+    let mut mems: HashSet<(usize, usize, usize)> = HashSet::new();
+
+    for positions in anchors.values() {
+        if positions.len() > DIRECT_REPEAT_MAX_OCC {
+            continue;
+        }
+
+        for (idx, &a) in positions.iter().enumerate() {
+            for &b in &positions[idx + 1..] {
+                let (mut start, mut copy_start) = (a, b);
+                while start > 0 && upper[start - 1] == upper[copy_start - 1] {
+                    start -= 1;
+                    copy_start -= 1;
+                }
+
+                let mut len = a - start + kmer_size;
+                while copy_start + len < n && upper[start + len] == upper[copy_start + len] {
+                    len += 1;
+                }
+
+                mems.insert((b - a, start, len));
+            }
+        }
+    }
+
+    let mut mems: Vec<(usize, usize, usize)> = mems.into_iter().collect();
+    mems.sort_unstable();
+
+    let mut repeats = Vec::new();
+    let mut emit = |diagonal: usize, start: usize, end: usize| {
+        let len = end - start;
+        if len < min_len {
+            return;
+        }
+
+        let identical = (start..end)
+            .filter(|&i| upper[i] == upper[i + diagonal])
+            .count();
+
+        repeats.push(DirectRepeat {
+            start,
+            copy_start: start + diagonal,
+            len,
+            identity: identical as f64 / len as f64,
+        });
+    };
+
+    let mut current: Option<(usize, usize, usize)> = None;
+    for (diagonal, start, len) in mems {
+        current = match current {
+            Some((d, s, e)) if d == diagonal && start <= e + max_gap => {
+                Some((d, s, e.max(start + len)))
+            }
+            Some((d, s, e)) => {
+                emit(d, s, e);
+                Some((diagonal, start, start + len))
+            }
+            None => Some((diagonal, start, start + len)),
+        };
+    }
+
+    if let Some((d, s, e)) = current {
+        emit(d, s, e);
+    }
+
+    repeats.sort_by_key(|r| (r.start, r.copy_start));
+    Ok(repeats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn ir(
+        start: usize,
+        end: usize,
+        arm_len: usize,
+        loop_len: usize,
+        mismatches: usize,
+    ) -> InvertedRepeat {
+        InvertedRepeat {
+            start,
+            end,
+            arm_len,
+            loop_len,
+            mismatches,
+        }
+    }
+
+    #[rstest]
+    // AACCTG / CAGGTT arms around a GGGG loop.
+    #[case(b"TTAACCTGGGGGCAGGTTGG", 6, 10, 0, vec![ir(2, 18, 6, 4, 0)])]
+    // Loop too long.
+    #[case(b"TTAACCTGGGGGCAGGTTGG", 6, 3, 0, vec![])]
+    // Even palindrome, EcoRI site.
+    #[case(b"ccGAATTCcc", 3, 0, 0, vec![ir(2, 8, 3, 0, 0)])]
+    #[case(b"AAAAAAAAAAAA", 3, 5, 0, vec![])]
+    #[case(b"", 3, 5, 0, vec![])]
+    fn test_find_inverted_repeats(
+        #[case] seq: &[u8],
+        #[case] min_arm: usize,
+        #[case] max_loop: usize,
+        #[case] max_mismatch: usize,
+        #[case] expected: Vec<InvertedRepeat>,
+    ) {
+        assert_eq!(
+            find_inverted_repeats(seq, min_arm, max_loop, max_mismatch).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_find_inverted_repeats_mismatch() {
+        // Outer arm pair (A, A) at positions 0 and 19 is a mismatch, (G, C) beyond it pairs.
+        let seq = b"GAAACCTGTTTTCAGGTTAC";
+        assert!(find_inverted_repeats(seq, 8, 4, 0).unwrap().is_empty());
+
+        let repeats = find_inverted_repeats(seq, 8, 4, 1).unwrap();
+        assert_eq!(repeats, vec![ir(0, 20, 8, 4, 1)]);
+    }
+
+    #[test]
+    fn test_find_inverted_repeats_invalid() {
+        assert!(find_inverted_repeats(b"ACGT", 0, 3, 0).is_err());
+    }
+
+    const SEGMENT: &[u8] = b"ACGGTCAGTTCAGGATCCTAGCATG";
+
+    #[test]
+    fn test_find_direct_repeats_exact() {
+        let seq = [SEGMENT, b"TTTTTTTTTT", SEGMENT].concat();
+        let repeats = find_direct_repeats(&seq, 20, 0).unwrap();
+
+        assert_eq!(
+            repeats,
+            vec![DirectRepeat {
+                start: 0,
+                copy_start: 35,
+                len: 25,
+                identity: 1.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_find_direct_repeats_diverged() {
+        let segment = [SEGMENT, &SEGMENT[..25]].concat();
+        let mut copy = segment.clone();
+        copy[25] = b'T';
+        let seq = [&segment, b"TTTTTTTTTT".as_slice(), &copy].concat();
+
+        // The mismatch splits the copy into exact matches shorter than 30.
+        assert!(find_direct_repeats(&seq, 30, 0).unwrap().is_empty());
+
+        let repeats = find_direct_repeats(&seq, 30, 2).unwrap();
+        assert_eq!(repeats.len(), 1);
+        assert_eq!((repeats[0].start, repeats[0].copy_start), (0, 60));
+        assert_eq!(repeats[0].len, 50);
+        assert!((repeats[0].identity - 49.0 / 50.0).abs() < 1e-9);
+    }
+
+    #[rstest]
+    #[case(b"", 5)]
+    #[case(b"ACGTGCATGACCA", 5)]
+    fn test_find_direct_repeats_none(#[case] seq: &[u8], #[case] min_len: usize) {
+        assert!(find_direct_repeats(seq, min_len, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_direct_repeats_invalid() {
+        assert!(find_direct_repeats(b"ACGT", 0, 0).is_err());
+    }
+}
@@ -1,10 +1,17 @@
+use super::seq::{Strand, reverse_complement};
+use crate::errors::BioError;
+use aho_corasick::{AhoCorasick, MatchKind};
 use bio::pattern_matching::myers::MyersBuilder;
 use memchr::memmem;
 
-/// Builds a Myers matcher with all IUPAC ambiguity codes pre-configured.
+/// Maximum pattern length handled by the single-word Myers implementation.
+const MYERS_WORD_SIZE: usize = 64;
+
+/// Builds a Myers builder with all IUPAC ambiguity codes pre-configured.
 #[inline]
-fn myers_builder(pattern: &[u8]) -> bio::pattern_matching::myers::Myers {
-    MyersBuilder::new()
+fn myers_builder() -> MyersBuilder {
+    let mut builder = MyersBuilder::new();
+    builder
         .ambig(b'N', b"ACGT")
         .ambig(b'R', b"AG")
         .ambig(b'Y', b"CT")
@@ -15,8 +22,108 @@ fn myers_builder(pattern: &[u8]) -> bio::pattern_matching::myers::Myers {
         .ambig(b'B', b"CGT")
         .ambig(b'D', b"AGT")
         .ambig(b'H', b"ACT")
-        .ambig(b'V', b"ACG")
-        .build_64(pattern)
+        .ambig(b'V', b"ACG");
+    builder
+}
+
+/// Returns a Myers builder with all IUPAC ambiguity codes pre-configured, as
+/// used by [`search_fuzzy_matches`] (`perf` feature).
+///
+/// Compiling a pattern once with `build_64` and reusing it avoids rebuilding
+/// the pattern bit vectors on every search call when scanning many sequences.
+#[cfg(feature = "perf")]
+pub fn iupac_myers_builder() -> MyersBuilder {
+    myers_builder()
+}
+
+/// A pattern occurrence reported by the `*_matches` search functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// Start of the match in the searched sequence (inclusive).
+    pub start: usize,
+    /// End of the match in the searched sequence (exclusive).
+    pub end: usize,
+    /// Strand of the pattern, with reverse strand matches reported in
+    /// forward strand coordinates.
+    pub strand: Strand,
+    /// Edit distance between the pattern and the match, `0` for exact search.
+    pub edits: u8,
+    /// Index of the matched pattern for multi-pattern search, otherwise `0`.
+    pub pattern_id: usize,
+}
+
+impl Match {
+    /// Creates an exact, forward strand match of pattern `0`.
+    fn exact(start: usize, len: usize) -> Self {
+        Self {
+            start,
+            end: start + len,
+            strand: Strand::Forward,
+            edits: 0,
+            pattern_id: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Searches `seq` for approximate matches of `pattern` using the Myers
+/// bit-parallel algorithm, with start positions found by traceback.
+///
+/// Reports one [`Match`] with at most `max_mismatches` edits per end
+/// position, ordered by end. IUPAC ambiguity codes in the pattern are
+/// expanded automatically. Patterns up to 64 bases use the single-word
+/// algorithm; longer patterns automatically fall back to the block-based
+/// variant.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `pattern` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::search_fuzzy_matches;
+///
+/// let hits = search_fuzzy_matches(b"TTACGTTT", b"ACGT", 0).unwrap();
+/// assert_eq!((hits[0].start, hits[0].end, hits[0].edits), (2, 6, 0));
+/// ```
+pub fn search_fuzzy_matches(
+    seq: &[u8],
+    pattern: &[u8],
+    max_mismatches: u8,
+) -> Result<Vec<Match>, BioError> {
+    if pattern.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "fuzzy search pattern cannot be empty.".to_string(),
+        ));
+    }
+
+    let builder = myers_builder();
+    let to_match = |(start, end, edits): (usize, usize, u8)| Match {
+        start,
+        end,
+        strand: Strand::Forward,
+        edits,
+        pattern_id: 0,
+    };
+
+    if pattern.len() <= MYERS_WORD_SIZE {
+        let mut myers = builder.build_64(pattern);
+        return Ok(myers.find_all(seq, max_mismatches).map(to_match).collect());
+    }
+
+    let mut myers = builder.build_long_64(pattern);
+    Ok(myers
+        .find_all(seq, max_mismatches as usize)
+        .map(|(start, end, dist)| to_match((start, end, dist as u8)))
+        .collect())
 }
 
 /// Searches `seq` for approximate matches of `pattern` using the Myers bit-parallel algorithm.
@@ -24,23 +131,181 @@ fn myers_builder(pattern: &[u8]) -> bio::pattern_matching::myers::Myers {
 /// IUPAC ambiguity codes in the pattern are expanded automatically.
 /// Returns a vec of `(end_position, edit_distance)` for every match with
 /// at most `max_mismatches` edits. The `end_position` is the inclusive end
-/// index of each match in `seq`.
-pub fn search_fuzzy(seq: &[u8], pattern: &[u8], max_mismatches: u8) -> Vec<(usize, u8)> {
-    let mut myers = myers_builder(pattern);
-    myers.find_all_lazy(seq, max_mismatches).collect()
+/// index of each match in `seq`. See [`search_fuzzy_matches`] for matches
+/// with start positions.
+///
+/// Patterns up to 64 bases use the single-word algorithm; longer patterns
+/// automatically fall back to the block-based variant.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `pattern` is empty.
+pub fn search_fuzzy(
+    seq: &[u8],
+    pattern: &[u8],
+    max_mismatches: u8,
+) -> Result<Vec<(usize, u8)>, BioError> {
+    if pattern.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "fuzzy search pattern cannot be empty.".to_string(),
+        ));
+    }
+
+    // Lazy search skips the traceback that only start positions need.
+    let builder = myers_builder();
+
+    if pattern.len() <= MYERS_WORD_SIZE {
+        let mut myers = builder.build_64(pattern);
+        return Ok(myers.find_all_lazy(seq, max_mismatches).collect());
+    }
+
+    let mut myers = builder.build_long_64(pattern);
+    Ok(myers
+        .find_all_lazy(seq, max_mismatches as usize)
+        .map(|(end, dist)| (end, dist as u8))
+        .collect())
+}
+
+/// Searches `seq` for all exact occurrences of `pattern`, see [`search_exact`].
+pub fn search_exact_matches(seq: &[u8], pattern: &[u8]) -> Vec<Match> {
+    memmem::find_iter(seq, pattern)
+        .map(|start| Match::exact(start, pattern.len()))
+        .collect()
 }
 
 /// Searches `seq` for all exact occurrences of `pattern`.
 ///
 /// Uses SIMD-accelerated substring search via [`memchr::memmem`].
-/// Returns the starting byte offset of each non-overlapping match.
+/// Returns the starting byte offset of each non-overlapping match. Never
+/// panics; an empty pattern matches at every offset `0..=seq.len()`.
 pub fn search_exact(seq: &[u8], pattern: &[u8]) -> Vec<usize> {
-    memmem::find_iter(seq, pattern).collect()
+    search_exact_matches(seq, pattern)
+        .into_iter()
+        .map(|m| m.start)
+        .collect()
+}
+
+/// Searches both strands of `seq` for exact occurrences of `pattern`, see
+/// [`search_exact_both_strands`].
+pub fn search_exact_both_strands_matches(seq: &[u8], pattern: &[u8]) -> Vec<Match> {
+    let rc = reverse_complement(pattern);
+
+    let mut hits: Vec<Match> = search_exact_matches(seq, pattern)
+        .into_iter()
+        .chain(search_exact_matches(seq, &rc).into_iter().map(|m| Match {
+            strand: Strand::Reverse,
+            ..m
+        }))
+        .collect();
+
+    hits.sort_unstable_by_key(|m| (m.start, m.strand));
+    hits
+}
+
+/// Searches both strands of `seq` for exact occurrences of `pattern`.
+///
+/// Reverse strand hits are found by searching for the reverse complement of
+/// `pattern`, and are reported by their start on the forward strand. Returns
+/// `(start, strand)` sorted by start then strand. Reverse complement palindromes
+/// (e.g. `GAATTC`) are reported once per strand.
+pub fn search_exact_both_strands(seq: &[u8], pattern: &[u8]) -> Vec<(usize, Strand)> {
+    search_exact_both_strands_matches(seq, pattern)
+        .into_iter()
+        .map(|m| (m.start, m.strand))
+        .collect()
+}
+
+/// Multi-pattern exact searcher backed by an Aho-Corasick automaton.
+///
+/// Build once and reuse across many sequences, e.g. when screening reads
+/// for dozens of adapters or contaminant probes.
+pub struct ExactMultiSearcher {
+    automaton: AhoCorasick,
+}
+
+impl ExactMultiSearcher {
+    /// Builds the automaton over `patterns`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `patterns` is empty,
+    /// contains an empty pattern, or the automaton cannot be built.
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Result<Self, BioError> {
+        if patterns.is_empty() || patterns.iter().any(|p| p.as_ref().is_empty()) {
+            return Err(BioError::InvalidParameterError(
+                "patterns must be non-empty.".to_string(),
+            ));
+        }
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::Standard)
+            .build(patterns)
+            .map_err(|e| BioError::InvalidParameterError(e.to_string()))?;
+
+        Ok(Self { automaton })
+    }
+
+    /// Returns a [`Match`] for every, possibly overlapping, occurrence of any
+    /// pattern in `seq`, sorted by start then pattern index.
+    pub fn search_matches(&self, seq: &[u8]) -> Vec<Match> {
+        let mut hits: Vec<Match> = self
+            .automaton
+            .find_overlapping_iter(seq)
+            .map(|m| Match {
+                pattern_id: m.pattern().as_usize(),
+                ..Match::exact(m.start(), m.len())
+            })
+            .collect();
+
+        hits.sort_unstable_by_key(|m| (m.start, m.pattern_id));
+        hits
+    }
+
+    /// Returns `(start, pattern_index)` for every, possibly overlapping, occurrence
+    /// of any pattern in `seq`, sorted by start then pattern index.
+    pub fn search(&self, seq: &[u8]) -> Vec<(usize, usize)> {
+        self.search_matches(seq)
+            .into_iter()
+            .map(|m| (m.start, m.pattern_id))
+            .collect()
+    }
+}
+
+/// Searches `seq` for exact occurrences of all `patterns` in a single pass.
+///
+/// Convenience wrapper around [`ExactMultiSearcher`]; prefer building the
+/// searcher once when screening many sequences. Returns `(start, pattern_index)`
+/// for every occurrence, including overlapping ones.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `patterns` is empty or
+/// contains an empty pattern.
+pub fn search_exact_multi<P: AsRef<[u8]>>(
+    seq: &[u8],
+    patterns: &[P],
+) -> Result<Vec<(usize, usize)>, BioError> {
+    Ok(ExactMultiSearcher::new(patterns)?.search(seq))
+}
+
+/// Searches `seq` for exact occurrences of all `patterns` in a single pass,
+/// see [`search_exact_multi`].
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `patterns` is empty or
+/// contains an empty pattern.
+pub fn search_exact_multi_matches<P: AsRef<[u8]>>(
+    seq: &[u8],
+    patterns: &[P],
+) -> Result<Vec<Match>, BioError> {
+    Ok(ExactMultiSearcher::new(patterns)?.search_matches(seq))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::*;
 
     #[test]
     fn test_search_exact_found() {
@@ -60,16 +325,23 @@ mod tests {
         assert_eq!(hits, vec![2]);
     }
 
+    #[test]
+    fn test_search_exact_degenerate() {
+        assert_eq!(search_exact(b"AC", b""), vec![0, 1, 2]);
+        assert!(search_exact(b"AC", b"ACGT").is_empty());
+        assert!(search_exact(b"", b"A").is_empty());
+    }
+
     #[test]
     fn test_search_fuzzy_exact_match() {
-        let hits = search_fuzzy(b"AACGTAA", b"CGT", 0);
+        let hits = search_fuzzy(b"AACGTAA", b"CGT", 0).unwrap();
         assert_eq!(hits.len(), 1);
         assert_eq!(hits[0].1, 0); // edit distance 0
     }
 
     #[test]
     fn test_search_fuzzy_with_mismatches() {
-        let hits = search_fuzzy(b"AACCTAA", b"CGT", 2);
+        let hits = search_fuzzy(b"AACCTAA", b"CGT", 2).unwrap();
         assert!(!hits.is_empty());
         for (_end, dist) in &hits {
             assert!(*dist <= 2);
@@ -78,7 +350,116 @@ mod tests {
 
     #[test]
     fn test_search_fuzzy_no_match() {
-        let hits = search_fuzzy(b"AAAAAAA", b"CGT", 0);
+        let hits = search_fuzzy(b"AAAAAAA", b"CGT", 0).unwrap();
         assert!(hits.is_empty());
     }
+
+    #[test]
+    fn test_search_exact_multi() {
+        let patterns: Vec<&[u8]> = vec![b"ACGT", b"GTA", b"TTT"];
+        let hits = search_exact_multi(b"ACGTACGTTT", &patterns).unwrap();
+        assert_eq!(hits, vec![(0, 0), (2, 1), (4, 0), (7, 2)]);
+    }
+
+    #[test]
+    fn test_search_exact_multi_overlapping_patterns() {
+        let patterns: Vec<&[u8]> = vec![b"AAA", b"AA"];
+        let hits = search_exact_multi(b"AAAA", &patterns).unwrap();
+        assert_eq!(hits, vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn test_search_exact_multi_invalid() {
+        let empty: Vec<&[u8]> = vec![];
+        assert!(search_exact_multi(b"ACGT", &empty).is_err());
+        assert!(search_exact_multi(b"ACGT", &[b"".as_slice()]).is_err());
+    }
+
+    #[test]
+    fn test_exact_multi_searcher_reuse() {
+        let searcher = ExactMultiSearcher::new(&["AGATCGGAAGAGC", "CTGTCTCTTATA"]).unwrap();
+        assert_eq!(searcher.search(b"TTAGATCGGAAGAGCTT"), vec![(2, 0)]);
+        assert!(searcher.search(b"ACGTACGT").is_empty());
+    }
+
+    #[test]
+    fn test_search_matches() {
+        let hits = search_exact_both_strands_matches(b"AACGTTT", b"AAC");
+        assert_eq!(
+            hits,
+            vec![
+                Match::exact(0, 3),
+                Match {
+                    strand: Strand::Reverse,
+                    ..Match::exact(3, 3)
+                },
+            ]
+        );
+
+        let searcher = ExactMultiSearcher::new(&["AAA", "AA"]).unwrap();
+        let spans: Vec<(usize, usize, usize)> = searcher
+            .search_matches(b"AAAA")
+            .iter()
+            .map(|m| (m.start, m.end, m.pattern_id))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![(0, 3, 0), (0, 2, 1), (1, 4, 0), (1, 3, 1), (2, 4, 1)]
+        );
+    }
+
+    #[test]
+    fn test_search_fuzzy_matches() {
+        // One deletion relative to the pattern.
+        let hits = search_fuzzy_matches(b"GGGACTTGGG", b"ACGT", 1).unwrap();
+        assert!(
+            hits.iter()
+                .any(|m| m.edits == 1 && m.start == 3 && m.end == 6)
+        );
+
+        for m in hits {
+            let (end, edits) = (m.end - 1, m.edits);
+            assert!(
+                search_fuzzy(b"GGGACTTGGG", b"ACGT", 1)
+                    .unwrap()
+                    .contains(&(end, edits))
+            );
+        }
+    }
+
+    #[test]
+    fn test_search_fuzzy_empty_pattern() {
+        assert!(search_fuzzy(b"ACGT", b"", 1).is_err());
+    }
+
+    #[test]
+    fn test_search_fuzzy_long_pattern() {
+        let pattern: Vec<u8> = b"ACGTTGCA".repeat(16);
+        let mut seq = b"TTTTT".to_vec();
+        seq.extend_from_slice(&pattern);
+        seq.extend_from_slice(b"GGGGG");
+
+        let hits = search_fuzzy(&seq, &pattern, 0).unwrap();
+        assert_eq!(hits, vec![(5 + pattern.len() - 1, 0)]);
+
+        // Introduce a single substitution.
+        seq[50] = if seq[50] == b'A' { b'C' } else { b'A' };
+        let hits = search_fuzzy(&seq, &pattern, 1).unwrap();
+        assert!(hits.iter().any(|(_, dist)| *dist == 1));
+        assert!(search_fuzzy(&seq, &pattern, 0).unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[case(b"AACGTTT", b"AAC", vec![(0, Strand::Forward), (3, Strand::Reverse)])]
+    #[case(b"AACGTTT", b"AAA", vec![(4, Strand::Reverse)])]
+    #[case(b"GGAATTCC", b"GAATTC", vec![(1, Strand::Forward), (1, Strand::Reverse)])]
+    #[case(b"ACCTTTGGT", b"ACC", vec![(0, Strand::Forward), (6, Strand::Reverse)])]
+    #[case(b"AAAA", b"CG", vec![])]
+    fn test_search_exact_both_strands(
+        #[case] seq: &[u8],
+        #[case] pattern: &[u8],
+        #[case] expected: Vec<(usize, Strand)>,
+    ) {
+        assert_eq!(search_exact_both_strands(seq, pattern), expected);
+    }
 }
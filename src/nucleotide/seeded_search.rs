@@ -0,0 +1,152 @@
+use super::search::{Match, search_fuzzy_matches};
+use super::statics::NT_LOOKUP;
+use crate::errors::BioError;
+use crate::intervals::merge_intervals;
+use memchr::memmem;
+
+/// Searches `seq` for approximate matches of a long `pattern` by seed and extend.
+///
+/// The pattern is cut into non-overlapping seeds of `seed_len` bases, which
+/// are located exactly with [`memchr::memmem`]. Each seed hit implies a
+/// candidate placement of the whole pattern; the surrounding window, padded by
+/// `max_edits` on both sides, is verified with Myers edit distance via
+/// [`search_fuzzy_matches`]. Only these windows are aligned, which is much
+/// faster than scanning megabase sequences with Myers for 200+ bp patterns.
+///
+/// Within each window the best matches are selected greedily by edit distance
+/// so that reported matches do not overlap, and they are returned sorted by
+/// start. Seeds containing ambiguous bases are not searched, and seeding is
+/// case sensitive.
+///
+/// The search is heuristic: a match is only found if at least one seed
+/// occurs in it without edits. That is guaranteed when `seed_len` is at most
+/// `pattern.len() / (max_edits + 1)`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `pattern` is empty, or
+/// `seed_len` is `0` or longer than `pattern`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::search_fuzzy_seeded;
+///
+/// let pattern = b"ACGTTGCAAGCTTAGGCATC".repeat(10);
+/// let mut seq = b"TTTTT".repeat(1000);
+/// seq.splice(2000..2000, pattern.iter().copied());
+/// seq[2100] = b'G';
+///
+/// let hits = search_fuzzy_seeded(&seq, &pattern, 4, 20).unwrap();
+/// assert_eq!(hits.len(), 1);
+/// assert_eq!((hits[0].start, hits[0].end, hits[0].edits), (2000, 2200, 1));
+/// ```
+pub fn search_fuzzy_seeded(
+    seq: &[u8],
+    pattern: &[u8],
+    max_edits: u8,
+    seed_len: usize,
+) -> Result<Vec<Match>, BioError> {
+    if pattern.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "fuzzy search pattern cannot be empty.".to_string(),
+        ));
+    }
+
+    if seed_len == 0 || seed_len > pattern.len() {
+        return Err(BioError::InvalidParameterError(format!(
+            "seed length must be in 1..={}, got {}.",
+            pattern.len(),
+            seed_len
+        )));
+    }
+
+    let m = pattern.len();
+    let pad = max_edits as usize;
+
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for (i, seed) in pattern.chunks_exact(seed_len).enumerate() {
+        if seed.iter().any(|nt| NT_LOOKUP[*nt as usize] > 3) {
+            continue;
+        }
+
+        let offset = i * seed_len;
+        for hit in memmem::find_iter(seq, seed) {
+            let start = hit.saturating_sub(offset + pad);
+            let end = (hit.saturating_sub(offset) + m + pad).min(seq.len());
+            windows.push((start, end));
+        }
+    }
+
+    let mut hits: Vec<Match> = Vec::new();
+    for window in merge_intervals(windows) {
+        let mut candidates =
+            search_fuzzy_matches(&seq[window.start..window.end], pattern, max_edits)?;
+        candidates.sort_unstable_by_key(|m| (m.edits, m.start));
+
+        let first = hits.len();
+        for candidate in candidates {
+            let candidate = Match {
+                start: candidate.start + window.start,
+                end: candidate.end + window.start,
+                ..candidate
+            };
+
+            if hits[first..]
+                .iter()
+                .all(|hit| candidate.end <= hit.start || candidate.start >= hit.end)
+            {
+                hits.push(candidate);
+            }
+        }
+    }
+
+    hits.sort_unstable_by_key(|m| m.start);
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::test_utils::random_sequence;
+    use rstest::*;
+
+    #[test]
+    fn test_seeded_finds_edited_copies() {
+        let pattern = random_sequence(&mut SplitMix64::new(1), 240, 0.5);
+        let mut seq = random_sequence(&mut SplitMix64::new(2), 50_000, 0.5);
+
+        // A copy with a substitution and a deletion, and an exact copy.
+        let mut edited = pattern.clone();
+        edited[30] = if edited[30] == b'A' { b'C' } else { b'A' };
+        edited.remove(150);
+        seq.splice(10_000..10_000 + edited.len(), edited.iter().copied());
+        seq.splice(30_000..30_240, pattern.iter().copied());
+
+        let hits = search_fuzzy_seeded(&seq, &pattern, 5, 24).unwrap();
+        let spans: Vec<(usize, usize, u8)> =
+            hits.iter().map(|m| (m.start, m.end, m.edits)).collect();
+        assert_eq!(spans, vec![(10_000, 10_239, 2), (30_000, 30_240, 0)]);
+
+        assert_eq!(search_fuzzy_seeded(&seq, &pattern, 1, 24).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_seeded_adjacent_copies() {
+        let pattern = random_sequence(&mut SplitMix64::new(3), 200, 0.5);
+        let seq = [pattern.as_slice(), b"ACG", &pattern].concat();
+
+        let hits = search_fuzzy_seeded(&seq, &pattern, 3, 20).unwrap();
+        let spans: Vec<(usize, usize)> = hits.iter().map(|m| (m.start, m.end)).collect();
+        assert_eq!(spans, vec![(0, 200), (203, 403)]);
+    }
+
+    #[rstest]
+    #[case(b"", 1)]
+    #[case(b"ACGT", 0)]
+    #[case(b"ACGT", 5)]
+    fn test_seeded_invalid(#[case] pattern: &[u8], #[case] seed_len: usize) {
+        assert!(search_fuzzy_seeded(b"ACGTACGT", pattern, 1, seed_len).is_err());
+    }
+}
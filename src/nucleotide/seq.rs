@@ -1,14 +1,106 @@
+use super::statics::{MAX_PHRED, PHRED_TO_ERROR};
+use crate::errors::BioError;
+
+/// DNA strand orientation relative to the reference/input sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+impl Strand {
+    /// Returns the conventional `+`/`-` symbol, as used in BED and PAF.
+    pub fn as_char(&self) -> char {
+        match self {
+            Strand::Forward => '+',
+            Strand::Reverse => '-',
+        }
+    }
+}
+
 /// Converts an error probability to a Phred quality score.
 ///
-/// Applies the standard formula: `Q = -10 * log10(error)`.
+/// Applies the standard formula: `Q = -10 * log10(error)`, saturating at
+/// [`MAX_PHRED`] for an error of `0.0`. Errors above `1.0` and `NaN` give `0`;
+/// use [`checked_error_to_phred`] to reject them instead.
 #[inline]
 pub fn error_to_phred(error: f64) -> u8 {
-    (-10_f64 * error.log10()) as u8
+    let phred = -10_f64 * error.log10();
+
+    match phred.is_nan() {
+        true => 0,
+        false => phred.clamp(0.0, MAX_PHRED as f64) as u8,
+    }
+}
+
+/// Like [`error_to_phred`], but rejects invalid error probabilities.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `error` is outside
+/// `[0.0, 1.0]` or `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{MAX_PHRED, checked_error_to_phred};
+///
+/// assert_eq!(checked_error_to_phred(0.001).unwrap(), 30);
+/// assert_eq!(checked_error_to_phred(0.0).unwrap(), MAX_PHRED);
+/// assert!(checked_error_to_phred(1.5).is_err());
+/// ```
+pub fn checked_error_to_phred(error: f64) -> Result<u8, BioError> {
+    if !(0.0..=1.0).contains(&error) {
+        return Err(BioError::InvalidParameterError(format!(
+            "error probability {} must be in range 0-1.",
+            error
+        )));
+    }
+
+    Ok(error_to_phred(error))
+}
+
+/// Error probability of a raw Phred+33 quality byte, looked up in
+/// [`PHRED_TO_ERROR`]. Bytes above `~` are treated as [`MAX_PHRED`] and
+/// bytes below [`PHRED_OFFSET`](super::PHRED_OFFSET) as error `1.0`, so every
+/// byte is accepted.
+#[inline]
+pub fn qual_to_error(qual: u8) -> f64 {
+    PHRED_TO_ERROR[(qual as usize).min(PHRED_TO_ERROR.len() - 1)]
+}
+
+/// Complements a single base, mapping `A` to `U` instead of `T` when `rna` is `true`.
+#[inline]
+fn complement(nt: u8, rna: bool) -> u8 {
+    match nt {
+        // Canonical
+        b'A' if rna => b'U',
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        b'U' if rna => b'A',
+        // Ambiguous
+        b'R' => b'Y', // AG <-> CT
+        b'Y' => b'R', // CT <-> AG
+        b'S' => b'S', // GC
+        b'W' => b'W', // AT
+        b'K' => b'M', // GT <-> AC
+        b'M' => b'K', // AC <-> GT
+        b'B' => b'V', // CGT <-> ACG
+        b'D' => b'H', // AGT <-> ACT
+        b'H' => b'D', // ACT <-> AGT
+        b'V' => b'B', // ACG <-> CGT
+        b'N' => b'N',
+        // Unknown nucleotides map to N
+        _ => b'N',
+    }
 }
 
 /// Returns the reverse complement of a DNA sequence.
 ///
-/// Handles all IUPAC ambiguity codes. Unrecognized bytes are mapped to `N`.
+/// Handles all IUPAC ambiguity codes. Unrecognized bytes, including `U`, are
+/// mapped to `N`; use [`reverse_complement_rna`] for RNA.
 ///
 /// # Examples
 ///
@@ -20,33 +112,52 @@ pub fn error_to_phred(error: f64) -> u8 {
 /// ```
 #[inline]
 pub fn reverse_complement(seq: &[u8]) -> Vec<u8> {
-    let mut rc = Vec::with_capacity(seq.len());
-
-    for &nt in seq.iter().rev() {
-        rc.push(match nt {
-            // Canonical
-            b'A' => b'T',
-            b'C' => b'G',
-            b'G' => b'C',
-            b'T' => b'A',
-            // Ambiguous
-            b'R' => b'Y', // AG <-> CT
-            b'Y' => b'R', // CT <-> AG
-            b'S' => b'S', // GC
-            b'W' => b'W', // AT
-            b'K' => b'M', // GT <-> AC
-            b'M' => b'K', // AC <-> GT
-            b'B' => b'V', // CGT <-> ACG
-            b'D' => b'H', // AGT <-> ACT
-            b'H' => b'D', // ACT <-> AGT
-            b'V' => b'B', // ACG <-> CGT
-            b'N' => b'N',
-            // Unknown nucleotides map to N
-            _ => b'N',
-        });
-    }
+    seq.iter().rev().map(|nt| complement(*nt, false)).collect()
+}
+
+/// Returns the reverse complement of an RNA sequence, pairing `A` with `U`.
+///
+/// Handles all IUPAC ambiguity codes. A `T` in the input is complemented to
+/// `A`, so mixed input is tolerated, but the output never contains `T`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::reverse_complement_rna;
+///
+/// assert_eq!(reverse_complement_rna(b"AACGU"), b"ACGUU");
+/// ```
+#[inline]
+pub fn reverse_complement_rna(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|nt| complement(*nt, true)).collect()
+}
 
-    rc
+/// Transcribes DNA to RNA by replacing `T`/`t` with `U`/`u`.
+///
+/// All other bytes, including softmasking, are kept as is.
+#[inline]
+pub fn transcribe(dna: &[u8]) -> Vec<u8> {
+    dna.iter()
+        .map(|nt| match nt {
+            b'T' => b'U',
+            b't' => b'u',
+            _ => *nt,
+        })
+        .collect()
+}
+
+/// Back-transcribes RNA to DNA by replacing `U`/`u` with `T`/`t`.
+///
+/// All other bytes, including softmasking, are kept as is.
+#[inline]
+pub fn back_transcribe(rna: &[u8]) -> Vec<u8> {
+    rna.iter()
+        .map(|nt| match nt {
+            b'U' => b'T',
+            b'u' => b't',
+            _ => *nt,
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -76,11 +187,70 @@ mod tests {
         assert_eq!(reverse_complement(seq), expected);
     }
 
+    #[rstest]
+    #[case(b"AUGC", b"GCAU")]
+    #[case(b"AUNR", b"YNAU")]
+    #[case(b"ATG", b"CAU")]
+    fn test_reverse_complement_rna(#[case] seq: &[u8], #[case] expected: &[u8]) {
+        assert_eq!(reverse_complement_rna(seq), expected);
+    }
+
+    #[rstest]
+    #[case(b"", b"")]
+    #[case(b"ATGCtaN", b"AUGCuaN")]
+    fn test_transcribe(#[case] dna: &[u8], #[case] rna: &[u8]) {
+        assert_eq!(transcribe(dna), rna);
+        assert_eq!(back_transcribe(rna), dna);
+    }
+
+    #[test]
+    fn test_rna_revcomp_matches_dna() {
+        let dna = b"ACGTTGCAAGTN";
+        assert_eq!(
+            reverse_complement_rna(&transcribe(dna)),
+            transcribe(&reverse_complement(dna))
+        );
+    }
+
     #[rstest]
     #[case(0.1, 10)]
     #[case(0.01, 20)]
     #[case(0.001, 30)]
+    // Saturates instead of wrapping.
+    #[case(0.0, MAX_PHRED)]
+    #[case(1e-12, MAX_PHRED)]
+    #[case(1.0, 0)]
+    #[case(2.0, 0)]
+    #[case(-0.5, 0)]
+    #[case(f64::NAN, 0)]
     fn test_error_to_phred(#[case] error: f64, #[case] expected: u8) {
         assert_eq!(error_to_phred(error), expected);
     }
+
+    #[rstest]
+    #[case(-0.1)]
+    #[case(1.01)]
+    #[case(f64::NAN)]
+    fn test_checked_error_to_phred_invalid(#[case] error: f64) {
+        assert!(checked_error_to_phred(error).is_err());
+    }
+
+    #[rstest]
+    #[case(b'!', 1.0)]
+    #[case(b'+', 0.1)]
+    #[case(b']', 1e-6)]
+    #[case(b'~', 10_f64.powf(-9.3))]
+    // Non-ASCII bytes saturate at MAX_PHRED.
+    #[case(255, 10_f64.powf(-9.3))]
+    #[case(0, 1.0)]
+    fn test_qual_to_error(#[case] qual: u8, #[case] expected: f64) {
+        assert!((qual_to_error(qual) - expected).abs() < 1e-12);
+    }
+
+    #[rstest]
+    #[case(b'~')]
+    #[case(255)]
+    fn test_qual_to_error_saturates_at_max_phred(#[case] qual: u8) {
+        assert_eq!(error_to_phred(qual_to_error(qual)), MAX_PHRED);
+    }
 }
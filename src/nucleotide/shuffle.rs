@@ -0,0 +1,178 @@
+use crate::rng::SplitMix64;
+
+/// Sequence shuffling strategy for [`shuffle_sequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShuffleMode {
+    /// Permutes individual bases, preserving base composition.
+    Mononucleotide,
+    /// Preserves dinucleotide counts as well as the first and last base
+    /// (Altschul & Erickson, 1985).
+    Dinucleotide,
+    /// Permutes complete codons in frame 0, preserving codon usage.
+    /// A trailing partial codon is kept in place.
+    Codon,
+}
+
+/// Shuffles `seq` according to `mode`, e.g. to generate null models for motif analyses.
+///
+/// Output is fully determined by `seed`.
+///
+/// NOTE - is case sensitive, `a` and `A` are treated as different bases.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{ShuffleMode, shuffle_sequence};
+///
+/// let shuffled = shuffle_sequence(b"ATGAAACCCGGGTAA", ShuffleMode::Codon, 42);
+/// assert_eq!(shuffled.len(), 15);
+/// assert_eq!(shuffled, shuffle_sequence(b"ATGAAACCCGGGTAA", ShuffleMode::Codon, 42));
+/// ```
+pub fn shuffle_sequence(seq: &[u8], mode: ShuffleMode, seed: u64) -> Vec<u8> {
+    let mut rng = SplitMix64::new(seed);
+
+    match mode {
+        ShuffleMode::Mononucleotide => {
+            let mut shuffled = seq.to_vec();
+            rng.shuffle(&mut shuffled);
+            shuffled
+        }
+        ShuffleMode::Dinucleotide => dinucleotide_shuffle(seq, &mut rng),
+        ShuffleMode::Codon => {
+            let mut codons: Vec<&[u8]> = seq.chunks_exact(3).collect();
+            rng.shuffle(&mut codons);
+
+            let mut shuffled = codons.concat();
+            shuffled.extend_from_slice(seq.chunks_exact(3).remainder());
+            shuffled
+        }
+    }
+}
+
+/// Altschul-Erickson dinucleotide shuffle via a random Eulerian walk.
+///
+/// Each dinucleotide is an edge in a multigraph over bases. A random last
+/// edge is chosen for every base such that the last edges form a tree
+/// rooted at the final base; the remaining edges are permuted freely. Walking
+/// the graph from the first base then uses every edge exactly once.
+fn dinucleotide_shuffle(seq: &[u8], rng: &mut SplitMix64) -> Vec<u8> {
+    if seq.len() < 3 {
+        return seq.to_vec();
+    }
+
+    let mut edges: Vec<Vec<u8>> = vec![Vec::new(); 256];
+    for w in seq.windows(2) {
+        edges[w[0] as usize].push(w[1]);
+    }
+
+    let last = seq[seq.len() - 1] as usize;
+    let vertices: Vec<usize> = (0..256)
+        .filter(|&v| v != last && !edges[v].is_empty())
+        .collect();
+
+    // Index into edges[v] of the edge used to leave v for the last time.
+    let mut last_edge = vec![0usize; 256];
+
+    loop {
+        for &v in &vertices {
+            last_edge[v] = rng.next_below(edges[v].len());
+        }
+
+        let reaches_last = vertices.iter().all(|&start| {
+            let mut v = start;
+            for _ in 0..=vertices.len() {
+                if v == last {
+                    return true;
+                }
+                v = edges[v][last_edge[v]] as usize;
+            }
+            v == last
+        });
+
+        if reaches_last {
+            break;
+        }
+    }
+
+    for &v in &vertices {
+        let n = edges[v].len();
+        edges[v].swap(last_edge[v], n - 1);
+        rng.shuffle(&mut edges[v][..n - 1]);
+    }
+    rng.shuffle(&mut edges[last]);
+
+    let mut next = vec![0usize; 256];
+    let mut shuffled = Vec::with_capacity(seq.len());
+    let mut v = seq[0];
+    shuffled.push(v);
+
+    for _ in 1..seq.len() {
+        let nt = edges[v as usize][next[v as usize]];
+        next[v as usize] += 1;
+        shuffled.push(nt);
+        v = nt;
+    }
+
+    shuffled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    const SEQ: &[u8] = b"ATGGCGTACGATCGATCGGGCTAGCTAGCATCGACTAGCATCAGCTACGAAA";
+
+    fn counts(items: impl Iterator<Item = Vec<u8>>) -> HashMap<Vec<u8>, usize> {
+        let mut counts = HashMap::new();
+        for item in items {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[rstest]
+    #[case(ShuffleMode::Mononucleotide)]
+    #[case(ShuffleMode::Dinucleotide)]
+    #[case(ShuffleMode::Codon)]
+    fn test_shuffle_deterministic(#[case] mode: ShuffleMode) {
+        let a = shuffle_sequence(SEQ, mode, 7);
+        assert_eq!(a, shuffle_sequence(SEQ, mode, 7));
+        assert_ne!(a, shuffle_sequence(SEQ, mode, 8));
+
+        let mono = |s: &[u8]| counts(s.iter().map(|nt| vec![*nt]));
+        assert_eq!(mono(&a), mono(SEQ));
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    fn test_dinucleotide_shuffle_preserves_counts(#[case] seed: u64) {
+        let shuffled = shuffle_sequence(SEQ, ShuffleMode::Dinucleotide, seed);
+        let di = |s: &[u8]| counts(s.windows(2).map(|w| w.to_vec()));
+
+        assert_eq!(di(&shuffled), di(SEQ));
+        assert_eq!(shuffled[0], SEQ[0]);
+        assert_eq!(shuffled[SEQ.len() - 1], SEQ[SEQ.len() - 1]);
+    }
+
+    #[test]
+    fn test_codon_shuffle_preserves_codons() {
+        let shuffled = shuffle_sequence(SEQ, ShuffleMode::Codon, 3);
+        let codons = |s: &[u8]| counts(s.chunks_exact(3).map(|c| c.to_vec()));
+
+        assert_eq!(codons(&shuffled), codons(SEQ));
+        // SEQ has 52 bases, the trailing base stays in place.
+        assert_eq!(shuffled[51], SEQ[51]);
+    }
+
+    #[rstest]
+    #[case(b"")]
+    #[case(b"A")]
+    #[case(b"AC")]
+    fn test_shuffle_short(#[case] seq: &[u8]) {
+        assert_eq!(shuffle_sequence(seq, ShuffleMode::Dinucleotide, 0), seq);
+    }
+}
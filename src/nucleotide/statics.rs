@@ -3,8 +3,12 @@ use lazy_static::lazy_static;
 /// ASCII offset for Phred+33 quality encoding (Sanger/Illumina 1.8+).
 pub const PHRED_OFFSET: usize = 33;
 
-/// Maximum Phred index stored in [`PHRED_TO_ERROR`] (Phred 60 = index 93).
-const MAX_PHRED_INDEX: usize = 93;
+/// Highest Phred score encodable in Phred+33 (`~`). Phred conversions
+/// saturate at this value.
+pub const MAX_PHRED: u8 = 93;
+
+/// Maximum Phred index stored in [`PHRED_TO_ERROR`] ([`MAX_PHRED`] = index 126).
+const MAX_PHRED_INDEX: usize = PHRED_OFFSET + MAX_PHRED as usize;
 
 lazy_static! {
     /// 2-bit nucleotide encoding table indexed by ASCII byte value.
@@ -33,8 +37,8 @@ lazy_static! {
     /// Phred score to error probability lookup table.
     ///
     /// Indexed by raw quality byte (Phred+33 encoded). Indices below
-    /// [`PHRED_OFFSET`] default to `1.0`. The table is capped at Phred 60
-    /// (index 93) since higher scores represent negligible error rates.
+    /// [`PHRED_OFFSET`] default to `1.0`. The table ends at [`MAX_PHRED`]
+    /// (index 126, `~`), the highest score Phred+33 can encode.
     pub static ref PHRED_TO_ERROR: [f64; MAX_PHRED_INDEX + 1] = {
         let mut error_lookup = [1.0; MAX_PHRED_INDEX + 1];
 
@@ -0,0 +1,232 @@
+use super::seq::reverse_complement;
+use crate::errors::BioError;
+
+/// Parameters of [`find_telomeres`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelomereParams {
+    /// Telomeric repeat unit as found at the 3' end of a contig. Its reverse
+    /// complement is searched at the 5' end.
+    pub motif: Vec<u8>,
+    /// Minimum length of a reported telomere.
+    pub min_len: usize,
+    /// Maximum number of bases between consecutive motif copies, and between
+    /// the contig end and the outermost copy.
+    pub max_gap: usize,
+}
+
+impl Default for TelomereParams {
+    /// The vertebrate `TTAGGG` repeat.
+    fn default() -> Self {
+        Self {
+            motif: b"TTAGGG".to_vec(),
+            min_len: 100,
+            max_gap: 30,
+        }
+    }
+}
+
+/// A telomeric repeat array at one end of a contig.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Telomere {
+    /// Zero-based half-open span, reaching the contig end.
+    pub start: usize,
+    pub end: usize,
+    /// Number of exact motif copies.
+    pub copies: usize,
+    /// Fraction of the span covered by exact motif copies.
+    pub purity: f64,
+}
+
+impl Telomere {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Telomeres found by [`find_telomeres`] at both ends of a contig.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ContigTelomeres {
+    pub start: Option<Telomere>,
+    pub end: Option<Telomere>,
+}
+
+impl ContigTelomeres {
+    /// Whether both ends are capped, as expected for a telomere-to-telomere
+    /// chromosome.
+    pub fn is_t2t(&self) -> bool {
+        self.start.is_some() && self.end.is_some()
+    }
+}
+
+/// Whether `motif` occurs at `pos` of `seq`, ignoring case.
+#[inline]
+fn motif_at(seq: &[u8], motif: &[u8], pos: usize) -> bool {
+    seq[pos..pos + motif.len()].eq_ignore_ascii_case(motif)
+}
+
+/// Walks exact copies of `motif` inwards from the 5' end of `seq`, allowing
+/// at most `max_gap` bases before each copy. Returns `(end, copies)`.
+fn walk_from_start(seq: &[u8], motif: &[u8], max_gap: usize) -> (usize, usize) {
+    let mut boundary = 0;
+    let mut copies = 0;
+
+    while let Some(pos) = (boundary..=boundary + max_gap)
+        .take_while(|pos| pos + motif.len() <= seq.len())
+        .find(|&pos| motif_at(seq, motif, pos))
+    {
+        boundary = pos + motif.len();
+        copies += 1;
+    }
+
+    (boundary, copies)
+}
+
+/// Walks exact copies of `motif` inwards from the 3' end of `seq`, allowing
+/// at most `max_gap` bases after each copy. Returns `(start, copies)`.
+fn walk_from_end(seq: &[u8], motif: &[u8], max_gap: usize) -> (usize, usize) {
+    let mut boundary = seq.len();
+    let mut copies = 0;
+
+    while let Some(pos) = (0..=max_gap)
+        .map_while(|gap| boundary.checked_sub(gap + motif.len()))
+        .find(|&pos| motif_at(seq, motif, pos))
+    {
+        boundary = pos;
+        copies += 1;
+    }
+
+    (boundary, copies)
+}
+
+/// Finds telomeric repeat arrays at both ends of a contig, for assembly
+/// telomere-to-telomere QC.
+///
+/// A telomere is a run of exact motif copies starting within `max_gap`
+/// bases of the contig end, each copy within `max_gap` bases of the previous
+/// one, so diverged repeat units are tolerated as gaps and lower the
+/// reported purity. The motif is searched as given at the 3' end and as its
+/// reverse complement at the 5' end (`CCCTAA` for the default `TTAGGG`).
+/// Arrays shorter than `min_len` are not reported. Matching is case
+/// insensitive.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if the motif is empty.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{TelomereParams, find_telomeres};
+///
+/// let contig = ["CCCTAA".repeat(30), "ACGTTGCA".repeat(50), "TTAGGG".repeat(10)].concat();
+///
+/// let telomeres = find_telomeres(contig.as_bytes(), &TelomereParams::default()).unwrap();
+/// let start = telomeres.start.unwrap();
+/// assert_eq!((start.start, start.end, start.copies), (0, 180, 30));
+/// assert_eq!(start.purity, 1.0);
+///
+/// // The 3' array is only 60 bases long.
+/// assert_eq!(telomeres.end, None);
+/// ```
+pub fn find_telomeres(seq: &[u8], params: &TelomereParams) -> Result<ContigTelomeres, BioError> {
+    if params.motif.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "telomere motif must not be empty.".to_string(),
+        ));
+    }
+
+    let motif_len = params.motif.len();
+    let telomere = |start: usize, end: usize, copies: usize| {
+        (end - start >= params.min_len.max(1)).then(|| Telomere {
+            start,
+            end,
+            copies,
+            purity: (copies * motif_len) as f64 / (end - start) as f64,
+        })
+    };
+
+    let (start_end, start_copies) =
+        walk_from_start(seq, &reverse_complement(&params.motif), params.max_gap);
+    let (end_start, end_copies) = walk_from_end(seq, &params.motif, params.max_gap);
+
+    Ok(ContigTelomeres {
+        start: telomere(0, start_end, start_copies),
+        end: telomere(end_start, seq.len(), end_copies),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn contig(start: &str, end: &str) -> Vec<u8> {
+        [start, &"GATCCATG".repeat(100), end].concat().into_bytes()
+    }
+
+    #[test]
+    fn test_find_telomeres_t2t() {
+        let seq = contig(&"CCCTAA".repeat(50), &"TTAGGG".repeat(40));
+        let telomeres = find_telomeres(&seq, &TelomereParams::default()).unwrap();
+
+        assert!(telomeres.is_t2t());
+        assert_eq!(telomeres.start.unwrap().len(), 300);
+        let end = telomeres.end.unwrap();
+        assert_eq!((end.start, end.end), (seq.len() - 240, seq.len()));
+        assert_eq!(end.copies, 40);
+    }
+
+    #[test]
+    fn test_find_telomeres_impure() {
+        // Variant TTGGGG units and a partial copy at the contig end.
+        let end = ["TTAGGGTTAGGGTTGGGG".repeat(20), "TTAG".to_string()].concat();
+        let seq = contig("", &end.to_lowercase());
+        let telomere = find_telomeres(&seq, &TelomereParams::default())
+            .unwrap()
+            .end
+            .unwrap();
+
+        assert_eq!(telomere.len(), 364);
+        assert_eq!(telomere.copies, 40);
+        assert!((telomere.purity - 240.0 / 364.0).abs() < 1e-9);
+    }
+
+    #[rstest]
+    // Wrong orientation at each end.
+    #[case("TTAGGG", "CCCTAA")]
+    // 3' repeat array too far from the contig end.
+    #[case("CCCTAA", "TTAGGG")]
+    fn test_find_telomeres_none(#[case] start_unit: &str, #[case] end_unit: &str) {
+        let end = [end_unit.repeat(40), "A".repeat(50)].concat();
+        let seq = contig(&start_unit.repeat(40), &end);
+        let telomeres = find_telomeres(&seq, &TelomereParams::default()).unwrap();
+
+        assert_eq!(telomeres.end, None);
+        assert_eq!(telomeres.start.is_some(), start_unit == "CCCTAA");
+    }
+
+    #[test]
+    fn test_find_telomeres_custom_motif() {
+        let params = TelomereParams {
+            motif: b"TTTAGGG".to_vec(),
+            ..Default::default()
+        };
+        let seq = contig("", &"TTTAGGG".repeat(20));
+
+        assert!(find_telomeres(&seq, &params).unwrap().end.is_some());
+        assert!(find_telomeres(b"", &params).unwrap().end.is_none());
+    }
+
+    #[test]
+    fn test_find_telomeres_invalid() {
+        let params = TelomereParams {
+            motif: Vec::new(),
+            ..Default::default()
+        };
+        assert!(find_telomeres(b"ACGT", &params).is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use super::search::search_fuzzy_matches;
+use super::seq::{Strand, reverse_complement};
+use crate::errors::BioError;
+use bio::io::fasta;
+use std::cmp::Reverse;
+
+/// Hits within this many bases of a contig end are terminal, as in VecScreen.
+const TERMINAL_DISTANCE: usize = 25;
+
+/// Lowest score of a reported hit (a weak terminal match).
+const MIN_SCORE: usize = 16;
+
+/// Score penalty per edit, approximating VecScreen's `+1/-5` scoring in
+/// which a mismatch costs its own match bonus plus the penalty.
+const EDIT_PENALTY: usize = 6;
+
+/// A set of vector, adapter and primer sequences for [`screen_vector`], such
+/// as NCBI UniVec.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VectorDb {
+    names: Vec<String>,
+    seqs: Vec<Vec<u8>>,
+}
+
+impl VectorDb {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a database from FASTA records, named by record id.
+    pub fn from_records(records: &[fasta::Record]) -> Self {
+        let mut db = Self::new();
+        for record in records {
+            db.add(record.id(), record.seq());
+        }
+
+        db
+    }
+
+    /// Adds a sequence. Matching is case insensitive.
+    pub fn add(&mut self, name: impl Into<String>, seq: &[u8]) {
+        self.names.push(name.into());
+        self.seqs.push(seq.to_ascii_uppercase());
+    }
+
+    pub fn len(&self) -> usize {
+        self.seqs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seqs.is_empty()
+    }
+
+    /// Name of the sequence with index `id`, as reported by [`VectorHit::vector_id`].
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+}
+
+/// VecScreen match categories, ordered by strength.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VectorMatchStrength {
+    Weak,
+    Moderate,
+    Strong,
+}
+
+impl VectorMatchStrength {
+    /// Categorizes a score with VecScreen's thresholds, which are lower for
+    /// terminal hits.
+    fn from_score(score: usize, terminal: bool) -> Option<Self> {
+        let (weak, moderate, strong) = match terminal {
+            true => (MIN_SCORE, 19, 24),
+            false => (23, 25, 30),
+        };
+
+        match score {
+            s if s >= strong => Some(Self::Strong),
+            s if s >= moderate => Some(Self::Moderate),
+            s if s >= weak => Some(Self::Weak),
+            _ => None,
+        }
+    }
+}
+
+/// A vector segment found by [`screen_vector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VectorHit {
+    /// Zero-based half-open span in the screened sequence.
+    pub start: usize,
+    pub end: usize,
+    /// Index of the vector in the [`VectorDb`].
+    pub vector_id: usize,
+    pub strand: Strand,
+    pub edits: u8,
+    /// Approximate VecScreen score, `len - 6 * edits`.
+    pub score: usize,
+    /// Whether the hit lies within 25 bases of a sequence end.
+    pub terminal: bool,
+    pub strength: VectorMatchStrength,
+}
+
+/// Result of [`screen_vector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorScreen {
+    /// Non-overlapping hits, sorted by start.
+    pub hits: Vec<VectorHit>,
+    /// Recommended `[start, end)` range to keep after trimming strong and
+    /// moderate terminal hits. Empty if nothing is left.
+    pub keep: (usize, usize),
+}
+
+impl VectorScreen {
+    /// Whether a strong or moderate hit lies inside the kept range, which
+    /// cannot be trimmed and calls for splitting or manual review.
+    pub fn has_internal_contamination(&self) -> bool {
+        self.hits.iter().any(|hit| {
+            hit.strength >= VectorMatchStrength::Moderate
+                && hit.start >= self.keep.0
+                && hit.end <= self.keep.1
+        })
+    }
+}
+
+/// Maximum number of edits a match of `len` bases can have and still score
+/// [`MIN_SCORE`].
+fn max_edits(len: usize) -> u8 {
+    (len.saturating_sub(MIN_SCORE) / EDIT_PENALTY).min(u8::MAX as usize) as u8
+}
+
+/// Screens `seq` for vector, adapter and primer contamination, in the manner
+/// of NCBI VecScreen, and recommends how to trim it.
+///
+/// Every database sequence is searched on both strands with Myers fuzzy
+/// search, as a whole anywhere in `seq`, and as a prefix or suffix of at
+/// least 16 bases overhanging either end of `seq`. Hits are scored as
+/// `len - 6 * edits`, approximating VecScreen's `+1/-5` scoring, and
+/// categorized as strong, moderate or weak with its thresholds: 24/19/16
+/// for terminal hits within 25 bases of an end, and 30/25/23 for internal
+/// hits. Overlapping hits are resolved by score.
+///
+/// Strong and moderate terminal hits are trimmed in
+/// [`VectorScreen::keep`]; internal hits are only reported. Each sequence is
+/// scanned in full, so the cost grows with the database size times the
+/// sequence length.
+///
+/// # Errors
+///
+/// Does not fail for a valid [`VectorDb`]; the `Result` forwards errors of
+/// [`search_fuzzy_matches`].
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{VectorDb, VectorMatchStrength, screen_vector};
+///
+/// let mut db = VectorDb::new();
+/// db.add("illumina_adapter", b"AGATCGGAAGAGCACACGTCTGAACTCCAGTCAC");
+///
+/// // The last 21 bases of the adapter precede the insert.
+/// let contig = b"ACACGTCTGAACTCCAGTCACGGCTTACGATTGCACCTAGGTTCAGCAAGGTCCATG";
+/// let screen = screen_vector(contig, &db).unwrap();
+///
+/// assert_eq!(screen.hits.len(), 1);
+/// assert_eq!(screen.hits[0].strength, VectorMatchStrength::Moderate);
+/// assert_eq!(screen.keep, (21, contig.len()));
+/// ```
+pub fn screen_vector(seq: &[u8], db: &VectorDb) -> Result<VectorScreen, BioError> {
+    let seq = seq.to_ascii_uppercase();
+    let len = seq.len();
+
+    let mut candidates: Vec<VectorHit> = Vec::new();
+    let mut push = |start: usize, end: usize, vector_id: usize, strand: Strand, edits: u8| {
+        let terminal = start <= TERMINAL_DISTANCE || end + TERMINAL_DISTANCE >= len;
+        let score = (end - start).saturating_sub(EDIT_PENALTY * edits as usize);
+
+        if let Some(strength) = VectorMatchStrength::from_score(score, terminal) {
+            candidates.push(VectorHit {
+                start,
+                end,
+                vector_id,
+                strand,
+                edits,
+                score,
+                terminal,
+                strength,
+            });
+        }
+    };
+
+    for (vector_id, vector) in db.seqs.iter().enumerate() {
+        let rc = reverse_complement(vector);
+
+        for (pattern, strand) in [(vector.as_slice(), Strand::Forward), (&rc, Strand::Reverse)] {
+            if pattern.len() < MIN_SCORE {
+                continue;
+            }
+
+            for hit in search_fuzzy_matches(&seq, pattern, max_edits(pattern.len()))? {
+                push(hit.start, hit.end, vector_id, strand, hit.edits);
+            }
+
+            // Pattern suffixes overhanging the 5' end and prefixes overhanging
+            // the 3' end; the best scoring ones survive overlap resolution.
+            for part_len in (MIN_SCORE..pattern.len()).rev() {
+                let k = max_edits(part_len);
+                let window = &seq[..(part_len + k as usize).min(len)];
+                let suffix = &pattern[pattern.len() - part_len..];
+
+                let best = search_fuzzy_matches(window, suffix, k)?
+                    .into_iter()
+                    .min_by_key(|hit| (hit.edits, hit.start));
+                if let Some(hit) = best {
+                    push(hit.start, hit.end, vector_id, strand, hit.edits);
+                }
+            }
+
+            for part_len in (MIN_SCORE..pattern.len()).rev() {
+                let k = max_edits(part_len);
+                let offset = len.saturating_sub(part_len + k as usize);
+                let prefix = &pattern[..part_len];
+
+                let best = search_fuzzy_matches(&seq[offset..], prefix, k)?
+                    .into_iter()
+                    .min_by_key(|hit| (hit.edits, Reverse(hit.end)));
+                if let Some(hit) = best {
+                    push(
+                        offset + hit.start,
+                        offset + hit.end,
+                        vector_id,
+                        strand,
+                        hit.edits,
+                    );
+                }
+            }
+        }
+    }
+
+    candidates.sort_unstable_by_key(|hit| (Reverse(hit.score), hit.start, hit.vector_id));
+    let mut hits: Vec<VectorHit> = Vec::new();
+    for candidate in candidates {
+        if hits
+            .iter()
+            .all(|hit| candidate.end <= hit.start || candidate.start >= hit.end)
+        {
+            hits.push(candidate);
+        }
+    }
+    hits.sort_unstable_by_key(|hit| hit.start);
+
+    let trimmed = hits
+        .iter()
+        .filter(|hit| hit.strength >= VectorMatchStrength::Moderate);
+    let keep_start = trimmed
+        .clone()
+        .filter(|hit| hit.start <= TERMINAL_DISTANCE)
+        .map(|hit| hit.end)
+        .max()
+        .unwrap_or(0);
+    let keep_end = trimmed
+        .filter(|hit| hit.end + TERMINAL_DISTANCE >= len)
+        .map(|hit| hit.start)
+        .min()
+        .unwrap_or(len)
+        .max(keep_start);
+
+    Ok(VectorScreen {
+        hits,
+        keep: (keep_start, keep_end),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::test_utils::random_sequence;
+    use rstest::*;
+
+    const ADAPTER: &[u8] = b"AGATCGGAAGAGCACACGTCTGAACTCCAGTCAC";
+
+    fn db() -> VectorDb {
+        let mut db = VectorDb::new();
+        db.add("adapter", ADAPTER);
+        db
+    }
+
+    #[test]
+    fn test_screen_vector_clean() {
+        let screen =
+            screen_vector(&random_sequence(&mut SplitMix64::new(1), 2000, 0.5), &db()).unwrap();
+        assert!(screen.hits.is_empty());
+        assert_eq!(screen.keep, (0, 2000));
+        assert!(!screen.has_internal_contamination());
+    }
+
+    #[test]
+    fn test_screen_vector_terminal_with_mismatch() {
+        let mut adapter = reverse_complement(ADAPTER);
+        adapter[10] = if adapter[10] == b'A' { b'C' } else { b'A' };
+        let seq = [
+            random_sequence(&mut SplitMix64::new(2), 1000, 0.5),
+            adapter,
+            b"GT".to_vec(),
+        ]
+        .concat();
+
+        let screen = screen_vector(&seq, &db()).unwrap();
+        let hit = screen.hits[0];
+        assert_eq!((hit.start, hit.end), (1000, 1034));
+        assert_eq!((hit.strand, hit.edits, hit.score), (Strand::Reverse, 1, 28));
+        assert_eq!(hit.strength, VectorMatchStrength::Strong);
+        assert!(hit.terminal);
+        assert_eq!(screen.keep, (0, 1000));
+    }
+
+    #[rstest]
+    // Adapter prefix overhanging the 3' end.
+    #[case(30, VectorMatchStrength::Strong)]
+    #[case(20, VectorMatchStrength::Moderate)]
+    #[case(17, VectorMatchStrength::Weak)]
+    fn test_screen_vector_partial_3_prime(
+        #[case] part_len: usize,
+        #[case] strength: VectorMatchStrength,
+    ) {
+        let seq = [
+            random_sequence(&mut SplitMix64::new(3), 500, 0.5).as_slice(),
+            &ADAPTER[..part_len],
+        ]
+        .concat();
+
+        let screen = screen_vector(&seq, &db()).unwrap();
+        assert_eq!(screen.hits.len(), 1);
+        assert_eq!(screen.hits[0].start, 500);
+        assert_eq!(screen.hits[0].strength, strength);
+
+        let expected_end = match strength {
+            VectorMatchStrength::Weak => seq.len(),
+            _ => 500,
+        };
+        assert_eq!(screen.keep, (0, expected_end));
+    }
+
+    #[test]
+    fn test_screen_vector_internal() {
+        let seq = [
+            random_sequence(&mut SplitMix64::new(4), 500, 0.5),
+            ADAPTER.to_ascii_lowercase(),
+            random_sequence(&mut SplitMix64::new(5), 500, 0.5),
+        ]
+        .concat();
+
+        let screen = screen_vector(&seq, &db()).unwrap();
+        assert_eq!(screen.hits.len(), 1);
+        assert!(!screen.hits[0].terminal);
+        assert_eq!(screen.keep, (0, seq.len()));
+        assert!(screen.has_internal_contamination());
+    }
+
+    #[test]
+    fn test_screen_vector_whole_sequence() {
+        let screen = screen_vector(ADAPTER, &db()).unwrap();
+        assert_eq!(screen.hits.len(), 1);
+        assert_eq!(screen.keep.0, screen.keep.1);
+    }
+}
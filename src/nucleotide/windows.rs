@@ -0,0 +1,130 @@
+use crate::errors::BioError;
+
+/// Iterator over overlapping or tiled windows of a sequence, created by [`windows`].
+///
+/// Yields `(start, window)` where `window` is `seq[start..start + size]`. The
+/// final window is truncated if needed so the whole sequence is covered.
+#[derive(Debug, Clone)]
+pub struct SeqWindows<'a> {
+    seq: &'a [u8],
+    size: usize,
+    step: usize,
+    start: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for SeqWindows<'a> {
+    type Item = (usize, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.start >= self.seq.len() {
+            return None;
+        }
+
+        let start = self.start;
+        let end = (start + self.size).min(self.seq.len());
+
+        self.done = end == self.seq.len();
+        self.start += self.step;
+
+        Some((start, &self.seq[start..end]))
+    }
+}
+
+/// Returns an iterator over windows of `size` bases, starting every `step` bases.
+///
+/// Use `step < size` for overlapping windows and `step == size` for tiling.
+/// Iteration stops at the first window reaching the end of `seq`, which may be
+/// shorter than `size`. Bases are skipped if `step > size`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `size` or `step` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::windows;
+///
+/// let w: Vec<(usize, &[u8])> = windows(b"ACGTACG", 4, 2).unwrap().collect();
+/// assert_eq!(w, vec![(0, &b"ACGT"[..]), (2, &b"GTAC"[..]), (4, &b"ACG"[..])]);
+/// ```
+pub fn windows(seq: &[u8], size: usize, step: usize) -> Result<SeqWindows<'_>, BioError> {
+    if size == 0 || step == 0 {
+        return Err(BioError::InvalidParameterError(format!(
+            "window size {} and step {} must be larger than 0.",
+            size, step
+        )));
+    }
+
+    Ok(SeqWindows {
+        seq,
+        size,
+        step,
+        start: 0,
+        done: false,
+    })
+}
+
+/// Computes `metric` over each window of [`windows`], returning `(start, end, value)`.
+///
+/// The result can be written as a genome browser track with
+/// [`write_bedgraph_track`](crate::coverage::write_bedgraph_track).
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `size` or `step` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::{gc_content, window_values};
+///
+/// let gc = window_values(b"GGCCATAT", 4, 4, gc_content).unwrap();
+/// assert_eq!(gc, vec![(0, 4, 1.0), (4, 8, 0.0)]);
+/// ```
+pub fn window_values<F>(
+    seq: &[u8],
+    size: usize,
+    step: usize,
+    metric: F,
+) -> Result<Vec<(usize, usize, f64)>, BioError>
+where
+    F: Fn(&[u8]) -> f64,
+{
+    Ok(windows(seq, size, step)?
+        .map(|(start, window)| (start, start + window.len(), metric(window)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(b"ACGTACGT", 4, 4, vec![(0, 4), (4, 8)])]
+    #[case(b"ACGTACGT", 4, 3, vec![(0, 4), (3, 7), (6, 8)])]
+    #[case(b"ACGTACGT", 10, 2, vec![(0, 8)])]
+    #[case(b"ACGTACGT", 2, 3, vec![(0, 2), (3, 5), (6, 8)])]
+    #[case(b"", 2, 1, vec![])]
+    fn test_windows(
+        #[case] seq: &[u8],
+        #[case] size: usize,
+        #[case] step: usize,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        let coords: Vec<(usize, usize)> = windows(seq, size, step)
+            .unwrap()
+            .map(|(start, w)| (start, start + w.len()))
+            .collect();
+        assert_eq!(coords, expected);
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 0)]
+    fn test_windows_invalid(#[case] size: usize, #[case] step: usize) {
+        assert!(windows(b"ACGT", size, step).is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! Composable record processing pipelines over FASTQ/FASTA streams.
+//!
+//! A [`Pipeline`] is an ordered list of named stages, each of which can drop,
+//! trim or rewrite a record, e.g. filter → trim → tag. Records are streamed
+//! through the stages with [`par_map_records`], so at most a few batches per
+//! thread are in flight regardless of the input size, and written in input
+//! order. [`Pipeline::run_fastq`] reads from a file or stdin and writes to a
+//! file or stdout, which is all a fastp-style command line tool needs.
+//!
+//! # Examples
+//!
+//! ```
+//! use bio::io::fastq;
+//! use bio_utils_rs::io::bio_fastq_reader_from_bytes;
+//! use bio_utils_rs::nucleotide::ReadFilter;
+//! use bio_utils_rs::pipeline::Pipeline;
+//!
+//! let input = b"@r1\nACGTACGTAAAAAAAA\n+\nIIIIIIIIIIIIIIII\n@r2\nACG\n+\nIII\n";
+//! let pipeline = Pipeline::new()
+//!     .trim_poly_a(8, 0)
+//!     .read_filter(ReadFilter { min_len: Some(5), ..Default::default() })
+//!     .tag("sample", "s1");
+//!
+//! let reader = bio_fastq_reader_from_bytes(input).unwrap();
+//! let mut buf = Vec::new();
+//! let stats = pipeline.run(reader.records(), &mut fastq::Writer::new(&mut buf)).unwrap();
+//!
+//! assert_eq!(buf, b"@r1 sample=s1\nACGTACGT\n+\nIIIIIIII\n");
+//! assert_eq!((stats.read, stats.written), (2, 1));
+//! assert_eq!(stats.dropped, vec![("trim_poly_a".to_string(), 0), ("read_filter".to_string(), 1), ("tag".to_string(), 0)]);
+//! ```
+
+use crate::errors::BioError;
+use crate::io::{RecordSink, bio_fastq_records, get_bufwriter, par_map_records};
+use crate::nucleotide::{ReadFilter, trim_poly_a};
+use bio::io::fastq;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A pipeline stage, returning `None` to drop the record.
+type StageFn<T> = Box<dyn Fn(T) -> Option<T> + Send + Sync>;
+
+/// Record counts of a [`Pipeline`] run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineStats {
+    /// Records read from the input.
+    pub read: usize,
+    /// Records that passed every stage and were written.
+    pub written: usize,
+    /// Records dropped by each stage, in stage order.
+    pub dropped: Vec<(String, usize)>,
+}
+
+/// An ordered list of named record processing stages, see the
+/// [module docs](self).
+pub struct Pipeline<T> {
+    stages: Vec<(String, StageFn<T>)>,
+    threads: usize,
+}
+
+impl<T> Default for Pipeline<T> {
+    fn default() -> Self {
+        Self {
+            stages: Vec::new(),
+            threads: 1,
+        }
+    }
+}
+
+impl<T: Send> Pipeline<T> {
+    /// An empty pipeline, which passes every record through unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of worker threads used by [`run`](Self::run), `1` by default.
+    /// Values below `1` are rejected when the pipeline is run.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Appends a stage that can drop (`None`) or rewrite a record.
+    pub fn stage<F>(mut self, name: &str, f: F) -> Self
+    where
+        F: Fn(T) -> Option<T> + Send + Sync + 'static,
+    {
+        self.stages.push((name.to_string(), Box::new(f)));
+        self
+    }
+
+    /// Appends a stage that keeps the records for which `predicate` is `true`.
+    pub fn filter<F>(self, name: &str, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.stage(name, move |record| predicate(&record).then_some(record))
+    }
+
+    /// Appends a stage that rewrites every record.
+    pub fn map<F>(self, name: &str, f: F) -> Self
+    where
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        self.stage(name, move |record| Some(f(record)))
+    }
+
+    /// Passes `record` through every stage, `None` if a stage drops it.
+    pub fn apply(&self, record: T) -> Option<T> {
+        self.stages
+            .iter()
+            .try_fold(record, |record, (_, stage)| stage(record))
+    }
+
+    /// Like [`apply`](Self::apply), counting the record as dropped by the
+    /// stage that drops it.
+    fn apply_counted(&self, mut record: T, dropped: &[AtomicUsize]) -> Option<T> {
+        for ((_, stage), count) in self.stages.iter().zip(dropped) {
+            match stage(record) {
+                Some(next) => record = next,
+                None => {
+                    count.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+            }
+        }
+
+        Some(record)
+    }
+
+    /// Streams `records` through the pipeline into `writer`, in input order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if the pipeline has `0`
+    /// threads, and the first read or write error otherwise.
+    pub fn run<I, E, S>(&self, records: I, writer: &mut S) -> Result<PipelineStats, BioError>
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+        I::IntoIter: Send,
+        E: Into<BioError>,
+        S: RecordSink<T> + ?Sized,
+    {
+        let read = AtomicUsize::new(0);
+        let dropped: Vec<AtomicUsize> = self.stages.iter().map(|_| AtomicUsize::new(0)).collect();
+
+        let written = par_map_records(records, writer, self.threads, |record| {
+            read.fetch_add(1, Ordering::Relaxed);
+            self.apply_counted(record, &dropped)
+        })?;
+
+        Ok(PipelineStats {
+            read: read.into_inner(),
+            written,
+            dropped: self
+                .stages
+                .iter()
+                .zip(dropped)
+                .map(|((name, _), count)| (name.clone(), count.into_inner()))
+                .collect(),
+        })
+    }
+}
+
+impl Pipeline<fastq::Record> {
+    /// Appends a `read_filter` stage dropping reads rejected by `filter`.
+    pub fn read_filter(self, filter: ReadFilter) -> Self {
+        self.filter("read_filter", move |record: &fastq::Record| {
+            filter.passes(record.seq(), Some(record.qual()))
+        })
+    }
+
+    /// Appends a `trim_poly_a` stage removing 5' poly(T) heads and 3' poly(A)
+    /// tails, see [`trim_poly_a`].
+    pub fn trim_poly_a(self, min_len: usize, max_mismatch: usize) -> Self {
+        self.map("trim_poly_a", move |record: fastq::Record| {
+            let (start, end) = trim_poly_a(record.seq(), min_len, max_mismatch);
+            match (start, end) == (0, record.seq().len()) {
+                true => record,
+                false => fastq::Record::with_attrs(
+                    record.id(),
+                    record.desc(),
+                    &record.seq()[start..end],
+                    &record.qual()[start..end],
+                ),
+            }
+        })
+    }
+
+    /// Appends a `tag` stage adding `key=value` to every read description.
+    pub fn tag(self, key: &str, value: &str) -> Self {
+        let tag = format!("{}={}", key, value);
+
+        self.map("tag", move |record: fastq::Record| {
+            let desc = match record.desc() {
+                Some(desc) => format!("{} {}", desc, tag),
+                None => tag.clone(),
+            };
+            fastq::Record::with_attrs(record.id(), Some(&desc), record.seq(), record.qual())
+        })
+    }
+
+    /// Runs the pipeline from a FASTQ file (plain or gzip), or stdin for
+    /// `None`, to a FASTQ file or stdout for `None`. Output files ending in
+    /// `.gz` are gzip-compressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError`] if a file cannot be opened, and read errors with
+    /// their file and record, see [`BioError::ParseErrorAt`].
+    pub fn run_fastq(
+        &self,
+        input: Option<PathBuf>,
+        output: Option<PathBuf>,
+    ) -> Result<PipelineStats, BioError> {
+        let records = bio_fastq_records(input)?;
+        let mut writer = fastq::Writer::new(get_bufwriter(output)?);
+
+        let stats = self.run(records, &mut writer)?;
+        writer.flush()?;
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::bio_fastq_reader_from_bytes;
+    use crate::test_utils::temp_seq_file;
+    use rstest::*;
+
+    fn fastq_input(n: usize) -> Vec<u8> {
+        (0..n)
+            .flat_map(|i| {
+                format!(
+                    "@r{}\n{}\n+\n{}\n",
+                    i,
+                    "A".repeat(i % 7 + 1),
+                    "I".repeat(i % 7 + 1)
+                )
+                .into_bytes()
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(4)]
+    fn test_pipeline_run(#[case] threads: usize) {
+        let input = fastq_input(3000);
+        let pipeline = Pipeline::new()
+            .threads(threads)
+            .filter("min_len", |r: &fastq::Record| r.seq().len() >= 3)
+            .filter("max_len", |r: &fastq::Record| r.seq().len() <= 5)
+            .tag("x", "1");
+
+        let reader = bio_fastq_reader_from_bytes(&input).unwrap();
+        let mut buf = Vec::new();
+        let stats = pipeline
+            .run(reader.records(), &mut fastq::Writer::new(&mut buf))
+            .unwrap();
+
+        let expected: Vec<u8> = (0..3000)
+            .filter(|i| (3..=5).contains(&(i % 7 + 1)))
+            .flat_map(|i| {
+                format!(
+                    "@r{} x=1\n{}\n+\n{}\n",
+                    i,
+                    "A".repeat(i % 7 + 1),
+                    "I".repeat(i % 7 + 1)
+                )
+                .into_bytes()
+            })
+            .collect();
+
+        assert_eq!(buf, expected);
+        assert_eq!(stats.read, 3000);
+        assert_eq!(
+            stats.written + stats.dropped.iter().map(|(_, n)| n).sum::<usize>(),
+            3000
+        );
+        assert_eq!(stats.dropped[0], ("min_len".to_string(), 858));
+        assert_eq!(stats.dropped[2], ("tag".to_string(), 0));
+    }
+
+    #[test]
+    fn test_pipeline_apply() {
+        let pipeline = Pipeline::new()
+            .map("double", |x: u32| x * 2)
+            .filter("even_half", |x| x % 4 == 0);
+
+        assert_eq!(pipeline.apply(2), Some(4));
+        assert_eq!(pipeline.apply(3), None);
+        assert_eq!(Pipeline::new().apply(3), Some(3));
+    }
+
+    #[test]
+    fn test_pipeline_run_fastq() {
+        let (_tmp_dir, input) = temp_seq_file("in.fastq");
+        let output = input.with_file_name("out.fastq");
+        std::fs::write(
+            &input,
+            b"@r1 d\nTTTTTTTTGACCGTAGGC\n+\nABCDEFGHIJKLMNOPQR\n",
+        )
+        .unwrap();
+
+        let stats = Pipeline::new()
+            .trim_poly_a(8, 0)
+            .tag("trimmed", "yes")
+            .run_fastq(Some(input), Some(output.clone()))
+            .unwrap();
+
+        assert_eq!(stats.written, 1);
+        assert_eq!(
+            std::fs::read(output).unwrap(),
+            b"@r1 d trimmed=yes\nGACCGTAGGC\n+\nIJKLMNOPQR\n"
+        );
+    }
+
+    #[test]
+    fn test_pipeline_invalid_threads() {
+        let reader = bio_fastq_reader_from_bytes(b"").unwrap();
+        let result = Pipeline::new()
+            .threads(0)
+            .run(reader.records(), &mut fastq::Writer::new(Vec::new()));
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! Primer design QC.
+//!
+//! Provides nearest-neighbor melting temperature ([`melting_temperature`]),
+//! 3' GC clamp checks ([`gc_clamp_count`]), dimer complementarity scoring
+//! ([`dimer_score`]) and hairpin detection ([`max_hairpin_stem`]).
+
+mod thermo;
+pub use thermo::*;
+
+mod structure;
+pub use structure::*;
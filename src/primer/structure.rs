@@ -0,0 +1,131 @@
+use super::thermo::{complement, normalize_primer};
+use crate::errors::BioError;
+
+/// Complementarity between two primers, named after the Primer3 `ANY`/`END` scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimerScore {
+    /// Maximum number of complementary bases over all ungapped antiparallel alignments.
+    pub any: usize,
+    /// Maximum number of contiguous complementary bases anchored at either primer's 3' end.
+    pub end: usize,
+}
+
+/// Longest run of complementary pairs starting at `a`'s 3' end, for every
+/// antiparallel offset against `b`.
+fn three_prime_run(a: &[u8], b: &[u8]) -> usize {
+    let last = a.len() - 1;
+
+    (0..b.len())
+        .map(|k0| {
+            // a[last - t] pairs with b[k0 + t].
+            (0..=last.min(b.len() - 1 - k0))
+                .take_while(|t| a[last - t] == complement(b[k0 + t]))
+                .count()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Scores the complementarity of primers `a` and `b` (both 5' to 3').
+///
+/// Use `dimer_score(p, p)` for self-dimers. A high `end` score indicates a
+/// primer-dimer that polymerase can extend.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if either primer is empty or
+/// contains non-`ACGT` bases.
+pub fn dimer_score(a: &[u8], b: &[u8]) -> Result<DimerScore, BioError> {
+    if a.is_empty() || b.is_empty() {
+        return Err(BioError::InvalidParameterError(
+            "primers cannot be empty.".to_string(),
+        ));
+    }
+
+    let a = normalize_primer(a)?;
+    let b = normalize_primer(b)?;
+
+    // For shift s, a[i] pairs antiparallel with b[s - i].
+    let any = (0..a.len() + b.len() - 1)
+        .map(|s| {
+            let lo = s.saturating_sub(b.len() - 1);
+            let hi = s.min(a.len() - 1);
+            (lo..=hi).filter(|&i| a[i] == complement(b[s - i])).count()
+        })
+        .max()
+        .unwrap_or(0);
+
+    let end = three_prime_run(&a, &b).max(three_prime_run(&b, &a));
+
+    Ok(DimerScore { any, end })
+}
+
+/// Returns the longest hairpin stem (contiguous complementary pairs) that can
+/// form within `seq` with a loop of at least `min_loop` bases.
+///
+/// Only perfectly paired, ungapped stems are considered. Returns `0` if no
+/// hairpin can form.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `seq` contains non-`ACGT` bases.
+pub fn max_hairpin_stem(seq: &[u8], min_loop: usize) -> Result<usize, BioError> {
+    let seq = normalize_primer(seq)?;
+    let n = seq.len();
+
+    let mut best = 0;
+
+    // (i, j) are the innermost stem pair, closing the loop seq[i + 1..j].
+    for i in 0..n {
+        for j in (i + min_loop + 1)..n {
+            let stem = (0..=i.min(n - 1 - j))
+                .take_while(|&t| seq[i - t] == complement(seq[j + t]))
+                .count();
+            best = best.max(stem);
+        }
+    }
+
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    // Perfect reverse complements pair along their whole length.
+    #[case(b"AACCGG", b"CCGGTT", 6, 6)]
+    #[case(b"AAAA", b"AAAA", 0, 0)]
+    // 3' end `GC` of a pairs with 3' end `GC` of b.
+    #[case(b"TTTTTGC", b"AAAAGC", 4, 2)]
+    fn test_dimer_score(
+        #[case] a: &[u8],
+        #[case] b: &[u8],
+        #[case] any: usize,
+        #[case] end: usize,
+    ) {
+        assert_eq!(dimer_score(a, b).unwrap(), DimerScore { any, end });
+    }
+
+    #[test]
+    fn test_self_dimer_palindrome() {
+        let score = dimer_score(b"GAATTC", b"GAATTC").unwrap();
+        assert_eq!(score, DimerScore { any: 6, end: 6 });
+    }
+
+    #[rstest]
+    #[case(b"GGGAAAACCC", 3, 3)]
+    #[case(b"GGGAACCC", 3, 2)]
+    #[case(b"AAAAAAAA", 3, 0)]
+    #[case(b"", 3, 0)]
+    fn test_max_hairpin_stem(#[case] seq: &[u8], #[case] min_loop: usize, #[case] expected: usize) {
+        assert_eq!(max_hairpin_stem(seq, min_loop).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(dimer_score(b"", b"ACGT").is_err());
+        assert!(max_hairpin_stem(b"ACNGT", 3).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::errors::BioError;
+
+/// Gas constant in cal/(K·mol).
+const R: f64 = 1.987;
+
+/// Nearest-neighbor `(ΔH kcal/mol, ΔS cal/(K·mol))` for a dinucleotide step.
+///
+/// Unified parameters from SantaLucia (1998), PNAS 95:1460-1465.
+#[inline]
+fn nn_params(a: u8, b: u8) -> (f64, f64) {
+    match (a, b) {
+        (b'A', b'A') | (b'T', b'T') => (-7.9, -22.2),
+        (b'A', b'T') => (-7.2, -20.4),
+        (b'T', b'A') => (-7.2, -21.3),
+        (b'C', b'A') | (b'T', b'G') => (-8.5, -22.7),
+        (b'G', b'T') | (b'A', b'C') => (-8.4, -22.4),
+        (b'C', b'T') | (b'A', b'G') => (-7.8, -21.0),
+        (b'G', b'A') | (b'T', b'C') => (-8.2, -22.2),
+        (b'C', b'G') => (-10.6, -27.2),
+        (b'G', b'C') => (-9.8, -24.4),
+        (b'G', b'G') | (b'C', b'C') => (-8.0, -19.9),
+        _ => unreachable!("sequence is validated to be ACGT"),
+    }
+}
+
+/// Initiation `(ΔH, ΔS)` for a terminal base pair.
+#[inline]
+fn init_params(nt: u8) -> (f64, f64) {
+    match nt {
+        b'G' | b'C' => (0.1, -2.8),
+        _ => (2.3, 4.1),
+    }
+}
+
+/// Returns the Watson-Crick complement of an uppercase `ACGT` base.
+#[inline]
+pub(crate) fn complement(nt: u8) -> u8 {
+    match nt {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        _ => b'N',
+    }
+}
+
+/// Uppercases `seq` and checks that it only contains `ACGT`.
+pub(crate) fn normalize_primer(seq: &[u8]) -> Result<Vec<u8>, BioError> {
+    let upper = seq.to_ascii_uppercase();
+
+    if let Some(pos) = upper
+        .iter()
+        .position(|nt| !matches!(nt, b'A' | b'C' | b'G' | b'T'))
+    {
+        return Err(BioError::InvalidParameterError(format!(
+            "primer contains non-ACGT base {:?} at position {}.",
+            seq[pos] as char, pos
+        )));
+    }
+
+    Ok(upper)
+}
+
+/// Reaction conditions for melting temperature calculations.
+#[derive(Debug, Clone, Copy)]
+pub struct TmParams {
+    /// Monovalent cation (Na+) concentration in mM.
+    pub na_mm: f64,
+    /// Total primer strand concentration in nM.
+    pub primer_nm: f64,
+}
+
+impl Default for TmParams {
+    /// 50 mM Na+ and 250 nM primer, common PCR defaults.
+    fn default() -> Self {
+        Self {
+            na_mm: 50.0,
+            primer_nm: 250.0,
+        }
+    }
+}
+
+/// Computes the melting temperature (°C) of a primer using nearest-neighbor thermodynamics.
+///
+/// Uses the SantaLucia (1998) unified parameters with terminal initiation,
+/// a symmetry correction for self-complementary sequences and the entropic
+/// salt correction `0.368 * (N - 1) * ln[Na+]`. The primer is assumed to
+/// anneal to a non-self-complementary target at `primer_nm / 4`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `seq` is shorter than 2 bases,
+/// contains non-`ACGT` bases, or the concentrations are not positive.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::primer::{TmParams, melting_temperature};
+///
+/// let tm = melting_temperature(b"AGCGGATAACAATTTCACACAGGA", &TmParams::default()).unwrap();
+/// assert!((56.0..58.0).contains(&tm));
+/// ```
+pub fn melting_temperature(seq: &[u8], params: &TmParams) -> Result<f64, BioError> {
+    if seq.len() < 2 {
+        return Err(BioError::InvalidParameterError(format!(
+            "primer length {} must be at least 2.",
+            seq.len()
+        )));
+    }
+
+    if params.na_mm <= 0.0 || params.primer_nm <= 0.0 {
+        return Err(BioError::InvalidParameterError(
+            "salt and primer concentrations must be positive.".to_string(),
+        ));
+    }
+
+    let seq = normalize_primer(seq)?;
+
+    let (mut dh, mut ds) = seq
+        .windows(2)
+        .map(|w| nn_params(w[0], w[1]))
+        .fold((0.0, 0.0), |(h, s), (dh, ds)| (h + dh, s + ds));
+
+    for nt in [seq[0], seq[seq.len() - 1]] {
+        let (ih, is) = init_params(nt);
+        dh += ih;
+        ds += is;
+    }
+
+    let self_complementary = seq
+        .iter()
+        .zip(seq.iter().rev())
+        .all(|(a, b)| *a == complement(*b));
+
+    let conc = params.primer_nm * 1e-9;
+    let ct = match self_complementary {
+        true => {
+            ds += -1.4;
+            conc
+        }
+        false => conc / 4.0,
+    };
+
+    ds += 0.368 * (seq.len() - 1) as f64 * (params.na_mm / 1000.0).ln();
+
+    Ok(dh * 1000.0 / (ds + R * ct.ln()) - 273.15)
+}
+
+/// Computes the Wallace rule melting temperature `2(A+T) + 4(G+C)` (°C).
+///
+/// Only meaningful for short oligos (< 14 bp); prefer [`melting_temperature`].
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `seq` contains non-`ACGT` bases.
+pub fn tm_wallace(seq: &[u8]) -> Result<f64, BioError> {
+    let seq = normalize_primer(seq)?;
+
+    let gc = seq.iter().filter(|nt| matches!(nt, b'G' | b'C')).count();
+    let at = seq.len() - gc;
+
+    Ok((2 * at + 4 * gc) as f64)
+}
+
+/// Counts `G`/`C` bases among the last `window` bases at the 3' end.
+///
+/// A common design rule is 1-3 G/C in the last 5 bases: enough to anchor
+/// extension, but not so many that mispriming is stabilized.
+pub fn gc_clamp_count(seq: &[u8], window: usize) -> usize {
+    seq[seq.len().saturating_sub(window)..]
+        .iter()
+        .filter(|nt| matches!(nt, b'G' | b'C' | b'g' | b'c'))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    // M13 reverse and M13 forward (-20) primers.
+    #[case(b"AGCGGATAACAATTTCACACAGGA", 56.7)]
+    #[case(b"GTAAAACGACGGCCAGT", 51.7)]
+    fn test_melting_temperature(#[case] seq: &[u8], #[case] approx: f64) {
+        let tm = melting_temperature(seq, &TmParams::default()).unwrap();
+        assert!((tm - approx).abs() < 0.1, "tm {} vs {}", tm, approx);
+    }
+
+    #[test]
+    fn test_melting_temperature_ordering() {
+        let params = TmParams::default();
+        let at_rich = melting_temperature(b"ATATATTAATATATTA", &params).unwrap();
+        let gc_rich = melting_temperature(b"GCGCGGCCGCGCGGCC", &params).unwrap();
+        assert!(gc_rich > at_rich);
+
+        let low_salt = TmParams {
+            na_mm: 10.0,
+            ..params
+        };
+        let tm = melting_temperature(b"GTAAAACGACGGCCAGT", &params).unwrap();
+        assert!(melting_temperature(b"GTAAAACGACGGCCAGT", &low_salt).unwrap() < tm);
+    }
+
+    #[rstest]
+    #[case(b"A")]
+    #[case(b"ACGNT")]
+    fn test_melting_temperature_invalid(#[case] seq: &[u8]) {
+        assert!(melting_temperature(seq, &TmParams::default()).is_err());
+    }
+
+    #[rstest]
+    #[case(b"AATTGGCC", 24.0)]
+    #[case(b"aaaa", 8.0)]
+    fn test_tm_wallace(#[case] seq: &[u8], #[case] expected: f64) {
+        assert_eq!(tm_wallace(seq).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(b"AAAAAGCGAT", 5, 3)]
+    #[case(b"AAAAAAAAAA", 5, 0)]
+    #[case(b"GC", 5, 2)]
+    #[case(b"", 5, 0)]
+    fn test_gc_clamp_count(#[case] seq: &[u8], #[case] window: usize, #[case] expected: usize) {
+        assert_eq!(gc_clamp_count(seq, window), expected);
+    }
+}
@@ -0,0 +1,200 @@
+//! Python bindings of the most used sequence functions (`python` feature).
+//!
+//! Built as the `bio_utils_rs` extension module with [maturin](https://www.maturin.rs),
+//! see `pyproject.toml`. Sequences are passed as ASCII `str`, so positions
+//! returned by the search functions are valid Python string indices.
+//!
+//! ```python
+//! import bio_utils_rs as bu
+//!
+//! bu.reverse_complement("AACG")            # "CGTT"
+//! bu.translate("ATGGCCTAA", table=11)      # "MA*"
+//! a = bu.frac_min_hash("ACGT" * 100, kmer_size=21, scaled=1)
+//! a.jaccard(bu.frac_min_hash("ACGT" * 50, kmer_size=21, scaled=1))
+//! ```
+//!
+//! [`BioError`]s are raised as `ValueError`, or `OSError` for I/O errors.
+
+use crate::aminoacid::Frame;
+use crate::aminoacid::codon_table::CodonTable;
+use crate::errors::BioError;
+use crate::kmers::{self, Sketch};
+use crate::nucleotide;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+
+impl From<BioError> for PyErr {
+    fn from(e: BioError) -> Self {
+        match e {
+            BioError::IoError(e) => PyIOError::new_err(e.to_string()),
+            e => PyValueError::new_err(e.to_string()),
+        }
+    }
+}
+
+/// Returns the bytes of `seq`, rejecting non-ASCII input whose byte offsets
+/// would not match Python string indices.
+fn ascii(seq: &str) -> PyResult<&[u8]> {
+    match seq.is_ascii() {
+        true => Ok(seq.as_bytes()),
+        false => Err(PyValueError::new_err("sequence must be ASCII.")),
+    }
+}
+
+/// Converts output of ASCII input back to a `str`.
+fn ascii_string(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).expect("ascii input gives ascii output")
+}
+
+/// Reverse complement of a DNA sequence with IUPAC codes, unrecognized
+/// symbols become `N`.
+#[pyfunction]
+pub fn reverse_complement(seq: &str) -> PyResult<String> {
+    Ok(ascii_string(nucleotide::reverse_complement(ascii(seq)?)))
+}
+
+/// GC content of a sequence, `0.0` if it is empty.
+#[pyfunction]
+pub fn gc_content(seq: &str) -> PyResult<f64> {
+    Ok(nucleotide::gc_content(ascii(seq)?))
+}
+
+/// Translates `seq` with NCBI codon `table` (`1` or `11`) in reading `frame`
+/// (`1`, `2`, `3`, `-1`, `-2` or `-3`).
+#[pyfunction]
+#[pyo3(signature = (seq, table = 1, frame = 1))]
+pub fn translate(seq: &str, table: u8, frame: i8) -> PyResult<String> {
+    let codon_table = CodonTable::from_ncbi_id(table)
+        .ok_or_else(|| PyValueError::new_err(format!("unsupported codon table {}.", table)))?;
+    let frame = Frame::from_number(frame)
+        .ok_or_else(|| PyValueError::new_err(format!("invalid reading frame {}.", frame)))?;
+
+    Ok(ascii_string(crate::aminoacid::translate(
+        codon_table,
+        &frame,
+        ascii(seq)?,
+    )))
+}
+
+/// Start positions of exact, non-overlapping occurrences of `pattern`.
+#[pyfunction]
+pub fn search_exact(seq: &str, pattern: &str) -> PyResult<Vec<usize>> {
+    Ok(nucleotide::search_exact(ascii(seq)?, ascii(pattern)?))
+}
+
+/// `(start, end, edits)` of IUPAC-aware matches of `pattern` with at most
+/// `max_mismatches` edits, end exclusive.
+#[pyfunction]
+pub fn search_fuzzy(
+    seq: &str,
+    pattern: &str,
+    max_mismatches: u8,
+) -> PyResult<Vec<(usize, usize, u8)>> {
+    Ok(
+        nucleotide::search_fuzzy_matches(ascii(seq)?, ascii(pattern)?, max_mismatches)?
+            .into_iter()
+            .map(|m| (m.start, m.end, m.edits))
+            .collect(),
+    )
+}
+
+/// A set of k-mer hashes, compared with [`jaccard`](PySketch::jaccard) and
+/// [`containment`](PySketch::containment).
+#[pyclass(name = "Sketch", frozen)]
+pub struct PySketch(Sketch);
+
+#[pymethods]
+impl PySketch {
+    /// Jaccard index with `other`.
+    pub fn jaccard(&self, other: &PySketch) -> f64 {
+        kmers::jaccard(&self.0, &other.0)
+    }
+
+    /// Fraction of this sketch contained in `reference`.
+    pub fn containment(&self, reference: &PySketch) -> f64 {
+        kmers::containment(&self.0, &reference.0)
+    }
+
+    /// Hashes in ascending order.
+    pub fn hashes(&self) -> Vec<u64> {
+        let mut hashes: Vec<u64> = self.0.iter().copied().collect();
+        hashes.sort_unstable();
+        hashes
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("Sketch(len={})", self.0.len())
+    }
+}
+
+/// FracMinHash sketch of the canonical k-mers of `seq`.
+#[pyfunction]
+pub fn frac_min_hash(seq: &str, kmer_size: usize, scaled: u64) -> PyResult<PySketch> {
+    Ok(PySketch(kmers::frac_min_hash(
+        kmer_size,
+        scaled,
+        ascii(seq)?,
+    )?))
+}
+
+#[pymodule]
+fn bio_utils_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySketch>()?;
+    m.add_function(wrap_pyfunction!(reverse_complement, m)?)?;
+    m.add_function(wrap_pyfunction!(gc_content, m)?)?;
+    m.add_function(wrap_pyfunction!(translate, m)?)?;
+    m.add_function(wrap_pyfunction!(search_exact, m)?)?;
+    m.add_function(wrap_pyfunction!(search_fuzzy, m)?)?;
+    m.add_function(wrap_pyfunction!(frac_min_hash, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_reverse_complement() {
+        assert_eq!(reverse_complement("AACGU").unwrap(), "NCGTT");
+        assert!(reverse_complement("ACGΤ").is_err());
+    }
+
+    #[rstest]
+    #[case(1, 1, "MA*")]
+    #[case(11, 1, "MA*")]
+    #[case(1, 2, "WP")]
+    #[case(1, -1, "LGH")]
+    fn test_translate(#[case] table: u8, #[case] frame: i8, #[case] expected: &str) {
+        assert_eq!(translate("ATGGCCTAA", table, frame).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(2, 1)]
+    #[case(1, 4)]
+    fn test_translate_invalid(#[case] table: u8, #[case] frame: i8) {
+        assert!(translate("ATG", table, frame).is_err());
+    }
+
+    #[test]
+    fn test_search() {
+        assert_eq!(search_exact("AACGTAACGT", "ACG").unwrap(), vec![1, 6]);
+        assert_eq!(search_fuzzy("AACGTAA", "ACTT", 1).unwrap(), vec![(1, 5, 1)]);
+        assert!(search_fuzzy("ACGT", "", 1).is_err());
+    }
+
+    #[test]
+    fn test_sketch() {
+        let a = frac_min_hash("ACGTTGCAAGTC", 5, 1).unwrap();
+        let b = frac_min_hash("ACGTTGCA", 5, 1).unwrap();
+
+        assert_eq!(a.jaccard(&a), 1.0);
+        assert_eq!(b.containment(&a), 1.0);
+        assert_eq!(b.__len__(), b.hashes().len());
+        assert!(frac_min_hash("ACGT", 0, 1).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+//! Run/sample level quality control.
+//!
+//! [`QcStats`] summarizes a set of reads and [`QcPolicy`] codifies acceptance
+//! criteria over those statistics, producing a machine-readable [`Verdict`].
+//! With the `io` feature, policies can be loaded from TOML or JSON.
+//! [`QcProfile`] collects read length, GC content and per-position quality
+//! distributions, which the `report` feature renders as a self-contained
+//! HTML report.
+
+mod policy;
+pub use policy::*;
+
+mod profile;
+pub use profile::*;
+
+#[cfg(feature = "report")]
+mod report;
+#[cfg(feature = "report")]
+pub use report::*;
@@ -0,0 +1,368 @@
+use crate::errors::BioError;
+use crate::nucleotide::{error_to_phred, qual_to_error};
+
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "io")]
+use std::path::Path;
+
+/// Summary statistics of a set of reads, as evaluated by a [`QcPolicy`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct QcStats {
+    pub num_reads: usize,
+    pub num_bases: usize,
+    pub mean_len: f64,
+    /// Phred score of the mean per-base error probability.
+    pub mean_phred: f64,
+    /// GC fraction of `ACGT` bases (case insensitive).
+    pub gc_content: f64,
+    /// Fraction of bases that are not `ACGT` (case insensitive).
+    pub n_fraction: f64,
+}
+
+impl QcStats {
+    /// Computes statistics from `(seq, qual)` pairs.
+    ///
+    /// Records without qualities (FASTA) do not contribute to `mean_phred`.
+    pub fn from_records<'a, I>(records: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a [u8], Option<&'a [u8]>)>,
+    {
+        let mut num_reads = 0;
+        let mut num_bases = 0;
+        let mut num_qual = 0;
+        let mut error_sum = 0.0;
+        let mut gc = 0;
+        let mut acgt = 0;
+
+        for (seq, qual) in records {
+            num_reads += 1;
+            num_bases += seq.len();
+
+            for nt in seq {
+                match nt {
+                    b'G' | b'C' | b'g' | b'c' => {
+                        gc += 1;
+                        acgt += 1;
+                    }
+                    b'A' | b'T' | b'a' | b't' => acgt += 1,
+                    _ => {}
+                }
+            }
+
+            if let Some(qual) = qual {
+                num_qual += qual.len();
+                error_sum += qual.iter().map(|phred| qual_to_error(*phred)).sum::<f64>();
+            }
+        }
+
+        let ratio = |a: usize, b: usize| match b {
+            0 => 0.0,
+            _ => a as f64 / b as f64,
+        };
+
+        let mean_phred = match num_qual {
+            0 => 0.0,
+            _ => error_to_phred(error_sum / num_qual as f64) as f64,
+        };
+
+        Self {
+            num_reads,
+            num_bases,
+            mean_len: ratio(num_bases, num_reads),
+            mean_phred,
+            gc_content: ratio(gc, acgt),
+            n_fraction: ratio(num_bases - acgt, num_bases),
+        }
+    }
+}
+
+/// Overall outcome of a QC evaluation, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub enum QcStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of [`QcPolicy::evaluate`], with one reason per violated threshold.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct Verdict {
+    pub status: QcStatus,
+    pub reasons: Vec<String>,
+}
+
+/// Warn and fail thresholds for a single metric. Unset thresholds are not checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "io", serde(default, deny_unknown_fields))]
+pub struct Bound {
+    pub warn: Option<f64>,
+    pub fail: Option<f64>,
+}
+
+/// Acceptance criteria over [`QcStats`].
+///
+/// `min_*` bounds are violated when the metric is below the threshold and
+/// `max_*` bounds when it is above. Every field defaults to unchecked, so a
+/// config only needs to list the metrics it cares about:
+///
+/// ```toml
+/// [min_mean_phred]
+/// warn = 25.0
+/// fail = 20.0
+///
+/// [max_n_fraction]
+/// fail = 0.05
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "io", serde(default, deny_unknown_fields))]
+pub struct QcPolicy {
+    pub min_reads: Bound,
+    pub min_bases: Bound,
+    pub min_mean_len: Bound,
+    pub min_mean_phred: Bound,
+    pub min_gc_content: Bound,
+    pub max_gc_content: Bound,
+    pub max_n_fraction: Bound,
+}
+
+impl QcPolicy {
+    /// Evaluates `stats` against every configured threshold.
+    ///
+    /// The verdict status is the most severe violation, or [`QcStatus::Pass`]
+    /// if none. Reasons are listed in field order.
+    pub fn evaluate(&self, stats: &QcStats) -> Verdict {
+        let checks = [
+            ("num_reads", stats.num_reads as f64, &self.min_reads, true),
+            ("num_bases", stats.num_bases as f64, &self.min_bases, true),
+            ("mean_len", stats.mean_len, &self.min_mean_len, true),
+            ("mean_phred", stats.mean_phred, &self.min_mean_phred, true),
+            ("gc_content", stats.gc_content, &self.min_gc_content, true),
+            ("gc_content", stats.gc_content, &self.max_gc_content, false),
+            ("n_fraction", stats.n_fraction, &self.max_n_fraction, false),
+        ];
+
+        let mut status = QcStatus::Pass;
+        let mut reasons = Vec::new();
+
+        for (name, value, bound, is_min) in checks {
+            let violates = |threshold: f64| match is_min {
+                true => value < threshold,
+                false => value > threshold,
+            };
+            let direction = match is_min {
+                true => "below",
+                false => "above",
+            };
+
+            let violation = [(QcStatus::Fail, bound.fail), (QcStatus::Warn, bound.warn)]
+                .into_iter()
+                .find_map(|(level, threshold)| {
+                    threshold.filter(|t| violates(*t)).map(|t| (level, t))
+                });
+
+            if let Some((level, threshold)) = violation {
+                let label = match level {
+                    QcStatus::Fail => "fail",
+                    _ => "warn",
+                };
+                reasons.push(format!(
+                    "{} {:.4} is {} {} threshold {:.4}.",
+                    name, value, direction, label, threshold
+                ));
+                status = status.max(level);
+            }
+        }
+
+        Verdict { status, reasons }
+    }
+
+    /// Parses a policy from a TOML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::TomlParseError`] if the TOML is invalid or contains unknown fields.
+    #[cfg(feature = "io")]
+    pub fn from_toml_str(s: &str) -> Result<Self, BioError> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Parses a policy from a JSON string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::SerializationError`] if the JSON is invalid or contains unknown fields.
+    #[cfg(feature = "io")]
+    pub fn from_json_str(s: &str) -> Result<Self, BioError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Reads a policy from a `.toml` or `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidFileExtensionError`] for other extensions,
+    /// [`BioError::IoError`] if the file cannot be read, or a parse error.
+    #[cfg(feature = "io")]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, BioError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&content),
+            Some("json") => Self::from_json_str(&content),
+            _ => Err(BioError::InvalidFileExtensionError(
+                path.display().to_string(),
+            )),
+        }
+    }
+
+    /// Checks that no `warn` threshold is stricter than its `fail` threshold.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] naming the first inconsistent bound.
+    pub fn validate(&self) -> Result<(), BioError> {
+        let bounds = [
+            ("min_reads", &self.min_reads, true),
+            ("min_bases", &self.min_bases, true),
+            ("min_mean_len", &self.min_mean_len, true),
+            ("min_mean_phred", &self.min_mean_phred, true),
+            ("min_gc_content", &self.min_gc_content, true),
+            ("max_gc_content", &self.max_gc_content, false),
+            ("max_n_fraction", &self.max_n_fraction, false),
+        ];
+
+        for (name, bound, is_min) in bounds {
+            if let (Some(warn), Some(fail)) = (bound.warn, bound.fail) {
+                let consistent = match is_min {
+                    true => warn >= fail,
+                    false => warn <= fail,
+                };
+
+                if !consistent {
+                    return Err(BioError::InvalidParameterError(format!(
+                        "{} warn threshold {} is stricter than fail threshold {}.",
+                        name, warn, fail
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn stats() -> QcStats {
+        QcStats {
+            num_reads: 1000,
+            num_bases: 150_000,
+            mean_len: 150.0,
+            mean_phred: 22.0,
+            gc_content: 0.45,
+            n_fraction: 0.01,
+        }
+    }
+
+    fn policy() -> QcPolicy {
+        QcPolicy {
+            min_mean_phred: Bound {
+                warn: Some(25.0),
+                fail: Some(20.0),
+            },
+            max_n_fraction: Bound {
+                warn: None,
+                fail: Some(0.05),
+            },
+            ..QcPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_from_records() {
+        let records: Vec<(&[u8], Option<&[u8]>)> =
+            vec![(b"ACGT", Some(b"IIII")), (b"GGNN", Some(b"IIII"))];
+        let stats = QcStats::from_records(records);
+
+        assert_eq!(stats.num_reads, 2);
+        assert_eq!(stats.num_bases, 8);
+        assert_eq!(stats.mean_len, 4.0);
+        assert_eq!(stats.mean_phred, 40.0);
+        assert!((stats.gc_content - 4.0 / 6.0).abs() < 1e-9);
+        assert_eq!(stats.n_fraction, 0.25);
+    }
+
+    #[rstest]
+    #[case(30.0, QcStatus::Pass, 0)]
+    #[case(22.0, QcStatus::Warn, 1)]
+    #[case(15.0, QcStatus::Fail, 1)]
+    fn test_evaluate(
+        #[case] mean_phred: f64,
+        #[case] status: QcStatus,
+        #[case] num_reasons: usize,
+    ) {
+        let stats = QcStats {
+            mean_phred,
+            ..stats()
+        };
+        let verdict = policy().evaluate(&stats);
+
+        assert_eq!(verdict.status, status);
+        assert_eq!(verdict.reasons.len(), num_reasons);
+    }
+
+    #[test]
+    fn test_evaluate_most_severe() {
+        let stats = QcStats {
+            n_fraction: 0.1,
+            ..stats()
+        };
+        let verdict = policy().evaluate(&stats);
+
+        assert_eq!(verdict.status, QcStatus::Fail);
+        assert_eq!(
+            verdict.reasons,
+            vec![
+                "mean_phred 22.0000 is below warn threshold 25.0000.",
+                "n_fraction 0.1000 is above fail threshold 0.0500.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(policy().validate().is_ok());
+
+        let invalid = QcPolicy {
+            max_n_fraction: Bound {
+                warn: Some(0.1),
+                fail: Some(0.05),
+            },
+            ..QcPolicy::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn test_from_toml_and_json() {
+        let toml = "[min_mean_phred]\nwarn = 25.0\nfail = 20.0\n\n[max_n_fraction]\nfail = 0.05\n";
+        assert_eq!(QcPolicy::from_toml_str(toml).unwrap(), policy());
+
+        let json =
+            r#"{"min_mean_phred": {"warn": 25.0, "fail": 20.0}, "max_n_fraction": {"fail": 0.05}}"#;
+        assert_eq!(QcPolicy::from_json_str(json).unwrap(), policy());
+
+        assert!(QcPolicy::from_toml_str("[min_phred]\nfail = 20.0\n").is_err());
+    }
+}
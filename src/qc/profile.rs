@@ -0,0 +1,234 @@
+use super::QcStats;
+use crate::errors::BioError;
+use crate::nucleotide::{error_to_phred, qual_to_error};
+use crate::stats::Histogram;
+
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
+/// Distributions of a set of reads for QC reports: read lengths, per-read GC
+/// content and per-position quality.
+///
+/// Built incrementally with [`add`](Self::add), so reads can be streamed,
+/// and combined across chunks or files with [`merge`](Self::merge).
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::qc::QcProfile;
+///
+/// let mut profile = QcProfile::new();
+/// profile.add(b"ACGT", Some(b"II++"));
+/// profile.add(b"GG", Some(b"II"));
+///
+/// assert_eq!(profile.length_histogram.count_at(4), 1);
+/// assert_eq!(profile.gc_histogram.quantile(1.0), Some(100));
+/// assert_eq!(profile.mean_phred_per_position(), vec![40, 40, 10, 10]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct QcProfile {
+    pub num_reads: usize,
+    pub num_bases: usize,
+    /// `G`/`C` and `ACGT` base counts (case insensitive).
+    pub gc_bases: usize,
+    pub acgt_bases: usize,
+    /// Read lengths, with unit bins by default.
+    pub length_histogram: Histogram,
+    /// Rounded GC percent of the `ACGT` bases of each read, with unit bins
+    /// by default. Reads without `ACGT` bases are not counted.
+    pub gc_histogram: Histogram,
+    /// Sum of Phred+33 error probabilities per read position.
+    pub position_error_sum: Vec<f64>,
+    /// Number of quality values per read position.
+    pub position_count: Vec<usize>,
+}
+
+impl QcProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a profile from `(seq, qual)` pairs, see [`add`](Self::add).
+    pub fn from_records<'a, I>(records: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a [u8], Option<&'a [u8]>)>,
+    {
+        let mut profile = Self::new();
+        for (seq, qual) in records {
+            profile.add(seq, qual);
+        }
+
+        profile
+    }
+
+    /// Adds a read. Records without qualities (FASTA) do not contribute to the
+    /// per-position quality.
+    pub fn add(&mut self, seq: &[u8], qual: Option<&[u8]>) {
+        self.num_reads += 1;
+        self.num_bases += seq.len();
+        self.length_histogram.add(seq.len() as u64);
+
+        let (gc, acgt) = seq.iter().fold((0, 0), |(gc, acgt), nt| match nt {
+            b'G' | b'C' | b'g' | b'c' => (gc + 1, acgt + 1),
+            b'A' | b'T' | b'a' | b't' => (gc, acgt + 1),
+            _ => (gc, acgt),
+        });
+        self.gc_bases += gc;
+        self.acgt_bases += acgt;
+        if acgt > 0 {
+            self.gc_histogram
+                .add((100.0 * gc as f64 / acgt as f64).round() as u64);
+        }
+
+        if let Some(qual) = qual {
+            if qual.len() > self.position_count.len() {
+                self.position_error_sum.resize(qual.len(), 0.0);
+                self.position_count.resize(qual.len(), 0);
+            }
+
+            for (i, phred) in qual.iter().enumerate() {
+                self.position_error_sum[i] += qual_to_error(*phred);
+                self.position_count[i] += 1;
+            }
+        }
+    }
+
+    /// Adds the reads of `other`, as if they had been added to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if the histogram bin widths
+    /// of the profiles differ.
+    pub fn merge(&mut self, other: &QcProfile) -> Result<(), BioError> {
+        // Check both widths first, so a failed merge leaves `self` unchanged.
+        if self.gc_histogram.bin_width() != other.gc_histogram.bin_width() {
+            return Err(BioError::InvalidParameterError(
+                "cannot merge profiles with different gc histogram bin widths.".to_string(),
+            ));
+        }
+        self.length_histogram.merge(&other.length_histogram)?;
+        self.gc_histogram.merge(&other.gc_histogram)?;
+
+        self.num_reads += other.num_reads;
+        self.num_bases += other.num_bases;
+        self.gc_bases += other.gc_bases;
+        self.acgt_bases += other.acgt_bases;
+
+        if other.position_count.len() > self.position_count.len() {
+            self.position_error_sum
+                .resize(other.position_count.len(), 0.0);
+            self.position_count.resize(other.position_count.len(), 0);
+        }
+        for (i, (error, count)) in other
+            .position_error_sum
+            .iter()
+            .zip(&other.position_count)
+            .enumerate()
+        {
+            self.position_error_sum[i] += error;
+            self.position_count[i] += count;
+        }
+
+        Ok(())
+    }
+
+    /// Phred score of the mean error probability at every read position.
+    pub fn mean_phred_per_position(&self) -> Vec<u8> {
+        self.position_error_sum
+            .iter()
+            .zip(&self.position_count)
+            .map(|(error, &count)| error_to_phred(error / count as f64))
+            .collect()
+    }
+
+    /// Summary statistics of the profiled reads, equal to
+    /// [`QcStats::from_records`] over the same reads.
+    pub fn stats(&self) -> QcStats {
+        let ratio = |a: usize, b: usize| match b {
+            0 => 0.0,
+            _ => a as f64 / b as f64,
+        };
+
+        let num_qual: usize = self.position_count.iter().sum();
+        let mean_phred = match num_qual {
+            0 => 0.0,
+            _ => {
+                error_to_phred(self.position_error_sum.iter().sum::<f64>() / num_qual as f64) as f64
+            }
+        };
+
+        QcStats {
+            num_reads: self.num_reads,
+            num_bases: self.num_bases,
+            mean_len: ratio(self.num_bases, self.num_reads),
+            mean_phred,
+            gc_content: ratio(self.gc_bases, self.acgt_bases),
+            n_fraction: ratio(self.num_bases - self.acgt_bases, self.num_bases),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn records() -> Vec<(&'static [u8], Option<&'static [u8]>)> {
+        vec![
+            (b"ACGTAC", Some(b"IIIII+")),
+            (b"GGNN", Some(b"5555")),
+            (b"ATAT", None),
+            (b"", Some(b"")),
+        ]
+    }
+
+    #[test]
+    fn test_qc_profile_stats_match() {
+        let records = records();
+        let profile = QcProfile::from_records(records.clone());
+        let stats = QcStats::from_records(records);
+
+        assert_eq!(profile.stats(), stats);
+    }
+
+    #[test]
+    fn test_qc_profile_distributions() {
+        let profile = QcProfile::from_records(records());
+
+        assert_eq!(
+            profile.length_histogram.bins().collect::<Vec<_>>(),
+            vec![(0, 1), (4, 2), (6, 1)]
+        );
+        assert_eq!(
+            profile.gc_histogram.bins().collect::<Vec<_>>(),
+            vec![(0, 1), (50, 1), (100, 1)]
+        );
+        assert_eq!(profile.position_count, vec![2, 2, 2, 2, 1, 1]);
+        assert_eq!(profile.mean_phred_per_position()[4..], [40, 10]);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(3)]
+    fn test_qc_profile_merge(#[case] split: usize) {
+        let records = records();
+        let mut profile = QcProfile::from_records(records[..split].to_vec());
+        profile
+            .merge(&QcProfile::from_records(records[split..].to_vec()))
+            .unwrap();
+
+        let expected = QcProfile::from_records(records);
+
+        assert!(
+            profile
+                .position_error_sum
+                .iter()
+                .zip(&expected.position_error_sum)
+                .all(|(a, b)| (a - b).abs() < 1e-12)
+        );
+        profile.position_error_sum = expected.position_error_sum.clone();
+        assert_eq!(profile, expected);
+    }
+}
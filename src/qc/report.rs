@@ -0,0 +1,218 @@
+use super::{QcProfile, Verdict};
+use crate::errors::BioError;
+use serde_json::json;
+use std::io::Write;
+
+/// Page layout of [`write_html_report`]. `__TITLE__` and `__DATA__` are
+/// replaced by the escaped title and the report JSON.
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>__TITLE__</title>
+<style>
+body { font-family: sans-serif; margin: 2em auto; max-width: 960px; color: #222; }
+table { border-collapse: collapse; }
+td, th { padding: 0.2em 1em; text-align: left; border-bottom: 1px solid #ddd; }
+.Pass { color: #2a7d2a; } .Warn { color: #b07800; } .Fail { color: #b02020; }
+svg { width: 100%; height: 260px; }
+svg text { font-size: 11px; }
+</style>
+</head>
+<body>
+<h1>__TITLE__</h1>
+<div id="verdict"></div>
+<table id="summary"></table>
+<h2>Read length</h2><svg id="lengths"></svg>
+<h2>Mean quality per position</h2><svg id="quality"></svg>
+<h2>GC content per read</h2><svg id="gc"></svg>
+<script>
+const DATA = __DATA__;
+const NS = "http://www.w3.org/2000/svg";
+
+function el(parent, name, attrs, text) {
+  const e = document.createElementNS(NS, name);
+  for (const [k, v] of Object.entries(attrs)) e.setAttribute(k, v);
+  if (text !== undefined) e.textContent = text;
+  parent.appendChild(e);
+  return e;
+}
+
+function plot(id, points, xlabel, ylabel, bars) {
+  const svg = document.getElementById(id);
+  const w = svg.clientWidth || 900, h = 260, m = { l: 60, r: 10, t: 10, b: 40 };
+  if (points.length === 0) { el(svg, "text", { x: m.l, y: h / 2 }, "No data"); return; }
+  const xs = points.map(p => p[0]), ys = points.map(p => p[1]);
+  const x0 = Math.min(...xs), x1 = Math.max(...xs) + (bars ? 1 : 0) || 1, y1 = Math.max(...ys) || 1;
+  const sx = x => m.l + (x - x0) / (x1 - x0 || 1) * (w - m.l - m.r);
+  const sy = y => h - m.b - y / y1 * (h - m.t - m.b);
+  el(svg, "line", { x1: m.l, y1: h - m.b, x2: w - m.r, y2: h - m.b, stroke: "#888" });
+  el(svg, "line", { x1: m.l, y1: m.t, x2: m.l, y2: h - m.b, stroke: "#888" });
+  for (const x of [x0, (x0 + x1) / 2, x1]) el(svg, "text", { x: sx(x), y: h - m.b + 14, "text-anchor": "middle" }, Math.round(x));
+  for (const y of [0, y1 / 2, y1]) el(svg, "text", { x: m.l - 4, y: sy(y) + 4, "text-anchor": "end" }, +y.toPrecision(3));
+  el(svg, "text", { x: (w + m.l) / 2, y: h - 4, "text-anchor": "middle" }, xlabel);
+  el(svg, "text", { x: 12, y: h / 2, transform: `rotate(-90 12 ${h / 2})`, "text-anchor": "middle" }, ylabel);
+  if (bars) {
+    const bw = Math.max(1, sx(x0 + 1) - sx(x0) - 1);
+    for (const [x, y] of points) el(svg, "rect", { x: sx(x), y: sy(y), width: bw, height: h - m.b - sy(y), fill: "#4a7ab5" });
+  } else {
+    el(svg, "polyline", { points: points.map(([x, y]) => `${sx(x)},${sy(y)}`).join(" "), fill: "none", stroke: "#4a7ab5", "stroke-width": 2 });
+  }
+}
+
+const summary = document.getElementById("summary");
+for (const [k, v] of Object.entries(DATA.summary)) {
+  const row = summary.insertRow();
+  row.insertCell().textContent = k;
+  row.insertCell().textContent = Number.isInteger(v) ? v : v.toFixed(4);
+}
+if (DATA.verdict) {
+  const v = document.getElementById("verdict");
+  el(v, "h2", {}).textContent = "QC status: " + DATA.verdict.status;
+  v.firstChild.className = DATA.verdict.status;
+  const ul = v.appendChild(document.createElement("ul"));
+  for (const r of DATA.verdict.reasons) ul.appendChild(document.createElement("li")).textContent = r;
+}
+plot("lengths", DATA.lengths, "Read length (bp)", "Reads", true);
+plot("quality", DATA.quality.map((q, i) => [i + 1, q]), "Position (bp)", "Mean Phred", false);
+plot("gc", DATA.gc, "GC (%)", "Reads", true);
+</script>
+</body>
+</html>
+"##;
+
+/// Escapes `&`, `<`, `>` and quotes for HTML text and attributes.
+fn escape_html(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&#39;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Writes `profile` and an optional `verdict` as a self-contained HTML page
+/// with a summary table and read length, per-position quality and GC content
+/// plots.
+///
+/// The data is embedded as JSON and plotted with a few lines of inline
+/// JavaScript, so the page needs no external resources and can be shared as
+/// a single file.
+///
+/// # Errors
+///
+/// Returns [`BioError::SerializationError`] if the data cannot be serialized,
+/// or [`BioError::IoError`] if writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::qc::{QcPolicy, QcProfile, write_html_report};
+///
+/// let profile = QcProfile::from_records([(b"ACGT".as_slice(), Some(b"IIII".as_slice()))]);
+/// let verdict = QcPolicy::default().evaluate(&profile.stats());
+///
+/// let mut html = Vec::new();
+/// write_html_report(&mut html, "Sample 1", &profile, Some(&verdict)).unwrap();
+/// assert!(String::from_utf8(html).unwrap().contains("<title>Sample 1</title>"));
+/// ```
+pub fn write_html_report<W: Write + ?Sized>(
+    writer: &mut W,
+    title: &str,
+    profile: &QcProfile,
+    verdict: Option<&Verdict>,
+) -> Result<(), BioError> {
+    let stats = profile.stats();
+    let data = json!({
+        "summary": {
+            "Reads": stats.num_reads,
+            "Bases": stats.num_bases,
+            "Mean length": stats.mean_len,
+            "Mean Phred": stats.mean_phred,
+            "GC content": stats.gc_content,
+            "N fraction": stats.n_fraction,
+        },
+        "verdict": verdict,
+        "lengths": profile.length_histogram.bins().collect::<Vec<_>>(),
+        "quality": profile.mean_phred_per_position(),
+        "gc": profile.gc_histogram.bins().collect::<Vec<_>>(),
+    });
+
+    // `<` only occurs inside JSON strings, where the `\u003c` escape is
+    // equivalent and cannot close the script element.
+    let data = serde_json::to_string(&data)?.replace('<', "\\u003c");
+
+    // Fill the slots of the template only, so placeholders in the title or
+    // the data are kept as text.
+    let title = escape_html(title);
+    let (head, tail) = TEMPLATE
+        .split_once("__DATA__")
+        .expect("template has a data slot");
+    write!(
+        writer,
+        "{}{}{}",
+        head.replace("__TITLE__", &title),
+        data,
+        tail.replace("__TITLE__", &title)
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qc::{QcStatus, Verdict};
+
+    fn render(title: &str, verdict: Option<&Verdict>) -> String {
+        let profile = QcProfile::from_records([
+            (b"ACGTAC".as_slice(), Some(b"IIIII+".as_slice())),
+            (b"GG".as_slice(), Some(b"55".as_slice())),
+        ]);
+
+        let mut html = Vec::new();
+        write_html_report(&mut html, title, &profile, verdict).unwrap();
+        String::from_utf8(html).unwrap()
+    }
+
+    #[test]
+    fn test_write_html_report_data() {
+        let html = render("run", None);
+
+        assert!(html.contains(r#""lengths":[[2,1],[6,1]]"#));
+        assert!(html.contains(r#""quality":[22,22,40,40,40,10]"#));
+        assert!(html.contains(r#""verdict":null"#));
+        assert!(!html.contains("__DATA__"));
+    }
+
+    #[test]
+    fn test_write_html_report_escapes() {
+        let verdict = Verdict {
+            status: QcStatus::Warn,
+            reasons: vec!["</script><b>".to_string()],
+        };
+        let html = render("<b>a & b</b>", Some(&verdict));
+
+        assert!(html.contains("<title>&lt;b&gt;a &amp; b&lt;/b&gt;</title>"));
+        assert!(html.contains(r#""status":"Warn""#));
+        assert!(html.contains(r#"\u003c/script>\u003cb>"#));
+        assert_eq!(html.matches("</script>").count(), 1);
+    }
+
+    #[test]
+    fn test_write_html_report_placeholders() {
+        let verdict = Verdict {
+            status: QcStatus::Pass,
+            reasons: vec!["__TITLE__".to_string()],
+        };
+        let html = render("__DATA__", Some(&verdict));
+
+        assert!(html.contains("<title>__DATA__</title>"));
+        assert!(html.contains(r#""reasons":["__TITLE__"]"#));
+        assert_eq!(html.matches(r#""Mean Phred":"#).count(), 1);
+    }
+}
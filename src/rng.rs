@@ -0,0 +1,109 @@
+//! Seeding convention for stochastic utilities.
+//!
+//! Every stochastic operation in this crate, such as
+//! [`shuffle_sequence`](crate::nucleotide::shuffle_sequence),
+//! [`mutate`](crate::nucleotide::mutate),
+//! [`subsample_count`](crate::sampling::subsample_count) or
+//! [`downsample_to_depth`](crate::coverage::downsample_to_depth), takes an
+//! explicit `u64` seed, either as an argument or as the seed of a
+//! [`Seedable`] configuration. The seed drives a small internal SplitMix64
+//! generator that only uses wrapping integer arithmetic, so the same seed
+//! gives the same output on every platform and without an external RNG crate.
+
+/// A configuration of a stochastic operation whose output is fully
+/// determined by its seed.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::nucleotide::AmbigPolicy;
+/// use bio_utils_rs::rng::Seedable;
+///
+/// let policy = AmbigPolicy::Random { seed: 1 };
+/// assert_eq!(policy.with_seed(7).seed(), Some(7));
+/// assert_eq!(AmbigPolicy::Skip.seed(), None);
+/// ```
+pub trait Seedable: Sized {
+    /// Seed of the operation, `None` if the configuration is deterministic.
+    fn seed(&self) -> Option<u64>;
+
+    /// Returns the configuration with its seed replaced, e.g. for replicate
+    /// runs. Deterministic configurations are returned unchanged.
+    fn with_seed(self, seed: u64) -> Self;
+}
+
+/// SplitMix64 generator. Fast, tiny state and good enough for simulation.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    #[inline]
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in `[0.0, 1.0)`.
+    #[inline]
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform integer in `[0, n)`. `n` must be non-zero.
+    #[inline]
+    pub(crate) fn next_below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Shuffles `items` in place with the Fisher-Yates algorithm.
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.next_below(i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    /// Standard normal sample via the Box-Muller transform.
+    #[cfg_attr(not(any(test, feature = "test-utils")), allow(dead_code))]
+    #[inline]
+    pub(crate) fn next_normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = SplitMix64::new(7);
+
+        for _ in 0..1000 {
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f));
+            assert!(rng.next_below(5) < 5);
+        }
+    }
+}
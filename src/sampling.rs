@@ -0,0 +1,147 @@
+//! Seeded subsampling of reads, records or any other items.
+//!
+//! Both functions follow the [`rng`](crate::rng) seeding convention, so the
+//! same seed selects the same items on every platform.
+
+use crate::errors::BioError;
+use crate::rng::SplitMix64;
+
+/// Keeps each item independently with probability `fraction`, in a single
+/// streaming pass. The number of kept items is binomial; use
+/// [`subsample_count`] for an exact count.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `fraction` is outside
+/// `[0.0, 1.0]`.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::sampling::subsample_fraction;
+///
+/// let kept: Vec<u32> = subsample_fraction(0..1000, 0.1, 42).unwrap().collect();
+/// assert!(kept.len() > 50 && kept.len() < 150);
+/// assert!(kept.windows(2).all(|pair| pair[0] < pair[1]));
+/// ```
+pub fn subsample_fraction<I>(
+    items: I,
+    fraction: f64,
+    seed: u64,
+) -> Result<impl Iterator<Item = I::Item>, BioError>
+where
+    I: IntoIterator,
+{
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(BioError::InvalidParameterError(format!(
+            "subsample fraction {} must be in range 0-1.",
+            fraction
+        )));
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    Ok(items.into_iter().filter(move |_| rng.next_f64() < fraction))
+}
+
+/// Picks `n` items uniformly at random, or every item if there are fewer,
+/// with reservoir sampling. Uses a single pass and memory for `n` items, so
+/// `items` can be a stream of unknown length. Kept items are returned in
+/// input order.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::sampling::subsample_count;
+///
+/// let kept = subsample_count(0..1000, 10, 42);
+/// assert_eq!(kept.len(), 10);
+/// assert_eq!(kept, subsample_count(0..1000, 10, 42));
+/// ```
+pub fn subsample_count<I>(items: I, n: usize, seed: u64) -> Vec<I::Item>
+where
+    I: IntoIterator,
+{
+    let items = items.into_iter();
+    let mut rng = SplitMix64::new(seed);
+    // `n` is an upper bound and may far exceed the input.
+    let mut reservoir: Vec<(usize, I::Item)> = Vec::with_capacity(n.min(items.size_hint().0));
+
+    for (i, item) in items.enumerate() {
+        if i < n {
+            reservoir.push((i, item));
+            continue;
+        }
+
+        let j = rng.next_below(i + 1);
+        if j < n {
+            reservoir[j] = (i, item);
+        }
+    }
+
+    reservoir.sort_unstable_by_key(|(i, _)| *i);
+    reservoir.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0.0, 0)]
+    #[case(1.0, 1000)]
+    fn test_subsample_fraction_bounds(#[case] fraction: f64, #[case] expected: usize) {
+        assert_eq!(
+            subsample_fraction(0..1000, fraction, 1).unwrap().count(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_subsample_fraction_reproducible() {
+        let a: Vec<u32> = subsample_fraction(0..10_000, 0.25, 7).unwrap().collect();
+        let b: Vec<u32> = subsample_fraction(0..10_000, 0.25, 7).unwrap().collect();
+        let c: Vec<u32> = subsample_fraction(0..10_000, 0.25, 8).unwrap().collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!((a.len() as f64 / 10_000.0 - 0.25).abs() < 0.02);
+    }
+
+    #[rstest]
+    #[case(-0.1)]
+    #[case(1.5)]
+    #[case(f64::NAN)]
+    fn test_subsample_fraction_invalid(#[case] fraction: f64) {
+        assert!(subsample_fraction(0..10, fraction, 1).is_err());
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(10, 10)]
+    #[case(200, 100)]
+    #[case(1 << 40, 100)]
+    #[case(usize::MAX, 100)]
+    fn test_subsample_count_len(#[case] n: usize, #[case] expected: usize) {
+        let kept = subsample_count(0..100, n, 3);
+
+        assert_eq!(kept.len(), expected);
+        assert!(kept.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_subsample_count_uniform() {
+        // Every item should be kept in about n / len of the replicates.
+        let mut kept = [0usize; 20];
+        for seed in 0..10_000 {
+            for item in subsample_count(0..20, 5, seed) {
+                kept[item] += 1;
+            }
+        }
+
+        assert!(
+            kept.iter()
+                .all(|&count| (count as f64 / 10_000.0 - 0.25).abs() < 0.03)
+        );
+    }
+}
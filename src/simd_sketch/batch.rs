@@ -0,0 +1,79 @@
+use super::traits::{Sketch, Sketcher};
+use crate::errors::BioError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Sketches `seqs` on `threads` worker threads and returns sketches in input order.
+///
+/// See [`sketch_many_with_progress`].
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `threads` is `0`.
+pub fn sketch_many(
+    seqs: &[&[u8]],
+    sketcher: &dyn Sketcher,
+    threads: usize,
+) -> Result<Vec<Sketch>, BioError> {
+    sketch_many_with_progress(seqs, sketcher, threads, |_, _| {})
+}
+
+/// Sketches `seqs` on `threads` worker threads and returns sketches in input order.
+///
+/// Workers pull the next sequence index from a shared counter, so sequences
+/// are never copied and only finished sketches are held in memory, regardless
+/// of how many sequences are queued. `callback(done, total)` is invoked from
+/// the worker threads after every finished sketch.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `threads` is `0`.
+pub fn sketch_many_with_progress<F>(
+    seqs: &[&[u8]],
+    sketcher: &dyn Sketcher,
+    threads: usize,
+    callback: F,
+) -> Result<Vec<Sketch>, BioError>
+where
+    F: Fn(usize, usize) + Sync,
+{
+    if threads == 0 {
+        return Err(BioError::InvalidParameterError(
+            "number of threads must be greater than 0.".to_string(),
+        ));
+    }
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+
+    let mut indexed: Vec<(usize, Sketch)> = thread::scope(|s| {
+        let handles: Vec<_> = (0..threads.min(seqs.len().max(1)))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut local = Vec::new();
+
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= seqs.len() {
+                            break;
+                        }
+
+                        local.push((i, sketcher.sketch(seqs[i])));
+                        callback(done.fetch_add(1, Ordering::Relaxed) + 1, seqs.len());
+                    }
+
+                    local
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("sketch worker thread panicked"))
+            .collect()
+    });
+
+    indexed.sort_unstable_by_key(|(i, _)| *i);
+
+    Ok(indexed.into_iter().map(|(_, sketch)| sketch).collect())
+}
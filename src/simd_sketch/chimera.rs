@@ -0,0 +1,507 @@
+use super::minimizer::MinimizerSketch;
+use super::overlap::MinimizerHit;
+use crate::errors::BioError;
+use crate::nucleotide::{Match, Strand, reverse_complement, search_fuzzy_matches};
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+
+/// Minimizer index of reference sequences, queried by [`detect_chimera`].
+#[derive(Debug, Clone)]
+pub struct ChimeraIndex {
+    index: FxHashMap<u64, Vec<(usize, MinimizerHit)>>,
+    kmer_size: usize,
+}
+
+impl ChimeraIndex {
+    /// Indexes the minimizers of `references`, computed in parallel with `sketcher`.
+    ///
+    /// Minimizers occurring more than `max_occurrences` times across all
+    /// references are dropped as repeats.
+    pub fn new(references: &[&[u8]], sketcher: &MinimizerSketch, max_occurrences: usize) -> Self {
+        let minimizers: Vec<Vec<MinimizerHit>> = references
+            .par_iter()
+            .map(|seq| sketcher.minimizers(seq))
+            .collect();
+
+        Self::from_minimizers(&minimizers, sketcher.kmer_size, max_occurrences)
+    }
+
+    /// Indexes precomputed minimizers, where `minimizers[i]` belongs to reference `i`.
+    pub fn from_minimizers(
+        minimizers: &[Vec<MinimizerHit>],
+        kmer_size: usize,
+        max_occurrences: usize,
+    ) -> Self {
+        let mut index: FxHashMap<u64, Vec<(usize, MinimizerHit)>> = FxHashMap::default();
+        for (reference, hits) in minimizers.iter().enumerate() {
+            for hit in hits {
+                index.entry(hit.value).or_default().push((reference, *hit));
+            }
+        }
+        index.retain(|_, occurrences| occurrences.len() <= max_occurrences);
+
+        Self { index, kmer_size }
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+}
+
+/// Parameters for [`detect_chimera`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChimeraParams {
+    /// Minimum number of colinear minimizers for a read segment to count.
+    pub min_anchors: usize,
+    /// Maximum distance between consecutive anchors of a segment on the read.
+    pub max_gap: usize,
+    /// Maximum diagonal drift between consecutive anchors of a segment, i.e.
+    /// the largest indel tolerated within a segment.
+    pub bandwidth: usize,
+    /// Adapter sequences searched for inside the read, on both strands.
+    pub adapters: Vec<Vec<u8>>,
+    /// Maximum edit distance of an adapter match.
+    pub max_adapter_edits: u8,
+    /// Adapter hits starting or ending within this many bases of a read end
+    /// are regular adapters rather than chimeric junctions.
+    pub adapter_margin: usize,
+}
+
+impl Default for ChimeraParams {
+    fn default() -> Self {
+        Self {
+            min_anchors: 4,
+            max_gap: 1000,
+            bandwidth: 100,
+            adapters: Vec::new(),
+            max_adapter_edits: 3,
+            adapter_margin: 100,
+        }
+    }
+}
+
+/// A part of a read colinear with one reference, reported by [`detect_chimera`].
+///
+/// Coordinates are 0-based, half-open and on the forward strand of the read
+/// and reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadSegment {
+    pub read_start: usize,
+    pub read_end: usize,
+    /// Index of the reference in the [`ChimeraIndex`].
+    pub reference: usize,
+    pub strand: Strand,
+    pub ref_start: usize,
+    pub ref_end: usize,
+    /// Number of colinear minimizers.
+    pub anchors: usize,
+}
+
+/// Result of [`detect_chimera`] for a single read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChimeraReport {
+    /// Colinear read segments, sorted by read start.
+    pub segments: Vec<ReadSegment>,
+    /// Internal adapter hits, sorted by start.
+    pub adapter_hits: Vec<Match>,
+    /// Suggested read positions to split at, sorted and deduplicated.
+    pub split_points: Vec<usize>,
+}
+
+impl ChimeraReport {
+    pub fn is_chimeric(&self) -> bool {
+        !self.split_points.is_empty()
+    }
+}
+
+/// Minimizer shared by the read and a reference.
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    read_pos: usize,
+    ref_pos: usize,
+    /// `ref_pos - read_pos` on the forward strand and `ref_pos + read_pos` on
+    /// the reverse strand, constant along a colinear segment.
+    diagonal: i64,
+}
+
+/// Splits anchors of one reference and strand into colinear runs.
+///
+/// Anchors are first clustered by diagonal, linking anchors whose diagonals
+/// differ by at most `bandwidth`, so that repeat hits interleaved on the read
+/// do not break a run. Each cluster is then split where consecutive anchors
+/// are more than `max_gap` apart on the read.
+fn colinear_runs(
+    mut anchors: Vec<Anchor>,
+    reference: usize,
+    strand: Strand,
+    kmer_size: usize,
+    params: &ChimeraParams,
+) -> Vec<ReadSegment> {
+    anchors.sort_unstable_by_key(|a| (a.diagonal, a.read_pos));
+
+    let mut segments = Vec::new();
+    let mut cluster_start = 0;
+
+    for i in 1..=anchors.len() {
+        if i < anchors.len()
+            && anchors[i].diagonal.abs_diff(anchors[i - 1].diagonal) as usize <= params.bandwidth
+        {
+            continue;
+        }
+
+        let cluster = &mut anchors[cluster_start..i];
+        cluster_start = i;
+        cluster.sort_unstable_by_key(|a| a.read_pos);
+
+        let mut run_start = 0;
+        for j in 1..=cluster.len() {
+            if j < cluster.len() && cluster[j].read_pos - cluster[j - 1].read_pos <= params.max_gap
+            {
+                continue;
+            }
+
+            let run = &cluster[run_start..j];
+            run_start = j;
+            if run.len() < params.min_anchors {
+                continue;
+            }
+
+            segments.push(ReadSegment {
+                read_start: run[0].read_pos,
+                read_end: run[run.len() - 1].read_pos + kmer_size,
+                reference,
+                strand,
+                ref_start: run.iter().map(|a| a.ref_pos).min().unwrap_or(0),
+                ref_end: run.iter().map(|a| a.ref_pos).max().unwrap_or(0) + kmer_size,
+                anchors: run.len(),
+            });
+        }
+    }
+
+    segments
+}
+
+/// Colinear segments of the read, choosing the best supported segment where
+/// segments overlap on the read and merging neighbours that continue the same
+/// alignment.
+fn read_segments(
+    read_minimizers: &[MinimizerHit],
+    index: &ChimeraIndex,
+    params: &ChimeraParams,
+) -> Vec<ReadSegment> {
+    let mut anchors: FxHashMap<(usize, Strand), Vec<Anchor>> = FxHashMap::default();
+    for hit in read_minimizers {
+        for (reference, other) in index.index.get(&hit.value).into_iter().flatten() {
+            let (read_pos, ref_pos) = (hit.pos as usize, other.pos as usize);
+            let strand = match hit.forward == other.forward {
+                true => Strand::Forward,
+                false => Strand::Reverse,
+            };
+            let diagonal = match strand {
+                Strand::Forward => ref_pos as i64 - read_pos as i64,
+                Strand::Reverse => ref_pos as i64 + read_pos as i64,
+            };
+
+            anchors
+                .entry((*reference, strand))
+                .or_default()
+                .push(Anchor {
+                    read_pos,
+                    ref_pos,
+                    diagonal,
+                });
+        }
+    }
+
+    let mut candidates: Vec<ReadSegment> = anchors
+        .into_iter()
+        .flat_map(|((reference, strand), anchors)| {
+            colinear_runs(anchors, reference, strand, index.kmer_size, params)
+        })
+        .collect();
+
+    // Keep the best supported segments, allowing at most half of a segment to
+    // overlap already accepted ones on the read.
+    candidates.sort_unstable_by_key(|s| {
+        (
+            std::cmp::Reverse(s.anchors),
+            s.read_start,
+            s.reference,
+            s.strand,
+        )
+    });
+    let mut accepted: Vec<ReadSegment> = Vec::new();
+    for candidate in candidates {
+        let overlap: usize = accepted
+            .iter()
+            .map(|s| {
+                s.read_end
+                    .min(candidate.read_end)
+                    .saturating_sub(s.read_start.max(candidate.read_start))
+            })
+            .sum();
+
+        if 2 * overlap <= candidate.read_end - candidate.read_start {
+            accepted.push(candidate);
+        }
+    }
+    accepted.sort_unstable_by_key(|s| s.read_start);
+
+    let mut segments: Vec<ReadSegment> = Vec::with_capacity(accepted.len());
+    for segment in accepted {
+        match segments.last_mut() {
+            Some(last) if continues_alignment(last, &segment, params) => {
+                last.read_end = last.read_end.max(segment.read_end);
+                last.ref_start = last.ref_start.min(segment.ref_start);
+                last.ref_end = last.ref_end.max(segment.ref_end);
+                last.anchors += segment.anchors;
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    segments
+}
+
+/// Whether `next` continues the alignment of `prev` on the same reference and
+/// strand, e.g. after a low quality stretch of the read, rather than jumping
+/// to another locus.
+fn continues_alignment(prev: &ReadSegment, next: &ReadSegment, params: &ChimeraParams) -> bool {
+    if prev.reference != next.reference || prev.strand != next.strand {
+        return false;
+    }
+
+    let read_gap = next.read_start as i64 - prev.read_end as i64;
+    let ref_gap = match prev.strand {
+        Strand::Forward => next.ref_start as i64 - prev.ref_end as i64,
+        Strand::Reverse => prev.ref_start as i64 - next.ref_end as i64,
+    };
+
+    read_gap.abs_diff(ref_gap) as usize <= params.max_gap
+}
+
+/// Non-overlapping internal adapter hits, preferring fewer edits.
+fn internal_adapter_hits(read: &[u8], params: &ChimeraParams) -> Result<Vec<Match>, BioError> {
+    let mut candidates = Vec::new();
+    for (i, adapter) in params.adapters.iter().enumerate() {
+        let rc = reverse_complement(adapter);
+        for (pattern, strand) in [
+            (adapter.as_slice(), Strand::Forward),
+            (&rc, Strand::Reverse),
+        ] {
+            for hit in search_fuzzy_matches(read, pattern, params.max_adapter_edits)? {
+                if hit.start >= params.adapter_margin
+                    && hit.end + params.adapter_margin <= read.len()
+                {
+                    candidates.push(Match {
+                        strand,
+                        pattern_id: i,
+                        ..hit
+                    });
+                }
+            }
+        }
+    }
+
+    candidates.sort_unstable_by_key(|m| (m.edits, m.start));
+    let mut hits: Vec<Match> = Vec::new();
+    for candidate in candidates {
+        if hits
+            .iter()
+            .all(|hit| candidate.end <= hit.start || candidate.start >= hit.end)
+        {
+            hits.push(candidate);
+        }
+    }
+    hits.sort_unstable_by_key(|m| m.start);
+
+    Ok(hits)
+}
+
+/// Flags a likely chimeric long read and suggests where to split it.
+///
+/// Two independent signals are combined:
+///
+/// - **Mapping discontinuities.** Minimizers shared with the references in
+///   `index` are chained into colinear segments. Consecutive segments on
+///   different references or strands, or whose read and reference gaps
+///   differ by more than `max_gap`, indicate a junction, and the midpoint
+///   between them is suggested as a split point.
+/// - **Internal adapters.** `adapters` are searched on both strands with
+///   Myers fuzzy search; hits at least `adapter_margin` bases away from the
+///   read ends suggest a split at their midpoint.
+///
+/// `read_minimizers` must be computed with the same k-mer size and hashing as
+/// the index, e.g. by [`MinimizerSketch::minimizers`].
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_anchors` is `0` or an
+/// adapter is empty.
+pub fn detect_chimera(
+    read: &[u8],
+    read_minimizers: &[MinimizerHit],
+    index: &ChimeraIndex,
+    params: &ChimeraParams,
+) -> Result<ChimeraReport, BioError> {
+    if params.min_anchors == 0 {
+        return Err(BioError::InvalidParameterError(
+            "minimum number of anchors must be greater than 0.".to_string(),
+        ));
+    }
+
+    let segments = read_segments(read_minimizers, index, params);
+    let adapter_hits = internal_adapter_hits(read, params)?;
+
+    let mut split_points: Vec<usize> = segments
+        .windows(2)
+        .map(|pair| (pair[0].read_end + pair[1].read_start) / 2)
+        .chain(adapter_hits.iter().map(|hit| (hit.start + hit.end) / 2))
+        .collect();
+    split_points.sort_unstable();
+    split_points.dedup();
+
+    Ok(ChimeraReport {
+        segments,
+        adapter_hits,
+        split_points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmers::mm_hash64;
+    use crate::nucleotide::NT_LOOKUP;
+    use crate::rng::SplitMix64;
+    use crate::simd_sketch::overlap::kmer_orientation;
+    use crate::test_utils::random_sequence;
+
+    const K: usize = 15;
+
+    /// Every canonical k-mer as a minimizer, to test without SIMD.
+    fn all_kmers(seq: &[u8]) -> Vec<MinimizerHit> {
+        (0..=seq.len().saturating_sub(K))
+            .filter_map(|i| {
+                let kmer = &seq[i..i + K];
+                let forward = kmer_orientation(kmer)?;
+                let canonical = match forward {
+                    true => kmer.to_vec(),
+                    false => reverse_complement(kmer),
+                };
+                let value = canonical
+                    .iter()
+                    .fold(0u64, |v, b| v << 2 | NT_LOOKUP[*b as usize] as u64);
+
+                Some(MinimizerHit {
+                    value: mm_hash64(value),
+                    pos: i as u32,
+                    forward,
+                })
+            })
+            .collect()
+    }
+
+    fn index(references: &[&[u8]]) -> ChimeraIndex {
+        let minimizers: Vec<Vec<MinimizerHit>> =
+            references.iter().map(|seq| all_kmers(seq)).collect();
+        ChimeraIndex::from_minimizers(&minimizers, K, 10)
+    }
+
+    #[test]
+    fn test_detect_chimera_junction() {
+        let a = random_sequence(&mut SplitMix64::new(1), 5000, 0.5);
+        let b = random_sequence(&mut SplitMix64::new(2), 5000, 0.5);
+        let index = index(&[&a, &b]);
+
+        // a[1000..2000] fused to the reverse complement of b[3000..4000].
+        let read = [
+            &a[1000..2000],
+            reverse_complement(&b[3000..4000]).as_slice(),
+        ]
+        .concat();
+        let report =
+            detect_chimera(&read, &all_kmers(&read), &index, &ChimeraParams::default()).unwrap();
+
+        assert!(report.is_chimeric());
+        assert_eq!(report.segments.len(), 2);
+        assert_eq!(
+            (report.segments[0].reference, report.segments[0].strand),
+            (0, Strand::Forward)
+        );
+        assert_eq!(
+            (report.segments[1].reference, report.segments[1].strand),
+            (1, Strand::Reverse)
+        );
+        assert_eq!(report.segments[1].ref_start, 3000);
+        assert_eq!(report.split_points.len(), 1);
+        assert!(report.split_points[0].abs_diff(1000) <= K);
+    }
+
+    #[test]
+    fn test_detect_chimera_same_reference_jump() {
+        let a = random_sequence(&mut SplitMix64::new(3), 8000, 0.5);
+        let index = index(&[&a]);
+
+        // Two distant loci of the same reference.
+        let read = [&a[500..1500], &a[6000..7000]].concat();
+        let report =
+            detect_chimera(&read, &all_kmers(&read), &index, &ChimeraParams::default()).unwrap();
+
+        assert_eq!(report.segments.len(), 2);
+        assert_eq!(report.split_points.len(), 1);
+    }
+
+    #[test]
+    fn test_detect_chimera_clean_read() {
+        let a = random_sequence(&mut SplitMix64::new(4), 5000, 0.5);
+        let index = index(&[&a]);
+
+        // A clean read with a 40 bp deletion and a stretch of noise.
+        let mut read = [&a[1000..2000], &a[2040..3000]].concat();
+        read.splice(
+            1500..1600,
+            random_sequence(&mut SplitMix64::new(5), 100, 0.5),
+        );
+        let report =
+            detect_chimera(&read, &all_kmers(&read), &index, &ChimeraParams::default()).unwrap();
+
+        assert!(!report.is_chimeric());
+        assert_eq!(report.segments.len(), 1);
+        assert_eq!(report.segments[0].ref_start, 1000);
+        assert_eq!(report.segments[0].ref_end, 3000);
+    }
+
+    #[test]
+    fn test_detect_chimera_internal_adapter() {
+        let adapter = b"AATGTACTTCGTTCAGTTACGTATTGCT".to_vec();
+        let insert = random_sequence(&mut SplitMix64::new(6), 600, 0.5);
+        let read = [
+            adapter.as_slice(),
+            &insert[..300],
+            &reverse_complement(&adapter),
+            &insert[300..],
+        ]
+        .concat();
+
+        let params = ChimeraParams {
+            adapters: vec![adapter],
+            ..Default::default()
+        };
+        let report = detect_chimera(&read, &[], &index(&[]), &params).unwrap();
+
+        // The adapter at the read start is ignored.
+        assert_eq!(report.adapter_hits.len(), 1);
+        assert_eq!(report.adapter_hits[0].strand, Strand::Reverse);
+        assert_eq!(report.split_points, vec![328 + 14]);
+    }
+
+    #[test]
+    fn test_detect_chimera_invalid() {
+        let params = ChimeraParams {
+            min_anchors: 0,
+            ..Default::default()
+        };
+        assert!(detect_chimera(b"ACGT", &[], &index(&[]), &params).is_err());
+    }
+}
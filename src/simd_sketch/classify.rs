@@ -0,0 +1,111 @@
+use super::database::SketchDatabase;
+use super::screen::check_min_containment;
+use super::traits::Sketcher;
+use crate::errors::BioError;
+use crate::taxonomy::{TaxId, Taxonomy};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+#[cfg(feature = "io")]
+use serde::Serialize;
+
+/// Taxonomic assignment of one query.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize))]
+pub struct TaxonAssignment {
+    pub query: String,
+    /// LCA of the matching references, `None` if unclassified.
+    pub taxid: Option<TaxId>,
+    /// Number of matching references with a known taxid.
+    pub references: usize,
+}
+
+/// Assigns each query to the lowest common ancestor of its matching references.
+///
+/// Each `(name, seq)` is queried against `database`. Every reference with a
+/// containment of at least `min_containment` and a taxid in `taxids` is a
+/// hit, and the query is assigned the [`Taxonomy::lca_many`] of its hits, as
+/// in Kraken. Queries without hits are left unclassified. Queries are
+/// processed in parallel and assignments are returned in input order.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_containment` is outside `[0.0, 1.0]`.
+pub fn classify<S: Sketcher>(
+    database: &SketchDatabase<S>,
+    taxonomy: &Taxonomy,
+    taxids: &HashMap<String, TaxId>,
+    queries: &[(&str, &[u8])],
+    min_containment: f64,
+) -> Result<Vec<TaxonAssignment>, BioError> {
+    check_min_containment(min_containment)?;
+
+    Ok(queries
+        .par_iter()
+        .map(|(name, seq)| {
+            let hits: Vec<TaxId> = database
+                .query(seq)
+                .into_iter()
+                .take_while(|m| m.containment >= min_containment)
+                .filter_map(|m| taxids.get(&m.name).copied())
+                .filter(|taxid| taxonomy.contains(*taxid))
+                .collect();
+
+            TaxonAssignment {
+                query: name.to_string(),
+                taxid: taxonomy.lca_many(hits.iter().copied()),
+                references: hits.len(),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmers::Sketch;
+
+    struct KmerSet(usize);
+
+    impl Sketcher for KmerSet {
+        fn sketch(&self, seq: &[u8]) -> Sketch {
+            seq.windows(self.0)
+                .map(|w| w.iter().fold(0u64, |h, b| h * 31 + *b as u64))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let taxonomy = Taxonomy::from_nodes([
+            (1, 1, "no rank"),
+            (561, 1, "genus"),
+            (562, 561, "species"),
+            (564, 561, "species"),
+            (1280, 1, "species"),
+        ]);
+        let taxids: HashMap<String, TaxId> =
+            [("ecoli", 562), ("efergusonii", 564), ("saureus", 1280)]
+                .into_iter()
+                .map(|(n, t)| (n.to_string(), t))
+                .collect();
+
+        let mut db = SketchDatabase::new(KmerSet(4));
+        db.add("ecoli", b"ACGTACGGTCAGTTTTGGGCCA").unwrap();
+        db.add("efergusonii", b"ACGTACGGTCAGAAAAAAAA").unwrap();
+        db.add("saureus", b"GAGTTTTATCGCTTCCATGACGCAG").unwrap();
+
+        let queries: Vec<(&str, &[u8])> = vec![
+            ("shared", b"ACGTACGGTCAG"),
+            ("specific", b"TTTTGGGCCA"),
+            ("unknown", b"CCCCCCCC"),
+        ];
+
+        let assignments = classify(&db, &taxonomy, &taxids, &queries, 1.0).unwrap();
+        let assigned: Vec<Option<TaxId>> = assignments.iter().map(|a| a.taxid).collect();
+
+        assert_eq!(assigned, vec![Some(561), Some(562), None]);
+        assert_eq!(assignments[0].references, 2);
+        assert_eq!(assignments[2].references, 0);
+    }
+}
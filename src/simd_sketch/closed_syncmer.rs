@@ -1,21 +1,70 @@
-use super::traits::Sketcher;
+use super::syncmer::validate_syncmer_sizes;
+use super::traits::{Sketch, Sketcher};
+use crate::errors::BioError;
 use packed_seq::{PackedSeqVec, SeqVec};
 use simd_minimizers::*;
-use std::collections::HashSet;
 
+/// Closed syncmer sketch.
+///
+/// A k-mer is a closed syncmer if its smallest s-mer is the first or the last
+/// one. The expected density is `2 / (kmer_size - smer_size + 1)` of all
+/// k-mers, and every window of `kmer_size - smer_size` consecutive k-mers is
+/// guaranteed to contain at least one syncmer.
 pub struct ClosedSyncmerSketch {
-    pub kmer_size: usize,
-    pub window_size: usize,
+    kmer_size: usize,
+    smer_size: usize,
+}
+
+impl ClosedSyncmerSketch {
+    /// Creates a closed syncmer sketcher with validated parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] unless `0 < smer_size < kmer_size <= 32`.
+    pub fn new(kmer_size: usize, smer_size: usize) -> Result<Self, BioError> {
+        validate_syncmer_sizes(kmer_size, smer_size)?;
+
+        Ok(Self {
+            kmer_size,
+            smer_size,
+        })
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+
+    pub fn smer_size(&self) -> usize {
+        self.smer_size
+    }
 }
 
 impl Sketcher for ClosedSyncmerSketch {
-    fn sketch(&self, seq: &[u8]) -> HashSet<u64> {
+    fn sketch(&self, seq: &[u8]) -> Sketch {
         let packed_seq = PackedSeqVec::from_ascii(seq);
         let mut syncmer_positions = Vec::new();
 
-        canonical_closed_syncmers(self.kmer_size, self.window_size)
+        canonical_closed_syncmers(self.smer_size, self.kmer_size - self.smer_size + 1)
             .run(packed_seq.as_slice(), &mut syncmer_positions)
             .values_u64()
             .collect()
     }
+
+    fn positions(&self, seq: &[u8]) -> Option<Vec<(usize, u64)>> {
+        let packed_seq = PackedSeqVec::from_ascii(seq);
+        let mut syncmer_positions = Vec::new();
+
+        Some(
+            canonical_closed_syncmers(self.smer_size, self.kmer_size - self.smer_size + 1)
+                .run(packed_seq.as_slice(), &mut syncmer_positions)
+                .pos_and_values_u64()
+                .map(|(pos, value)| (pos as usize, value))
+                .collect(),
+        )
+    }
+
+    /// Whether a k-mer is a syncmer depends only on the k-mer itself.
+    fn context_len(&self) -> usize {
+        self.kmer_size
+    }
 }
@@ -0,0 +1,240 @@
+use super::traits::{Sketch, Sketcher};
+use crate::errors::BioError;
+use rustc_hash::FxHashMap;
+
+/// A reference hit for a [`SketchDatabase`] query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SketchMatch {
+    pub name: String,
+    /// Number of hashes shared by the query and the reference.
+    pub shared: usize,
+    /// Fraction of query hashes found in the reference.
+    pub containment: f64,
+    /// Jaccard index between the query and reference sketches.
+    pub jaccard: f64,
+}
+
+/// In-memory sketch search engine with incremental updates.
+///
+/// Owns the reference sketches and a reverse index from hash to the
+/// references containing it, so a query only visits references that share at
+/// least one hash with it. References can be added and removed at any time.
+pub struct SketchDatabase<S: Sketcher> {
+    sketcher: S,
+    /// Reference slots, `None` after removal.
+    references: Vec<Option<(String, Sketch)>>,
+    ids: FxHashMap<String, usize>,
+    index: FxHashMap<u64, Vec<usize>>,
+}
+
+impl<S: Sketcher> SketchDatabase<S> {
+    /// Creates an empty database that sketches sequences with `sketcher`.
+    pub fn new(sketcher: S) -> Self {
+        Self {
+            sketcher,
+            references: Vec::new(),
+            ids: FxHashMap::default(),
+            index: FxHashMap::default(),
+        }
+    }
+
+    pub fn sketcher(&self) -> &S {
+        &self.sketcher
+    }
+
+    /// Number of references.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.ids.contains_key(name)
+    }
+
+    /// Reference names, in insertion order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.references
+            .iter()
+            .flatten()
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the sketch of reference `name`.
+    pub fn get(&self, name: &str) -> Option<&Sketch> {
+        self.ids
+            .get(name)
+            .and_then(|&id| self.references[id].as_ref())
+            .map(|(_, sketch)| sketch)
+    }
+
+    /// Sketches `seq` and adds it as reference `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::DuplicateIdError`] if `name` is already present.
+    pub fn add(&mut self, name: &str, seq: &[u8]) -> Result<(), BioError> {
+        let sketch = self.sketcher.sketch(seq);
+        self.add_sketch(name, sketch)
+    }
+
+    /// Adds a precomputed sketch as reference `name`.
+    ///
+    /// The sketch must come from a sketcher with the same parameters as the
+    /// database's, otherwise queries are meaningless.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::DuplicateIdError`] if `name` is already present.
+    pub fn add_sketch(&mut self, name: &str, sketch: Sketch) -> Result<(), BioError> {
+        if self.ids.contains_key(name) {
+            return Err(BioError::DuplicateIdError(name.to_string()));
+        }
+
+        let id = self.references.len();
+        for hash in &sketch {
+            self.index.entry(*hash).or_default().push(id);
+        }
+
+        self.ids.insert(name.to_string(), id);
+        self.references.push(Some((name.to_string(), sketch)));
+
+        Ok(())
+    }
+
+    /// Removes reference `name`, returning its sketch if it was present.
+    pub fn remove(&mut self, name: &str) -> Option<Sketch> {
+        let id = self.ids.remove(name)?;
+        let (_, sketch) = self.references[id].take()?;
+
+        for hash in &sketch {
+            if let Some(postings) = self.index.get_mut(hash) {
+                postings.retain(|&i| i != id);
+                if postings.is_empty() {
+                    self.index.remove(hash);
+                }
+            }
+        }
+
+        Some(sketch)
+    }
+
+    /// Sketches `seq` and returns matching references, see [`SketchDatabase::query_sketch`].
+    pub fn query(&self, seq: &[u8]) -> Vec<SketchMatch> {
+        self.query_sketch(&self.sketcher.sketch(seq))
+    }
+
+    /// Returns all references sharing at least one hash with `sketch`.
+    ///
+    /// Matches are ranked by containment, then shared hashes (both descending),
+    /// then name.
+    pub fn query_sketch(&self, sketch: &Sketch) -> Vec<SketchMatch> {
+        let mut shared: FxHashMap<usize, usize> = FxHashMap::default();
+
+        for hash in sketch {
+            for &id in self.index.get(hash).into_iter().flatten() {
+                *shared.entry(id).or_default() += 1;
+            }
+        }
+
+        let mut matches: Vec<SketchMatch> = shared
+            .into_iter()
+            .filter_map(|(id, shared)| {
+                let (name, reference) = self.references[id].as_ref()?;
+                let union = sketch.len() + reference.len() - shared;
+
+                Some(SketchMatch {
+                    name: name.clone(),
+                    shared,
+                    containment: shared as f64 / sketch.len() as f64,
+                    jaccard: shared as f64 / union as f64,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.containment
+                .total_cmp(&a.containment)
+                .then(b.shared.cmp(&a.shared))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exact k-mer set, to keep tests independent of SIMD sketch parameters.
+    struct KmerSet(usize);
+
+    impl Sketcher for KmerSet {
+        fn sketch(&self, seq: &[u8]) -> Sketch {
+            seq.windows(self.0)
+                .map(|w| w.iter().fold(0u64, |h, b| h * 31 + *b as u64))
+                .collect()
+        }
+    }
+
+    fn database() -> SketchDatabase<KmerSet> {
+        let mut db = SketchDatabase::new(KmerSet(4));
+        db.add("a", b"ACGTACGGTCAGT").unwrap();
+        db.add("b", b"ACGTACGGTCAGTTTTGGGCCA").unwrap();
+        db.add("c", b"GGGGGGGGGG").unwrap();
+        db
+    }
+
+    #[test]
+    fn test_query_ranking() {
+        let db = database();
+        let matches = db.query(b"ACGTACGGTCAGTTTT");
+
+        let names: Vec<&str> = matches.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["b", "a"]);
+        assert_eq!(matches[0].containment, 1.0);
+        assert!(matches[1].containment < 1.0);
+        assert!(matches[0].jaccard < 1.0);
+    }
+
+    #[test]
+    fn test_add_duplicate() {
+        let mut db = database();
+        assert!(matches!(
+            db.add("a", b"ACGT"),
+            Err(BioError::DuplicateIdError(_))
+        ));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut db = database();
+
+        assert!(db.remove("b").is_some());
+        assert!(db.remove("b").is_none());
+        assert_eq!(db.len(), 2);
+        assert_eq!(db.names().collect::<Vec<&str>>(), vec!["a", "c"]);
+
+        let names: Vec<String> = db
+            .query(b"ACGTACGGTCAGTTTT")
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec!["a"]);
+
+        db.add("b", b"TTTTGGGCCA").unwrap();
+        assert!(db.contains("b"));
+        assert!(db.get("b").is_some());
+    }
+
+    #[test]
+    fn test_query_no_match() {
+        let db = database();
+        assert!(db.query(b"CCCCCCCC").is_empty());
+        assert!(db.query(b"").is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use super::traits::{Sketch, Sketcher};
+use crate::errors::BioError;
+use crate::io::needletail_reader;
+use rayon::prelude::*;
+use std::path::Path;
+
+/// Number of records read before a batch is processed in parallel.
+const SKETCH_BATCH_SIZE: usize = 1024;
+
+/// Reads `(name, seq)` records from a FASTA/FASTQ file and passes them to
+/// `f` in batches of at most `SKETCH_BATCH_SIZE`, in file order. Names are
+/// the record ID up to the first whitespace.
+pub(super) fn for_each_batch<F>(path: &Path, mut f: F) -> Result<(), BioError>
+where
+    F: FnMut(&mut Vec<(String, Vec<u8>)>),
+{
+    let mut reader = needletail_reader(Some(path.to_path_buf()))?;
+    let mut batch = Vec::with_capacity(SKETCH_BATCH_SIZE);
+
+    while let Some(record) = reader.next() {
+        let record = record?;
+
+        let name = String::from_utf8_lossy(record.id())
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        batch.push((name, record.seq().into_owned()));
+
+        if batch.len() == SKETCH_BATCH_SIZE {
+            f(&mut batch);
+            batch.clear();
+        }
+    }
+
+    if !batch.is_empty() {
+        f(&mut batch);
+    }
+
+    Ok(())
+}
+
+/// Sketches every record of a FASTA/FASTQ file (plain or gzip-compressed).
+///
+/// Records are read with [`needletail_reader`] in batches of
+/// `SKETCH_BATCH_SIZE` and each batch is sketched in parallel with rayon, so
+/// at most one batch of sequences is held in memory. Names are the record ID
+/// up to the first whitespace, and sketches are returned in file order.
+///
+/// # Errors
+///
+/// Returns [`BioError`] if the file does not exist, has an unrecognized
+/// extension, or cannot be parsed.
+pub fn sketch_file(
+    path: &Path,
+    sketcher: &dyn Sketcher,
+) -> Result<Vec<(String, Sketch)>, BioError> {
+    let mut sketches = Vec::new();
+
+    for_each_batch(path, |batch| {
+        sketches.par_extend(batch.par_drain(..).map(|(name, seq)| {
+            let sketch = sketcher.sketch(&seq);
+            (name, sketch)
+        }));
+    })?;
+
+    Ok(sketches)
+}
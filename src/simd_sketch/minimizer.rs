@@ -1,15 +1,42 @@
-use super::traits::Sketcher;
+use super::overlap::{MinimizerHit, kmer_orientation};
+use super::traits::{Sketch, Sketcher};
 use packed_seq::{PackedSeqVec, SeqVec};
 use simd_minimizers::{canonical_minimizers, seq_hash};
-use std::collections::HashSet;
 
 pub struct MinimizerSketch {
     pub kmer_size: usize,
     pub window_size: usize,
 }
 
+impl MinimizerSketch {
+    /// Returns the canonical minimizers of `seq` with their positions and orientation.
+    ///
+    /// Palindromic minimizers and minimizers containing ambiguous bases are
+    /// skipped, since their orientation is undefined.
+    pub fn minimizers(&self, seq: &[u8]) -> Vec<MinimizerHit> {
+        let packed_seq = PackedSeqVec::from_ascii(seq);
+        let hasher = <seq_hash::NtHasher>::new(self.kmer_size);
+
+        let mut minimizer_positions = Vec::with_capacity(seq.len() * 2 / (self.window_size + 1));
+
+        canonical_minimizers(self.kmer_size, self.window_size)
+            .hasher(&hasher)
+            .run(packed_seq.as_slice(), &mut minimizer_positions)
+            .pos_and_values_u64()
+            .filter_map(|(pos, value)| {
+                let kmer = &seq[pos as usize..pos as usize + self.kmer_size];
+                Some(MinimizerHit {
+                    value,
+                    pos,
+                    forward: kmer_orientation(kmer)?,
+                })
+            })
+            .collect()
+    }
+}
+
 impl Sketcher for MinimizerSketch {
-    fn sketch(&self, seq: &[u8]) -> HashSet<u64> {
+    fn sketch(&self, seq: &[u8]) -> Sketch {
         let packed_seq = PackedSeqVec::from_ascii(seq);
         let hasher = <seq_hash::NtHasher>::new(self.kmer_size);
 
@@ -25,4 +52,24 @@ impl Sketcher for MinimizerSketch {
             .values_u64()
             .collect()
     }
+
+    fn context_len(&self) -> usize {
+        self.kmer_size + self.window_size - 1
+    }
+
+    /// Consecutive windows sharing a minimizer report it once.
+    fn positions(&self, seq: &[u8]) -> Option<Vec<(usize, u64)>> {
+        let packed_seq = PackedSeqVec::from_ascii(seq);
+        let hasher = <seq_hash::NtHasher>::new(self.kmer_size);
+        let mut minimizer_positions = Vec::with_capacity(seq.len() * 2 / (self.window_size + 1));
+
+        Some(
+            canonical_minimizers(self.kmer_size, self.window_size)
+                .hasher(&hasher)
+                .run(packed_seq.as_slice(), &mut minimizer_positions)
+                .pos_and_values_u64()
+                .map(|(pos, value)| (pos as usize, value))
+                .collect(),
+        )
+    }
 }
@@ -1,13 +1,44 @@
+//! SIMD-accelerated sketching and sketch-based search.
+//!
+//! Minimizer and syncmer sketchers built on `simd-minimizers` implement the
+//! shared [`Sketcher`] trait from [`kmers`](crate::kmers), next to
+//! [`FracMinHashSketch`]. On top of them, this module provides parallel batch
+//! and file sketching, an incremental [`SketchDatabase`], containment
+//! [screening](screen()), LCA [classification](classify()), minimizer-based
+//! read overlaps and chimeric read detection.
+//!
+//! Requires the `sketch` feature (also enabled by the legacy `simd` feature).
+
+pub mod batch;
+pub mod chimera;
+pub mod classify;
 pub mod closed_syncmer;
+pub mod database;
+#[cfg(feature = "io")]
+pub mod file;
 pub mod index;
 pub mod minimizer;
 pub mod open_syncmer;
+pub mod overlap;
+pub mod screen;
+mod syncmer;
 pub mod traits;
 pub mod types;
 
+pub use crate::kmers::{FracMinHashSketch, StreamingSketch};
+pub use batch::{sketch_many, sketch_many_with_progress};
+pub use chimera::{ChimeraIndex, ChimeraParams, ChimeraReport, ReadSegment, detect_chimera};
+pub use classify::{TaxonAssignment, classify};
 pub use closed_syncmer::ClosedSyncmerSketch;
+pub use database::{SketchDatabase, SketchMatch};
+#[cfg(feature = "io")]
+pub use file::sketch_file;
 pub use index::build_reverse_index;
 pub use minimizer::MinimizerSketch;
 pub use open_syncmer::OpenSyncmerSketch;
-pub use traits::Sketcher;
+pub use overlap::{MinimizerHit, Overlap, OverlapParams, chain_overlaps, find_overlaps};
+#[cfg(feature = "io")]
+pub use screen::screen_file;
+pub use screen::{ScreenHit, screen};
+pub use traits::{Sketch, Sketcher};
 pub use types::SketchType;
@@ -1,21 +1,107 @@
-use super::traits::Sketcher;
+use super::syncmer::{is_centered_offset, scalar_open_syncmers, validate_open_syncmer};
+use super::traits::{Sketch, Sketcher};
+use crate::errors::BioError;
 use packed_seq::{PackedSeqVec, SeqVec};
 use simd_minimizers::*;
-use std::collections::HashSet;
 
+/// Open syncmer sketch.
+///
+/// A k-mer is an open syncmer if its smallest s-mer starts at `offset`
+/// within the k-mer. The expected density is `1 / (kmer_size - smer_size + 1)`
+/// of all k-mers. The SIMD kernel is used when `offset` is the center of an
+/// odd number of s-mers, which also gives the best conservation under
+/// mutations; other offsets fall back to a scalar implementation.
 pub struct OpenSyncmerSketch {
-    pub kmer_size: usize,
-    pub window_size: usize,
+    kmer_size: usize,
+    smer_size: usize,
+    offset: usize,
+}
+
+impl OpenSyncmerSketch {
+    /// Creates an open syncmer sketcher with validated parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] unless
+    /// `0 < smer_size < kmer_size <= 32` and `offset <= kmer_size - smer_size`.
+    pub fn new(kmer_size: usize, smer_size: usize, offset: usize) -> Result<Self, BioError> {
+        validate_open_syncmer(kmer_size, smer_size, offset)?;
+
+        Ok(Self {
+            kmer_size,
+            smer_size,
+            offset,
+        })
+    }
+
+    /// Creates an open syncmer sketcher with the s-mer offset centered in the k-mer.
+    ///
+    /// # Errors
+    ///
+    /// See [`OpenSyncmerSketch::new`].
+    pub fn centered(kmer_size: usize, smer_size: usize) -> Result<Self, BioError> {
+        Self::new(
+            kmer_size,
+            smer_size,
+            kmer_size.saturating_sub(smer_size) / 2,
+        )
+    }
+
+    pub fn kmer_size(&self) -> usize {
+        self.kmer_size
+    }
+
+    pub fn smer_size(&self) -> usize {
+        self.smer_size
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
 }
 
 impl Sketcher for OpenSyncmerSketch {
-    fn sketch(&self, seq: &[u8]) -> HashSet<u64> {
+    fn sketch(&self, seq: &[u8]) -> Sketch {
+        if !is_centered_offset(self.kmer_size, self.smer_size, self.offset) {
+            return scalar_open_syncmers(seq, self.kmer_size, self.smer_size, self.offset)
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect();
+        }
+
         let packed_seq = PackedSeqVec::from_ascii(seq);
         let mut syncmer_positions = Vec::new();
 
-        canonical_open_syncmers(self.kmer_size, self.window_size)
+        canonical_open_syncmers(self.smer_size, self.kmer_size - self.smer_size + 1)
             .run(packed_seq.as_slice(), &mut syncmer_positions)
             .values_u64()
             .collect()
     }
+
+    fn positions(&self, seq: &[u8]) -> Option<Vec<(usize, u64)>> {
+        if !is_centered_offset(self.kmer_size, self.smer_size, self.offset) {
+            return Some(scalar_open_syncmers(
+                seq,
+                self.kmer_size,
+                self.smer_size,
+                self.offset,
+            ));
+        }
+
+        let packed_seq = PackedSeqVec::from_ascii(seq);
+        let mut syncmer_positions = Vec::new();
+
+        Some(
+            canonical_open_syncmers(self.smer_size, self.kmer_size - self.smer_size + 1)
+                .run(packed_seq.as_slice(), &mut syncmer_positions)
+                .pos_and_values_u64()
+                .map(|(pos, value)| (pos as usize, value))
+                .collect(),
+        )
+    }
+
+    /// Whether a k-mer is a syncmer depends only on the k-mer itself.
+    fn context_len(&self) -> usize {
+        self.kmer_size
+    }
 }
@@ -0,0 +1,331 @@
+use super::minimizer::MinimizerSketch;
+use crate::nucleotide::{NT_LOOKUP, Strand};
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+
+/// Maximum number of preceding anchors scored per anchor during chaining.
+const CHAIN_LOOKBACK: usize = 50;
+
+/// A minimizer occurrence within a read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinimizerHit {
+    /// Canonical minimizer value.
+    pub value: u64,
+    /// 0-based start of the k-mer in the read.
+    pub pos: u32,
+    /// `true` if the forward k-mer is the canonical orientation.
+    pub forward: bool,
+}
+
+/// Returns whether `kmer` sorts before its reverse complement, `None` for
+/// palindromes and k-mers with ambiguous bases.
+pub(super) fn kmer_orientation(kmer: &[u8]) -> Option<bool> {
+    if kmer.iter().any(|nt| NT_LOOKUP[*nt as usize] >= 4) {
+        return None;
+    }
+
+    kmer.iter()
+        .zip(kmer.iter().rev())
+        .map(|(a, b)| NT_LOOKUP[*a as usize].cmp(&(3 - NT_LOOKUP[*b as usize])))
+        .find(|ord| ord.is_ne())
+        .map(|ord| ord.is_lt())
+}
+
+/// Parameters for [`find_overlaps`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlapParams {
+    /// Minimum number of chained minimizers to report an overlap.
+    pub min_anchors: usize,
+    /// Maximum distance between consecutive anchors on either read.
+    pub max_gap: usize,
+    /// Maximum difference between the query and target gaps of consecutive anchors.
+    pub bandwidth: usize,
+    /// Minimizers occurring more often than this across all reads are ignored as repeats.
+    pub max_occurrences: usize,
+}
+
+impl Default for OverlapParams {
+    fn default() -> Self {
+        Self {
+            min_anchors: 4,
+            max_gap: 5000,
+            bandwidth: 500,
+            max_occurrences: 200,
+        }
+    }
+}
+
+/// Candidate overlap between two reads.
+///
+/// Coordinates are 0-based, half-open and on the forward strand of each read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    /// Index of the query read, always smaller than `target`.
+    pub query: usize,
+    pub target: usize,
+    /// Relative strand of the target with respect to the query.
+    pub strand: Strand,
+    pub query_start: usize,
+    pub query_end: usize,
+    pub target_start: usize,
+    pub target_end: usize,
+    /// Number of chained minimizers.
+    pub anchors: usize,
+}
+
+/// Shared minimizer between a query and target read, as `(query_pos, target_pos)`.
+type Anchor = (u32, u32);
+
+/// Finds the best colinear chain of `anchors` and returns its anchor indices.
+///
+/// For the reverse strand, target positions decrease along the chain.
+fn best_chain(anchors: &mut [Anchor], strand: Strand, params: &OverlapParams) -> Vec<usize> {
+    let target_coord = |pos: u32| -> i64 {
+        match strand {
+            Strand::Forward => pos as i64,
+            Strand::Reverse => -(pos as i64),
+        }
+    };
+
+    anchors.sort_unstable_by_key(|&(q, t)| (q, target_coord(t)));
+
+    let mut scores = vec![1usize; anchors.len()];
+    let mut prev: Vec<Option<usize>> = vec![None; anchors.len()];
+
+    for i in 0..anchors.len() {
+        let (qi, ti) = (anchors[i].0 as i64, target_coord(anchors[i].1));
+
+        for j in (i.saturating_sub(CHAIN_LOOKBACK)..i).rev() {
+            let dq = qi - anchors[j].0 as i64;
+            let dt = ti - target_coord(anchors[j].1);
+
+            if dq <= 0 || dt <= 0 || dq.max(dt) as usize > params.max_gap {
+                continue;
+            }
+            if (dq - dt).unsigned_abs() as usize > params.bandwidth {
+                continue;
+            }
+
+            if scores[j] + 1 > scores[i] {
+                scores[i] = scores[j] + 1;
+                prev[i] = Some(j);
+            }
+        }
+    }
+
+    let Some(mut end) = (0..scores.len()).max_by_key(|&i| (scores[i], std::cmp::Reverse(i))) else {
+        return Vec::new();
+    };
+
+    let mut chain = vec![end];
+    while let Some(p) = prev[end] {
+        chain.push(p);
+        end = p;
+    }
+    chain.reverse();
+
+    chain
+}
+
+/// Chains shared minimizers between reads into candidate overlaps.
+///
+/// `minimizers[i]` holds the minimizers of read `i`, e.g. from
+/// [`MinimizerSketch::minimizers`], computed with k-mer size `kmer_size`. For
+/// every read pair sharing minimizers, anchors are grouped by relative strand
+/// and the highest scoring colinear chain is reported if it has at least
+/// `min_anchors` anchors. At most one overlap is reported per read pair and
+/// strand, sorted by `(query, target, strand)`.
+pub fn chain_overlaps(
+    minimizers: &[Vec<MinimizerHit>],
+    kmer_size: usize,
+    params: &OverlapParams,
+) -> Vec<Overlap> {
+    let mut index: FxHashMap<u64, Vec<(usize, MinimizerHit)>> = FxHashMap::default();
+    for (read, hits) in minimizers.iter().enumerate() {
+        for hit in hits {
+            index.entry(hit.value).or_default().push((read, *hit));
+        }
+    }
+    index.retain(|_, occurrences| occurrences.len() <= params.max_occurrences);
+
+    let mut overlaps: Vec<Overlap> = minimizers
+        .par_iter()
+        .enumerate()
+        .flat_map_iter(|(query, hits)| {
+            let mut anchors: FxHashMap<(usize, Strand), Vec<Anchor>> = FxHashMap::default();
+
+            for hit in hits {
+                for (target, other) in index.get(&hit.value).into_iter().flatten() {
+                    if *target <= query {
+                        continue;
+                    }
+
+                    let strand = match hit.forward == other.forward {
+                        true => Strand::Forward,
+                        false => Strand::Reverse,
+                    };
+                    anchors
+                        .entry((*target, strand))
+                        .or_default()
+                        .push((hit.pos, other.pos));
+                }
+            }
+
+            anchors
+                .into_iter()
+                .filter_map(|((target, strand), mut anchors)| {
+                    if anchors.len() < params.min_anchors {
+                        return None;
+                    }
+
+                    let chain = best_chain(&mut anchors, strand, params);
+                    if chain.len() < params.min_anchors {
+                        return None;
+                    }
+
+                    let query_pos = chain.iter().map(|&i| anchors[i].0 as usize);
+                    let target_pos = chain.iter().map(|&i| anchors[i].1 as usize);
+
+                    Some(Overlap {
+                        query,
+                        target,
+                        strand,
+                        query_start: query_pos.clone().min()?,
+                        query_end: query_pos.max()? + kmer_size,
+                        target_start: target_pos.clone().min()?,
+                        target_end: target_pos.max()? + kmer_size,
+                        anchors: chain.len(),
+                    })
+                })
+                .collect::<Vec<Overlap>>()
+        })
+        .collect();
+
+    overlaps.sort_unstable_by_key(|o| (o.query, o.target, o.strand));
+    overlaps
+}
+
+/// Finds candidate overlaps between long reads from shared minimizers.
+///
+/// Computes the minimizers of every read with `sketcher` in parallel and
+/// chains them with [`chain_overlaps`]. This is the seeding stage of an
+/// overlap-layout assembler or polisher; overlaps are approximate and should
+/// be verified by alignment.
+pub fn find_overlaps(
+    reads: &[&[u8]],
+    sketcher: &MinimizerSketch,
+    params: &OverlapParams,
+) -> Vec<Overlap> {
+    let minimizers: Vec<Vec<MinimizerHit>> = reads
+        .par_iter()
+        .map(|read| sketcher.minimizers(read))
+        .collect();
+
+    chain_overlaps(&minimizers, sketcher.kmer_size, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmers::mm_hash64;
+    use crate::nucleotide::reverse_complement;
+    use crate::rng::SplitMix64;
+    use crate::test_utils::random_sequence;
+
+    /// Plain Rust window minimizers, to test chaining without SIMD.
+    fn naive_minimizers(seq: &[u8], k: usize, w: usize) -> Vec<MinimizerHit> {
+        let kmers: Vec<(u32, u64, bool)> = (0..=seq.len() - k)
+            .filter_map(|i| {
+                let kmer = &seq[i..i + k];
+                let forward = kmer_orientation(kmer)?;
+                let canonical = match forward {
+                    true => kmer.to_vec(),
+                    false => reverse_complement(kmer),
+                };
+                let value = canonical
+                    .iter()
+                    .fold(0u64, |v, b| v << 2 | NT_LOOKUP[*b as usize] as u64);
+                Some((i as u32, mm_hash64(value), forward))
+            })
+            .collect();
+
+        let mut hits: Vec<MinimizerHit> = kmers
+            .windows(w)
+            .map(|window| {
+                let (pos, value, forward) = *window.iter().min_by_key(|(_, v, _)| *v).unwrap();
+                MinimizerHit {
+                    value,
+                    pos,
+                    forward,
+                }
+            })
+            .collect();
+        hits.dedup();
+        hits
+    }
+
+    #[test]
+    fn test_kmer_orientation() {
+        assert_eq!(kmer_orientation(b"AAC"), Some(true));
+        assert_eq!(kmer_orientation(b"GTT"), Some(false));
+        assert_eq!(kmer_orientation(b"ACGT"), None);
+        assert_eq!(kmer_orientation(b"ANC"), None);
+    }
+
+    #[test]
+    fn test_chain_overlaps() {
+        let genome = random_sequence(&mut SplitMix64::new(1), 3000, 0.5);
+        let a = genome[0..2000].to_vec();
+        let b = genome[1200..3000].to_vec();
+        let c = reverse_complement(&genome[1500..2800]);
+        let d = random_sequence(&mut SplitMix64::new(2), 1500, 0.5);
+
+        let minimizers: Vec<Vec<MinimizerHit>> = [&a, &b, &c, &d]
+            .iter()
+            .map(|read| naive_minimizers(read, 15, 10))
+            .collect();
+
+        let overlaps = chain_overlaps(&minimizers, 15, &OverlapParams::default());
+        let pairs: Vec<(usize, usize, Strand)> = overlaps
+            .iter()
+            .map(|o| (o.query, o.target, o.strand))
+            .collect();
+
+        assert_eq!(
+            pairs,
+            vec![
+                (0, 1, Strand::Forward),
+                (0, 2, Strand::Reverse),
+                (1, 2, Strand::Reverse)
+            ]
+        );
+
+        // a[1200..2000] overlaps b[0..800].
+        let ab = &overlaps[0];
+        assert!(ab.query_start.abs_diff(1200) < 50 && ab.query_end.abs_diff(2000) < 50);
+        assert!(ab.target_start < 50 && ab.target_end.abs_diff(800) < 50);
+
+        // a[1500..2000] overlaps the last 500 bases of c.
+        let ac = &overlaps[1];
+        assert!(ac.query_start.abs_diff(1500) < 50);
+        assert!(ac.target_start.abs_diff(800) < 50 && ac.target_end.abs_diff(1300) < 50);
+    }
+
+    #[test]
+    fn test_chain_overlaps_min_anchors() {
+        let minimizers = vec![
+            vec![MinimizerHit {
+                value: 1,
+                pos: 0,
+                forward: true,
+            }],
+            vec![MinimizerHit {
+                value: 1,
+                pos: 10,
+                forward: true,
+            }],
+        ];
+
+        assert!(chain_overlaps(&minimizers, 15, &OverlapParams::default()).is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+use super::database::SketchDatabase;
+use super::traits::Sketcher;
+use crate::errors::BioError;
+use rayon::prelude::*;
+
+#[cfg(feature = "io")]
+use serde::Serialize;
+#[cfg(feature = "io")]
+use std::path::Path;
+
+/// Best reference hit for one screened read or contig.
+///
+/// With the `io` feature, rows can be written with
+/// [`write_tsv`](crate::io::write_tsv) or [`write_json`](crate::io::write_json).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize))]
+pub struct ScreenHit {
+    pub query: String,
+    /// Best-matching reference, `None` if no reference reached the minimum containment.
+    pub reference: Option<String>,
+    /// Fraction of query hashes found in the best reference.
+    pub containment: f64,
+    /// Number of hashes shared with the best reference.
+    pub shared: usize,
+    /// Number of hashes in the query sketch.
+    pub query_hashes: usize,
+}
+
+pub(super) fn check_min_containment(min_containment: f64) -> Result<(), BioError> {
+    if !(0.0..=1.0).contains(&min_containment) {
+        return Err(BioError::InvalidParameterError(format!(
+            "min containment {} must be in range 0-1.",
+            min_containment
+        )));
+    }
+
+    Ok(())
+}
+
+fn screen_one<S: Sketcher>(
+    database: &SketchDatabase<S>,
+    name: &str,
+    seq: &[u8],
+    min_containment: f64,
+) -> ScreenHit {
+    let sketch = database.sketcher().sketch(seq);
+
+    let best = database
+        .query_sketch(&sketch)
+        .into_iter()
+        .next()
+        .filter(|m| m.containment >= min_containment);
+
+    match best {
+        Some(m) => ScreenHit {
+            query: name.to_string(),
+            reference: Some(m.name),
+            containment: m.containment,
+            shared: m.shared,
+            query_hashes: sketch.len(),
+        },
+        None => ScreenHit {
+            query: name.to_string(),
+            reference: None,
+            containment: 0.0,
+            shared: 0,
+            query_hashes: sketch.len(),
+        },
+    }
+}
+
+/// Screens reads or contigs against a [`SketchDatabase`], like `mash screen`.
+///
+/// Each `(name, seq)` is sketched with the database's sketcher and assigned
+/// the reference with the highest containment (fraction of query hashes found
+/// in the reference), if it reaches `min_containment`. Queries are processed
+/// in parallel and hits are returned in input order. Useful for flagging
+/// contaminant contigs in an assembly.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_containment` is outside `[0.0, 1.0]`.
+pub fn screen<S: Sketcher>(
+    database: &SketchDatabase<S>,
+    queries: &[(&str, &[u8])],
+    min_containment: f64,
+) -> Result<Vec<ScreenHit>, BioError> {
+    check_min_containment(min_containment)?;
+
+    Ok(queries
+        .par_iter()
+        .map(|(name, seq)| screen_one(database, name, seq, min_containment))
+        .collect())
+}
+
+/// Screens every record of a FASTA/FASTQ file against a [`SketchDatabase`].
+///
+/// Streams the file in batches, see [`screen`] and [`sketch_file`](super::sketch_file).
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if `min_containment` is outside
+/// `[0.0, 1.0]`, or [`BioError`] if the file cannot be opened or parsed.
+#[cfg(feature = "io")]
+pub fn screen_file<S: Sketcher>(
+    database: &SketchDatabase<S>,
+    path: &Path,
+    min_containment: f64,
+) -> Result<Vec<ScreenHit>, BioError> {
+    check_min_containment(min_containment)?;
+
+    let mut hits = Vec::new();
+
+    super::file::for_each_batch(path, |batch| {
+        hits.par_extend(
+            batch
+                .par_iter()
+                .map(|(name, seq)| screen_one(database, name, seq, min_containment)),
+        );
+    })?;
+
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmers::Sketch;
+
+    struct KmerSet(usize);
+
+    impl Sketcher for KmerSet {
+        fn sketch(&self, seq: &[u8]) -> Sketch {
+            seq.windows(self.0)
+                .map(|w| w.iter().fold(0u64, |h, b| h * 31 + *b as u64))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_screen() {
+        let mut db = SketchDatabase::new(KmerSet(4));
+        db.add("host", b"ACGTACGGTCAGTTTTGGGCCA").unwrap();
+        db.add("phix", b"GAGTTTTATCGCTTCCATGACGCAG").unwrap();
+
+        let queries: Vec<(&str, &[u8])> = vec![
+            ("contig_1", b"ACGTACGGTCAG"),
+            ("contig_2", b"TTTATCGCTTCCATGAAAAA"),
+            ("contig_3", b"CCCCCCCC"),
+        ];
+
+        let hits = screen(&db, &queries, 0.5).unwrap();
+        let refs: Vec<Option<&str>> = hits.iter().map(|h| h.reference.as_deref()).collect();
+
+        assert_eq!(refs, vec![Some("host"), Some("phix"), None]);
+        assert_eq!(hits[0].containment, 1.0);
+        assert!(hits[1].containment < 1.0);
+        assert_eq!(hits[2].shared, 0);
+    }
+
+    #[test]
+    fn test_screen_invalid_containment() {
+        let db = SketchDatabase::new(KmerSet(4));
+        assert!(screen(&db, &[], 1.5).is_err());
+    }
+}
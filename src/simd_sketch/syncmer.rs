@@ -0,0 +1,139 @@
+use crate::errors::BioError;
+use crate::kmers::{CanonicalKmerIter, MAX_KMER_SIZE, NtHashIter};
+
+/// Checks that `0 < smer_size < kmer_size <= MAX_KMER_SIZE`.
+pub(super) fn validate_syncmer_sizes(kmer_size: usize, smer_size: usize) -> Result<(), BioError> {
+    if kmer_size > MAX_KMER_SIZE {
+        return Err(BioError::InvalidParameterError(format!(
+            "syncmer kmer size {} must be at most {}.",
+            kmer_size, MAX_KMER_SIZE
+        )));
+    }
+
+    if smer_size == 0 || smer_size >= kmer_size {
+        return Err(BioError::InvalidParameterError(format!(
+            "smer size {} must be in range 1-{}.",
+            smer_size,
+            kmer_size.saturating_sub(1)
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks syncmer sizes and that `offset` is a valid s-mer position within a k-mer.
+pub(super) fn validate_open_syncmer(
+    kmer_size: usize,
+    smer_size: usize,
+    offset: usize,
+) -> Result<(), BioError> {
+    validate_syncmer_sizes(kmer_size, smer_size)?;
+
+    if offset > kmer_size - smer_size {
+        return Err(BioError::InvalidParameterError(format!(
+            "syncmer offset {} must be in range 0-{}.",
+            offset,
+            kmer_size - smer_size
+        )));
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `offset` is the centered s-mer position supported by the SIMD open syncmer kernel.
+pub(super) fn is_centered_offset(kmer_size: usize, smer_size: usize, offset: usize) -> bool {
+    let num_smers = kmer_size - smer_size + 1;
+    num_smers % 2 == 1 && offset == num_smers / 2
+}
+
+/// Scalar open syncmers with an arbitrary s-mer offset.
+///
+/// A k-mer is selected if its smallest canonical ntHash s-mer (leftmost on
+/// ties) starts at `offset`. Selected k-mers are returned as
+/// `(position, canonical 2-bit value)` in position order.
+pub(super) fn scalar_open_syncmers(
+    seq: &[u8],
+    kmer_size: usize,
+    smer_size: usize,
+    offset: usize,
+) -> Vec<(usize, u64)> {
+    let mut smer_hashes: Vec<u64> = vec![0; seq.len().saturating_sub(smer_size - 1)];
+    for (pos, _, canonical) in NtHashIter::new(seq, smer_size).expect("validated smer size") {
+        smer_hashes[pos] = canonical;
+    }
+
+    CanonicalKmerIter::new(seq, kmer_size)
+        .expect("validated kmer size")
+        .filter_map(|(pos, _, canonical)| {
+            let (min_pos, _) = smer_hashes[pos..=pos + kmer_size - smer_size]
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, h)| **h)?;
+
+            (min_pos == offset).then_some((pos, canonical))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use rstest::*;
+
+    #[rstest]
+    #[case(21, 11, true)]
+    #[case(32, 1, true)]
+    #[case(33, 11, false)]
+    #[case(21, 0, false)]
+    #[case(21, 21, false)]
+    fn test_validate_syncmer_sizes(#[case] k: usize, #[case] s: usize, #[case] ok: bool) {
+        assert_eq!(validate_syncmer_sizes(k, s).is_ok(), ok);
+    }
+
+    #[rstest]
+    #[case(21, 11, 10, true)]
+    #[case(21, 11, 11, false)]
+    fn test_validate_open_syncmer(
+        #[case] k: usize,
+        #[case] s: usize,
+        #[case] t: usize,
+        #[case] ok: bool,
+    ) {
+        assert_eq!(validate_open_syncmer(k, s, t).is_ok(), ok);
+    }
+
+    #[rstest]
+    #[case(21, 11, 5, true)]
+    #[case(21, 11, 0, false)]
+    #[case(21, 12, 5, false)]
+    fn test_is_centered_offset(
+        #[case] k: usize,
+        #[case] s: usize,
+        #[case] t: usize,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_centered_offset(k, s, t), expected);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(3)]
+    fn test_scalar_open_syncmer_density(#[case] offset: usize) {
+        let mut rng = SplitMix64::new(3);
+        let seq: Vec<u8> = (0..100_000).map(|_| b"ACGT"[rng.next_below(4)]).collect();
+
+        let all = CanonicalKmerIter::new(&seq, 15).unwrap().count();
+        let syncmers = scalar_open_syncmers(&seq, 15, 9, offset);
+
+        // Expected density is 1 / (k - s + 1) = 1 / 7.
+        let density = syncmers.len() as f64 / all as f64;
+        assert!((density - 1.0 / 7.0).abs() < 0.02, "density {}", density);
+    }
+
+    #[test]
+    fn test_scalar_open_syncmer_short_seq() {
+        assert!(scalar_open_syncmers(b"ACGT", 15, 9, 0).is_empty());
+        assert!(scalar_open_syncmers(b"", 15, 9, 0).is_empty());
+    }
+}
@@ -1,5 +1 @@
-use std::collections::HashSet;
-
-pub trait Sketcher: Send + Sync {
-    fn sketch(&self, seq: &[u8]) -> HashSet<u64>;
-}
+pub use crate::kmers::{Sketch, Sketcher};
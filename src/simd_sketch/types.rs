@@ -1,6 +1,72 @@
-#[derive(Debug, Clone)]
+use super::closed_syncmer::ClosedSyncmerSketch;
+use super::minimizer::MinimizerSketch;
+use super::open_syncmer::OpenSyncmerSketch;
+use crate::errors::BioError;
+use crate::kmers::{FracMinHashSketch, Sketcher};
+
+/// Sketching scheme and its parameters.
+///
+/// Downstream code can switch schemes by changing the variant alone, since
+/// [`SketchType::sketcher`] returns a boxed [`Sketcher`] usable anywhere a
+/// sketcher is expected, e.g. in a `SketchDatabase`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum SketchType {
-    Minimizer,
-    OpenSyncmer,
-    ClosedSyncmer,
+    FracMinHash {
+        kmer_size: usize,
+        scaled: u64,
+    },
+    Minimizer {
+        kmer_size: usize,
+        window_size: usize,
+    },
+    OpenSyncmer {
+        kmer_size: usize,
+        smer_size: usize,
+        offset: usize,
+    },
+    ClosedSyncmer {
+        kmer_size: usize,
+        smer_size: usize,
+    },
+}
+
+impl SketchType {
+    /// Builds the sketcher for this scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if the parameters are
+    /// invalid for the scheme.
+    pub fn sketcher(&self) -> Result<Box<dyn Sketcher>, BioError> {
+        Ok(match *self {
+            SketchType::FracMinHash { kmer_size, scaled } => {
+                Box::new(FracMinHashSketch::new(kmer_size, scaled)?)
+            }
+            SketchType::Minimizer {
+                kmer_size,
+                window_size,
+            } => {
+                if kmer_size == 0 || window_size == 0 {
+                    return Err(BioError::InvalidParameterError(format!(
+                        "minimizer kmer size {} and window size {} must be greater than 0.",
+                        kmer_size, window_size
+                    )));
+                }
+
+                Box::new(MinimizerSketch {
+                    kmer_size,
+                    window_size,
+                })
+            }
+            SketchType::OpenSyncmer {
+                kmer_size,
+                smer_size,
+                offset,
+            } => Box::new(OpenSyncmerSketch::new(kmer_size, smer_size, offset)?),
+            SketchType::ClosedSyncmer {
+                kmer_size,
+                smer_size,
+            } => Box::new(ClosedSyncmerSketch::new(kmer_size, smer_size)?),
+        })
+    }
 }
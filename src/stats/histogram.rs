@@ -0,0 +1,571 @@
+use crate::errors::BioError;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
+/// Lower bound of the bin holding the `q` quantile of `bins`, given as
+/// ascending `(lower bound, count)` pairs with `total` observations. `None`
+/// if there are none or `q` is outside `[0.0, 1.0]`.
+fn quantile_of<I>(bins: I, total: u64, q: f64) -> Option<u64>
+where
+    I: IntoIterator<Item = (u64, u64)>,
+{
+    if total == 0 || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+
+    let rank = ((q * total as f64).ceil() as u64).max(1);
+    let mut cumulative = 0;
+
+    bins.into_iter().find_map(|(lower, count)| {
+        cumulative += count;
+        (cumulative >= rank).then_some(lower)
+    })
+}
+
+/// Returns `total` if it is the sum of `counts`, for validating deserialized
+/// histograms.
+#[cfg(feature = "io")]
+fn checked_total<'a, I>(counts: I, total: u64) -> Result<u64, BioError>
+where
+    I: IntoIterator<Item = &'a u64>,
+{
+    let sum = counts
+        .into_iter()
+        .try_fold(0u64, |sum, &count| sum.checked_add(count));
+
+    match sum == Some(total) {
+        true => Ok(total),
+        false => Err(BioError::InvalidParameterError(format!(
+            "histogram total {} does not match its bin counts.",
+            total
+        ))),
+    }
+}
+
+/// Histogram of integer values with bins of a fixed width.
+///
+/// Bin `i` holds values in `[i * bin_width, (i + 1) * bin_width)`, and only
+/// non-empty bins are stored, so values may be unbounded, e.g. k-mer counts
+/// of repeats. With the default `bin_width` of `1` every value has its own
+/// bin and quantiles are exact.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::stats::Histogram;
+///
+/// let mut lengths = Histogram::default();
+/// lengths.extend([150, 150, 151, 98]);
+///
+/// assert_eq!(lengths.total(), 4);
+/// assert_eq!(lengths.median(), Some(150));
+/// assert_eq!(lengths.quantile(1.0), Some(151));
+/// assert_eq!(lengths.count_at(150), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "io", serde(try_from = "RawHistogram"))]
+pub struct Histogram {
+    bin_width: u64,
+    /// Count per bin index.
+    counts: BTreeMap<u64, u64>,
+    total: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            bin_width: 1,
+            counts: BTreeMap::new(),
+            total: 0,
+        }
+    }
+}
+
+impl Histogram {
+    /// Creates an empty histogram with bins of `bin_width` values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `bin_width` is `0`.
+    pub fn new(bin_width: u64) -> Result<Self, BioError> {
+        if bin_width == 0 {
+            return Err(BioError::InvalidParameterError(
+                "bin width must be greater than 0.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            bin_width,
+            ..Self::default()
+        })
+    }
+
+    pub fn bin_width(&self) -> u64 {
+        self.bin_width
+    }
+
+    /// Number of observations.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Adds one observation of `value`.
+    pub fn add(&mut self, value: u64) {
+        self.add_count(value, 1);
+    }
+
+    /// Adds `count` observations of `value`.
+    pub fn add_count(&mut self, value: u64, count: u64) {
+        if count == 0 {
+            return;
+        }
+
+        *self.counts.entry(value / self.bin_width).or_insert(0) += count;
+        self.total += count;
+    }
+
+    /// Adds the observations of `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if the bin widths differ.
+    pub fn merge(&mut self, other: &Histogram) -> Result<(), BioError> {
+        if self.bin_width != other.bin_width {
+            return Err(BioError::InvalidParameterError(format!(
+                "cannot merge histograms with bin widths {} and {}.",
+                self.bin_width, other.bin_width
+            )));
+        }
+
+        for (&bin, &count) in &other.counts {
+            *self.counts.entry(bin).or_insert(0) += count;
+        }
+        self.total += other.total;
+
+        Ok(())
+    }
+
+    /// Number of observations in the bin containing `value`.
+    pub fn count_at(&self, value: u64) -> u64 {
+        self.counts
+            .get(&(value / self.bin_width))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Non-empty bins as `(lower bound, count)`, in ascending order.
+    pub fn bins(&self) -> impl DoubleEndedIterator<Item = (u64, u64)> + '_ {
+        self.counts
+            .iter()
+            .map(|(&bin, &count)| (bin * self.bin_width, count))
+    }
+
+    /// Lower bound of the bin holding the `q` quantile, i.e. the smallest
+    /// value with at least a fraction `q` of the observations at or below
+    /// its bin. `None` if the histogram is empty or `q` is outside
+    /// `[0.0, 1.0]`.
+    pub fn quantile(&self, q: f64) -> Option<u64> {
+        quantile_of(self.bins(), self.total, q)
+    }
+
+    /// The `0.5` [`quantile`](Self::quantile).
+    pub fn median(&self) -> Option<u64> {
+        self.quantile(0.5)
+    }
+
+    /// Mean of the bin midpoints weighted by count, exact for a `bin_width`
+    /// of `1`. `NaN` if the histogram is empty.
+    pub fn mean(&self) -> f64 {
+        let offset = (self.bin_width - 1) as f64 / 2.0;
+        let sum: f64 = self
+            .bins()
+            .map(|(lower, count)| (lower as f64 + offset) * count as f64)
+            .sum();
+
+        sum / self.total as f64
+    }
+}
+
+/// Serialized form of [`Histogram`], validated on deserialization.
+#[cfg(feature = "io")]
+#[derive(Deserialize)]
+struct RawHistogram {
+    bin_width: u64,
+    counts: BTreeMap<u64, u64>,
+    total: u64,
+}
+
+#[cfg(feature = "io")]
+impl TryFrom<RawHistogram> for Histogram {
+    type Error = BioError;
+
+    fn try_from(raw: RawHistogram) -> Result<Self, Self::Error> {
+        let mut histogram = Self::new(raw.bin_width)?;
+        histogram.counts = raw.counts;
+        histogram.total = checked_total(histogram.counts.values(), raw.total)?;
+
+        Ok(histogram)
+    }
+}
+
+impl Extend<u64> for Histogram {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, values: I) {
+        values.into_iter().for_each(|value| self.add(value));
+    }
+}
+
+/// Histogram of integer values with logarithmic bins, for long-tailed data
+/// such as nanopore read lengths or coverage of repeats.
+///
+/// Bin `i` holds values in `[base^i, base^(i + 1))`, and `0` has a bin of its
+/// own. Bounds are reported as the smallest integer in the bin.
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::stats::LogHistogram;
+///
+/// let mut lengths = LogHistogram::new(10.0).unwrap();
+/// lengths.extend([5, 50, 500, 700, 20_000]);
+///
+/// assert_eq!(lengths.median(), Some(100));
+/// let bins: Vec<(u64, u64)> = lengths.bins().collect();
+/// assert_eq!(bins, vec![(1, 1), (10, 1), (100, 2), (10_000, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "io", serde(try_from = "RawLogHistogram"))]
+pub struct LogHistogram {
+    base: f64,
+    zeros: u64,
+    /// Count per bin exponent.
+    counts: BTreeMap<u64, u64>,
+    total: u64,
+}
+
+impl LogHistogram {
+    /// Creates an empty histogram with bins growing by a factor of `base`,
+    /// e.g. `2.0` or `10.0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if `base` is not a finite
+    /// number greater than `1.0`.
+    pub fn new(base: f64) -> Result<Self, BioError> {
+        if !(base.is_finite() && base > 1.0) {
+            return Err(BioError::InvalidParameterError(format!(
+                "log histogram base {} must be greater than 1.",
+                base
+            )));
+        }
+
+        Ok(Self {
+            base,
+            zeros: 0,
+            counts: BTreeMap::new(),
+            total: 0,
+        })
+    }
+
+    pub fn base(&self) -> f64 {
+        self.base
+    }
+
+    /// Number of observations.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Exponent of the bin holding `value > 0`.
+    fn exponent(&self, value: u64) -> u64 {
+        let mut exponent = ((value as f64).ln() / self.base.ln()).floor().max(0.0) as u64;
+
+        // Correct rounding errors at exact powers of the base. Bounds past
+        // `u64::MAX` hold no values, which also ends the upward search.
+        while self
+            .lower_bound(exponent + 1)
+            .is_some_and(|bound| bound <= value)
+        {
+            exponent += 1;
+        }
+        while exponent > 0 && self.lower_bound(exponent).is_none_or(|bound| bound > value) {
+            exponent -= 1;
+        }
+
+        exponent
+    }
+
+    /// Smallest integer in the bin with `exponent`, `None` if it exceeds
+    /// `u64::MAX`.
+    fn lower_bound(&self, exponent: u64) -> Option<u64> {
+        let bound = self.base.powf(exponent as f64);
+        if bound >= u64::MAX as f64 {
+            return None;
+        }
+
+        // Snap bounds within rounding error of an integer, e.g. 10^3.
+        match (bound - bound.round()).abs() < 1e-9 * bound {
+            true => Some(bound.round() as u64),
+            false => Some(bound.ceil() as u64),
+        }
+    }
+
+    /// Adds one observation of `value`.
+    pub fn add(&mut self, value: u64) {
+        self.add_count(value, 1);
+    }
+
+    /// Adds `count` observations of `value`.
+    pub fn add_count(&mut self, value: u64, count: u64) {
+        match value {
+            0 => self.zeros += count,
+            _ => {
+                if count == 0 {
+                    return;
+                }
+                *self.counts.entry(self.exponent(value)).or_insert(0) += count;
+            }
+        }
+        self.total += count;
+    }
+
+    /// Adds the observations of `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidParameterError`] if the bases differ.
+    pub fn merge(&mut self, other: &LogHistogram) -> Result<(), BioError> {
+        if self.base != other.base {
+            return Err(BioError::InvalidParameterError(format!(
+                "cannot merge log histograms with bases {} and {}.",
+                self.base, other.base
+            )));
+        }
+
+        for (&exponent, &count) in &other.counts {
+            *self.counts.entry(exponent).or_insert(0) += count;
+        }
+        self.zeros += other.zeros;
+        self.total += other.total;
+
+        Ok(())
+    }
+
+    /// Number of observations in the bin containing `value`.
+    pub fn count_at(&self, value: u64) -> u64 {
+        match value {
+            0 => self.zeros,
+            _ => self.counts.get(&self.exponent(value)).copied().unwrap_or(0),
+        }
+    }
+
+    /// Non-empty bins as `(lower bound, count)`, in ascending order, with
+    /// the bin of `0` first.
+    pub fn bins(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        let zeros = (self.zeros > 0).then_some((0, self.zeros));
+
+        zeros
+            .into_iter()
+            .chain(self.counts.iter().map(|(&exponent, &count)| {
+                let lower = self
+                    .lower_bound(exponent)
+                    .expect("bins only hold exponents of values");
+                (lower, count)
+            }))
+    }
+
+    /// Lower bound of the bin holding the `q` quantile, see
+    /// [`Histogram::quantile`].
+    pub fn quantile(&self, q: f64) -> Option<u64> {
+        quantile_of(self.bins(), self.total, q)
+    }
+
+    /// The `0.5` [`quantile`](Self::quantile).
+    pub fn median(&self) -> Option<u64> {
+        self.quantile(0.5)
+    }
+}
+
+/// Serialized form of [`LogHistogram`], validated on deserialization.
+#[cfg(feature = "io")]
+#[derive(Deserialize)]
+struct RawLogHistogram {
+    base: f64,
+    zeros: u64,
+    counts: BTreeMap<u64, u64>,
+    total: u64,
+}
+
+#[cfg(feature = "io")]
+impl TryFrom<RawLogHistogram> for LogHistogram {
+    type Error = BioError;
+
+    fn try_from(raw: RawLogHistogram) -> Result<Self, Self::Error> {
+        let mut histogram = Self::new(raw.base)?;
+        if let Some(&exponent) = raw
+            .counts
+            .keys()
+            .find(|&&e| histogram.lower_bound(e).is_none())
+        {
+            return Err(BioError::InvalidParameterError(format!(
+                "log histogram bin {} exceeds the range of u64.",
+                exponent
+            )));
+        }
+
+        histogram.zeros = raw.zeros;
+        histogram.counts = raw.counts;
+        histogram.total = checked_total(
+            std::iter::once(&histogram.zeros).chain(histogram.counts.values()),
+            raw.total,
+        )?;
+
+        Ok(histogram)
+    }
+}
+
+impl Extend<u64> for LogHistogram {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, values: I) {
+        values.into_iter().for_each(|value| self.add(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0.0, Some(1))]
+    #[case(0.25, Some(1))]
+    #[case(0.5, Some(2))]
+    #[case(0.9, Some(10))]
+    #[case(1.0, Some(10))]
+    #[case(1.5, None)]
+    #[case(f64::NAN, None)]
+    fn test_histogram_quantile(#[case] q: f64, #[case] expected: Option<u64>) {
+        let mut histogram = Histogram::default();
+        histogram.extend([1, 1, 2, 2, 3, 4, 5, 10]);
+
+        assert_eq!(histogram.quantile(q), expected);
+    }
+
+    #[test]
+    fn test_histogram_bin_width() {
+        let mut histogram = Histogram::new(10).unwrap();
+        histogram.extend([0, 9, 10, 25, 29]);
+        histogram.add_count(1000, 0);
+
+        assert_eq!(
+            histogram.bins().collect::<Vec<_>>(),
+            vec![(0, 2), (10, 1), (20, 2)]
+        );
+        assert_eq!(histogram.count_at(21), 2);
+        assert_eq!(histogram.median(), Some(10));
+        assert_eq!(histogram.mean(), (4.5 * 2.0 + 14.5 + 24.5 * 2.0) / 5.0);
+        assert!(Histogram::new(0).is_err());
+        assert!(Histogram::default().mean().is_nan());
+    }
+
+    #[test]
+    fn test_histogram_merge() {
+        let mut a = Histogram::default();
+        let mut b = Histogram::default();
+        a.extend([1, 2, 2]);
+        b.extend([2, 7]);
+        a.merge(&b).unwrap();
+
+        let mut expected = Histogram::default();
+        expected.extend([1, 2, 2, 2, 7]);
+        assert_eq!(a, expected);
+        assert!(a.merge(&Histogram::new(2).unwrap()).is_err());
+    }
+
+    #[rstest]
+    #[case(2.0, 1, 1)]
+    #[case(2.0, 1023, 512)]
+    #[case(2.0, 1024, 1024)]
+    #[case(10.0, 999, 100)]
+    #[case(10.0, 1000, 1000)]
+    #[case(10.0, 1_000_000_000_000, 1_000_000_000_000)]
+    #[case(1.5, 3, 3)]
+    #[case(1.5, 2, 2)]
+    #[case(2.0, u64::MAX, 1 << 63)]
+    #[case(10.0, u64::MAX, 10_000_000_000_000_000_000)]
+    fn test_log_histogram_bins(#[case] base: f64, #[case] value: u64, #[case] lower: u64) {
+        let mut histogram = LogHistogram::new(base).unwrap();
+        histogram.add(value);
+
+        assert_eq!(histogram.bins().collect::<Vec<_>>(), vec![(lower, 1)]);
+        assert_eq!(histogram.count_at(lower), 1);
+    }
+
+    #[test]
+    fn test_log_histogram_zeros_and_merge() {
+        let mut a = LogHistogram::new(2.0).unwrap();
+        let mut b = LogHistogram::new(2.0).unwrap();
+        a.extend([0, 0, 3]);
+        b.extend([0, 100, 120]);
+        a.merge(&b).unwrap();
+
+        assert_eq!(a.bins().collect::<Vec<_>>(), vec![(0, 3), (2, 1), (64, 2)]);
+        assert_eq!(a.total(), 6);
+        assert_eq!(a.median(), Some(0));
+        assert_eq!(a.quantile(0.6), Some(2));
+        assert!(a.merge(&LogHistogram::new(10.0).unwrap()).is_err());
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn test_histogram_serde() {
+        let mut histogram = Histogram::new(5).unwrap();
+        histogram.extend([1, 7, 8]);
+        let json = serde_json::to_string(&histogram).unwrap();
+        assert_eq!(serde_json::from_str::<Histogram>(&json).unwrap(), histogram);
+
+        let mut log_histogram = LogHistogram::new(2.0).unwrap();
+        log_histogram.extend([0, 3, 100]);
+        let json = serde_json::to_string(&log_histogram).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LogHistogram>(&json).unwrap(),
+            log_histogram
+        );
+    }
+
+    #[cfg(feature = "io")]
+    #[rstest]
+    #[case(r#"{"bin_width":0,"counts":{},"total":0}"#)]
+    #[case(r#"{"bin_width":1,"counts":{"2":3},"total":2}"#)]
+    fn test_histogram_deserialize_invalid(#[case] json: &str) {
+        assert!(serde_json::from_str::<Histogram>(json).is_err());
+    }
+
+    #[cfg(feature = "io")]
+    #[rstest]
+    #[case(r#"{"base":1.0,"zeros":0,"counts":{},"total":0}"#)]
+    #[case(r#"{"base":2.0,"zeros":1,"counts":{"3":1},"total":1}"#)]
+    #[case(r#"{"base":2.0,"zeros":0,"counts":{"64":1},"total":1}"#)]
+    fn test_log_histogram_deserialize_invalid(#[case] json: &str) {
+        assert!(serde_json::from_str::<LogHistogram>(json).is_err());
+    }
+
+    #[rstest]
+    #[case(1.0)]
+    #[case(0.5)]
+    #[case(f64::INFINITY)]
+    #[case(f64::NAN)]
+    fn test_log_histogram_invalid_base(#[case] base: f64) {
+        assert!(LogHistogram::new(base).is_err());
+    }
+}
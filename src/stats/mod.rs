@@ -0,0 +1,11 @@
+//! Summary statistics shared across the crate.
+//!
+//! [`Histogram`] bins integer values with a fixed width and [`LogHistogram`]
+//! with logarithmic bins for long-tailed data. Both support streaming
+//! [`add`](Histogram::add), [`merge`](Histogram::merge) of partial results,
+//! quantile queries and, with the `io` feature, serde serialization. They
+//! back QC length and GC distributions, k-mer spectra and coverage depth
+//! distributions.
+
+mod histogram;
+pub use histogram::*;
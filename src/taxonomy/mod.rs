@@ -0,0 +1,9 @@
+//! NCBI-style taxonomy trees and lowest common ancestor (LCA) queries.
+//!
+//! [`Taxonomy`] is loaded from a `nodes.dmp`-style file and resolves lineages
+//! and LCAs, and [`read_taxid_map`] loads a reference name to taxid mapping.
+//! Together with a sketch database (`sketch` feature) they form a minimal
+//! Kraken-like classifier.
+
+mod tree;
+pub use tree::*;
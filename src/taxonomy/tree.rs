@@ -0,0 +1,274 @@
+use crate::errors::BioError;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+/// NCBI taxonomy identifier.
+pub type TaxId = u32;
+
+/// Splits a `.dmp` line into its `\t|\t` separated fields.
+fn dmp_fields(line: &str) -> Vec<&str> {
+    line.trim_end_matches(['\n', '\r'])
+        .trim_end_matches("\t|")
+        .split("\t|\t")
+        .map(|f| f.trim())
+        .collect()
+}
+
+fn parse_taxid(field: &str, line_num: usize) -> Result<TaxId, BioError> {
+    field.parse().map_err(|_| {
+        BioError::InvalidFormatError(format!("invalid taxid {:?} on line {}.", field, line_num))
+    })
+}
+
+/// Taxonomy tree of `taxid -> (parent, rank)` nodes.
+///
+/// The root is the node that is its own parent (taxid `1` in NCBI dumps).
+///
+/// # Examples
+///
+/// ```
+/// use bio_utils_rs::taxonomy::Taxonomy;
+///
+/// let nodes = "1\t|\t1\t|\tno rank\t|\n\
+///              2\t|\t1\t|\tsuperkingdom\t|\n\
+///              561\t|\t2\t|\tgenus\t|\n\
+///              562\t|\t561\t|\tspecies\t|\n\
+///              564\t|\t561\t|\tspecies\t|\n";
+/// let taxonomy = Taxonomy::from_nodes_dmp(nodes.as_bytes()).unwrap();
+///
+/// assert_eq!(taxonomy.lca(562, 564), Some(561));
+/// assert_eq!(taxonomy.lineage(562), vec![562, 561, 2, 1]);
+/// assert_eq!(taxonomy.rank(561), Some("genus"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Taxonomy {
+    nodes: HashMap<TaxId, (TaxId, String)>,
+}
+
+impl Taxonomy {
+    /// Builds a taxonomy from `(taxid, parent, rank)` triples.
+    pub fn from_nodes<I, S>(nodes: I) -> Self
+    where
+        I: IntoIterator<Item = (TaxId, TaxId, S)>,
+        S: Into<String>,
+    {
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|(taxid, parent, rank)| (taxid, (parent, rank.into())))
+                .collect(),
+        }
+    }
+
+    /// Parses an NCBI `nodes.dmp` file (`taxid | parent | rank | ...`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BioError::InvalidFormatError`] if a line has fewer than three
+    /// fields or a non-numeric taxid, and [`BioError::IoError`] if reading fails.
+    pub fn from_nodes_dmp<R: BufRead>(reader: R) -> Result<Self, BioError> {
+        let mut nodes = HashMap::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields = dmp_fields(&line);
+            if fields.len() < 3 {
+                return Err(BioError::InvalidFormatError(format!(
+                    "expected at least 3 fields on line {}.",
+                    i + 1
+                )));
+            }
+
+            let taxid = parse_taxid(fields[0], i + 1)?;
+            let parent = parse_taxid(fields[1], i + 1)?;
+            nodes.insert(taxid, (parent, fields[2].to_string()));
+        }
+
+        Ok(Self { nodes })
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, taxid: TaxId) -> bool {
+        self.nodes.contains_key(&taxid)
+    }
+
+    /// Parent of `taxid`, `None` for the root or unknown taxids.
+    pub fn parent(&self, taxid: TaxId) -> Option<TaxId> {
+        self.nodes
+            .get(&taxid)
+            .map(|(parent, _)| *parent)
+            .filter(|parent| *parent != taxid)
+    }
+
+    pub fn rank(&self, taxid: TaxId) -> Option<&str> {
+        self.nodes.get(&taxid).map(|(_, rank)| rank.as_str())
+    }
+
+    /// Path from `taxid` up to the root, inclusive.
+    ///
+    /// Unknown taxids yield an empty lineage. Cycles in a malformed tree are
+    /// cut at the first repeated node.
+    pub fn lineage(&self, taxid: TaxId) -> Vec<TaxId> {
+        if !self.contains(taxid) {
+            return Vec::new();
+        }
+
+        let mut lineage = vec![taxid];
+        let mut seen = HashSet::from([taxid]);
+
+        while let Some(parent) = self.parent(*lineage.last().unwrap()) {
+            if !seen.insert(parent) {
+                break;
+            }
+            lineage.push(parent);
+        }
+
+        lineage
+    }
+
+    /// Lowest common ancestor of `a` and `b`, `None` if either is unknown or
+    /// they share no ancestor.
+    pub fn lca(&self, a: TaxId, b: TaxId) -> Option<TaxId> {
+        let ancestors: HashSet<TaxId> = self.lineage(a).into_iter().collect();
+        self.lineage(b).into_iter().find(|t| ancestors.contains(t))
+    }
+
+    /// Lowest common ancestor of all known taxids in `taxids`.
+    ///
+    /// Unknown taxids are ignored. Returns `None` if no taxid is known.
+    pub fn lca_many<I: IntoIterator<Item = TaxId>>(&self, taxids: I) -> Option<TaxId> {
+        taxids
+            .into_iter()
+            .filter(|t| self.contains(*t))
+            .try_fold(None, |acc: Option<TaxId>, t| match acc {
+                None => Some(Some(t)),
+                Some(acc) => self.lca(acc, t).map(Some),
+            })
+            .flatten()
+    }
+}
+
+/// Reads a reference name to taxid mapping.
+///
+/// Each non-empty line holds a name and a taxid separated by a tab, such as a
+/// two-column `seqid2taxid.map`. Extra columns are ignored.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidFormatError`] if a line has fewer than two
+/// columns or a non-numeric taxid, and [`BioError::IoError`] if reading fails.
+pub fn read_taxid_map<R: BufRead>(reader: R) -> Result<HashMap<String, TaxId>, BioError> {
+    let mut map = HashMap::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+        match (fields.next(), fields.next()) {
+            (Some(name), Some(taxid)) => {
+                map.insert(name.to_string(), parse_taxid(taxid.trim(), i + 1)?);
+            }
+            _ => {
+                return Err(BioError::InvalidFormatError(format!(
+                    "expected name and taxid on line {}.",
+                    i + 1
+                )));
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn taxonomy() -> Taxonomy {
+        Taxonomy::from_nodes([
+            (1, 1, "no rank"),
+            (2, 1, "superkingdom"),
+            (1224, 2, "phylum"),
+            (561, 1224, "genus"),
+            (562, 561, "species"),
+            (564, 561, "species"),
+            (1239, 2, "phylum"),
+            (1280, 1239, "species"),
+        ])
+    }
+
+    #[rstest]
+    #[case(562, 564, Some(561))]
+    #[case(562, 1280, Some(2))]
+    #[case(562, 562, Some(562))]
+    #[case(562, 561, Some(561))]
+    #[case(562, 9999, None)]
+    fn test_lca(#[case] a: TaxId, #[case] b: TaxId, #[case] expected: Option<TaxId>) {
+        assert_eq!(taxonomy().lca(a, b), expected);
+        assert_eq!(taxonomy().lca(b, a), expected);
+    }
+
+    #[rstest]
+    #[case(vec![562, 564], Some(561))]
+    #[case(vec![562, 564, 1280], Some(2))]
+    #[case(vec![562, 9999], Some(562))]
+    #[case(vec![], None)]
+    fn test_lca_many(#[case] taxids: Vec<TaxId>, #[case] expected: Option<TaxId>) {
+        assert_eq!(taxonomy().lca_many(taxids), expected);
+    }
+
+    #[test]
+    fn test_lineage() {
+        let taxonomy = taxonomy();
+        assert_eq!(taxonomy.lineage(562), vec![562, 561, 1224, 2, 1]);
+        assert_eq!(taxonomy.lineage(1), vec![1]);
+        assert!(taxonomy.lineage(9999).is_empty());
+        assert_eq!(taxonomy.parent(1), None);
+    }
+
+    #[test]
+    fn test_lineage_cycle() {
+        let taxonomy = Taxonomy::from_nodes([(1, 2, "a"), (2, 1, "b")]);
+        assert_eq!(taxonomy.lineage(1), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_from_nodes_dmp() {
+        let data = b"1\t|\t1\t|\tno rank\t|\t\t|\n562\t|\t1\t|\tspecies\t|\tEC\t|\n";
+        let taxonomy = Taxonomy::from_nodes_dmp(&data[..]).unwrap();
+
+        assert_eq!(taxonomy.len(), 2);
+        assert_eq!(taxonomy.parent(562), Some(1));
+        assert_eq!(taxonomy.rank(562), Some("species"));
+
+        assert!(Taxonomy::from_nodes_dmp(&b"x\t|\t1\t|\tspecies\t|\n"[..]).is_err());
+        assert!(Taxonomy::from_nodes_dmp(&b"1\t|\t1\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_read_taxid_map() {
+        let map = read_taxid_map(&b"NC_000913.3\t562\n\nNC_007795.1\t1280\textra\n"[..]).unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["NC_000913.3"], 562);
+        assert_eq!(map["NC_007795.1"], 1280);
+
+        assert!(read_taxid_map(&b"ref_only\n"[..]).is_err());
+        assert!(read_taxid_map(&b"ref\tabc\n"[..]).is_err());
+    }
+}
@@ -1,10 +1,311 @@
+//! Deterministic test data generators.
+//!
+//! Available to downstream crates through the `test-utils` feature, so
+//! integration tests and benchmarks can share seeded FASTQ/FASTA fixtures
+//! instead of ad-hoc files.
+
+use crate::errors::BioError;
+use crate::nucleotide::PHRED_OFFSET;
+use crate::rng::{Seedable, SplitMix64};
+use std::io::Write;
+
+#[cfg(test)]
 use std::fs::File;
+#[cfg(test)]
 use std::path::PathBuf;
+#[cfg(test)]
 use tempfile::TempDir;
 
+/// Read length distribution for generated records.
+#[derive(Debug, Clone)]
+pub enum LengthDistribution {
+    /// Every record has the same length.
+    Fixed(usize),
+    /// Uniform over `[min, max]`.
+    Uniform { min: usize, max: usize },
+    /// Normal with the given mean and standard deviation, truncated at `min`.
+    Normal { mean: f64, sd: f64, min: usize },
+}
+
+impl LengthDistribution {
+    fn sample(&self, rng: &mut SplitMix64) -> usize {
+        match *self {
+            LengthDistribution::Fixed(len) => len,
+            LengthDistribution::Uniform { min, max } => match (max - min).checked_add(1) {
+                Some(n) => min + rng.next_below(n),
+                // The range covers every `usize`.
+                None => rng.next_u64() as usize,
+            },
+            LengthDistribution::Normal { mean, sd, min } => {
+                let len = (mean + sd * rng.next_normal()).round();
+                (len.max(0.0) as usize).max(min)
+            }
+        }
+    }
+}
+
+/// Per-position quality model for generated FASTQ records.
+#[derive(Debug, Clone)]
+pub enum QualityProfile {
+    /// Every base has the same Phred score.
+    Constant(u8),
+    /// Illumina-like: starts near `max_phred` and decays linearly towards
+    /// `min_phred` at the 3' end, with a little per-base jitter.
+    Illumina { max_phred: u8, min_phred: u8 },
+    /// Nanopore-like: position independent, normally distributed around `mean_phred`.
+    Nanopore { mean_phred: u8, sd: f64 },
+    /// Targets an expected mean Phred over the read, shaped by a positional
+    /// [`QualityDecay`] and normally distributed per-base noise with sd `sd`.
+    Decay {
+        mean_phred: f64,
+        decay: QualityDecay,
+        sd: f64,
+    },
+}
+
+/// Positional shape of [`QualityProfile::Decay`].
+///
+/// Each shape is centered so that it does not change the expected mean of the
+/// read, only how quality is distributed along it.
+#[derive(Debug, Clone, Copy)]
+pub enum QualityDecay {
+    /// Position independent (ONT-like).
+    Flat,
+    /// Linear decay, `drop` Phred lower at the 3' end than at the 5' end.
+    Linear { drop: f64 },
+    /// Quadratic decay that is nearly flat early on and falls off towards the
+    /// 3' end (Illumina-like), `drop` Phred lower at the 3' end than at the 5' end.
+    Tail { drop: f64 },
+}
+
+impl QualityDecay {
+    /// Phred offset from the mean at relative position `frac` in `[0, 1]`.
+    fn offset(&self, frac: f64) -> f64 {
+        match *self {
+            QualityDecay::Flat => 0.0,
+            QualityDecay::Linear { drop } => drop * (0.5 - frac),
+            QualityDecay::Tail { drop } => drop * (1.0 / 3.0 - frac * frac),
+        }
+    }
+}
+
+impl QualityProfile {
+    fn phred_at(&self, pos: usize, len: usize, rng: &mut SplitMix64) -> u8 {
+        let phred = match *self {
+            QualityProfile::Constant(phred) => phred as f64,
+            QualityProfile::Illumina {
+                max_phred,
+                min_phred,
+            } => {
+                let frac = pos as f64 / len.max(1) as f64;
+                let phred = max_phred as f64 - frac * (max_phred as f64 - min_phred as f64);
+                phred + rng.next_normal()
+            }
+            QualityProfile::Nanopore { mean_phred, sd } => {
+                mean_phred as f64 + sd * rng.next_normal()
+            }
+            QualityProfile::Decay {
+                mean_phred,
+                decay,
+                sd,
+            } => {
+                let frac = (pos as f64 + 0.5) / len.max(1) as f64;
+                mean_phred + decay.offset(frac) + sd * rng.next_normal()
+            }
+        };
+
+        phred.round().clamp(2.0, 60.0) as u8
+    }
+
+    fn validate(&self) -> Result<(), BioError> {
+        let (mean_phred, sd) = match *self {
+            QualityProfile::Constant(_) | QualityProfile::Illumina { .. } => return Ok(()),
+            QualityProfile::Nanopore { mean_phred, sd } => (mean_phred as f64, sd),
+            QualityProfile::Decay { mean_phred, sd, .. } => (mean_phred, sd),
+        };
+
+        if !(0.0..=60.0).contains(&mean_phred) {
+            return Err(BioError::InvalidParameterError(format!(
+                "mean phred {} must be in range 0-60.",
+                mean_phred
+            )));
+        }
+
+        if !(sd >= 0.0 && sd.is_finite()) {
+            return Err(BioError::InvalidParameterError(format!(
+                "quality sd {} must be a non-negative number.",
+                sd
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates a seeded Phred+33 quality string of length `len` from `profile`.
+///
+/// Uses the same model as [`generate_fastq`], so it can be used to build
+/// quality strings for hand-made records or to exercise the quality metrics
+/// with a known expected mean. Per-base scores are clamped to `2..=60`.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] if the profile has a mean
+/// Phred outside `0..=60` or a negative standard deviation.
+pub fn simulate_quality(
+    len: usize,
+    profile: &QualityProfile,
+    seed: u64,
+) -> Result<Vec<u8>, BioError> {
+    profile.validate()?;
+    let mut rng = SplitMix64::new(seed);
+    Ok(random_quality(&mut rng, len, profile))
+}
+
+/// Configuration for generated sequence files.
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub num_records: usize,
+    pub length: LengthDistribution,
+    /// Expected GC fraction in `[0.0, 1.0]`.
+    pub gc: f64,
+    pub quality: QualityProfile,
+    pub seed: u64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            num_records: 100,
+            length: LengthDistribution::Fixed(150),
+            gc: 0.5,
+            quality: QualityProfile::Illumina {
+                max_phred: 38,
+                min_phred: 25,
+            },
+            seed: 0,
+        }
+    }
+}
+
+impl Seedable for SimConfig {
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+}
+
+impl SimConfig {
+    fn validate(&self) -> Result<(), BioError> {
+        self.quality.validate()?;
+
+        if !(0.0..=1.0).contains(&self.gc) {
+            return Err(BioError::InvalidParameterError(format!(
+                "gc fraction {} must be in range 0-1.",
+                self.gc
+            )));
+        }
+
+        match self.length {
+            LengthDistribution::Uniform { min, max } if min > max => {
+                return Err(BioError::InvalidParameterError(format!(
+                    "min length {} cannot be larger than max length {}.",
+                    min, max
+                )));
+            }
+            LengthDistribution::Normal { sd, .. } if !(sd >= 0.0 && sd.is_finite()) => {
+                return Err(BioError::InvalidParameterError(format!(
+                    "length sd {} must be a non-negative number.",
+                    sd
+                )));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates a random DNA sequence of length `len` with expected GC fraction `gc`.
+pub(crate) fn random_sequence(rng: &mut SplitMix64, len: usize, gc: f64) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            let is_gc = rng.next_f64() < gc;
+            match (is_gc, rng.next_below(2)) {
+                (true, 0) => b'G',
+                (true, _) => b'C',
+                (false, 0) => b'A',
+                (false, _) => b'T',
+            }
+        })
+        .collect()
+}
+
+/// Generates a Phred+33 quality string of length `len`.
+fn random_quality(rng: &mut SplitMix64, len: usize, profile: &QualityProfile) -> Vec<u8> {
+    (0..len)
+        .map(|pos| profile.phred_at(pos, len, rng) + PHRED_OFFSET as u8)
+        .collect()
+}
+
+/// Writes `config.num_records` FASTQ records named `read_{i}` to `writer`.
+///
+/// Output is fully determined by `config`, including the seed.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] for an invalid config, or
+/// [`BioError::IoError`] if writing fails.
+pub fn generate_fastq<W: Write>(writer: &mut W, config: &SimConfig) -> Result<(), BioError> {
+    config.validate()?;
+    let mut rng = SplitMix64::new(config.seed);
+
+    for i in 0..config.num_records {
+        let len = config.length.sample(&mut rng);
+        let seq = random_sequence(&mut rng, len, config.gc);
+        let qual = random_quality(&mut rng, len, &config.quality);
+
+        writeln!(writer, "@read_{}", i)?;
+        writer.write_all(&seq)?;
+        writer.write_all(b"\n+\n")?;
+        writer.write_all(&qual)?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+/// Writes `config.num_records` FASTA records named `seq_{i}` to `writer`.
+///
+/// The quality profile is ignored.
+///
+/// # Errors
+///
+/// Returns [`BioError::InvalidParameterError`] for an invalid config, or
+/// [`BioError::IoError`] if writing fails.
+pub fn generate_fasta<W: Write>(writer: &mut W, config: &SimConfig) -> Result<(), BioError> {
+    config.validate()?;
+    let mut rng = SplitMix64::new(config.seed);
+
+    for i in 0..config.num_records {
+        let len = config.length.sample(&mut rng);
+        let seq = random_sequence(&mut rng, len, config.gc);
+
+        writeln!(writer, ">seq_{}", i)?;
+        writer.write_all(&seq)?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
 /// Creates a temporary directory with an empty file of the given name.
 ///
 /// The returned [`TempDir`] must be kept alive for the file to remain on disk.
+#[cfg(test)]
 pub fn temp_seq_file(filename: &str) -> (TempDir, PathBuf) {
     let tmp_dir = TempDir::new().expect("failed to create temp dir");
     let tmp_file = tmp_dir.path().join(filename);
@@ -12,3 +313,157 @@ pub fn temp_seq_file(filename: &str) -> (TempDir, PathBuf) {
 
     (tmp_dir, tmp_file)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fastq_lines(config: &SimConfig) -> Vec<String> {
+        let mut buf = Vec::new();
+        generate_fastq(&mut buf, config).unwrap();
+        String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_fastq_deterministic() {
+        let config = SimConfig::default();
+        assert_eq!(fastq_lines(&config), fastq_lines(&config));
+
+        let other = SimConfig {
+            seed: 1,
+            ..SimConfig::default()
+        };
+        assert_ne!(fastq_lines(&config), fastq_lines(&other));
+    }
+
+    #[test]
+    fn test_generate_fastq_valid_records() {
+        let config = SimConfig {
+            num_records: 20,
+            length: LengthDistribution::Uniform { min: 10, max: 50 },
+            ..SimConfig::default()
+        };
+        let lines = fastq_lines(&config);
+
+        assert_eq!(lines.len(), 80);
+        for record in lines.chunks_exact(4) {
+            assert!(record[0].starts_with('@'));
+            assert_eq!(record[2], "+");
+            assert_eq!(record[1].len(), record[3].len());
+            assert!((10..=50).contains(&record[1].len()));
+        }
+    }
+
+    #[test]
+    fn test_uniform_length_full_range() {
+        let mut rng = SplitMix64::new(1);
+        let full = LengthDistribution::Uniform {
+            min: 0,
+            max: usize::MAX,
+        };
+        let top = LengthDistribution::Uniform {
+            min: usize::MAX,
+            max: usize::MAX,
+        };
+
+        assert!((0..100).any(|_| full.sample(&mut rng) > u32::MAX as usize));
+        assert_eq!(top.sample(&mut rng), usize::MAX);
+    }
+
+    #[test]
+    fn test_generate_gc() {
+        let config = SimConfig {
+            num_records: 1,
+            length: LengthDistribution::Fixed(10_000),
+            gc: 0.0,
+            ..SimConfig::default()
+        };
+
+        let mut buf = Vec::new();
+        generate_fasta(&mut buf, &config).unwrap();
+        let seq = buf.split(|b| *b == b'\n').nth(1).unwrap();
+        assert_eq!(seq.len(), 10_000);
+        assert!(!seq.iter().any(|nt| *nt == b'G' || *nt == b'C'));
+    }
+
+    #[test]
+    fn test_simulate_quality_mean() {
+        for decay in [
+            QualityDecay::Flat,
+            QualityDecay::Linear { drop: 10.0 },
+            QualityDecay::Tail { drop: 15.0 },
+        ] {
+            let profile = QualityProfile::Decay {
+                mean_phred: 25.0,
+                decay,
+                sd: 2.0,
+            };
+            let qual = simulate_quality(10_000, &profile, 3).unwrap();
+            let mean = qual
+                .iter()
+                .map(|q| (q - PHRED_OFFSET as u8) as f64)
+                .sum::<f64>()
+                / qual.len() as f64;
+
+            assert_eq!(qual.len(), 10_000);
+            assert!((mean - 25.0).abs() < 0.2, "{:?}: {}", decay, mean);
+        }
+    }
+
+    #[test]
+    fn test_simulate_quality_decay_shape() {
+        let profile = QualityProfile::Decay {
+            mean_phred: 30.0,
+            decay: QualityDecay::Tail { drop: 20.0 },
+            sd: 0.0,
+        };
+        let qual = simulate_quality(100, &profile, 0).unwrap();
+
+        assert!(qual.windows(2).all(|w| w[0] >= w[1]));
+        assert!(qual[0] - qual[99] >= 19);
+        assert_eq!(qual, simulate_quality(100, &profile, 0).unwrap());
+    }
+
+    #[test]
+    fn test_simulate_quality_invalid() {
+        let profile = QualityProfile::Decay {
+            mean_phred: 30.0,
+            decay: QualityDecay::Flat,
+            sd: -1.0,
+        };
+        assert!(simulate_quality(10, &profile, 0).is_err());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        for length in [
+            LengthDistribution::Uniform { min: 5, max: 4 },
+            LengthDistribution::Normal {
+                mean: 100.0,
+                sd: -1.0,
+                min: 0,
+            },
+            LengthDistribution::Normal {
+                mean: 100.0,
+                sd: f64::NAN,
+                min: 0,
+            },
+        ] {
+            let config = SimConfig {
+                length,
+                ..SimConfig::default()
+            };
+            assert!(generate_fasta(&mut Vec::new(), &config).is_err());
+        }
+
+        let config = SimConfig {
+            gc: 1.5,
+            ..SimConfig::default()
+        };
+        assert!(generate_fasta(&mut Vec::new(), &config).is_err());
+    }
+}